@@ -0,0 +1,129 @@
+use std::net::{IpAddr, SocketAddr};
+
+/// Whether `addr` matches an entry of `C2_TRUSTED_PROXIES`. Entries are plain
+/// addresses or CIDR ranges (`10.0.0.0/8`, `fd00::/8`).
+pub fn is_trusted_proxy(addr: IpAddr, trusted_proxies: &[String]) -> bool {
+    trusted_proxies
+        .iter()
+        .any(|entry| matches_entry(addr, entry.trim()))
+}
+
+/// Whether a browser `Origin` matches an entry of `C2_ALLOWED_ORIGINS`.
+/// Entries are compared as `scheme://host[:port]`, ignoring case and a
+/// trailing slash; `*` allows any origin.
+pub fn is_trusted_origin(origin: &str, allowed_origins: &[String]) -> bool {
+    let origin = origin.trim().trim_end_matches('/');
+    allowed_origins.iter().any(|entry| {
+        let entry = entry.trim().trim_end_matches('/');
+        entry == "*" || entry.eq_ignore_ascii_case(origin)
+    })
+}
+
+/// Resolves the originating client address. `X-Forwarded-For` is only
+/// honoured when the direct peer is a trusted proxy; the chain is then walked
+/// from the right, skipping trusted hops, and the first untrusted address is
+/// the client. `forwarded_for` takes every header value in arrival order.
+pub fn real_client_ip<'a>(
+    forwarded_for: impl IntoIterator<Item = &'a str>,
+    peer_addr: IpAddr,
+    trusted_proxies: &[String],
+) -> IpAddr {
+    if !is_trusted_proxy(peer_addr, trusted_proxies) {
+        return peer_addr;
+    }
+    let hops: Vec<&str> = forwarded_for
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .collect();
+    let mut client = peer_addr;
+    for hop in hops.into_iter().rev() {
+        let Some(addr) = parse_hop(hop) else {
+            break;
+        };
+        client = addr;
+        if !is_trusted_proxy(addr, trusted_proxies) {
+            break;
+        }
+    }
+    client
+}
+
+fn parse_hop(value: &str) -> Option<IpAddr> {
+    value
+        .parse::<IpAddr>()
+        .ok()
+        .or_else(|| value.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+fn matches_entry(addr: IpAddr, entry: &str) -> bool {
+    let Some((network, prefix)) = entry.split_once('/') else {
+        return entry.parse::<IpAddr>().is_ok_and(|trusted| trusted == addr);
+    };
+    let (Ok(network), Ok(prefix)) = (network.parse::<IpAddr>(), prefix.parse::<u32>()) else {
+        return false;
+    };
+    match (addr, network) {
+        (IpAddr::V4(addr), IpAddr::V4(network)) if prefix <= 32 => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(addr) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(addr), IpAddr::V6(network)) if prefix <= 128 => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(addr) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trusted() -> Vec<String> {
+        vec!["10.0.0.0/8".to_string(), "192.0.2.1".to_string()]
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn single_hop_behind_a_trusted_proxy() {
+        let client = real_client_ip(["203.0.113.7"], ip("10.1.2.3"), &trusted());
+        assert_eq!(client, ip("203.0.113.7"));
+        let client = real_client_ip(["203.0.113.7:52100"], ip("192.0.2.1"), &trusted());
+        assert_eq!(client, ip("203.0.113.7"));
+        assert_eq!(real_client_ip([], ip("10.1.2.3"), &trusted()), ip("10.1.2.3"));
+    }
+
+    #[test]
+    fn chain_skips_trusted_proxy_hops_from_the_right() {
+        let client = real_client_ip(
+            ["198.51.100.9, 203.0.113.7", "10.9.9.9"],
+            ip("10.1.2.3"),
+            &trusted(),
+        );
+        assert_eq!(client, ip("203.0.113.7"), "the left-most hop is client supplied");
+        let client = real_client_ip(["garbage, 10.9.9.9"], ip("10.1.2.3"), &trusted());
+        assert_eq!(client, ip("10.9.9.9"));
+    }
+
+    #[test]
+    fn spoofed_header_from_an_untrusted_peer_is_ignored() {
+        let client = real_client_ip(["10.0.0.1, 127.0.0.1"], ip("203.0.113.7"), &trusted());
+        assert_eq!(client, ip("203.0.113.7"));
+        assert_eq!(real_client_ip(["198.51.100.9"], ip("203.0.113.7"), &[]), ip("203.0.113.7"));
+    }
+
+    #[test]
+    fn origins_match_allowed_entries_only() {
+        let allowed = vec!["https://console.example.com/".to_string()];
+        assert!(is_trusted_origin("https://Console.example.com", &allowed));
+        assert!(!is_trusted_origin("http://console.example.com", &allowed));
+        assert!(!is_trusted_origin("https://console.example.com.evil.test", &allowed));
+        assert!(!is_trusted_origin("https://console.example.com", &[]));
+        assert!(is_trusted_origin("https://any.test", &["*".to_string()]));
+    }
+}
@@ -1,6 +1,18 @@
+mod client_ip;
+mod read_only;
+
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
 use std::{env, fmt};
 
+pub use client_ip::{is_trusted_origin, is_trusted_proxy, real_client_ip};
+pub use read_only::{ReadOnlyMode, READ_ONLY_MESSAGE};
+
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+pub const DEFAULT_CORRELATION_HEADER: &str = "x-correlation-id";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Environment {
@@ -22,6 +34,19 @@ impl Environment {
             _ => Self::Local,
         }
     }
+
+    /// Staging and prod fail closed: the gateway token is required and
+    /// oversized page requests are rejected instead of capped.
+    pub fn is_production(self) -> bool {
+        matches!(self, Self::Staging | Self::Prod)
+    }
+
+    pub fn default_log_level(self) -> &'static str {
+        match self {
+            Self::Local | Self::Dev => "debug",
+            Self::Test | Self::Staging | Self::Prod => "info",
+        }
+    }
 }
 
 impl fmt::Display for Environment {
@@ -45,25 +70,73 @@ pub struct ServiceConfig {
     pub bind_addr: String,
     pub metrics_addr: Option<String>,
     pub log_level: String,
+    /// `text` (default) or `json`.
+    pub log_format: String,
     pub data_dir: String,
     pub trusted_proxies: Vec<String>,
+    /// Browser origins allowed to call the service cross-origin; empty
+    /// leaves cross-origin requests to the browser's same-origin policy.
+    pub allowed_origins: Vec<String>,
+    pub max_page_size: usize,
+    pub strict_page_size: bool,
+    pub require_auth: bool,
+    /// Upper bound on a single storage call before it fails as timed out.
+    pub storage_timeout_ms: u64,
+    /// Start with writes rejected; see [`ReadOnlyMode`].
+    pub read_only: bool,
+    /// How long a tenant's capability catalog is reused when validating
+    /// capability references; 0 reads it on every validation.
+    pub capability_cache_ttl_ms: u64,
+    /// Lowercase name of the header that carries a request's correlation id
+    /// between services and back to the caller.
+    pub correlation_header: String,
+    /// Responses smaller than this are not compressed, even when the client
+    /// accepts gzip or brotli.
+    pub compression_min_bytes: usize,
+    /// Opt-in periodic usage telemetry; see `c2_observability::telemetry`.
+    pub telemetry: bool,
+    pub telemetry_interval_secs: u64,
+    /// Where telemetry reports are posted; they are only logged when unset.
+    pub telemetry_endpoint: Option<String>,
 }
 
 impl ServiceConfig {
     pub fn from_env(default_service_name: &str) -> Self {
-        let service_name = env_var("C2_SERVICE_NAME", default_service_name.to_string());
-        let environment = Environment::from_env(&env_var("C2_ENV", "local".to_string()));
-        let region = env::var("C2_REGION").ok();
-        let bind_addr = env_var("C2_BIND_ADDR", "0.0.0.0:8080".to_string());
-        let metrics_addr = env::var("C2_METRICS_ADDR").ok();
-        let log_level = env_var("C2_LOG_LEVEL", "info".to_string());
-        let data_dir = env_var("C2_DATA_DIR", "/var/lib/c2".to_string());
-        let trusted_proxies = env::var("C2_TRUSTED_PROXIES")
-            .unwrap_or_default()
-            .split(',')
-            .map(|value| value.trim().to_string())
-            .filter(|value| !value.is_empty())
-            .collect();
+        Self::from_lookup(default_service_name, process_env)
+    }
+
+    /// Loads the configuration from `lookup` instead of the process
+    /// environment.
+    pub fn from_lookup(
+        default_service_name: &str,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        let vars = Vars(lookup);
+        let service_name = vars.string("C2_SERVICE_NAME", default_service_name.to_string());
+        let environment = Environment::from_env(&vars.string("C2_ENV", "local".to_string()));
+        let region = vars.get("C2_REGION");
+        let bind_addr = vars.string("C2_BIND_ADDR", "0.0.0.0:8080".to_string());
+        let metrics_addr = vars.get("C2_METRICS_ADDR");
+        let log_level = vars.string("C2_LOG_LEVEL", environment.default_log_level().to_string());
+        let log_format = vars.string("C2_LOG_FORMAT", "text".to_string()).to_ascii_lowercase();
+        let data_dir = vars.string("C2_DATA_DIR", "/var/lib/c2".to_string());
+        let trusted_proxies = vars.list("C2_TRUSTED_PROXIES");
+        let allowed_origins = vars.list("C2_ALLOWED_ORIGINS");
+        let max_page_size = vars.parse("C2_MAX_PAGE_SIZE", 500).max(1);
+        let strict_page_size = vars.bool("C2_STRICT_PAGE_SIZE", environment.is_production());
+        let require_auth = vars.bool("C2_REQUIRE_AUTH", environment.is_production());
+        let storage_timeout_ms = vars.parse("C2_STORAGE_TIMEOUT_MS", 10_000).max(1);
+        let read_only = vars.bool("C2_READ_ONLY", false);
+        let capability_cache_ttl_ms = vars.parse("C2_CAPABILITY_CACHE_TTL_MS", 5_000);
+        let correlation_header =
+            vars.string("C2_CORRELATION_HEADER", DEFAULT_CORRELATION_HEADER.to_string())
+                .to_ascii_lowercase();
+        let compression_min_bytes = vars.parse("C2_COMPRESSION_MIN_BYTES", 1_024);
+        let telemetry = vars.bool("C2_TELEMETRY", false);
+        let telemetry_interval_secs = vars.parse("C2_TELEMETRY_INTERVAL_SECS", 3_600).max(1);
+        let telemetry_endpoint = vars
+            .get("C2_TELEMETRY_ENDPOINT")
+            .filter(|value| !value.trim().is_empty());
 
         Self {
             service_name,
@@ -72,8 +145,46 @@ impl ServiceConfig {
             bind_addr,
             metrics_addr,
             log_level,
+            log_format,
             data_dir,
             trusted_proxies,
+            allowed_origins,
+            max_page_size,
+            strict_page_size,
+            require_auth,
+            storage_timeout_ms,
+            read_only,
+            capability_cache_ttl_ms,
+            correlation_header,
+            compression_min_bytes,
+            telemetry,
+            telemetry_interval_secs,
+            telemetry_endpoint,
+        }
+    }
+
+    pub fn storage_timeout(&self) -> Duration {
+        Duration::from_millis(self.storage_timeout_ms)
+    }
+
+    pub fn capability_cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.capability_cache_ttl_ms)
+    }
+
+    pub fn telemetry_interval(&self) -> Duration {
+        Duration::from_secs(self.telemetry_interval_secs)
+    }
+
+    /// Effective list limit for a request. Limits above `max_page_size` are
+    /// capped, or rejected with `None` when `strict_page_size` is set.
+    pub fn page_limit(&self, requested: Option<usize>) -> Option<usize> {
+        let limit = requested.unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit <= self.max_page_size {
+            Some(limit)
+        } else if self.strict_page_size {
+            None
+        } else {
+            Some(self.max_page_size)
         }
     }
 }
@@ -151,6 +262,13 @@ impl GatewayAuthConfig {
         }
     }
 
+    pub fn validate(&self, require_auth: bool) -> Result<(), String> {
+        if require_auth && self.api_token.as_deref().is_none_or(str::is_empty) {
+            return Err("C2_GATEWAY_API_TOKEN is required when C2_REQUIRE_AUTH is set".to_string());
+        }
+        Ok(())
+    }
+
     pub fn is_bypassed(&self, path: &str) -> bool {
         self.bypass_paths
             .iter()
@@ -158,43 +276,330 @@ impl GatewayAuthConfig {
     }
 }
 
+/// Headers the gateway itself sets on every forwarded request. Client-sent
+/// copies are always dropped, so upstreams can trust them.
+pub const GATEWAY_OWNED_HEADERS: &[&str] = &["x-c2-client", "x-forwarded-proto", "x-real-ip"];
+
+/// Extra request headers the gateway removes or sets before forwarding.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GatewayHeaderRules {
+    pub strip: Vec<String>,
+    pub set: BTreeMap<String, String>,
+}
+
+impl GatewayHeaderRules {
+    /// Reads `C2_GATEWAY_STRIP_HEADERS`, a comma-separated list of names, and
+    /// `C2_GATEWAY_SET_HEADERS`, a JSON object of names to values.
+    pub fn from_env() -> Result<Self, String> {
+        let strip = env::var("C2_GATEWAY_STRIP_HEADERS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty())
+            .collect();
+        let set: BTreeMap<String, String> = match env::var("C2_GATEWAY_SET_HEADERS") {
+            Ok(value) => serde_json::from_str(&value)
+                .map_err(|err| format!("invalid C2_GATEWAY_SET_HEADERS: {err}"))?,
+            Err(_) => BTreeMap::new(),
+        };
+        let rules = Self {
+            strip,
+            set: set
+                .into_iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value))
+                .collect(),
+        };
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for name in self.strip.iter().chain(self.set.keys()) {
+            let valid = !name.is_empty()
+                && name
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+            if !valid {
+                return Err(format!("invalid gateway header name: {name:?}"));
+            }
+        }
+        if let Some(name) = self.set.keys().find(|name| is_gateway_owned(name)) {
+            return Err(format!("{name} is set by the gateway and cannot be configured"));
+        }
+        Ok(())
+    }
+
+    /// Every header dropped from client requests: the configured ones plus
+    /// [`GATEWAY_OWNED_HEADERS`].
+    pub fn stripped(&self) -> impl Iterator<Item = &str> {
+        GATEWAY_OWNED_HEADERS
+            .iter()
+            .copied()
+            .chain(self.strip.iter().map(String::as_str).filter(|name| !is_gateway_owned(name)))
+    }
+}
+
+fn is_gateway_owned(name: &str) -> bool {
+    GATEWAY_OWNED_HEADERS
+        .iter()
+        .any(|owned| owned.eq_ignore_ascii_case(name))
+}
+
+/// One of the services behind the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayTarget {
+    Api,
+    Web,
+    Mcp,
+}
+
+/// Sends paths equal to `prefix`, or under it, to `upstream`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayRoute {
+    pub prefix: String,
+    pub upstream: GatewayTarget,
+}
+
+impl GatewayRoute {
+    pub fn new(prefix: &str, upstream: GatewayTarget) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            upstream,
+        }
+    }
+
+    /// `/mcp` matches `/mcp` and `/mcp/...` but not `/mcpx`.
+    fn matches(&self, path: &str) -> bool {
+        match path.strip_prefix(self.prefix.as_str()) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/') || self.prefix.ends_with('/'),
+            None => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayConfig {
     pub api: GatewayUpstream,
     pub web: GatewayUpstream,
+    pub mcp: GatewayUpstream,
+    /// Longest matching prefix wins; unmatched paths go to web.
+    pub routes: Vec<GatewayRoute>,
     pub tls: Option<GatewayTlsConfig>,
     pub auth: GatewayAuthConfig,
+    pub headers: GatewayHeaderRules,
 }
 
 impl GatewayConfig {
-    pub fn from_env() -> Self {
-        Self {
+    /// Reads the upstreams and `C2_GATEWAY_ROUTES`, a JSON array of
+    /// `{"prefix": "/mcp", "upstream": "mcp"}` entries that replaces
+    /// [`GatewayConfig::default_routes`] when set.
+    pub fn from_env() -> Result<Self, String> {
+        let routes = match env::var("C2_GATEWAY_ROUTES") {
+            Ok(value) => serde_json::from_str(&value)
+                .map_err(|err| format!("invalid C2_GATEWAY_ROUTES: {err}"))?,
+            Err(_) => Self::default_routes(),
+        };
+        Ok(Self {
             api: GatewayUpstream::from_env("C2_GATEWAY_API", "c2-api", 8080),
             web: GatewayUpstream::from_env("C2_GATEWAY_WEB", "c2-web", 8080),
+            mcp: GatewayUpstream::from_env("C2_GATEWAY_MCP", "c2-mcp", 8080),
+            routes,
             tls: GatewayTlsConfig::from_env(),
             auth: GatewayAuthConfig::from_env(),
+            headers: GatewayHeaderRules::from_env()?,
+        })
+    }
+
+    /// The API's paths, plus the MCP endpoint; everything else is the web UI.
+    pub fn default_routes() -> Vec<GatewayRoute> {
+        vec![
+            GatewayRoute::new("/v1", GatewayTarget::Api),
+            GatewayRoute::new("/health", GatewayTarget::Api),
+            GatewayRoute::new("/openapi.json", GatewayTarget::Api),
+            GatewayRoute::new("/mcp", GatewayTarget::Mcp),
+        ]
+    }
+
+    pub fn route(&self, path: &str) -> GatewayTarget {
+        self.routes
+            .iter()
+            .filter(|route| route.matches(path))
+            .max_by_key(|route| route.prefix.len())
+            .map_or(GatewayTarget::Web, |route| route.upstream)
+    }
+
+    pub fn upstream(&self, target: GatewayTarget) -> &GatewayUpstream {
+        match target {
+            GatewayTarget::Api => &self.api,
+            GatewayTarget::Web => &self.web,
+            GatewayTarget::Mcp => &self.mcp,
         }
     }
 }
 
 fn env_var(key: &str, default: String) -> String {
-    env::var(key).unwrap_or(default)
+    Vars(process_env).string(key, default)
 }
 
 fn env_var_u16(key: &str, default: u16) -> u16 {
-    env::var(key)
-        .ok()
-        .and_then(|value| value.parse::<u16>().ok())
-        .unwrap_or(default)
+    Vars(process_env).parse(key, default)
 }
 
 fn env_var_bool(key: &str, default: bool) -> bool {
-    env::var(key)
-        .ok()
-        .map(|value| match value.to_ascii_lowercase().as_str() {
-            "1" | "true" | "yes" | "on" => true,
-            "0" | "false" | "no" | "off" => false,
-            _ => default,
-        })
-        .unwrap_or(default)
+    Vars(process_env).bool(key, default)
+}
+
+fn process_env(key: &str) -> Option<String> {
+    env::var(key).ok()
+}
+
+/// Typed reads of configuration variables from a lookup, so a loader can be
+/// fed a map in tests instead of the process environment.
+struct Vars<F>(F);
+
+impl<F: Fn(&str) -> Option<String>> Vars<F> {
+    fn get(&self, key: &str) -> Option<String> {
+        (self.0)(key)
+    }
+
+    fn string(&self, key: &str, default: String) -> String {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// A comma-separated list, trimmed, without empty entries.
+    fn list(&self, key: &str) -> Vec<String> {
+        self.get(key)
+            .unwrap_or_default()
+            .split(',')
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    fn parse<T: FromStr>(&self, key: &str, default: T) -> T {
+        self.get(key)
+            .and_then(|value| value.parse::<T>().ok())
+            .unwrap_or(default)
+    }
+
+    fn bool(&self, key: &str, default: bool) -> bool {
+        self.get(key)
+            .map(|value| match value.to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => true,
+                "0" | "false" | "no" | "off" => false,
+                _ => default,
+            })
+            .unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn gateway(routes: Vec<GatewayRoute>) -> GatewayConfig {
+        let upstream = |host: &str| GatewayUpstream {
+            host: host.to_string(),
+            port: 8080,
+            tls: false,
+            sni: None,
+        };
+        GatewayConfig {
+            api: upstream("c2-api"),
+            web: upstream("c2-web"),
+            mcp: upstream("c2-mcp"),
+            routes,
+            tls: None,
+            auth: GatewayAuthConfig::from_env(),
+            headers: GatewayHeaderRules::default(),
+        }
+    }
+
+    #[test]
+    fn gateway_routes_by_longest_prefix_and_falls_back_to_web() {
+        let config = gateway(GatewayConfig::default_routes());
+        assert_eq!(config.route("/mcp"), GatewayTarget::Mcp);
+        assert_eq!(config.upstream(config.route("/mcp")).host, "c2-mcp");
+        assert_eq!(config.route("/v1/missions"), GatewayTarget::Api);
+        assert_eq!(config.route("/openapi.json"), GatewayTarget::Api);
+        assert_eq!(config.route("/mcpx"), GatewayTarget::Web);
+        assert_eq!(config.route("/unknown/page"), GatewayTarget::Web);
+        assert_eq!(config.upstream(config.route("/")).host, "c2-web");
+
+        let routes: Vec<GatewayRoute> = serde_json::from_str(
+            r#"[{"prefix": "/api", "upstream": "api"}, {"prefix": "/api/mcp", "upstream": "mcp"}]"#,
+        )
+        .unwrap();
+        let config = gateway(routes);
+        assert_eq!(config.route("/api/mcp/session"), GatewayTarget::Mcp);
+        assert_eq!(config.route("/api/v1"), GatewayTarget::Api);
+        assert_eq!(config.route("/v1/missions"), GatewayTarget::Web);
+    }
+
+    #[test]
+    fn gateway_owned_headers_are_always_stripped_and_never_configurable() {
+        let rules = GatewayHeaderRules {
+            strip: vec!["x-debug".to_string(), "X-C2-Client".to_string()],
+            set: BTreeMap::new(),
+        };
+        let stripped: Vec<&str> = rules.stripped().collect();
+        assert_eq!(stripped, ["x-c2-client", "x-forwarded-proto", "x-real-ip", "x-debug"]);
+        assert!(GatewayHeaderRules::default().stripped().any(|name| name == "x-c2-client"));
+
+        let spoofing = GatewayHeaderRules {
+            strip: Vec::new(),
+            set: BTreeMap::from([("x-c2-client".to_string(), "token".to_string())]),
+        };
+        assert!(spoofing.validate().is_err());
+        let invalid = GatewayHeaderRules {
+            strip: vec!["x debug".to_string()],
+            set: BTreeMap::new(),
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    fn service_config(vars: &[(&str, &str)]) -> ServiceConfig {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        ServiceConfig::from_lookup("c2-test", |key| vars.get(key).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn prod_fails_closed_where_local_relaxes() {
+        let prod = service_config(&[("C2_ENV", "prod")]);
+        let local = service_config(&[("C2_ENV", "local")]);
+
+        assert_eq!(prod.environment, Environment::Prod);
+        assert_eq!((prod.log_level.as_str(), prod.strict_page_size), ("info", true));
+        assert_eq!(local.environment, Environment::Local);
+        assert_eq!((local.log_level.as_str(), local.strict_page_size), ("debug", false));
+
+        let anonymous = GatewayAuthConfig {
+            api_token: None,
+            ..GatewayAuthConfig::from_env()
+        };
+        assert!(prod.require_auth);
+        assert!(anonymous.validate(prod.require_auth).is_err());
+        assert!(!local.require_auth);
+        assert!(anonymous.validate(local.require_auth).is_ok());
+    }
+
+    #[test]
+    fn page_limits_default_cap_or_reject_oversized_requests() {
+        let capped = service_config(&[("C2_MAX_PAGE_SIZE", "2")]);
+        assert_eq!(capped.page_limit(Some(1_000)), Some(2));
+        assert_eq!(capped.page_limit(Some(2)), Some(2));
+        assert_eq!(capped.page_limit(Some(0)), Some(0));
+        assert_eq!(capped.page_limit(None), Some(2));
+
+        let roomy = service_config(&[]);
+        assert_eq!(roomy.page_limit(None), Some(DEFAULT_PAGE_SIZE));
+        assert_eq!(roomy.page_limit(Some(1_000)), Some(500));
+
+        let strict = service_config(&[("C2_MAX_PAGE_SIZE", "2"), ("C2_STRICT_PAGE_SIZE", "true")]);
+        assert_eq!(strict.page_limit(Some(1_000)), None);
+        assert_eq!(strict.page_limit(Some(1)), Some(1));
+        assert_eq!(service_config(&[("C2_MAX_PAGE_SIZE", "0")]).max_page_size, 1);
+    }
 }
@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Error text for writes rejected while a service is read-only.
+pub const READ_ONLY_MESSAGE: &str = "service read-only";
+
+/// Runtime read-only switch, seeded from `C2_READ_ONLY` and flipped by
+/// operators while the service runs (for example around a migration). Reads
+/// keep serving; handlers reject writes while it is enabled.
+///
+/// ```
+/// use c2_config::ReadOnlyMode;
+///
+/// let mode = ReadOnlyMode::new(false);
+/// assert!(mode.toggle());
+/// assert!(mode.is_enabled());
+/// assert!(mode.set(false));
+/// assert!(!mode.is_enabled());
+/// ```
+#[derive(Debug, Default)]
+pub struct ReadOnlyMode {
+    enabled: AtomicBool,
+}
+
+impl ReadOnlyMode {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Sets the mode and returns the previous one.
+    pub fn set(&self, enabled: bool) -> bool {
+        self.enabled.swap(enabled, Ordering::Relaxed)
+    }
+
+    /// Flips the mode and returns the new one.
+    pub fn toggle(&self) -> bool {
+        !self.enabled.fetch_xor(true, Ordering::Relaxed)
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum SecurityClassification {
     Unclassified,
@@ -12,6 +13,45 @@ pub enum SecurityClassification {
     TopSecret,
 }
 
+impl SecurityClassification {
+    pub fn level(self) -> u8 {
+        self as u8
+    }
+
+    /// Banner text for the level, as shown at the top and bottom of a
+    /// classified view. Controlled unclassified information is `CUI`.
+    ///
+    /// ```
+    /// use c2_core::SecurityClassification;
+    ///
+    /// assert_eq!(SecurityClassification::TopSecret.marking(), "TOP SECRET");
+    /// ```
+    pub fn marking(self) -> &'static str {
+        match self {
+            Self::Unclassified => "UNCLASSIFIED",
+            Self::Controlled => "CUI",
+            Self::Restricted => "RESTRICTED",
+            Self::Confidential => "CONFIDENTIAL",
+            Self::Secret => "SECRET",
+            Self::TopSecret => "TOP SECRET",
+        }
+    }
+
+    /// Banner background as a CSS hex color, following the usual marking
+    /// colors (green, purple, blue, red, orange); restricted has no common
+    /// standard and uses brown.
+    pub fn banner_color(self) -> &'static str {
+        match self {
+            Self::Unclassified => "#007A33",
+            Self::Controlled => "#502B85",
+            Self::Restricted => "#8B5E3C",
+            Self::Confidential => "#0033A0",
+            Self::Secret => "#C8102E",
+            Self::TopSecret => "#FF8C00",
+        }
+    }
+}
+
 impl Default for SecurityClassification {
     fn default() -> Self {
         Self::Unclassified
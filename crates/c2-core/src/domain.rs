@@ -1,13 +1,17 @@
 use crate::classification::SecurityClassification;
 use crate::ids::{
-    AssetId, CapabilityId, IncidentId, MissionId, TaskId, TeamId, TenantId, UnitId,
+    AssetId, AssignmentId, CapabilityId, GeofenceId, IncidentId, MissionId, TaskId, TeamId,
+    TenantId, UnitId,
 };
 use crate::time::EpochMillis;
+use c2_geo::{Coordinate, GeoFence};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum OperationalPriority {
+    #[default]
     Routine,
     Elevated,
     Urgent,
@@ -15,6 +19,7 @@ pub enum OperationalPriority {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum MissionStatus {
     Planned,
@@ -24,7 +29,16 @@ pub enum MissionStatus {
     Aborted,
 }
 
+impl MissionStatus {
+    /// Completed and aborted missions are final; any other status may change
+    /// freely. Keeping the current status is always allowed.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        self == next || !matches!(self, Self::Completed | Self::Aborted)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TaskStatus {
     Pending,
@@ -34,7 +48,16 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+impl TaskStatus {
+    /// Completed and cancelled tasks are final, like
+    /// [`MissionStatus::can_transition_to`].
+    pub fn can_transition_to(self, next: Self) -> bool {
+        self == next || !matches!(self, Self::Completed | Self::Cancelled)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AssetStatus {
     Available,
@@ -45,6 +68,7 @@ pub enum AssetStatus {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReadinessState {
     Ready,
@@ -60,6 +84,7 @@ impl Default for ReadinessState {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CommsStatus {
     Online,
@@ -75,6 +100,7 @@ impl Default for CommsStatus {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum MaintenanceState {
     None,
@@ -90,7 +116,8 @@ impl Default for MaintenanceState {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AssetKind {
     Personnel,
@@ -105,6 +132,7 @@ pub enum AssetKind {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum IncidentType {
     Defense,
@@ -118,6 +146,7 @@ pub enum IncidentType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum IncidentStatus {
     Reported,
@@ -129,6 +158,7 @@ pub enum IncidentStatus {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Mission {
     pub id: MissionId,
     pub tenant_id: TenantId,
@@ -138,9 +168,21 @@ pub struct Mission {
     pub classification: SecurityClassification,
     pub created_at_ms: EpochMillis,
     pub updated_at_ms: EpochMillis,
+    /// When the mission was archived. Archived missions stay readable by id
+    /// and are never removed, but default listings leave them out. Deleting a
+    /// mission removes it outright instead.
+    #[serde(default)]
+    pub archived_at_ms: Option<EpochMillis>,
+}
+
+impl Mission {
+    pub fn is_archived(&self) -> bool {
+        self.archived_at_ms.is_some()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Task {
     pub id: TaskId,
     pub mission_id: MissionId,
@@ -149,11 +191,88 @@ pub struct Task {
     pub status: TaskStatus,
     pub priority: OperationalPriority,
     pub classification: SecurityClassification,
+    /// Tasks that must complete before this one can proceed. The worker keeps
+    /// the task blocked until they have.
+    #[serde(default)]
+    pub depends_on: Vec<TaskId>,
     pub created_at_ms: EpochMillis,
     pub updated_at_ms: EpochMillis,
 }
 
+impl Task {
+    /// Status change implied by the state of `depends_on`: a pending or in
+    /// progress task blocks while any dependency is incomplete, and a blocked
+    /// task goes back to pending once they all complete. Tasks without
+    /// dependencies are left alone, so a manual block stays in place.
+    pub fn dependency_transition(&self, dependencies_complete: bool) -> Option<TaskStatus> {
+        if self.depends_on.is_empty() {
+            return None;
+        }
+        match self.status {
+            TaskStatus::Pending | TaskStatus::InProgress if !dependencies_complete => {
+                Some(TaskStatus::Blocked)
+            }
+            TaskStatus::Blocked if dependencies_complete => Some(TaskStatus::Pending),
+            _ => None,
+        }
+    }
+}
+
+/// Task counts behind a mission's progress. Cancelled tasks are left out, so
+/// they neither keep a mission open nor count as done.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionProgress {
+    pub total: u64,
+    pub completed: u64,
+}
+
+impl MissionProgress {
+    /// Builds the progress from per-status task counts.
+    ///
+    /// ```
+    /// use c2_core::{MissionProgress, TaskStatus};
+    ///
+    /// let progress = MissionProgress::from_counts([
+    ///     (TaskStatus::Completed, 2),
+    ///     (TaskStatus::Pending, 1),
+    ///     (TaskStatus::Blocked, 1),
+    ///     (TaskStatus::Cancelled, 3),
+    /// ]);
+    /// assert_eq!(progress.fraction(), 0.5);
+    /// assert_eq!(progress.open(), 2);
+    /// ```
+    pub fn from_counts(counts: impl IntoIterator<Item = (TaskStatus, u64)>) -> Self {
+        counts
+            .into_iter()
+            .fold(Self::default(), |mut progress, (status, count)| {
+                match status {
+                    TaskStatus::Cancelled => {}
+                    TaskStatus::Completed => {
+                        progress.total += count;
+                        progress.completed += count;
+                    }
+                    _ => progress.total += count,
+                }
+                progress
+            })
+    }
+
+    /// Tasks still pending, in progress or blocked.
+    pub fn open(&self) -> u64 {
+        self.total - self.completed
+    }
+
+    /// Share of tasks completed, `0.0` for a mission without tasks.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.completed as f64 / self.total as f64
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Asset {
     pub id: AssetId,
     pub tenant_id: TenantId,
@@ -166,15 +285,102 @@ pub struct Asset {
     pub comms_status: CommsStatus,
     #[serde(default)]
     pub maintenance_state: MaintenanceState,
+    /// Start and end of the scheduled maintenance window.
+    #[serde(default)]
+    pub maintenance_window: Option<(EpochMillis, EpochMillis)>,
     #[serde(default)]
     pub unit_id: Option<UnitId>,
     #[serde(default)]
     pub capability_ids: Vec<CapabilityId>,
+    #[serde(default)]
+    pub position: Option<Coordinate>,
     pub classification: SecurityClassification,
     pub created_at_ms: EpochMillis,
     pub updated_at_ms: EpochMillis,
 }
 
+impl Asset {
+    pub fn status_differs(&self, other: &Asset) -> bool {
+        self.readiness != other.readiness
+            || self.comms_status != other.comms_status
+            || self.maintenance_state != other.maintenance_state
+    }
+
+    /// Next maintenance state once the window boundaries have passed:
+    /// scheduled becomes in progress at the start, and scheduled or in
+    /// progress becomes complete at the end.
+    pub fn maintenance_transition(&self, now_ms: EpochMillis) -> Option<MaintenanceState> {
+        let (start_ms, end_ms) = self.maintenance_window?;
+        match self.maintenance_state {
+            MaintenanceState::Scheduled | MaintenanceState::InProgress if now_ms >= end_ms => {
+                Some(MaintenanceState::Complete)
+            }
+            MaintenanceState::Scheduled if now_ms >= start_ms => Some(MaintenanceState::InProgress),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AssetStatusEvent {
+    pub asset_id: AssetId,
+    pub tenant_id: TenantId,
+    pub readiness: ReadinessState,
+    pub comms_status: CommsStatus,
+    pub maintenance_state: MaintenanceState,
+    pub recorded_at_ms: EpochMillis,
+}
+
+impl From<&Asset> for AssetStatusEvent {
+    fn from(asset: &Asset) -> Self {
+        Self {
+            asset_id: asset.id,
+            tenant_id: asset.tenant_id,
+            readiness: asset.readiness,
+            comms_status: asset.comms_status,
+            maintenance_state: asset.maintenance_state,
+            recorded_at_ms: asset.updated_at_ms,
+        }
+    }
+}
+
+/// A unit or team put on a mission. Exactly one of `unit_id` and `team_id`
+/// is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionAssignment {
+    pub id: AssignmentId,
+    pub tenant_id: TenantId,
+    pub mission_id: MissionId,
+    #[serde(default)]
+    pub unit_id: Option<UnitId>,
+    #[serde(default)]
+    pub team_id: Option<TeamId>,
+    /// What the unit or team does on the mission, such as `lead` or `support`.
+    pub role: String,
+    /// Copied from the mission when assigned.
+    pub classification: SecurityClassification,
+    pub assigned_at_ms: EpochMillis,
+}
+
+impl MissionAssignment {
+    /// Stable id for one unit or team on one mission, so assigning it again
+    /// updates the role instead of adding a second record.
+    pub fn derive_id(
+        tenant_id: TenantId,
+        mission_id: MissionId,
+        unit_id: Option<UnitId>,
+        team_id: Option<TeamId>,
+    ) -> AssignmentId {
+        let assignee = match (unit_id, team_id) {
+            (Some(unit_id), _) => format!("unit/{unit_id}"),
+            (None, Some(team_id)) => format!("team/{team_id}"),
+            (None, None) => String::new(),
+        };
+        AssignmentId::from_name(tenant_id, &format!("{mission_id}/{assignee}"))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Unit {
     pub id: UnitId,
@@ -218,14 +424,57 @@ pub struct Capability {
     pub updated_at_ms: EpochMillis,
 }
 
+/// One distinct code/category/classification combination in a tenant's
+/// capability catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityCode {
+    pub code: String,
+    pub category: Option<String>,
+    pub classification: SecurityClassification,
+}
+
+/// How many of a tenant's records share `key` at one classification. Counts
+/// are split by classification so callers can drop what a reader may not see.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupCount<K> {
+    pub key: K,
+    pub classification: SecurityClassification,
+    pub count: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Incident {
     pub id: IncidentId,
     pub tenant_id: TenantId,
     pub incident_type: IncidentType,
     pub status: IncidentStatus,
+    #[serde(default)]
+    pub priority: OperationalPriority,
     pub summary: String,
+    #[serde(default)]
+    pub position: Option<Coordinate>,
+    /// Radius of the affected area around `position`, in metres.
+    #[serde(default)]
+    pub affected_radius_m: Option<f64>,
     pub classification: SecurityClassification,
     pub created_at_ms: EpochMillis,
     pub updated_at_ms: EpochMillis,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Geofence {
+    pub id: GeofenceId,
+    pub tenant_id: TenantId,
+    pub name: String,
+    pub fence: GeoFence,
+    #[serde(default = "default_true")]
+    pub active: bool,
+    pub classification: SecurityClassification,
+    pub created_at_ms: EpochMillis,
+    pub updated_at_ms: EpochMillis,
+}
+
+fn default_true() -> bool {
+    true
+}
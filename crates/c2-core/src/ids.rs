@@ -1,10 +1,31 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIdError {
+    pub message: String,
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseIdError {}
+
+fn parse_uuid(value: &str) -> Result<Uuid, ParseIdError> {
+    Uuid::parse_str(value).map_err(|err| ParseIdError {
+        message: format!("invalid id {value:?}: {err}"),
+    })
+}
+
 macro_rules! id_type {
-    ($name:ident) => {
+    ($name:ident, $tag:literal) => {
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
         pub struct $name(Uuid);
 
         impl $name {
@@ -12,6 +33,16 @@ macro_rules! id_type {
                 Self(Uuid::new_v4())
             }
 
+            /// Stable UUIDv5 for `name` in the tenant's namespace, for seeders
+            /// that must not create duplicates when re-run.
+            pub fn from_name(tenant: TenantId, name: &str) -> Self {
+                Self(derive_v5(
+                    tenant.0,
+                    concat!(stringify!($name), ":"),
+                    name,
+                ))
+            }
+
             pub fn from_uuid(value: Uuid) -> Self {
                 Self(value)
             }
@@ -19,6 +50,21 @@ macro_rules! id_type {
             pub fn as_uuid(&self) -> Uuid {
                 self.0
             }
+
+            /// `<tag>_<uuid>`, e.g. `mission_<uuid>`, for logs and external
+            /// references. Storage keeps the plain UUID from `Display`.
+            pub fn to_tagged_string(&self) -> String {
+                format!(concat!($tag, "_{}"), self.0)
+            }
+
+            /// Parses the [`Self::to_tagged_string`] form; an id tagged for
+            /// another type is rejected.
+            pub fn from_tagged_str(value: &str) -> Result<Self, ParseIdError> {
+                let uuid = value.strip_prefix(concat!($tag, "_")).ok_or_else(|| ParseIdError {
+                    message: format!(concat!("expected ", $tag, "_<uuid>, got {:?}"), value),
+                })?;
+                parse_uuid(uuid).map(Self)
+            }
         }
 
         impl fmt::Display for $name {
@@ -26,17 +72,84 @@ macro_rules! id_type {
                 write!(f, "{}", self.0)
             }
         }
+
+        /// Parses the plain UUID written by `Display`.
+        impl FromStr for $name {
+            type Err = ParseIdError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                parse_uuid(value).map(Self)
+            }
+        }
     };
 }
 
-id_type!(TenantId);
-id_type!(UserId);
-id_type!(UnitId);
-id_type!(TeamId);
-id_type!(CapabilityId);
-id_type!(MissionId);
-id_type!(TaskId);
-id_type!(AssetId);
-id_type!(IncidentId);
-id_type!(MessageId);
-id_type!(CorrelationId);
+id_type!(TenantId, "tenant");
+id_type!(UserId, "user");
+id_type!(UnitId, "unit");
+id_type!(TeamId, "team");
+id_type!(CapabilityId, "capability");
+id_type!(MissionId, "mission");
+id_type!(TaskId, "task");
+id_type!(AssetId, "asset");
+id_type!(IncidentId, "incident");
+id_type!(GeofenceId, "geofence");
+id_type!(AssignmentId, "assignment");
+id_type!(MessageId, "message");
+id_type!(CorrelationId, "correlation");
+id_type!(ApiKeyId, "api_key");
+
+impl CorrelationId {
+    /// The id carried by an incoming correlation header, in plain or tagged
+    /// form, or a fresh one when the value is missing or unreadable.
+    pub fn from_header(value: Option<&str>) -> Self {
+        value
+            .map(str::trim)
+            .and_then(|value| value.parse().or_else(|_| Self::from_tagged_str(value)).ok())
+            .unwrap_or_else(Self::new)
+    }
+}
+
+/// UUIDv5 of `kind` followed by `name` under `namespace`. The kind prefix keeps
+/// a mission and an asset seeded under the same name apart.
+fn derive_v5(namespace: Uuid, kind: &str, name: &str) -> Uuid {
+    Uuid::new_v5(&namespace, format!("{kind}{name}").as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name_is_stable_and_scoped_to_tenant_and_type() {
+        let tenant = TenantId::new();
+        let mission = MissionId::from_name(tenant, "Harbor watch");
+        assert_eq!(mission, MissionId::from_name(tenant, "Harbor watch"));
+        assert_eq!(mission.as_uuid().get_version_num(), 5);
+
+        assert_ne!(mission, MissionId::from_name(TenantId::new(), "Harbor watch"));
+        assert_ne!(mission, MissionId::from_name(tenant, "Harbor patrol"));
+        assert_ne!(mission.as_uuid(), AssetId::from_name(tenant, "Harbor watch").as_uuid());
+
+        let tenant = TenantId::from_uuid(Uuid::from_u128(0x0000_0001));
+        let fixed = MissionId::from_name(tenant, "Harbor watch");
+        assert_eq!(fixed.to_string(), "0f3cc41d-0dc3-5c5b-940d-e29bf3a2e079");
+    }
+
+    #[test]
+    fn tagged_ids_round_trip_and_reject_another_type() {
+        let mission = MissionId::new();
+        let tagged = mission.to_tagged_string();
+        assert_eq!(tagged, format!("mission_{mission}"));
+        assert_eq!(MissionId::from_tagged_str(&tagged), Ok(mission));
+        assert_eq!(mission.to_string().parse::<MissionId>(), Ok(mission));
+
+        let asset = AssetId::from_uuid(mission.as_uuid()).to_tagged_string();
+        let err = MissionId::from_tagged_str(&asset).unwrap_err();
+        assert_eq!(err.message, format!("expected mission_<uuid>, got {asset:?}"));
+        assert!(MissionId::from_tagged_str(&mission.to_string()).is_err());
+        assert!(MissionId::from_tagged_str("mission_not-a-uuid").is_err());
+        let api_key = ApiKeyId::from_uuid(mission.as_uuid());
+        assert_eq!(api_key.to_tagged_string(), format!("api_key_{mission}"));
+    }
+}
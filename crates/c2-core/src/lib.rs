@@ -2,17 +2,22 @@ pub mod classification;
 pub mod domain;
 pub mod error;
 pub mod ids;
+pub mod symbology;
 pub mod time;
 
 pub use classification::SecurityClassification;
 pub use domain::{
-    Asset, AssetKind, AssetStatus, Capability, CommsStatus, Incident, IncidentStatus,
-    IncidentType, MaintenanceState, Mission, MissionStatus, OperationalPriority, ReadinessState,
-    Task, TaskStatus, Team, Unit,
+    Asset, AssetKind, AssetStatus, AssetStatusEvent, Capability, CapabilityCode, CommsStatus,
+    Geofence, GroupCount, Incident, IncidentStatus, IncidentType, MaintenanceState, Mission,
+    MissionAssignment, MissionProgress, MissionStatus, OperationalPriority, ReadinessState, Task,
+    TaskStatus, Team, Unit,
 };
 pub use error::{C2Error, C2Result, ErrorCode};
 pub use ids::{
-    AssetId, CapabilityId, CorrelationId, IncidentId, MessageId, MissionId, TaskId, TeamId,
-    TenantId, UnitId, UserId,
+    ApiKeyId, AssetId, AssignmentId, CapabilityId, CorrelationId, GeofenceId, IncidentId,
+    MessageId, MissionId, ParseIdError, TaskId, TeamId, TenantId, UnitId, UserId,
+};
+pub use symbology::{sidc_for, SymbolTable};
+pub use time::{
+    from_rfc3339, now_epoch_millis, to_rfc3339, Clock, EpochMillis, FixedClock, SystemClock,
 };
-pub use time::{now_epoch_millis, EpochMillis};
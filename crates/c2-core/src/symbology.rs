@@ -0,0 +1,207 @@
+//! MIL-STD-2525C / APP-6 symbol identification codes (SIDC) for assets.
+//!
+//! Codes are the 15-character warfighting form, always friendly:
+//!
+//! | Position | Meaning          | Source                                   |
+//! |----------|------------------|------------------------------------------|
+//! | 1        | coding scheme    | `S` (warfighting)                        |
+//! | 2        | affiliation      | `F` (friend)                             |
+//! | 3        | battle dimension | per kind, see below                      |
+//! | 4        | status           | `P` present, `D` degraded/maintenance, `X` lost |
+//! | 5-10     | function id      | per kind, see below                      |
+//! | 11-15    | modifiers        | `-----`                                  |
+//!
+//! | Kind           | Dimension | Function id |
+//! |----------------|-----------|-------------|
+//! | `personnel`    | `G`       | `UCI---`    |
+//! | `drone`        | `A`       | `MFQ---`    |
+//! | `ugv`          | `G`       | `EVAU--`    |
+//! | `vehicle`      | `G`       | `EVA---`    |
+//! | `aircraft`     | `A`       | `MF----`    |
+//! | `sensor`       | `G`       | `ES----`    |
+//! | `comms_relay`  | `G`       | `UUS---`    |
+//! | `command_post` | `G`       | `UH----`    |
+//! | `other`        | `G`       | `------`    |
+//!
+//! Entries can be replaced per kind with `C2_SIDC_OVERRIDES`, e.g.
+//! `drone=A:MFQR--,vehicle=S:------` (dimension `P`, `A`, `G`, `S` or `U`).
+
+use crate::domain::{AssetKind, AssetStatus};
+use std::collections::HashMap;
+use std::env;
+use std::sync::OnceLock;
+
+const FUNCTION_ID_LEN: usize = 6;
+
+static INSTALLED: OnceLock<SymbolTable> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleDimension {
+    Space,
+    Air,
+    Ground,
+    SeaSurface,
+    Subsurface,
+}
+
+impl BattleDimension {
+    pub fn code(self) -> char {
+        match self {
+            Self::Space => 'P',
+            Self::Air => 'A',
+            Self::Ground => 'G',
+            Self::SeaSurface => 'S',
+            Self::Subsurface => 'U',
+        }
+    }
+
+    fn from_code(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "P" => Some(Self::Space),
+            "A" => Some(Self::Air),
+            "G" => Some(Self::Ground),
+            "S" => Some(Self::SeaSurface),
+            "U" => Some(Self::Subsurface),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolEntry {
+    pub dimension: BattleDimension,
+    pub function_id: String,
+}
+
+impl SymbolEntry {
+    fn new(dimension: BattleDimension, function_id: &str) -> Self {
+        Self {
+            dimension,
+            function_id: function_id.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    overrides: HashMap<AssetKind, SymbolEntry>,
+}
+
+impl SymbolTable {
+    /// Standard table plus `C2_SIDC_OVERRIDES`, if set.
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("C2_SIDC_OVERRIDES") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Parses `<kind>=<dimension>:<function id>` pairs separated by commas.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut overrides = HashMap::new();
+        for pair in value.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let invalid = || format!("invalid C2_SIDC_OVERRIDES entry {pair:?}");
+            let (kind, entry) = pair.split_once('=').ok_or_else(invalid)?;
+            let (dimension, function_id) = entry.split_once(':').ok_or_else(invalid)?;
+            let kind = parse_kind(kind).ok_or_else(invalid)?;
+            let dimension = BattleDimension::from_code(dimension).ok_or_else(invalid)?;
+            let function_id = function_id.trim().to_ascii_uppercase();
+            if function_id.len() > FUNCTION_ID_LEN
+                || !function_id
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+            {
+                return Err(invalid());
+            }
+            overrides.insert(kind, SymbolEntry::new(dimension, &function_id));
+        }
+        Ok(Self { overrides })
+    }
+
+    /// Makes this table the one used by [`sidc_for`]. Only the first call
+    /// takes effect.
+    pub fn install(self) {
+        let _ = INSTALLED.set(self);
+    }
+
+    pub fn entry(&self, kind: AssetKind) -> SymbolEntry {
+        self.overrides
+            .get(&kind)
+            .cloned()
+            .unwrap_or_else(|| standard_entry(kind))
+    }
+
+    pub fn sidc(&self, kind: AssetKind, status: AssetStatus) -> String {
+        let entry = self.entry(kind);
+        let status = match status {
+            AssetStatus::Available | AssetStatus::Assigned => 'P',
+            AssetStatus::Degraded | AssetStatus::Maintenance => 'D',
+            AssetStatus::Lost => 'X',
+        };
+        format!(
+            "SF{}{}{:-<width$}-----",
+            entry.dimension.code(),
+            status,
+            entry.function_id,
+            width = FUNCTION_ID_LEN
+        )
+    }
+}
+
+/// SIDC for an asset using the installed table, or the standard one.
+pub fn sidc_for(kind: AssetKind, status: AssetStatus) -> String {
+    INSTALLED.get_or_init(SymbolTable::default).sidc(kind, status)
+}
+
+fn standard_entry(kind: AssetKind) -> SymbolEntry {
+    use BattleDimension::{Air, Ground};
+    match kind {
+        AssetKind::Personnel => SymbolEntry::new(Ground, "UCI---"),
+        AssetKind::Drone => SymbolEntry::new(Air, "MFQ---"),
+        AssetKind::Ugv => SymbolEntry::new(Ground, "EVAU--"),
+        AssetKind::Vehicle => SymbolEntry::new(Ground, "EVA---"),
+        AssetKind::Aircraft => SymbolEntry::new(Air, "MF----"),
+        AssetKind::Sensor => SymbolEntry::new(Ground, "ES----"),
+        AssetKind::CommsRelay => SymbolEntry::new(Ground, "UUS---"),
+        AssetKind::CommandPost => SymbolEntry::new(Ground, "UH----"),
+        AssetKind::Other => SymbolEntry::new(Ground, "------"),
+    }
+}
+
+fn parse_kind(value: &str) -> Option<AssetKind> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "personnel" => Some(AssetKind::Personnel),
+        "drone" => Some(AssetKind::Drone),
+        "ugv" => Some(AssetKind::Ugv),
+        "vehicle" => Some(AssetKind::Vehicle),
+        "aircraft" => Some(AssetKind::Aircraft),
+        "sensor" => Some(AssetKind::Sensor),
+        "comms_relay" => Some(AssetKind::CommsRelay),
+        "command_post" => Some(AssetKind::CommandPost),
+        "other" => Some(AssetKind::Other),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aircraft_are_air_symbols_and_overrides_can_put_a_kind_at_sea() {
+        let standard = SymbolTable::default();
+        assert_eq!(standard.sidc(AssetKind::Aircraft, AssetStatus::Available), "SFAPMF---------");
+        assert_eq!(standard.sidc(AssetKind::Vehicle, AssetStatus::Lost), "SFGXEVA--------");
+
+        // There is no ship kind; a deployment with boats maps one onto the sea.
+        let table = SymbolTable::parse("other=s:cl, drone=A:MFQR--").unwrap();
+        assert_eq!(table.entry(AssetKind::Other).dimension, BattleDimension::SeaSurface);
+        assert_eq!(table.sidc(AssetKind::Other, AssetStatus::Degraded), "SFSDCL---------");
+        assert_eq!(table.sidc(AssetKind::Drone, AssetStatus::Assigned), "SFAPMFQR-------");
+        assert_eq!(table.sidc(AssetKind::Aircraft, AssetStatus::Available), "SFAPMF---------");
+
+        assert!(SymbolTable::parse("ship=S:CL").is_err());
+        assert!(SymbolTable::parse("other=X:CL").is_err());
+        assert!(SymbolTable::parse("other=S:CLXXXXX").is_err());
+    }
+}
@@ -1,5 +1,9 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::error::{C2Error, C2Result, ErrorCode};
+
 pub type EpochMillis = u64;
 
 pub fn now_epoch_millis() -> EpochMillis {
@@ -8,3 +12,111 @@ pub fn now_epoch_millis() -> EpochMillis {
         .unwrap_or_default();
     duration.as_millis() as EpochMillis
 }
+
+/// Formats `ms` as an RFC 3339 UTC timestamp with millisecond precision.
+/// Values past the largest representable date clamp to it.
+///
+/// ```
+/// use c2_core::{from_rfc3339, to_rfc3339};
+///
+/// assert_eq!(to_rfc3339(1_700_000_000_123), "2023-11-14T22:13:20.123Z");
+/// assert_eq!(from_rfc3339(&to_rfc3339(1_700_000_000_123)).unwrap(), 1_700_000_000_123);
+/// ```
+pub fn to_rfc3339(ms: EpochMillis) -> String {
+    i64::try_from(ms)
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Parses an RFC 3339 timestamp in any offset into epoch millis. Precision
+/// below a millisecond is truncated; times before the epoch are rejected.
+///
+/// ```
+/// use c2_core::{from_rfc3339, ErrorCode};
+///
+/// assert_eq!(from_rfc3339("2023-11-15T00:13:20.5+02:00").unwrap(), 1_700_000_000_500);
+/// assert_eq!(from_rfc3339("2023-11-14 22:13").unwrap_err().code, ErrorCode::InvalidInput);
+/// ```
+pub fn from_rfc3339(value: &str) -> C2Result<EpochMillis> {
+    let parsed = DateTime::parse_from_rfc3339(value.trim()).map_err(|err| {
+        C2Error::new(
+            ErrorCode::InvalidInput,
+            format!("invalid RFC 3339 timestamp {value:?}: {err}"),
+        )
+    })?;
+    EpochMillis::try_from(parsed.timestamp_millis()).map_err(|_| {
+        C2Error::new(
+            ErrorCode::InvalidInput,
+            format!("timestamp {value:?} is before the Unix epoch"),
+        )
+    })
+}
+
+/// Source of the current time for code that schedules or expires things, so
+/// it can run against a controlled clock.
+pub trait Clock: Send + Sync {
+    fn now_ms(&self) -> EpochMillis;
+}
+
+/// The system clock, via [`now_epoch_millis`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> EpochMillis {
+        now_epoch_millis()
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Debug, Default)]
+pub struct FixedClock {
+    now_ms: AtomicU64,
+}
+
+impl FixedClock {
+    pub fn new(now_ms: EpochMillis) -> Self {
+        Self {
+            now_ms: AtomicU64::new(now_ms),
+        }
+    }
+
+    pub fn set(&self, now_ms: EpochMillis) {
+        self.now_ms.store(now_ms, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, delta_ms: EpochMillis) {
+        self.now_ms.fetch_add(delta_ms, Ordering::SeqCst);
+    }
+}
+
+impl Clock for FixedClock {
+    fn now_ms(&self) -> EpochMillis {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc3339_round_trips_millis_and_rejects_malformed_input() {
+        for ms in [0, 1_700_000_000_123, 1_700_000_000_999] {
+            assert_eq!(from_rfc3339(&to_rfc3339(ms)).unwrap(), ms);
+        }
+        assert_eq!(to_rfc3339(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(from_rfc3339(" 2023-11-14T22:13:20.1239Z ").unwrap(), 1_700_000_000_123);
+        assert_eq!(from_rfc3339("2023-11-14T23:13:20.123+01:00").unwrap(), 1_700_000_000_123);
+
+        let malformed = ["", "yesterday", "2023-11-14", "2023-11-14T22:13:20", "2023-13-01T00:00Z"];
+        for malformed in malformed {
+            let err = from_rfc3339(malformed).unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidInput, "{malformed:?}");
+        }
+        let err = from_rfc3339("1969-12-31T23:59:59Z").unwrap_err();
+        assert!(err.message.contains("before the Unix epoch"));
+    }
+}
@@ -1,5 +1,6 @@
 use bevy_ecs::prelude::*;
 use bevy_ecs::schedule::Schedule;
+use c2_geo::{great_circle_arc, Coordinate};
 use std::cell::RefCell;
 use std::collections::HashMap;
 
@@ -21,6 +22,38 @@ const KIND_MAX: usize = 8;
 const DEFAULT_ALTITUDE: f32 = 0.0;
 const DEFAULT_SIZE: f32 = 6.0;
 const DEFAULT_HEADING: f32 = 0.0;
+/// Heading sentinel: any non-finite heading (NaN by convention) asks the ECS
+/// to derive the facing from the entity's previous position.
+pub const ECS_HEADING_DERIVE: f32 = f32::NAN;
+/// Alpha scale for stale entities when no removal age is set to fade towards.
+const STALE_ALPHA_SCALE: f32 = 0.25;
+/// Mean Earth radius, matching `c2_geo`'s great-circle distance.
+const DEFAULT_EARTH_RADIUS_M: f64 = 6_371_008.8;
+const DEFAULT_ARC_SEGMENTS: u32 = 64;
+const MAX_ARC_SEGMENTS: u32 = 512;
+
+/// Status codes returned by the commit functions and `ecs_last_error`.
+/// Commits return the applied record count on success.
+pub const ECS_OK: i32 = 0;
+pub const ECS_ERR_IDS_SHORT: i32 = -1;
+pub const ECS_ERR_GEOS_SHORT: i32 = -2;
+pub const ECS_ERR_KINDS_SHORT: i32 = -3;
+pub const ECS_ERR_ALTS_SHORT: i32 = -4;
+pub const ECS_ERR_SIZES_SHORT: i32 = -5;
+pub const ECS_ERR_HEADINGS_SHORT: i32 = -6;
+pub const ECS_ERR_COLORS_SHORT: i32 = -7;
+pub const ECS_ERR_KIND_OUT_OF_RANGE: i32 = -8;
+pub const ECS_ERR_NULL_POINTER: i32 = -9;
+pub const ECS_ERR_SNAPSHOT_INVALID: i32 = -10;
+pub const ECS_ERR_SNAPSHOT_VERSION: i32 = -11;
+
+/// Snapshot blob: `SNAPSHOT_MAGIC`, a little-endian `u16` version, two
+/// reserved bytes, a `u32` entity count, then one little-endian
+/// `EcsIngestRecord` per entity.
+const SNAPSHOT_MAGIC: [u8; 4] = *b"C2EC";
+const SNAPSHOT_VERSION: u16 = 1;
+const SNAPSHOT_HEADER_LEN: usize = 12;
+const SNAPSHOT_RECORD_LEN: usize = 40;
 
 #[derive(Component, Debug, Clone, Copy)]
 struct Altitude(f32);
@@ -28,6 +61,15 @@ struct Altitude(f32);
 #[derive(Component, Debug, Clone, Copy)]
 struct Heading(f32);
 
+/// Tick clock (ms) at the entity's last upsert.
+#[derive(Component, Debug, Clone, Copy)]
+struct LastUpdate(f64);
+
+/// Upsert order, for evicting the least recently updated entities when the
+/// world is over `max_entities`.
+#[derive(Component, Debug, Clone, Copy)]
+struct UpdateSeq(u64);
+
 #[derive(Component, Debug, Clone, Copy)]
 struct RenderSize(f32);
 
@@ -63,11 +105,125 @@ struct Cartesian {
     z: f32,
 }
 
+/// One entity in the packed ingest layout (40 bytes, little-endian).
+/// Callers fill a contiguous array of these instead of the parallel
+/// `ecs_ingest_*` buffers.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct EcsIngestRecord {
+    pub id: u64,
+    pub lat_deg: f32,
+    pub lon_deg: f32,
+    pub altitude: f32,
+    pub heading: f32,
+    pub size: f32,
+    pub kind: u8,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+    pub _reserved: [u8; 7],
+}
+
+impl EcsIngestRecord {
+    fn write_le(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.id.to_le_bytes());
+        for value in [self.lat_deg, self.lon_deg, self.altitude, self.heading, self.size] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(&[self.kind, self.r, self.g, self.b, self.a]);
+        out.extend_from_slice(&[0; 7]);
+    }
+
+    fn read_le(bytes: &[u8]) -> Self {
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        let f32_at = |at: usize| f32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        Self {
+            id: u64_at(0),
+            lat_deg: f32_at(8),
+            lon_deg: f32_at(12),
+            altitude: f32_at(16),
+            heading: f32_at(20),
+            size: f32_at(24),
+            kind: bytes[28],
+            r: bytes[29],
+            g: bytes[30],
+            b: bytes[31],
+            a: bytes[32],
+            _reserved: [0; 7],
+        }
+    }
+}
+
+impl Default for EcsIngestRecord {
+    fn default() -> Self {
+        let color = RenderColor::default();
+        Self {
+            id: 0,
+            lat_deg: 0.0,
+            lon_deg: 0.0,
+            altitude: DEFAULT_ALTITUDE,
+            heading: DEFAULT_HEADING,
+            size: DEFAULT_SIZE,
+            kind: KIND_UNKNOWN,
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: color.a,
+            _reserved: [0; 7],
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+struct ArcId(u64);
+
+/// Great-circle polyline for a route or link, sampled once on upsert. Point
+/// altitudes are in metres above the surface.
+#[derive(Component, Debug, Clone)]
+struct RouteArc {
+    points: Vec<Coordinate>,
+}
+
 #[derive(Resource, Debug, Clone, Copy)]
 struct GlobeRadius {
     value: f32,
 }
 
+/// Ages (ms since the last upsert) at which entities start fading and are
+/// despawned; `0` disables either step.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+struct Staleness {
+    fade_after_ms: f64,
+    remove_after_ms: f64,
+}
+
+impl Staleness {
+    /// Alpha falls linearly from full at `fade_after_ms` to zero at
+    /// `remove_after_ms`, or drops to `STALE_ALPHA_SCALE` without a removal age.
+    fn alpha_scale(&self, age_ms: f64) -> f32 {
+        if self.fade_after_ms <= 0.0 || age_ms <= self.fade_after_ms {
+            1.0
+        } else if self.remove_after_ms > self.fade_after_ms {
+            let span = self.remove_after_ms - self.fade_after_ms;
+            (1.0 - (age_ms - self.fade_after_ms) / span).clamp(0.0, 1.0) as f32
+        } else {
+            STALE_ALPHA_SCALE
+        }
+    }
+
+    fn is_expired(&self, age_ms: f64) -> bool {
+        self.remove_after_ms > 0.0 && age_ms > self.remove_after_ms
+    }
+}
+
+/// Radius in metres for geographic queries, independent of the render
+/// globe's `GlobeRadius`.
+#[derive(Resource, Debug, Clone, Copy)]
+struct EarthRadius {
+    meters: f64,
+}
+
 struct WorldState {
     world: World,
     schedule: Schedule,
@@ -85,11 +241,25 @@ struct WorldState {
     ingest_sizes: Vec<f32>,
     ingest_colors: Vec<u8>,
     ingest_headings: Vec<f32>,
+    ingest_records: Vec<EcsIngestRecord>,
     kind_ids: Vec<Vec<u64>>,
+    query_ids: Vec<u64>,
+    arc_map: HashMap<u64, Entity>,
+    render_arc_ids: Vec<u64>,
+    render_arc_point_counts: Vec<u32>,
+    render_arc_positions: Vec<f32>,
+    snapshot: Vec<u8>,
+    /// Clock passed to the last `ecs_tick_at`; stamps upserts until the next.
+    now_ms: f64,
+    update_seq: u64,
+    /// Entity cap enforced after commits and ticks; `0` is unbounded.
+    max_entities: usize,
+    last_evicted: usize,
+    last_error: i32,
 }
 
 impl WorldState {
-    fn new() -> Self {
+    fn new(seed_demo: bool) -> Self {
         let world = World::new();
         let mut schedule = Schedule::default();
         schedule.add_systems(update_cartesian);
@@ -110,17 +280,36 @@ impl WorldState {
             ingest_sizes: Vec::new(),
             ingest_colors: Vec::new(),
             ingest_headings: Vec::new(),
+            ingest_records: Vec::new(),
             kind_ids: (0..KIND_MAX).map(|_| Vec::new()).collect(),
+            query_ids: Vec::new(),
+            arc_map: HashMap::new(),
+            render_arc_ids: Vec::new(),
+            render_arc_point_counts: Vec::new(),
+            render_arc_positions: Vec::new(),
+            snapshot: Vec::new(),
+            now_ms: 0.0,
+            update_seq: 0,
+            max_entities: 0,
+            last_evicted: 0,
+            last_error: ECS_OK,
         };
         state
             .world
             .insert_resource(GlobeRadius { value: 1.0 });
-        state.seed_demo();
+        state.world.insert_resource(EarthRadius {
+            meters: DEFAULT_EARTH_RADIUS_M,
+        });
+        state.world.insert_resource(Staleness::default());
+        if seed_demo {
+            state.seed_demo();
+        }
         state
     }
 
     fn seed_demo(&mut self) {
         let id = 1;
+        let seq = self.next_update_seq();
         let entity = self.world.spawn((
             EntityId(id),
             GeoPosition {
@@ -133,10 +322,17 @@ impl WorldState {
             RenderSize(DEFAULT_SIZE),
             RenderColor::default(),
             Cartesian::default(),
+            LastUpdate(self.now_ms),
+            UpdateSeq(seq),
         ));
         self.id_map.insert(id, entity.id());
     }
 
+    fn next_update_seq(&mut self) -> u64 {
+        self.update_seq += 1;
+        self.update_seq
+    }
+
     fn upsert_entity(
         &mut self,
         id: u64,
@@ -149,10 +345,20 @@ impl WorldState {
         color: RenderColor,
     ) {
         if let Some(entity) = self.id_map.get(&id).copied() {
+            let previous_heading = self.world.get::<Heading>(entity).map(|value| value.0);
+            let mut heading = normalize_heading(heading);
             if let Some(mut geo) = self.world.get_mut::<GeoPosition>(entity) {
+                if heading.is_none() {
+                    heading = if (geo.lat_deg, geo.lon_deg) == (lat_deg, lon_deg) {
+                        previous_heading
+                    } else {
+                        Some(bearing_deg(geo.lat_deg, geo.lon_deg, lat_deg, lon_deg))
+                    };
+                }
                 geo.lat_deg = lat_deg;
                 geo.lon_deg = lon_deg;
             }
+            let heading = heading.unwrap_or(DEFAULT_HEADING);
             if let Some(mut kind_component) = self.world.get_mut::<EntityKind>(entity) {
                 kind_component.0 = kind;
             } else {
@@ -178,8 +384,13 @@ impl WorldState {
             } else {
                 self.world.entity_mut(entity).insert(color);
             }
+            let seq = self.next_update_seq();
+            self.world
+                .entity_mut(entity)
+                .insert((LastUpdate(self.now_ms), UpdateSeq(seq)));
             return;
         }
+        let seq = self.next_update_seq();
         let entity = self.world.spawn((
             EntityId(id),
             GeoPosition {
@@ -188,10 +399,12 @@ impl WorldState {
             },
             EntityKind(kind),
             Altitude(altitude),
-            Heading(heading),
+            Heading(normalize_heading(heading).unwrap_or(DEFAULT_HEADING)),
             RenderSize(size),
             color,
             Cartesian::default(),
+            LastUpdate(self.now_ms),
+            UpdateSeq(seq),
         ));
         self.id_map.insert(id, entity.id());
     }
@@ -202,9 +415,111 @@ impl WorldState {
         }
     }
 
+    /// Registers or replaces the arc `id` from one point to another,
+    /// `segments` (0 for the default) long and lifted by `bulge_m` mid-way.
+    fn upsert_arc(
+        &mut self,
+        id: u64,
+        from: Coordinate,
+        to: Coordinate,
+        bulge_m: f64,
+        segments: u32,
+    ) {
+        let segments = match segments {
+            0 => DEFAULT_ARC_SEGMENTS,
+            value => value.min(MAX_ARC_SEGMENTS),
+        };
+        let arc = RouteArc {
+            points: great_circle_arc(from, to, segments as usize + 1, bulge_m),
+        };
+        if let Some(entity) = self.arc_map.get(&id).copied()
+            && let Ok(mut entity_mut) = self.world.get_entity_mut(entity)
+        {
+            entity_mut.insert(arc);
+            return;
+        }
+        let entity = self.world.spawn((ArcId(id), arc)).id();
+        self.arc_map.insert(id, entity);
+    }
+
+    fn remove_arc(&mut self, id: u64) {
+        if let Some(entity) = self.arc_map.remove(&id) {
+            let _ = self.world.despawn(entity);
+        }
+    }
+
+    fn set_max_entities(&mut self, max_entities: usize) {
+        self.max_entities = max_entities;
+        self.evict_over_cap();
+    }
+
+    /// Despawns the least recently updated entities until the world is back
+    /// under `max_entities`, recording how many went in `last_evicted`.
+    fn evict_over_cap(&mut self) -> usize {
+        let excess = if self.max_entities == 0 {
+            0
+        } else {
+            self.id_map.len().saturating_sub(self.max_entities)
+        };
+        self.last_evicted = excess;
+        if excess == 0 {
+            return 0;
+        }
+        let mut query = self.world.query::<(&EntityId, Option<&UpdateSeq>)>();
+        let mut order: Vec<(u64, u64)> = query
+            .iter(&self.world)
+            .map(|(entity_id, seq)| (seq.map(|value| value.0).unwrap_or(0), entity_id.0))
+            .collect();
+        order.select_nth_unstable(excess - 1);
+        for (_, id) in order.into_iter().take(excess) {
+            self.remove_entity(id);
+        }
+        excess
+    }
+
     fn tick(&mut self) {
+        self.evict_over_cap();
         self.schedule.run(&mut self.world);
         self.refresh_render_buffers();
+        self.refresh_arc_buffers();
+    }
+
+    /// Advances the clock, despawns entities past the removal age and ticks.
+    fn tick_at(&mut self, now_ms: f64) {
+        self.now_ms = now_ms;
+        self.remove_expired();
+        self.tick();
+    }
+
+    fn set_staleness(&mut self, fade_after_ms: f64, remove_after_ms: f64) {
+        self.world.insert_resource(Staleness {
+            fade_after_ms: fade_after_ms.max(0.0),
+            remove_after_ms: remove_after_ms.max(0.0),
+        });
+    }
+
+    fn remove_expired(&mut self) {
+        let staleness = self.staleness();
+        if staleness.remove_after_ms <= 0.0 {
+            return;
+        }
+        let now_ms = self.now_ms;
+        let mut query = self.world.query::<(&EntityId, &LastUpdate)>();
+        let expired: Vec<u64> = query
+            .iter(&self.world)
+            .filter(|(_, last_update)| staleness.is_expired(now_ms - last_update.0))
+            .map(|(entity_id, _)| entity_id.0)
+            .collect();
+        for id in expired {
+            self.remove_entity(id);
+        }
+    }
+
+    fn staleness(&self) -> Staleness {
+        self.world
+            .get_resource::<Staleness>()
+            .copied()
+            .unwrap_or_default()
     }
 
     fn set_globe_radius(&mut self, radius: f32) {
@@ -213,6 +528,100 @@ impl WorldState {
         }
     }
 
+    fn set_earth_radius(&mut self, meters: f64) {
+        if meters.is_finite() && meters > 0.0 {
+            self.world.insert_resource(EarthRadius { meters });
+        }
+    }
+
+    /// Fills `query_ids` with the entities within `radius_m` great-circle
+    /// metres of the point and returns how many there are.
+    fn query_radius(&mut self, lat_deg: f32, lon_deg: f32, radius_m: f64) -> usize {
+        self.query_ids.clear();
+        let earth_radius = self
+            .world
+            .get_resource::<EarthRadius>()
+            .map(|value| value.meters)
+            .unwrap_or(DEFAULT_EARTH_RADIUS_M);
+        let mut query = self.world.query::<(&EntityId, &GeoPosition)>();
+        for (entity_id, geo) in query.iter(&self.world) {
+            let distance = haversine_m(lat_deg, lon_deg, geo.lat_deg, geo.lon_deg, earth_radius);
+            if distance <= radius_m {
+                self.query_ids.push(entity_id.0);
+            }
+        }
+        self.query_ids.len()
+    }
+
+    /// Serializes every entity into `snapshot`, ordered by id.
+    fn write_snapshot(&mut self) {
+        let mut query = self.world.query::<(
+            &EntityId,
+            &GeoPosition,
+            Option<&EntityKind>,
+            Option<&Altitude>,
+            Option<&Heading>,
+            Option<&RenderSize>,
+            Option<&RenderColor>,
+        )>();
+        let mut records: Vec<EcsIngestRecord> = query
+            .iter(&self.world)
+            .map(|(entity_id, geo, kind, altitude, heading, size, color)| {
+                let color = color.copied().unwrap_or_default();
+                EcsIngestRecord {
+                    id: entity_id.0,
+                    lat_deg: geo.lat_deg,
+                    lon_deg: geo.lon_deg,
+                    altitude: altitude.map(|value| value.0).unwrap_or(DEFAULT_ALTITUDE),
+                    heading: heading.map(|value| value.0).unwrap_or(DEFAULT_HEADING),
+                    size: size.map(|value| value.0).unwrap_or(DEFAULT_SIZE),
+                    kind: kind.map(|value| value.0).unwrap_or(KIND_UNKNOWN),
+                    r: color.r,
+                    g: color.g,
+                    b: color.b,
+                    a: color.a,
+                    _reserved: [0; 7],
+                }
+            })
+            .collect();
+        records.sort_unstable_by_key(|record| record.id);
+
+        self.snapshot.clear();
+        self.snapshot
+            .reserve(SNAPSHOT_HEADER_LEN + records.len() * SNAPSHOT_RECORD_LEN);
+        self.snapshot.extend_from_slice(&SNAPSHOT_MAGIC);
+        self.snapshot.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        self.snapshot.extend_from_slice(&[0; 2]);
+        let count = u32::try_from(records.len()).unwrap_or(u32::MAX);
+        self.snapshot.extend_from_slice(&count.to_le_bytes());
+        for record in &records {
+            record.write_le(&mut self.snapshot);
+        }
+    }
+
+    /// Replaces every entity with those in a snapshot blob and returns the
+    /// restored count. A rejected blob leaves the world untouched.
+    fn restore_snapshot(&mut self, bytes: &[u8]) -> i32 {
+        let records = match parse_snapshot(bytes) {
+            Ok(records) => records,
+            Err(code) => {
+                self.last_error = code;
+                return code;
+            }
+        };
+        let ids: Vec<u64> = self.id_map.keys().copied().collect();
+        for id in ids {
+            self.remove_entity(id);
+        }
+        for record in &records {
+            self.ingest_record(*record);
+        }
+        self.evict_over_cap();
+        self.refresh_render_buffers();
+        self.last_error = ECS_OK;
+        applied_count(records.len())
+    }
+
     fn reserve_ingest(&mut self, count: usize) {
         if self.ingest_ids.len() < count {
             self.ingest_ids.resize(count, 0);
@@ -266,26 +675,30 @@ impl WorldState {
         }
     }
 
-    fn ingest_commit(&mut self, count: usize) {
+    /// Applies up to `count` staged records. A count beyond the reserved id
+    /// buffer is truncated and flagged; any other short buffer rejects the
+    /// whole commit.
+    fn ingest_commit(&mut self, count: usize) -> i32 {
+        let requested = count;
         let count = count.min(self.ingest_ids.len());
-        let geo_len = count * 2;
-        if self.ingest_geos.len() < geo_len {
-            return;
-        }
-        if self.ingest_kinds.len() < count {
-            return;
-        }
-        if self.ingest_alts.len() < count {
-            return;
-        }
-        if self.ingest_sizes.len() < count {
-            return;
-        }
-        if self.ingest_headings.len() < count {
-            return;
-        }
-        if self.ingest_colors.len() < count * 4 {
-            return;
+        let shortfall = if self.ingest_geos.len() < count * 2 {
+            Some(ECS_ERR_GEOS_SHORT)
+        } else if self.ingest_kinds.len() < count {
+            Some(ECS_ERR_KINDS_SHORT)
+        } else if self.ingest_alts.len() < count {
+            Some(ECS_ERR_ALTS_SHORT)
+        } else if self.ingest_sizes.len() < count {
+            Some(ECS_ERR_SIZES_SHORT)
+        } else if self.ingest_headings.len() < count {
+            Some(ECS_ERR_HEADINGS_SHORT)
+        } else if self.ingest_colors.len() < count * 4 {
+            Some(ECS_ERR_COLORS_SHORT)
+        } else {
+            None
+        };
+        if let Some(code) = shortfall {
+            self.last_error = code;
+            return code;
         }
         for index in 0..count {
             let id = self.ingest_ids[index];
@@ -305,6 +718,38 @@ impl WorldState {
             };
             self.upsert_entity(id, lat, lon, kind, altitude, heading, size, color);
         }
+        self.evict_over_cap();
+        self.last_error = if count < requested {
+            ECS_ERR_IDS_SHORT
+        } else {
+            ECS_OK
+        };
+        applied_count(count)
+    }
+
+    fn reserve_ingest_packed(&mut self, count: usize) {
+        if self.ingest_records.len() < count {
+            self.ingest_records.resize(count, EcsIngestRecord::default());
+        }
+    }
+
+    fn ingest_record(&mut self, record: EcsIngestRecord) {
+        let color = RenderColor {
+            r: record.r,
+            g: record.g,
+            b: record.b,
+            a: record.a,
+        };
+        self.upsert_entity(
+            record.id,
+            record.lat_deg,
+            record.lon_deg,
+            record.kind,
+            record.altitude,
+            record.heading,
+            record.size,
+            color,
+        );
     }
 
     fn refresh_render_buffers(&mut self) {
@@ -323,6 +768,8 @@ impl WorldState {
         for list in &mut self.kind_ids {
             list.clear();
         }
+        let staleness = self.staleness();
+        let now_ms = self.now_ms;
         let mut query = self
             .world
             .query::<(
@@ -332,8 +779,9 @@ impl WorldState {
                 Option<&RenderColor>,
                 Option<&RenderSize>,
                 Option<&Heading>,
+                Option<&LastUpdate>,
             )>();
-        for (entity_id, cart, kind, color, size, heading) in query.iter(&self.world) {
+        for (entity_id, cart, kind, color, size, heading, last_update) in query.iter(&self.world) {
             self.render_ids.push(entity_id.0);
             self.render_positions.push(cart.x);
             self.render_positions.push(cart.y);
@@ -342,7 +790,11 @@ impl WorldState {
             self.render_colors.push(render_color.r);
             self.render_colors.push(render_color.g);
             self.render_colors.push(render_color.b);
-            self.render_colors.push(render_color.a);
+            let alpha_scale = last_update
+                .map(|value| staleness.alpha_scale(now_ms - value.0))
+                .unwrap_or(1.0);
+            self.render_colors
+                .push((f32::from(render_color.a) * alpha_scale).round() as u8);
             let render_size = size.map(|value| value.0).unwrap_or(DEFAULT_SIZE);
             self.render_sizes.push(render_size);
             let index = kind.map(|value| value.0 as usize).unwrap_or(KIND_UNKNOWN as usize);
@@ -355,6 +807,74 @@ impl WorldState {
             self.render_headings.push(render_heading);
         }
     }
+
+    fn refresh_arc_buffers(&mut self) {
+        self.render_arc_ids.clear();
+        self.render_arc_point_counts.clear();
+        self.render_arc_positions.clear();
+        let globe_radius = self
+            .world
+            .get_resource::<GlobeRadius>()
+            .map(|value| value.value)
+            .unwrap_or(1.0);
+        let earth_radius = self
+            .world
+            .get_resource::<EarthRadius>()
+            .map(|value| value.meters)
+            .unwrap_or(DEFAULT_EARTH_RADIUS_M);
+        // Arc altitudes are metres; scale them to globe units.
+        let altitude_scale = f64::from(globe_radius) / earth_radius;
+        let mut query = self.world.query::<(&ArcId, &RouteArc)>();
+        for (arc_id, arc) in query.iter(&self.world) {
+            self.render_arc_ids.push(arc_id.0);
+            self.render_arc_point_counts.push(arc.points.len() as u32);
+            for point in &arc.points {
+                let lift = (point.altitude_m.unwrap_or(0.0) * altitude_scale) as f32;
+                let (x, y, z) = geo_to_cartesian(
+                    point.latitude as f32,
+                    point.longitude as f32,
+                    globe_radius + lift,
+                );
+                self.render_arc_positions.extend_from_slice(&[x, y, z]);
+            }
+        }
+    }
+}
+
+fn parse_snapshot(bytes: &[u8]) -> Result<Vec<EcsIngestRecord>, i32> {
+    if bytes.len() < SNAPSHOT_HEADER_LEN || bytes[..4] != SNAPSHOT_MAGIC {
+        return Err(ECS_ERR_SNAPSHOT_INVALID);
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    // Older layouts get their own arm here when the version is bumped.
+    let record_len = match version {
+        1 => SNAPSHOT_RECORD_LEN,
+        _ => return Err(ECS_ERR_SNAPSHOT_VERSION),
+    };
+    let count = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize;
+    let body = &bytes[SNAPSHOT_HEADER_LEN..];
+    if count.checked_mul(record_len) != Some(body.len()) {
+        return Err(ECS_ERR_SNAPSHOT_INVALID);
+    }
+    Ok(body
+        .chunks_exact(record_len)
+        .map(EcsIngestRecord::read_le)
+        .collect())
+}
+
+fn applied_count(count: usize) -> i32 {
+    i32::try_from(count).unwrap_or(i32::MAX)
+}
+
+/// Narrows an FFI kind to `u8`, recording `ECS_ERR_KIND_OUT_OF_RANGE` for
+/// kinds the render buckets do not know about.
+fn checked_kind(state: &mut WorldState, kind: u32) -> u8 {
+    state.last_error = if (kind as usize) < KIND_MAX {
+        ECS_OK
+    } else {
+        ECS_ERR_KIND_OUT_OF_RANGE
+    };
+    kind.min(u8::MAX as u32) as u8
 }
 
 fn update_cartesian(
@@ -387,6 +907,38 @@ fn geo_to_cartesian(lat_deg: f32, lon_deg: f32, radius: f32) -> (f32, f32, f32)
     (x, y, z)
 }
 
+/// Heading wrapped into `[0, 360)`, or `None` for the derive sentinel.
+fn normalize_heading(heading: f32) -> Option<f32> {
+    if !heading.is_finite() {
+        return None;
+    }
+    let wrapped = heading.rem_euclid(360.0);
+    // Tiny negative inputs round up to exactly 360.
+    Some(if wrapped >= 360.0 { 0.0 } else { wrapped })
+}
+
+/// Initial great-circle bearing from the first point to the second, in
+/// degrees clockwise from north.
+fn bearing_deg(lat1_deg: f32, lon1_deg: f32, lat2_deg: f32, lon2_deg: f32) -> f32 {
+    let lat1 = f64::from(lat1_deg).to_radians();
+    let lat2 = f64::from(lat2_deg).to_radians();
+    let d_lon = (f64::from(lon2_deg) - f64::from(lon1_deg)).to_radians();
+    let y = d_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+    normalize_heading(y.atan2(x).to_degrees() as f32).unwrap_or(DEFAULT_HEADING)
+}
+
+/// Great-circle distance between two points, computed in `f64` since `f32`
+/// loses metres at Earth scale.
+fn haversine_m(lat1_deg: f32, lon1_deg: f32, lat2_deg: f32, lon2_deg: f32, radius: f64) -> f64 {
+    let lat1 = f64::from(lat1_deg).to_radians();
+    let lat2 = f64::from(lat2_deg).to_radians();
+    let d_lat = lat2 - lat1;
+    let d_lon = (f64::from(lon2_deg) - f64::from(lon1_deg)).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * radius * a.sqrt().min(1.0).asin()
+}
+
 thread_local! {
     static ECS_STATE: RefCell<Option<WorldState>> = RefCell::new(None);
 }
@@ -398,7 +950,7 @@ where
     ECS_STATE.with(|cell| {
         let mut state = cell.borrow_mut();
         if state.is_none() {
-            *state = Some(WorldState::new());
+            *state = Some(WorldState::new(true));
         }
         f(state.as_mut().expect("ECS state must be initialized"))
     })
@@ -406,18 +958,24 @@ where
 
 #[unsafe(no_mangle)]
 pub extern "C" fn ecs_init() {
+    ecs_init_with_options(1);
+}
+
+/// Initializes the world once; a non-zero `seed_demo` spawns the demo asset.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_init_with_options(seed_demo: u32) {
     ECS_STATE.with(|cell| {
         let mut state = cell.borrow_mut();
         if state.is_none() {
-            *state = Some(WorldState::new());
+            *state = Some(WorldState::new(seed_demo != 0));
         }
     });
 }
 
 #[unsafe(no_mangle)]
-pub extern "C" fn ecs_reset() {
+pub extern "C" fn ecs_reset(seed_demo: u32) {
     ECS_STATE.with(|cell| {
-        *cell.borrow_mut() = Some(WorldState::new());
+        *cell.borrow_mut() = Some(WorldState::new(seed_demo != 0));
     });
 }
 
@@ -426,11 +984,33 @@ pub extern "C" fn ecs_tick() {
     with_state(|state| state.tick());
 }
 
+/// Ticks with the caller's clock (for example `Date.now()`), which stamps
+/// upserts until the next tick and drives fading of stale entities.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_tick_at(now_ms: f64) {
+    with_state(|state| state.tick_at(now_ms));
+}
+
+/// Entities not upserted for `fade_after_ms` fade out and are despawned once
+/// older than `remove_after_ms`, measured by the `ecs_tick_at` clock. `0`
+/// disables either step; both are off by default.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_set_staleness(fade_after_ms: f64, remove_after_ms: f64) {
+    with_state(|state| state.set_staleness(fade_after_ms, remove_after_ms));
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn ecs_set_globe_radius(radius: f32) {
     with_state(|state| state.set_globe_radius(radius));
 }
 
+/// Sets the Earth radius in metres used by `ecs_query_radius`; non-positive
+/// values are ignored.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_set_earth_radius(meters: f64) {
+    with_state(|state| state.set_earth_radius(meters));
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn ecs_upsert_entity(id: u64, lat_deg: f32, lon_deg: f32) {
     with_state(|state| {
@@ -558,14 +1138,121 @@ pub extern "C" fn ecs_ingest_headings_ptr() -> *mut f32 {
 }
 
 #[unsafe(no_mangle)]
-pub extern "C" fn ecs_ingest_commit(count: usize) {
-    with_state(|state| state.ingest_commit(count));
+pub extern "C" fn ecs_ingest_commit(count: usize) -> i32 {
+    with_state(|state| state.ingest_commit(count))
+}
+
+/// Caps the world at `max_entities` (`0` lifts the cap). Commits, restores
+/// and ticks evict the least recently updated entities beyond it; single
+/// upserts may overshoot until the next tick.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_set_max_entities(max_entities: usize) {
+    with_state(|state| state.set_max_entities(max_entities));
+}
+
+/// Entities evicted by the most recent commit, restore, tick or cap change.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_last_evicted() -> usize {
+    with_state(|state| state.last_evicted)
+}
+
+/// Status of the most recent commit or kind-taking upsert.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_last_error() -> i32 {
+    with_state(|state| state.last_error)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_ingest_record_size() -> usize {
+    std::mem::size_of::<EcsIngestRecord>()
+}
+
+/// Grows the packed ingest buffer and returns it, for callers (JS) that
+/// cannot hand over their own memory.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_ingest_packed_reserve(count: usize) -> *mut EcsIngestRecord {
+    with_state(|state| {
+        state.reserve_ingest_packed(count);
+        state.ingest_records.as_mut_ptr()
+    })
+}
+
+/// Upserts `count` contiguous records starting at `ptr` and returns the
+/// number applied, or `ECS_ERR_NULL_POINTER`. Records with an unknown kind
+/// are still applied, but leave `ECS_ERR_KIND_OUT_OF_RANGE` behind.
+///
+/// # Safety
+/// `ptr` must be valid for reads of `count` records; it need not be aligned.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ecs_ingest_commit_packed(ptr: *const EcsIngestRecord, count: usize) -> i32 {
+    with_state(|state| {
+        if ptr.is_null() {
+            state.last_error = ECS_ERR_NULL_POINTER;
+            return ECS_ERR_NULL_POINTER;
+        }
+        let mut last_error = ECS_OK;
+        for index in 0..count {
+            let mut record = unsafe { ptr.add(index).read_unaligned() };
+            record.kind = checked_kind(state, u32::from(record.kind));
+            if state.last_error != ECS_OK {
+                last_error = state.last_error;
+            }
+            state.ingest_record(record);
+        }
+        state.evict_over_cap();
+        state.last_error = last_error;
+        applied_count(count)
+    })
+}
+
+/// Serializes the world into the snapshot buffer and returns it; read
+/// `ecs_snapshot_len` bytes afterwards. The blob is versioned for
+/// `ecs_restore`.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_snapshot_ptr() -> *const u8 {
+    with_state(|state| {
+        state.write_snapshot();
+        state.snapshot.as_ptr()
+    })
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_snapshot_len() -> usize {
+    with_state(|state| state.snapshot.len())
+}
+
+/// Grows the snapshot buffer to `len` bytes and returns it, for callers (JS)
+/// that need somewhere to copy a saved blob before `ecs_restore`.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_snapshot_reserve(len: usize) -> *mut u8 {
+    with_state(|state| {
+        state.snapshot.resize(len, 0);
+        state.snapshot.as_mut_ptr()
+    })
+}
+
+/// Replaces the world with the snapshot at `ptr` and returns the number of
+/// entities restored, or `ECS_ERR_NULL_POINTER`, `ECS_ERR_SNAPSHOT_INVALID`
+/// or `ECS_ERR_SNAPSHOT_VERSION`.
+///
+/// # Safety
+/// `ptr` must be valid for reads of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ecs_restore(ptr: *const u8, len: usize) -> i32 {
+    with_state(|state| {
+        if ptr.is_null() {
+            state.last_error = ECS_ERR_NULL_POINTER;
+            return ECS_ERR_NULL_POINTER;
+        }
+        let bytes = unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec();
+        state.restore_snapshot(&bytes)
+    })
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn ecs_upsert_entity_kind(id: u64, lat_deg: f32, lon_deg: f32, kind: u32) {
-    let kind = kind.min(u8::MAX as u32) as u8;
     with_state(|state| {
+        let kind = checked_kind(state, kind);
         state.upsert_entity(
             id,
             lat_deg,
@@ -592,13 +1279,16 @@ pub extern "C" fn ecs_upsert_entity_style(
     b: u8,
     a: u8,
 ) {
-    let kind = kind.min(u8::MAX as u32) as u8;
     let color = RenderColor { r, g, b, a };
     with_state(|state| {
+        let kind = checked_kind(state, kind);
         state.upsert_entity(id, lat_deg, lon_deg, kind, altitude, DEFAULT_HEADING, size, color)
     });
 }
 
+/// `heading` is wrapped into `[0, 360)`; pass NaN (`ECS_HEADING_DERIVE`) to
+/// face along the move since the previous upsert, keeping the last heading
+/// while the entity stands still.
 #[unsafe(no_mangle)]
 pub extern "C" fn ecs_upsert_entity_style_heading(
     id: u64,
@@ -613,9 +1303,11 @@ pub extern "C" fn ecs_upsert_entity_style_heading(
     b: u8,
     a: u8,
 ) {
-    let kind = kind.min(u8::MAX as u32) as u8;
     let color = RenderColor { r, g, b, a };
-    with_state(|state| state.upsert_entity(id, lat_deg, lon_deg, kind, altitude, heading, size, color));
+    with_state(|state| {
+        let kind = checked_kind(state, kind);
+        state.upsert_entity(id, lat_deg, lon_deg, kind, altitude, heading, size, color)
+    });
 }
 
 #[unsafe(no_mangle)]
@@ -638,6 +1330,85 @@ pub extern "C" fn ecs_kind_ids_len(kind: u32) -> usize {
     })
 }
 
+/// Fills the query result buffer (`ecs_query_ids_ptr`/`_len`) with the ids of
+/// entities within `radius_m` metres (great-circle) of the point and returns
+/// the count. Reads current positions, so no tick is needed after upserts.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_query_radius(lat_deg: f32, lon_deg: f32, radius_m: f64) -> usize {
+    with_state(|state| state.query_radius(lat_deg, lon_deg, radius_m))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_query_ids_ptr() -> *const u64 {
+    with_state(|state| state.query_ids.as_ptr())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_query_ids_len() -> usize {
+    with_state(|state| state.query_ids.len())
+}
+
+/// Registers (or replaces) a great-circle arc between two points for route
+/// and link rendering. `bulge_m` lifts the middle of the arc off the surface
+/// and `segments` of 0 picks the default resolution. Arcs are drawn from the
+/// `ecs_arc_*` buffers after the next tick and are not part of snapshots.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_upsert_arc(
+    id: u64,
+    from_lat_deg: f32,
+    from_lon_deg: f32,
+    to_lat_deg: f32,
+    to_lon_deg: f32,
+    bulge_m: f64,
+    segments: u32,
+) {
+    let from = Coordinate::new(f64::from(from_lat_deg), f64::from(from_lon_deg));
+    let to = Coordinate::new(f64::from(to_lat_deg), f64::from(to_lon_deg));
+    with_state(|state| state.upsert_arc(id, from, to, bulge_m, segments));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_remove_arc(id: u64) {
+    with_state(|state| state.remove_arc(id));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_arc_count() -> usize {
+    with_state(|state| state.arc_map.len())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_arc_ids_ptr() -> *const u64 {
+    with_state(|state| state.render_arc_ids.as_ptr())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_arc_ids_len() -> usize {
+    with_state(|state| state.render_arc_ids.len())
+}
+
+/// Points per arc, in `ecs_arc_ids_ptr` order; each arc's positions follow
+/// the previous arc's in `ecs_arc_positions_ptr`.
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_arc_point_counts_ptr() -> *const u32 {
+    with_state(|state| state.render_arc_point_counts.as_ptr())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_arc_point_counts_len() -> usize {
+    with_state(|state| state.render_arc_point_counts.len())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_arc_positions_ptr() -> *const f32 {
+    with_state(|state| state.render_arc_positions.as_ptr())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ecs_arc_positions_len() -> usize {
+    with_state(|state| state.render_arc_positions.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -649,4 +1420,288 @@ mod tests {
         assert!(y.abs() < 1e-6);
         assert!(z.abs() < 1e-6);
     }
+
+    #[test]
+    fn ingest_commit_packed_upserts_records() {
+        ecs_reset(0);
+        let records = [
+            EcsIngestRecord {
+                id: 100,
+                lat_deg: 45.0,
+                lon_deg: 15.0,
+                kind: KIND_SHIP,
+                ..EcsIngestRecord::default()
+            },
+            EcsIngestRecord {
+                id: 101,
+                lat_deg: -10.0,
+                lon_deg: 120.0,
+                kind: KIND_FLIGHT,
+                heading: 90.0,
+                ..EcsIngestRecord::default()
+            },
+        ];
+        let size = ecs_ingest_record_size();
+        assert_eq!(size, 40);
+        let mut bytes = vec![0u8; size * records.len() + 1];
+        for (index, record) in records.iter().enumerate() {
+            let src = unsafe {
+                std::slice::from_raw_parts((record as *const EcsIngestRecord).cast::<u8>(), size)
+            };
+            bytes[1 + index * size..1 + (index + 1) * size].copy_from_slice(src);
+        }
+        let applied = unsafe { ecs_ingest_commit_packed(bytes[1..].as_ptr().cast(), records.len()) };
+        assert_eq!(applied, 2);
+        ecs_tick();
+
+        assert_eq!(ecs_entity_count(), 2);
+        with_state(|state| {
+            assert!(state.id_map.contains_key(&100));
+            assert!(state.id_map.contains_key(&101));
+            assert!(state.kind_ids[KIND_SHIP as usize].contains(&100));
+            assert!(state.kind_ids[KIND_FLIGHT as usize].contains(&101));
+        });
+    }
+
+    #[test]
+    fn ingest_commit_packed_flags_unknown_kinds() {
+        ecs_reset(0);
+        let records = [
+            EcsIngestRecord {
+                id: 110,
+                kind: KIND_MAX as u8,
+                ..EcsIngestRecord::default()
+            },
+            EcsIngestRecord {
+                id: 111,
+                kind: KIND_SHIP,
+                ..EcsIngestRecord::default()
+            },
+        ];
+        let applied = unsafe { ecs_ingest_commit_packed(records.as_ptr(), records.len()) };
+        assert_eq!(applied, 2);
+        assert_eq!(ecs_last_error(), ECS_ERR_KIND_OUT_OF_RANGE);
+
+        let applied = unsafe { ecs_ingest_commit_packed(records[1..].as_ptr(), 1) };
+        assert_eq!(applied, 1);
+        assert_eq!(ecs_last_error(), ECS_OK);
+    }
+
+    #[test]
+    fn ingest_commit_reports_short_color_buffer() {
+        ecs_reset(0);
+        ecs_ingest_reserve(2);
+        with_state(|state| {
+            state.ingest_ids[0] = 200;
+            state.ingest_ids[1] = 201;
+            state.ingest_colors.truncate(4);
+        });
+
+        assert_eq!(ecs_ingest_commit(2), ECS_ERR_COLORS_SHORT);
+        assert_eq!(ecs_last_error(), ECS_ERR_COLORS_SHORT);
+        assert_eq!(ecs_entity_count(), 0);
+
+        ecs_upsert_entity_kind(202, 0.0, 0.0, KIND_MAX as u32);
+        assert_eq!(ecs_last_error(), ECS_ERR_KIND_OUT_OF_RANGE);
+    }
+
+    #[test]
+    fn init_without_demo_seed_starts_empty() {
+        ecs_init_with_options(0);
+        assert_eq!(ecs_entity_count(), 0);
+
+        ecs_reset(1);
+        assert_eq!(ecs_entity_count(), 1);
+    }
+
+    #[test]
+    fn query_radius_returns_entities_within_great_circle_distance() {
+        ecs_reset(0);
+        // One degree of latitude is about 111.2 km.
+        ecs_upsert_entity(1, 0.0, 0.0);
+        ecs_upsert_entity(2, 0.1, 0.0);
+        ecs_upsert_entity(3, 0.4, 0.0);
+        ecs_upsert_entity(4, 0.5, 0.0);
+        ecs_upsert_entity(5, 0.0, -1.0);
+        ecs_upsert_entity(6, 45.0, 15.0);
+
+        assert_eq!(ecs_query_radius(0.0, 0.0, 50_000.0), 3);
+        let mut ids = with_state(|state| state.query_ids.clone());
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(ecs_query_ids_len(), 3);
+
+        // Across the antimeridian the points are about 22 km apart.
+        ecs_upsert_entity(7, 0.0, -179.9);
+        assert_eq!(ecs_query_radius(0.0, 179.9, 25_000.0), 1);
+        assert_eq!(with_state(|state| state.query_ids.clone()), vec![7]);
+
+        // On a globe half the size, 0.5 degrees is within 50 km as well.
+        ecs_set_earth_radius(DEFAULT_EARTH_RADIUS_M / 2.0);
+        assert_eq!(ecs_query_radius(0.0, 0.0, 50_000.0), 4);
+    }
+
+    #[test]
+    fn stale_entities_fade_and_are_removed() {
+        ecs_reset(0);
+        ecs_set_staleness(1_000.0, 5_000.0);
+        ecs_tick_at(0.0);
+        ecs_upsert_entity(1, 10.0, 10.0);
+        ecs_upsert_entity(2, 20.0, 20.0);
+        ecs_tick_at(2_000.0);
+        ecs_upsert_entity(2, 20.0, 20.0);
+
+        // Entity 1 is halfway between the fade and remove ages.
+        ecs_tick_at(3_000.0);
+        let alpha = |id: u64| {
+            with_state(|state| {
+                let index = state.render_ids.iter().position(|value| *value == id)?;
+                Some(state.render_colors[index * 4 + 3])
+            })
+        };
+        assert_eq!(alpha(1), Some(128));
+        assert_eq!(alpha(2), Some(255));
+
+        ecs_tick_at(5_500.0);
+        assert_eq!(ecs_entity_count(), 1);
+        assert_eq!(alpha(1), None);
+        assert_eq!(alpha(2), Some(96));
+    }
+
+    #[test]
+    fn snapshot_restores_into_fresh_world() {
+        let mut source = WorldState::new(false);
+        let color = RenderColor {
+            r: 1,
+            g: 2,
+            b: 3,
+            a: 4,
+        };
+        source.upsert_entity(30, 45.5, 15.25, KIND_SHIP, 0.0, 90.0, 8.0, color);
+        source.upsert_entity(10, -33.9, 151.2, KIND_FLIGHT, 0.2, 270.0, 6.0, color);
+        source.upsert_entity(20, 0.0, 0.0, KIND_ASSET, 0.0, 0.0, 4.0, RenderColor::default());
+        source.write_snapshot();
+        assert_eq!(source.snapshot.len(), SNAPSHOT_HEADER_LEN + 3 * SNAPSHOT_RECORD_LEN);
+
+        let mut restored = WorldState::new(false);
+        assert_eq!(restored.restore_snapshot(&source.snapshot), 3);
+        restored.write_snapshot();
+        assert_eq!(restored.snapshot, source.snapshot);
+
+        let mut query = restored.world.query::<(&EntityId, &GeoPosition)>();
+        let mut positions: Vec<(u64, f32, f32)> = query
+            .iter(&restored.world)
+            .map(|(id, geo)| (id.0, geo.lat_deg, geo.lon_deg))
+            .collect();
+        positions.sort_by_key(|entry| entry.0);
+        assert_eq!(
+            positions,
+            vec![(10, -33.9, 151.2), (20, 0.0, 0.0), (30, 45.5, 15.25)]
+        );
+
+        let mut future = source.snapshot.clone();
+        future[4] = 2;
+        assert_eq!(restored.restore_snapshot(&future), ECS_ERR_SNAPSHOT_VERSION);
+        assert_eq!(restored.restore_snapshot(&source.snapshot[..20]), ECS_ERR_SNAPSHOT_INVALID);
+        assert_eq!(restored.id_map.len(), 3);
+    }
+
+    #[test]
+    fn arcs_render_as_great_circle_polylines() {
+        let mut state = WorldState::new(false);
+        state.set_globe_radius(100.0);
+        let (west, east) = (Coordinate::new(0.0, -30.0), Coordinate::new(0.0, 30.0));
+        state.upsert_arc(5, west, east, DEFAULT_EARTH_RADIUS_M / 10.0, 4);
+        let (zagreb, new_york) = (Coordinate::new(45.8, 16.0), Coordinate::new(40.6, -73.8));
+        state.upsert_arc(6, zagreb, new_york, 0.0, 0);
+        state.tick();
+
+        assert_eq!(state.arc_map.len(), 2);
+        let mut counts: Vec<(u64, u32)> = state
+            .render_arc_ids
+            .iter()
+            .copied()
+            .zip(state.render_arc_point_counts.iter().copied())
+            .collect();
+        counts.sort_by_key(|entry| entry.0);
+        assert_eq!(counts, vec![(5, 5), (6, DEFAULT_ARC_SEGMENTS + 1)]);
+        assert_eq!(
+            state.render_arc_positions.len(),
+            3 * (5 + DEFAULT_ARC_SEGMENTS as usize + 1)
+        );
+
+        let first_arc = state.render_arc_ids.iter().position(|id| *id == 5).unwrap();
+        let offset: usize = state.render_arc_point_counts[..first_arc]
+            .iter()
+            .map(|count| *count as usize * 3)
+            .sum();
+        let points = &state.render_arc_positions[offset..offset + 15];
+        let (x, y, z) = geo_to_cartesian(0.0, -30.0, 100.0);
+        assert!((points[0] - x).abs() < 1e-4 && (points[1] - y).abs() < 1e-4);
+        assert!((points[2] - z).abs() < 1e-4);
+        // A bulge of a tenth of the Earth radius lifts the midpoint by a
+        // tenth of the globe radius, over lat 0 / lon 0.
+        let (x, y, z) = geo_to_cartesian(0.0, 0.0, 110.0);
+        assert!((points[6] - x).abs() < 1e-3 && (points[7] - y).abs() < 1e-3);
+        assert!((points[8] - z).abs() < 1e-3);
+
+        state.remove_arc(6);
+        state.tick();
+        assert_eq!(state.render_arc_ids, vec![5]);
+    }
+
+    #[test]
+    fn headings_normalize_and_derive_from_motion() {
+        let mut state = WorldState::new(false);
+        let color = RenderColor::default();
+        let heading_of = |state: &mut WorldState, id: u64| {
+            let entity = state.id_map[&id];
+            state.world.get::<Heading>(entity).unwrap().0
+        };
+        state.upsert_entity(1, 10.0, 20.0, KIND_SHIP, 0.0, 450.0, 6.0, color);
+        assert_eq!(heading_of(&mut state, 1), 90.0);
+        state.upsert_entity(1, 10.0, 20.0, KIND_SHIP, 0.0, -90.0, 6.0, color);
+        assert_eq!(heading_of(&mut state, 1), 270.0);
+        assert_eq!(normalize_heading(-1e-6), Some(0.0));
+
+        // New entities without a heading face north until they move.
+        state.upsert_entity(2, 0.0, 10.0, KIND_SHIP, 0.0, ECS_HEADING_DERIVE, 6.0, color);
+        assert_eq!(heading_of(&mut state, 2), DEFAULT_HEADING);
+        state.upsert_entity(2, 0.0, 10.5, KIND_SHIP, 0.0, ECS_HEADING_DERIVE, 6.0, color);
+        assert!((heading_of(&mut state, 2) - 90.0).abs() < 0.01);
+        // Standing still keeps the derived facing.
+        state.upsert_entity(2, 0.0, 10.5, KIND_SHIP, 0.0, ECS_HEADING_DERIVE, 6.0, color);
+        assert!((heading_of(&mut state, 2) - 90.0).abs() < 0.01);
+        state.upsert_entity(2, -0.5, 10.5, KIND_SHIP, 0.0, ECS_HEADING_DERIVE, 6.0, color);
+        assert!((heading_of(&mut state, 2) - 180.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn max_entities_evicts_least_recently_updated() {
+        let mut state = WorldState::new(false);
+        let color = RenderColor::default();
+        state.set_max_entities(3);
+        for id in 1..=3 {
+            state.upsert_entity(id, 0.0, id as f32, KIND_SHIP, 0.0, 0.0, 6.0, color);
+        }
+        // Refreshing 1 makes 2 the oldest.
+        state.upsert_entity(1, 1.0, 1.0, KIND_SHIP, 0.0, 0.0, 6.0, color);
+
+        state.reserve_ingest(2);
+        state.ingest_ids[..2].copy_from_slice(&[4, 5]);
+        assert_eq!(state.ingest_commit(2), 2);
+        assert_eq!(state.last_evicted, 2);
+        assert_eq!(state.id_map.len(), 3);
+        let mut ids: Vec<u64> = state.id_map.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 4, 5]);
+
+        state.set_max_entities(1);
+        assert_eq!(state.last_evicted, 2);
+        assert_eq!(state.id_map.keys().copied().collect::<Vec<_>>(), vec![5]);
+        state.set_max_entities(0);
+        state.upsert_entity(6, 0.0, 6.0, KIND_SHIP, 0.0, 0.0, 6.0, color);
+        state.tick();
+        assert_eq!((state.id_map.len(), state.last_evicted), (2, 0));
+    }
 }
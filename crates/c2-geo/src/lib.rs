@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Coordinate {
     pub latitude: f64,
     pub longitude: f64,
@@ -17,6 +18,17 @@ pub struct BoundingBox {
 }
 
 impl BoundingBox {
+    /// Edges are finite and in range, with `south <= north` and
+    /// `west <= east`; boxes crossing the antimeridian are not supported.
+    pub fn is_valid(&self) -> bool {
+        (-90.0..=90.0).contains(&self.south)
+            && (-90.0..=90.0).contains(&self.north)
+            && (-180.0..=180.0).contains(&self.west)
+            && (-180.0..=180.0).contains(&self.east)
+            && self.south <= self.north
+            && self.west <= self.east
+    }
+
     pub fn contains(&self, coord: Coordinate) -> bool {
         coord.latitude <= self.north
             && coord.latitude >= self.south
@@ -31,3 +43,297 @@ pub enum GeoFence {
     Circle { center: Coordinate, radius_m: f64 },
     Polygon { vertices: Vec<Coordinate> },
 }
+
+/// A named area of responsibility, used to tag points without a geocoder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedRegion {
+    pub name: String,
+    pub area: RegionArea,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionArea {
+    Bbox(BoundingBox),
+    Fence(GeoFence),
+}
+
+impl NamedRegion {
+    pub fn contains(&self, coord: Coordinate) -> bool {
+        match &self.area {
+            RegionArea::Bbox(bbox) => bbox.contains(coord),
+            RegionArea::Fence(fence) => fence.contains(coord),
+        }
+    }
+}
+
+/// Names of the regions containing `point`, in the order given. Regions may
+/// overlap, so a point can carry several names or none.
+///
+/// ```
+/// use c2_geo::{classify_point, BoundingBox, Coordinate, GeoFence, NamedRegion, RegionArea};
+///
+/// let regions = [
+///     NamedRegion {
+///         name: "north-sector".to_string(),
+///         area: RegionArea::Bbox(BoundingBox {
+///             north: 46.5,
+///             south: 45.5,
+///             east: 16.5,
+///             west: 15.5,
+///         }),
+///     },
+///     NamedRegion {
+///         name: "capital-district".to_string(),
+///         area: RegionArea::Fence(GeoFence::Circle {
+///             center: Coordinate::new(45.815, 15.982),
+///             radius_m: 10_000.0,
+///         }),
+///     },
+/// ];
+/// let names = classify_point(Coordinate::new(45.8, 15.95), &regions);
+/// assert_eq!(names, ["north-sector", "capital-district"]);
+/// assert!(classify_point(Coordinate::new(43.5, 16.4), &regions).is_empty());
+/// ```
+pub fn classify_point(point: Coordinate, regions: &[NamedRegion]) -> Vec<String> {
+    regions
+        .iter()
+        .filter(|region| region.contains(point))
+        .map(|region| region.name.clone())
+        .collect()
+}
+
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+impl Coordinate {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            altitude_m: None,
+            accuracy_m: None,
+        }
+    }
+
+    /// Latitude and longitude are finite and within their ranges.
+    pub fn is_valid(&self) -> bool {
+        (-90.0..=90.0).contains(&self.latitude) && (-180.0..=180.0).contains(&self.longitude)
+    }
+
+    /// Latitude and longitude rounded to `decimals` places, coarsening the
+    /// position to a grid (one decimal is about 11 km of latitude). Altitude
+    /// and accuracy are kept as they are.
+    ///
+    /// ```
+    /// use c2_geo::Coordinate;
+    ///
+    /// let coord = Coordinate {
+    ///     latitude: 45.8153,
+    ///     longitude: 15.9819,
+    ///     altitude_m: Some(122.4),
+    ///     accuracy_m: None,
+    /// };
+    /// let coarse = coord.quantize(1);
+    /// assert_eq!((coarse.latitude, coarse.longitude), (45.8, 16.0));
+    /// assert_eq!(coarse.altitude_m, Some(122.4));
+    /// ```
+    pub fn quantize(&self, decimals: u8) -> Coordinate {
+        let scale = 10f64.powi(i32::from(decimals));
+        Coordinate {
+            latitude: (self.latitude * scale).round() / scale,
+            longitude: (self.longitude * scale).round() / scale,
+            ..*self
+        }
+    }
+
+    /// Great-circle (haversine) distance in metres, ignoring altitude.
+    pub fn distance_m(&self, other: Coordinate) -> f64 {
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let dlat = lat2 - lat1;
+        let dlon = (other.longitude - self.longitude).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_M * a.sqrt().asin()
+    }
+}
+
+/// Samples the great circle from `from` to `to` into `points` coordinates
+/// (at least two) for drawing routes and links on the globe. Altitude is
+/// interpolated between the endpoints (missing altitudes count as zero)
+/// plus a `bulge_m` lift that peaks at the middle of the arc. Antipodal
+/// endpoints have no unique great circle; one is picked arbitrarily.
+///
+/// ```
+/// use c2_geo::{great_circle_arc, Coordinate};
+///
+/// let from = Coordinate::new(0.0, -30.0);
+/// let to = Coordinate::new(0.0, 30.0);
+/// let arc = great_circle_arc(from, to, 5, 50_000.0);
+/// assert_eq!(arc.len(), 5);
+/// assert_eq!((arc[0].latitude, arc[0].longitude), (0.0, -30.0));
+/// assert_eq!((arc[4].latitude, arc[4].longitude), (0.0, 30.0));
+/// assert_eq!(arc[0].altitude_m, Some(0.0));
+///
+/// // The midpoint sits on the equator, halfway along, at the full bulge.
+/// let mid = arc[2];
+/// assert!(mid.latitude.abs() < 1e-9 && mid.longitude.abs() < 1e-9);
+/// assert!((mid.altitude_m.unwrap() - 50_000.0).abs() < 1e-6);
+///
+/// let (zagreb, new_york) = (Coordinate::new(45.8, 16.0), Coordinate::new(40.6, -73.8));
+/// let route = great_circle_arc(zagreb, new_york, 33, 0.0);
+/// let half = route[16];
+/// let total = route[0].distance_m(route[32]);
+/// assert!((route[0].distance_m(half) - total / 2.0).abs() < 1.0);
+/// assert!((half.distance_m(route[32]) - total / 2.0).abs() < 1.0);
+/// ```
+pub fn great_circle_arc(
+    from: Coordinate,
+    to: Coordinate,
+    points: usize,
+    bulge_m: f64,
+) -> Vec<Coordinate> {
+    let points = points.max(2);
+    let start = unit_vector(from);
+    let end = unit_vector(to);
+    let angle = dot(start, end).clamp(-1.0, 1.0).acos();
+    let mut axis = cross(start, end);
+    if norm(axis) < 1e-12 {
+        // Coincident or antipodal: any axis perpendicular to `start` works.
+        let helper = if start[2].abs() < 0.9 { [0.0, 0.0, 1.0] } else { [1.0, 0.0, 0.0] };
+        axis = cross(start, helper);
+    }
+    let axis = scale(axis, 1.0 / norm(axis));
+    let tangent = cross(axis, start);
+    let from_alt = from.altitude_m.unwrap_or(0.0);
+    let to_alt = to.altitude_m.unwrap_or(0.0);
+
+    (0..points)
+        .map(|index| {
+            let t = index as f64 / (points - 1) as f64;
+            let lift = bulge_m * (std::f64::consts::PI * t).sin();
+            let altitude = from_alt + (to_alt - from_alt) * t + lift;
+            let (latitude, longitude) = if index == 0 {
+                (from.latitude, from.longitude)
+            } else if index == points - 1 {
+                (to.latitude, to.longitude)
+            } else {
+                let (sin, cos) = (angle * t).sin_cos();
+                let [x, y, z] = add(scale(start, cos), scale(tangent, sin));
+                (z.clamp(-1.0, 1.0).asin().to_degrees(), y.atan2(x).to_degrees())
+            };
+            Coordinate {
+                latitude,
+                longitude,
+                altitude_m: Some(altitude),
+                accuracy_m: None,
+            }
+        })
+        .collect()
+}
+
+fn unit_vector(coord: Coordinate) -> [f64; 3] {
+    let (lat, lon) = (coord.latitude.to_radians(), coord.longitude.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn scale(a: [f64; 3], factor: f64) -> [f64; 3] {
+    [a[0] * factor, a[1] * factor, a[2] * factor]
+}
+
+fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+impl GeoFence {
+    /// Polygons are tested with planar ray casting on lat/lon, which is
+    /// adequate for fences that do not span the antimeridian or a pole.
+    pub fn contains(&self, coord: Coordinate) -> bool {
+        match self {
+            Self::Circle { center, radius_m } => center.distance_m(coord) <= *radius_m,
+            Self::Polygon { vertices } => {
+                if vertices.len() < 3 {
+                    return false;
+                }
+                let (x, y) = (coord.longitude, coord.latitude);
+                let mut inside = false;
+                let mut j = vertices.len() - 1;
+                for i in 0..vertices.len() {
+                    let (xi, yi) = (vertices[i].longitude, vertices[i].latitude);
+                    let (xj, yj) = (vertices[j].longitude, vertices[j].latitude);
+                    if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                inside
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_point_in_overlapping_regions_carries_every_name() {
+        let regions = [
+            NamedRegion {
+                name: "sector".to_string(),
+                area: RegionArea::Bbox(BoundingBox {
+                    north: 46.0,
+                    south: 45.0,
+                    east: 16.0,
+                    west: 15.0,
+                }),
+            },
+            NamedRegion {
+                name: "patrol-area".to_string(),
+                area: RegionArea::Fence(GeoFence::Polygon {
+                    vertices: vec![
+                        Coordinate::new(45.5, 15.5),
+                        Coordinate::new(45.5, 17.0),
+                        Coordinate::new(44.5, 17.0),
+                        Coordinate::new(44.5, 15.5),
+                    ],
+                }),
+            },
+        ];
+
+        let both = classify_point(Coordinate::new(45.2, 15.8), &regions);
+        assert_eq!(both, ["sector", "patrol-area"]);
+        assert_eq!(classify_point(Coordinate::new(45.8, 15.2), &regions), ["sector"]);
+        assert_eq!(classify_point(Coordinate::new(44.8, 16.5), &regions), ["patrol-area"]);
+        assert!(classify_point(Coordinate::new(43.0, 16.5), &regions).is_empty());
+    }
+    #[test]
+    fn quantize_rounds_only_the_horizontal_position() {
+        let coord = Coordinate {
+            latitude: -33.8688,
+            longitude: 151.2093,
+            altitude_m: Some(58.25),
+            accuracy_m: Some(4.5),
+        };
+        let coarse = coord.quantize(1);
+        assert_eq!((coarse.latitude, coarse.longitude), (-33.9, 151.2));
+        assert_eq!((coarse.altitude_m, coarse.accuracy_m), (Some(58.25), Some(4.5)));
+        let whole = coord.quantize(0);
+        assert_eq!((whole.latitude, whole.longitude), (-34.0, 151.0));
+    }
+}
@@ -0,0 +1,37 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+use c2_config::real_client_ip;
+
+use crate::HttpPolicy;
+
+/// Logs each request with the originating client address, resolved through
+/// `X-Forwarded-For` when the peer is one of `C2_TRUSTED_PROXIES`.
+pub async fn access_log(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let client_ip = req.peer_addr().map(|peer| {
+        let forwarded_for = req
+            .headers()
+            .get_all("x-forwarded-for")
+            .filter_map(|value| value.to_str().ok());
+        let trusted_proxies = req
+            .app_data::<web::Data<HttpPolicy>>()
+            .map(|policy| policy.trusted_proxies.as_slice())
+            .unwrap_or_default();
+        real_client_ip(forwarded_for, peer.ip(), trusted_proxies)
+    });
+    let method = req.method().clone();
+    let path = req.path().to_string();
+    let response = next.call(req).await?;
+    tracing::debug!(
+        client_ip = ?client_ip,
+        method = %method,
+        path = %path,
+        status = response.status().as_u16(),
+        "request"
+    );
+    Ok(response)
+}
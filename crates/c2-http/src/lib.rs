@@ -0,0 +1,27 @@
+//! Actix middleware shared by the HTTP services (`c2-api`, `c2-web`). Each
+//! reads an [`HttpPolicy`] registered as app data; without one it treats no
+//! proxy as trusted and allows no cross-origin caller.
+
+mod access_log;
+mod origin;
+
+use c2_config::ServiceConfig;
+
+pub use access_log::access_log;
+pub use origin::trusted_origin;
+
+/// The parts of [`ServiceConfig`] the middleware needs.
+#[derive(Debug, Clone, Default)]
+pub struct HttpPolicy {
+    pub trusted_proxies: Vec<String>,
+    pub allowed_origins: Vec<String>,
+}
+
+impl HttpPolicy {
+    pub fn from_config(config: &ServiceConfig) -> Self {
+        Self {
+            trusted_proxies: config.trusted_proxies.clone(),
+            allowed_origins: config.allowed_origins.clone(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use c2_config::is_trusted_origin;
+
+use crate::HttpPolicy;
+
+const ALLOWED_METHODS: &str = "GET, POST, PUT, PATCH, DELETE";
+const PREFLIGHT_MAX_AGE_SECS: &str = "600";
+
+/// Enforces `C2_ALLOWED_ORIGINS` once it is set: requests from another
+/// origin are refused unless listed, listed origins get CORS headers, and
+/// their preflights are answered here. Same-origin requests and requests
+/// without an `Origin` header pass untouched.
+pub async fn trusted_origin(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let allowed_origins = req
+        .app_data::<web::Data<HttpPolicy>>()
+        .map(|policy| policy.allowed_origins.clone())
+        .unwrap_or_default();
+    let origin = req
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let Some(origin) = origin.filter(|origin| {
+        !allowed_origins.is_empty() && !is_same_origin(&req, origin)
+    }) else {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    };
+    if !is_trusted_origin(&origin, &allowed_origins) {
+        let response = HttpResponse::Forbidden()
+            .json(serde_json::json!({ "error": "origin not allowed" }));
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    let preflight = req.method() == Method::OPTIONS
+        && req.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+    if preflight {
+        let mut response = HttpResponse::NoContent();
+        response
+            .insert_header((header::ACCESS_CONTROL_ALLOW_METHODS, ALLOWED_METHODS))
+            .insert_header((header::ACCESS_CONTROL_MAX_AGE, PREFLIGHT_MAX_AGE_SECS));
+        if let Some(headers) = req.headers().get(header::ACCESS_CONTROL_REQUEST_HEADERS) {
+            response.insert_header((header::ACCESS_CONTROL_ALLOW_HEADERS, headers.clone()));
+        }
+        let mut response = req.into_response(response.finish());
+        allow_origin(response.headers_mut(), &origin);
+        return Ok(response.map_into_right_body());
+    }
+
+    let mut response = next.call(req).await?;
+    allow_origin(response.headers_mut(), &origin);
+    Ok(response.map_into_left_body())
+}
+
+fn allow_origin(headers: &mut header::HeaderMap, origin: &str) {
+    if let Ok(origin) = HeaderValue::from_str(origin) {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+    }
+}
+
+fn is_same_origin(req: &ServiceRequest, origin: &str) -> bool {
+    let info = req.connection_info();
+    let own = format!("{}://{}", info.scheme(), info.host());
+    own.eq_ignore_ascii_case(origin.trim_end_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::{middleware, test, App};
+
+    #[actix_web::test]
+    async fn only_listed_origins_get_through_once_configured() {
+        let policy = HttpPolicy {
+            allowed_origins: vec!["https://console.example.com".to_string()],
+            ..HttpPolicy::default()
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(policy))
+                .wrap(middleware::from_fn(trusted_origin))
+                .route("/v1/ping", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let get = |origin: Option<&str>| {
+            let request = test::TestRequest::get().uri("/v1/ping");
+            match origin {
+                Some(origin) => request.insert_header((header::ORIGIN, origin)),
+                None => request,
+            }
+            .to_request()
+        };
+
+        let response = test::call_service(&app, get(Some("https://console.example.com"))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://console.example.com"
+        );
+
+        let response = test::call_service(&app, get(Some("https://evil.test"))).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = test::call_service(&app, get(None)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        let response = test::call_service(&app, get(Some("http://localhost:8080"))).await;
+        assert_eq!(response.status(), StatusCode::OK, "same origin");
+
+        let preflight = test::TestRequest::default()
+            .method(Method::OPTIONS)
+            .uri("/v1/ping")
+            .insert_header((header::ORIGIN, "https://console.example.com"))
+            .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "POST"))
+            .insert_header((header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type"))
+            .to_request();
+        let response = test::call_service(&app, preflight).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers().get(header::ACCESS_CONTROL_ALLOW_HEADERS).unwrap(),
+            "content-type"
+        );
+    }
+}
@@ -1,7 +1,12 @@
-use c2_core::{SecurityClassification, TenantId, UserId};
+use c2_core::{ApiKeyId, Clock, EpochMillis, SecurityClassification, TenantId, UserId};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::str::FromStr;
 
+/// Prefix of every generated API key secret, so leaked keys are easy to spot.
+pub const API_KEY_PREFIX: &str = "c2k_";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Role {
@@ -35,6 +40,30 @@ pub enum Permission {
     Admin,
 }
 
+impl Permission {
+    /// The snake_case name used in headers, JSON and metric labels.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ViewMissions => "view_missions",
+            Self::EditMissions => "edit_missions",
+            Self::DispatchAssets => "dispatch_assets",
+            Self::ViewUnits => "view_units",
+            Self::EditUnits => "edit_units",
+            Self::ViewTeams => "view_teams",
+            Self::EditTeams => "edit_teams",
+            Self::ViewCapabilities => "view_capabilities",
+            Self::EditCapabilities => "edit_capabilities",
+            Self::ViewIncidents => "view_incidents",
+            Self::ManageUsers => "manage_users",
+            Self::ManagePolicies => "manage_policies",
+            Self::AccessClassified => "access_classified",
+            Self::IngestData => "ingest_data",
+            Self::ExportData => "export_data",
+            Self::Admin => "admin",
+        }
+    }
+}
+
 impl FromStr for Role {
     type Err = ();
 
@@ -104,6 +133,10 @@ impl AuthContext {
     pub fn allows(&self, permission: Permission) -> bool {
         self.permissions.iter().any(|candidate| *candidate == permission)
     }
+
+    pub fn is_expired(&self, clock: &dyn Clock) -> bool {
+        clock.now_ms() >= self.expires_at_ms
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,3 +150,126 @@ pub struct TokenClaims {
     pub issued_at_ms: u64,
     pub expires_at_ms: u64,
 }
+
+/// Why [`verify_token`] rejected a token's claims.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    WrongIssuer,
+    WrongAudience,
+    Expired,
+    NotYetValid,
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::WrongIssuer => "token was issued by an unexpected issuer",
+            Self::WrongAudience => "token is meant for another audience",
+            Self::Expired => "token has expired",
+            Self::NotYetValid => "token is not valid yet",
+        })
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// Tolerated difference between the issuer's clock and ours.
+const CLOCK_SKEW_MS: u64 = 60_000;
+
+/// The subject of a token whose signature the caller has already checked,
+/// once its issuer, audience and lifetime are acceptable at `now_ms`.
+pub fn verify_token(
+    claims: &TokenClaims,
+    issuer: &str,
+    audience: &str,
+    now_ms: EpochMillis,
+) -> Result<Subject, TokenError> {
+    if claims.issuer != issuer {
+        return Err(TokenError::WrongIssuer);
+    }
+    if claims.audience != audience {
+        return Err(TokenError::WrongAudience);
+    }
+    if now_ms >= claims.expires_at_ms.saturating_add(CLOCK_SKEW_MS) {
+        return Err(TokenError::Expired);
+    }
+    if claims.issued_at_ms > now_ms.saturating_add(CLOCK_SKEW_MS) {
+        return Err(TokenError::NotYetValid);
+    }
+    Ok(Subject {
+        tenant_id: claims.tenant_id,
+        user_id: claims.user_id,
+        roles: claims.roles.clone(),
+        clearance: claims.clearance,
+    })
+}
+
+/// Tenant-scoped credential for machine clients. Only the SHA-256 of the
+/// secret is kept; the secret itself is shown once, when the key is created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: ApiKeyId,
+    pub tenant_id: TenantId,
+    pub label: String,
+    pub hashed_secret: String,
+    pub scopes: Vec<Permission>,
+    /// Roles and clearance of the creator at the time the key was issued.
+    pub roles: Vec<Role>,
+    pub clearance: SecurityClassification,
+    pub created_by: UserId,
+    pub created_at_ms: EpochMillis,
+    pub revoked: bool,
+}
+
+impl ApiKey {
+    /// New key and its plaintext secret.
+    pub fn issue(
+        tenant_id: TenantId,
+        label: String,
+        scopes: Vec<Permission>,
+        creator: &Subject,
+        created_at_ms: EpochMillis,
+    ) -> (Self, String) {
+        let secret = generate_api_key_secret();
+        let key = Self {
+            id: ApiKeyId::new(),
+            tenant_id,
+            label,
+            hashed_secret: hash_api_key_secret(&secret),
+            scopes,
+            roles: creator.roles.clone(),
+            clearance: creator.clearance,
+            created_by: creator.user_id,
+            created_at_ms,
+            revoked: false,
+        };
+        (key, secret)
+    }
+
+    /// Auth context for a request presenting this key. Keys carry exactly
+    /// their scopes as permissions and do not expire until revoked.
+    pub fn auth_context(&self, now_ms: EpochMillis) -> AuthContext {
+        AuthContext {
+            subject: Subject {
+                tenant_id: self.tenant_id,
+                user_id: self.created_by,
+                roles: self.roles.clone(),
+                clearance: self.clearance,
+            },
+            permissions: self.scopes.clone(),
+            issued_at_ms: now_ms,
+            expires_at_ms: u64::MAX,
+        }
+    }
+}
+
+pub fn generate_api_key_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    format!("{API_KEY_PREFIX}{}", hex::encode(bytes))
+}
+
+/// Lowercase hex SHA-256, the form stored in [`ApiKey::hashed_secret`].
+pub fn hash_api_key_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
@@ -0,0 +1,174 @@
+//! Wire encodings for envelopes. Frames in a format other than JSON carry its
+//! tag, so subscribers decode whatever publishers chose; untagged frames are
+//! JSON.
+
+use crate::MessagingError;
+use serde::{de::DeserializeOwned, Serialize};
+
+pub trait Codec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, MessagingError>;
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, MessagingError>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, MessagingError> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, MessagingError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborCodec;
+
+impl Codec for CborCodec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, MessagingError> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes)
+            .map_err(|err| MessagingError::Codec(err.to_string()))?;
+        Ok(bytes)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, MessagingError> {
+        ciborium::from_reader(bytes).map_err(|err| MessagingError::Codec(err.to_string()))
+    }
+}
+
+/// MessagePack with struct fields encoded by name, so envelopes decode the
+/// same way whether or not the reader knows the concrete payload type.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePackCodec;
+
+impl Codec for MessagePackCodec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, MessagingError> {
+        rmp_serde::to_vec_named(value).map_err(|err| MessagingError::Codec(err.to_string()))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, MessagingError> {
+        rmp_serde::from_slice(bytes).map_err(|err| MessagingError::Codec(err.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WireFormat {
+    #[default]
+    Json,
+    Cbor,
+    MessagePack,
+}
+
+impl WireFormat {
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Cbor => "cbor",
+            Self::MessagePack => "msgpack",
+        }
+    }
+
+    pub fn from_tag(tag: &[u8]) -> Option<Self> {
+        match tag {
+            b"json" => Some(Self::Json),
+            b"cbor" => Some(Self::Cbor),
+            b"msgpack" => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Parses a tag as written in configuration, ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::from_tag(value.trim().to_ascii_lowercase().as_bytes())
+    }
+}
+
+impl Codec for WireFormat {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, MessagingError> {
+        match self {
+            Self::Json => JsonCodec.encode(value),
+            Self::Cbor => CborCodec.encode(value),
+            Self::MessagePack => MessagePackCodec.encode(value),
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, MessagingError> {
+        match self {
+            Self::Json => JsonCodec.decode(bytes),
+            Self::Cbor => CborCodec.decode(bytes),
+            Self::MessagePack => MessagePackCodec.decode(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MessageEnvelope, MessageMetadata};
+    use c2_core::{
+        MessageId, Mission, MissionId, MissionStatus, OperationalPriority, SecurityClassification,
+        TenantId,
+    };
+
+    const FORMATS: [WireFormat; 3] = [WireFormat::Json, WireFormat::Cbor, WireFormat::MessagePack];
+
+    fn envelope() -> MessageEnvelope<Mission> {
+        let tenant_id = TenantId::new();
+        let mut metadata = MessageMetadata::new(
+            MessageId::new(),
+            tenant_id,
+            SecurityClassification::Restricted,
+            1_700_000_000_000,
+            "c2-api".to_string(),
+        );
+        metadata.schema = Some("mission.v1".to_string());
+        MessageEnvelope {
+            metadata,
+            payload: Mission {
+                id: MissionId::new(),
+                tenant_id,
+                name: "Bridge survey".to_string(),
+                status: MissionStatus::Active,
+                priority: OperationalPriority::Elevated,
+                classification: SecurityClassification::Restricted,
+                created_at_ms: 1_700_000_000_000,
+                updated_at_ms: 1_700_000_000_500,
+                archived_at_ms: None,
+            },
+        }
+    }
+
+    #[test]
+    fn every_format_round_trips_the_same_envelope() {
+        let sent = envelope();
+        let expected = serde_json::to_value(&sent).unwrap();
+        for format in FORMATS {
+            let bytes = format.encode(&sent).unwrap();
+            let received: MessageEnvelope<Mission> = format.decode(&bytes).unwrap();
+            assert_eq!(serde_json::to_value(&received).unwrap(), expected, "{format:?}");
+            assert_eq!(WireFormat::from_tag(format.tag().as_bytes()), Some(format));
+        }
+        assert_eq!(WireFormat::parse(" MsgPack "), Some(WireFormat::MessagePack));
+        assert_eq!(WireFormat::from_tag(b"xml"), None);
+    }
+
+    #[test]
+    fn binary_formats_write_smaller_frames_than_json() {
+        let sent = envelope();
+        let json = WireFormat::Json.encode(&sent).unwrap().len();
+        let cbor = WireFormat::Cbor.encode(&sent).unwrap().len();
+        let msgpack = WireFormat::MessagePack.encode(&sent).unwrap().len();
+        assert!(cbor < json, "cbor {cbor} bytes, json {json}");
+        assert!(msgpack < json, "msgpack {msgpack} bytes, json {json}");
+    }
+
+    #[test]
+    fn a_frame_in_another_format_does_not_decode() {
+        let bytes = WireFormat::Cbor.encode(&envelope()).unwrap();
+        let decoded = WireFormat::Json.decode::<MessageEnvelope<Mission>>(&bytes);
+        assert!(matches!(decoded, Err(MessagingError::Serde(_))));
+    }
+}
@@ -0,0 +1,17 @@
+use c2_core::CorrelationId;
+use std::future::Future;
+
+tokio::task_local! {
+    static CURRENT: CorrelationId;
+}
+
+/// Runs `future` with `id` as the current correlation id. Events published
+/// inside it through [`crate::EventBus`] are stamped with the id.
+pub async fn scope<F: Future>(id: CorrelationId, future: F) -> F::Output {
+    CURRENT.scope(id, future).await
+}
+
+/// The correlation id of the enclosing [`scope`], if any.
+pub fn current() -> Option<CorrelationId> {
+    CURRENT.try_with(|id| *id).ok()
+}
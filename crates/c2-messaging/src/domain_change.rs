@@ -0,0 +1,101 @@
+use crate::event_bus::{DomainEvent, EntityKind, EventOp};
+use crate::zmq_transport::{MessagingError, ZmqPublisher, ZmqPublisherConfig};
+use crate::{Classified, MessageEnvelope, MessageMetadata};
+use c2_core::{
+    now_epoch_millis, CorrelationId, EpochMillis, MessageId, SecurityClassification, TenantId,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::{env, fmt};
+
+pub const DOMAIN_CHANGE_TOPIC: &str = "domain.change";
+pub const DOMAIN_CHANGE_SCHEMA: &str = "domain.change.v1";
+
+/// Bus payload announcing a write; consumers fetch the record if they need it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomainChange {
+    pub entity: EntityKind,
+    pub op: EventOp,
+    pub id: String,
+    pub tenant_id: TenantId,
+    pub classification: SecurityClassification,
+    pub changed_at_ms: EpochMillis,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<CorrelationId>,
+}
+
+impl DomainChange {
+    pub fn from_event(event: &DomainEvent, changed_at_ms: EpochMillis) -> Self {
+        Self {
+            entity: event.entity,
+            op: event.op,
+            id: event.id.clone(),
+            tenant_id: event.tenant,
+            classification: event.classification,
+            changed_at_ms,
+            correlation_id: event.correlation_id,
+        }
+    }
+}
+
+impl Classified for DomainChange {
+    fn classification(&self) -> SecurityClassification {
+        self.classification
+    }
+}
+
+/// Forwards [`DomainEvent`]s to ZeroMQ as `MessageEnvelope<DomainChange>` on
+/// the `domain.change` topic.
+pub struct ChangeRelay {
+    publisher: Mutex<ZmqPublisher>,
+    source_service: String,
+}
+
+impl ChangeRelay {
+    pub fn new(
+        config: &ZmqPublisherConfig,
+        source_service: impl Into<String>,
+    ) -> Result<Self, MessagingError> {
+        Ok(Self {
+            publisher: Mutex::new(ZmqPublisher::new(config)?),
+            source_service: source_service.into(),
+        })
+    }
+
+    /// A relay for `C2_ZMQ_PUB_*`, or `None` when `C2_ZMQ_PUB_ENDPOINT` is
+    /// unset.
+    pub fn from_env(source_service: &str) -> Result<Option<Self>, MessagingError> {
+        if env::var("C2_ZMQ_PUB_ENDPOINT").is_err() {
+            return Ok(None);
+        }
+        let config = ZmqPublisherConfig::from_env("");
+        Self::new(&config, source_service).map(Some)
+    }
+
+    pub fn relay(&self, event: &DomainEvent) -> Result<(), MessagingError> {
+        let now = now_epoch_millis();
+        let mut metadata = MessageMetadata::new(
+            MessageId::new(),
+            event.tenant,
+            event.classification,
+            now,
+            self.source_service.clone(),
+        );
+        metadata.correlation_id = event.correlation_id;
+        metadata.schema = Some(DOMAIN_CHANGE_SCHEMA.to_string());
+        let envelope = MessageEnvelope {
+            metadata,
+            payload: DomainChange::from_event(event, now),
+        };
+        let publisher = self.publisher.lock().unwrap_or_else(|err| err.into_inner());
+        publisher.publish_checked(DOMAIN_CHANGE_TOPIC, &envelope)
+    }
+}
+
+impl fmt::Debug for ChangeRelay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangeRelay")
+            .field("source_service", &self.source_service)
+            .finish_non_exhaustive()
+    }
+}
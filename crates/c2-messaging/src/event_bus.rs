@@ -0,0 +1,226 @@
+use crate::domain_change::ChangeRelay;
+use c2_core::{CorrelationId, SecurityClassification, TenantId};
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+pub const RELAY_FAILURES_TOTAL: &str = "c2_messaging_relay_failures_total";
+
+/// Events buffered per subscriber before it starts missing them, and per
+/// tenant for resuming subscribers.
+pub const DEFAULT_EVENT_BUS_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Mission,
+    Asset,
+    Incident,
+    Task,
+    Unit,
+    Team,
+    Capability,
+    Geofence,
+    Assignment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventOp {
+    Upsert,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainEvent {
+    pub entity: EntityKind,
+    pub op: EventOp,
+    pub id: String,
+    pub tenant: TenantId,
+    pub classification: SecurityClassification,
+    /// Position in the tenant's stream, assigned on publish starting at 1.
+    pub sequence: u64,
+    /// The record as written, for upserts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record: Option<Value>,
+    /// The request that caused the write; filled from
+    /// [`crate::correlation::current`] on publish when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<CorrelationId>,
+}
+
+impl DomainEvent {
+    pub fn upsert<T: Serialize>(
+        entity: EntityKind,
+        id: impl ToString,
+        tenant: TenantId,
+        classification: SecurityClassification,
+        record: &T,
+    ) -> Self {
+        Self {
+            entity,
+            op: EventOp::Upsert,
+            id: id.to_string(),
+            tenant,
+            classification,
+            sequence: 0,
+            record: serde_json::to_value(record).ok(),
+            correlation_id: None,
+        }
+    }
+
+    pub fn delete(
+        entity: EntityKind,
+        id: impl ToString,
+        tenant: TenantId,
+        classification: SecurityClassification,
+    ) -> Self {
+        Self {
+            entity,
+            op: EventOp::Delete,
+            id: id.to_string(),
+            tenant,
+            classification,
+            sequence: 0,
+            record: None,
+            correlation_id: None,
+        }
+    }
+}
+
+/// What a resuming subscriber missed since the sequence it last saw.
+#[derive(Debug, Clone)]
+pub enum Replay {
+    /// Every event after that sequence, oldest first.
+    Events(Vec<DomainEvent>),
+    /// Some of those events are no longer buffered, or the sequence was never
+    /// issued by this process, so the subscriber has to refetch.
+    Gap,
+}
+
+#[derive(Debug)]
+struct TenantChannel {
+    sender: broadcast::Sender<DomainEvent>,
+    last_sequence: u64,
+    recent: VecDeque<DomainEvent>,
+}
+
+impl TenantChannel {
+    fn new(capacity: usize) -> Self {
+        Self {
+            sender: broadcast::channel(capacity).0,
+            last_sequence: 0,
+            recent: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    fn replay_after(&self, sequence: u64) -> Replay {
+        let oldest = self
+            .recent
+            .front()
+            .map(|event| event.sequence)
+            .unwrap_or(self.last_sequence + 1);
+        if sequence > self.last_sequence || sequence + 1 < oldest {
+            return Replay::Gap;
+        }
+        Replay::Events(
+            self.recent
+                .iter()
+                .filter(|event| event.sequence > sequence)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+/// In-process fan-out of write events, one broadcast channel per tenant.
+/// Subscribers only ever see their own tenant's events. The last `capacity`
+/// events of each tenant are kept so reconnecting subscribers can resume.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    channels: Arc<Mutex<HashMap<TenantId, TenantChannel>>>,
+    capacity: usize,
+    relay: Option<Arc<ChangeRelay>>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            channels: Arc::new(Mutex::new(HashMap::new())),
+            capacity: capacity.max(1),
+            relay: None,
+        }
+    }
+
+    /// Also forwards every published event to other services through `relay`.
+    /// Failed sends are counted in `c2_messaging_relay_failures_total`.
+    pub fn with_relay(mut self, relay: ChangeRelay) -> Self {
+        self.relay = Some(Arc::new(relay));
+        self
+    }
+
+    pub fn subscribe(&self, tenant: TenantId) -> broadcast::Receiver<DomainEvent> {
+        let mut channels = self.channels.lock().unwrap_or_else(|err| err.into_inner());
+        channels
+            .entry(tenant)
+            .or_insert_with(|| TenantChannel::new(self.capacity))
+            .sender
+            .subscribe()
+    }
+
+    /// Subscribes and returns the buffered events after `sequence`. Nothing
+    /// published in between is lost or delivered twice.
+    pub fn resume(
+        &self,
+        tenant: TenantId,
+        sequence: u64,
+    ) -> (Replay, broadcast::Receiver<DomainEvent>) {
+        let mut channels = self.channels.lock().unwrap_or_else(|err| err.into_inner());
+        let channel = channels
+            .entry(tenant)
+            .or_insert_with(|| TenantChannel::new(self.capacity));
+        (channel.replay_after(sequence), channel.sender.subscribe())
+    }
+
+    /// The sequence of the tenant's latest event, or 0 before its first.
+    pub fn last_sequence(&self, tenant: TenantId) -> u64 {
+        let channels = self.channels.lock().unwrap_or_else(|err| err.into_inner());
+        channels.get(&tenant).map_or(0, |channel| channel.last_sequence)
+    }
+
+    /// Assigns the next sequence for the event's tenant, buffers the event and
+    /// delivers it to the current subscribers, if any.
+    pub fn publish(&self, mut event: DomainEvent) {
+        if event.correlation_id.is_none() {
+            event.correlation_id = crate::correlation::current();
+        }
+        {
+            let mut channels = self.channels.lock().unwrap_or_else(|err| err.into_inner());
+            let channel = channels
+                .entry(event.tenant)
+                .or_insert_with(|| TenantChannel::new(self.capacity));
+            channel.last_sequence += 1;
+            event.sequence = channel.last_sequence;
+            if channel.recent.len() == self.capacity {
+                channel.recent.pop_front();
+            }
+            channel.recent.push_back(event.clone());
+            // No subscribers is fine; the event stays buffered for resumes.
+            let _ = channel.sender.send(event.clone());
+        }
+        if let Some(relay) = &self.relay
+            && relay.relay(&event).is_err()
+        {
+            counter!(RELAY_FAILURES_TOTAL).increment(1);
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_BUS_CAPACITY)
+    }
+}
@@ -1,7 +1,22 @@
-use c2_core::{CorrelationId, EpochMillis, MessageId, SecurityClassification, TenantId};
+use c2_core::{
+    Asset, Capability, CorrelationId, EpochMillis, Geofence, Incident, MessageId, Mission,
+    MissionAssignment, SecurityClassification, Task, Team, TenantId, Unit,
+};
 use serde::{Deserialize, Serialize};
 
+mod codec;
+pub mod correlation;
+mod domain_change;
+mod event_bus;
+mod signing;
 mod zmq_transport;
+pub use codec::{CborCodec, Codec, JsonCodec, MessagePackCodec, WireFormat};
+pub use domain_change::{ChangeRelay, DomainChange, DOMAIN_CHANGE_SCHEMA, DOMAIN_CHANGE_TOPIC};
+pub use event_bus::{
+    DomainEvent, EntityKind, EventBus, EventOp, Replay, DEFAULT_EVENT_BUS_CAPACITY,
+    RELAY_FAILURES_TOTAL,
+};
+pub use signing::{verify_frame, SignatureError, SigningKey};
 pub use zmq_transport::{
     MessagingError, ZmqPublisher, ZmqPublisherConfig, ZmqSubscriber, ZmqSubscriberConfig,
 };
@@ -44,3 +59,48 @@ pub struct MessageEnvelope<T> {
     pub metadata: MessageMetadata,
     pub payload: T,
 }
+
+impl<T: Classified> MessageEnvelope<T> {
+    /// Rejects envelopes labelled below the classification of their payload.
+    /// Over-labelling is allowed.
+    pub fn check_classification(&self) -> Result<(), MessagingError> {
+        let payload = self.payload.classification();
+        if self.metadata.classification < payload {
+            return Err(MessagingError::Classification(format!(
+                "envelope labelled {:?} carries {:?} payload",
+                self.metadata.classification, payload
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Payloads that carry their own classification marking.
+pub trait Classified {
+    fn classification(&self) -> SecurityClassification;
+}
+
+macro_rules! classified {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Classified for $ty {
+                fn classification(&self) -> SecurityClassification {
+                    self.classification
+                }
+            }
+        )*
+    };
+}
+
+classified!(
+    Mission,
+    Task,
+    Asset,
+    Unit,
+    Team,
+    Capability,
+    Incident,
+    Geofence,
+    MissionAssignment,
+    DomainEvent,
+);
@@ -0,0 +1,113 @@
+//! HMAC-SHA256 signatures over serialized envelopes.
+//!
+//! The signature covers the payload frame byte for byte, in whatever wire
+//! format it was written, and travels as lowercase hex in a frame of its own
+//! after the payload. It is deliberately not a `MessageMetadata` field:
+//! checking a signature stored inside the envelope would mean re-encoding the
+//! decoded envelope, and that need not reproduce the signed bytes.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Shared secret for envelope signatures. `Debug` never prints the key.
+#[derive(Clone)]
+pub struct SigningKey(Vec<u8>);
+
+impl SigningKey {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self(key.into())
+    }
+
+    pub fn sign(&self, bytes: &[u8]) -> String {
+        let mut mac = self.mac();
+        mac.update(bytes);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    pub fn verify(&self, bytes: &[u8], signature: &str) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        let mut mac = self.mac();
+        mac.update(bytes);
+        mac.verify_slice(&signature).is_ok()
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.0).expect("HMAC accepts keys of any length")
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SigningKey(..)")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    Missing,
+    Invalid,
+}
+
+impl SignatureError {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Missing => "missing_signature",
+            Self::Invalid => "bad_signature",
+        }
+    }
+}
+
+/// Checks the signature frame sent after `payload` against the payload
+/// bytes exactly as received.
+pub fn verify_frame(
+    key: &SigningKey,
+    payload: &[u8],
+    signature: Option<&[u8]>,
+) -> Result<(), SignatureError> {
+    let Some(signature) = signature else {
+        return Err(SignatureError::Missing);
+    };
+    let signature = std::str::from_utf8(signature).map_err(|_| SignatureError::Invalid)?;
+    if key.verify(payload, signature) {
+        Ok(())
+    } else {
+        Err(SignatureError::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_untouched_payload_verifies() {
+        let key = SigningKey::new("secret");
+        let payload = br#"{"metadata":{},"payload":{"name":"Alpha"}}"#;
+        let signature = key.sign(payload);
+
+        assert_eq!(verify_frame(&key, payload, Some(signature.as_bytes())), Ok(()));
+        let tampered = br#"{"metadata":{},"payload":{"name":"Bravo"}}"#;
+        assert_eq!(
+            verify_frame(&key, tampered, Some(signature.as_bytes())),
+            Err(SignatureError::Invalid)
+        );
+        // Equivalent JSON is still different bytes.
+        let reordered = br#"{"payload":{"name":"Alpha"},"metadata":{}}"#;
+        assert_eq!(
+            verify_frame(&key, reordered, Some(signature.as_bytes())),
+            Err(SignatureError::Invalid)
+        );
+        let other = SigningKey::new("other");
+        assert_eq!(
+            verify_frame(&other, payload, Some(signature.as_bytes())),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(verify_frame(&key, payload, None), Err(SignatureError::Missing));
+        assert_eq!(verify_frame(&key, payload, Some(b"zz")), Err(SignatureError::Invalid));
+    }
+}
@@ -1,13 +1,21 @@
-use crate::MessageEnvelope;
+use crate::codec::{Codec, WireFormat};
+use crate::signing::{verify_frame, SigningKey};
+use crate::{Classified, MessageEnvelope};
+use metrics::counter;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{env, fmt};
 
+pub const REJECTED_FRAMES_TOTAL: &str = "c2_messaging_rejected_frames_total";
+
 #[derive(Debug)]
 pub enum MessagingError {
     Zmq(zmq::Error),
     Serde(serde_json::Error),
     InvalidFrame(String),
     Utf8(String),
+    Codec(String),
+    TooLarge { size: usize, limit: usize },
+    Classification(String),
 }
 
 impl fmt::Display for MessagingError {
@@ -17,6 +25,11 @@ impl fmt::Display for MessagingError {
             Self::Serde(err) => write!(f, "serialization error: {}", err),
             Self::InvalidFrame(message) => write!(f, "invalid frame: {}", message),
             Self::Utf8(message) => write!(f, "utf8 error: {}", message),
+            Self::Codec(message) => write!(f, "codec error: {}", message),
+            Self::TooLarge { size, limit } => {
+                write!(f, "message of {} bytes exceeds limit of {} bytes", size, limit)
+            }
+            Self::Classification(message) => write!(f, "classification error: {}", message),
         }
     }
 }
@@ -41,6 +54,11 @@ pub struct ZmqPublisherConfig {
     pub bind: bool,
     pub high_water_mark: Option<i32>,
     pub linger_ms: Option<i32>,
+    /// Largest serialized envelope `publish` will send; unset means no limit.
+    pub max_message_bytes: Option<usize>,
+    /// Signs every published envelope when set.
+    pub signing_key: Option<SigningKey>,
+    pub wire_format: WireFormat,
 }
 
 impl ZmqPublisherConfig {
@@ -50,6 +68,9 @@ impl ZmqPublisherConfig {
             bind: true,
             high_water_mark: None,
             linger_ms: Some(0),
+            max_message_bytes: None,
+            signing_key: None,
+            wire_format: WireFormat::Json,
         }
     }
 
@@ -59,6 +80,9 @@ impl ZmqPublisherConfig {
             bind: false,
             high_water_mark: None,
             linger_ms: Some(0),
+            max_message_bytes: None,
+            signing_key: None,
+            wire_format: WireFormat::Json,
         }
     }
 
@@ -68,17 +92,31 @@ impl ZmqPublisherConfig {
         let bind = env_var_bool("C2_ZMQ_PUB_BIND", true);
         let high_water_mark = env_var_i32("C2_ZMQ_PUB_HWM");
         let linger_ms = env_var_i32("C2_ZMQ_PUB_LINGER_MS").or(Some(0));
+        let max_message_bytes = env::var("C2_ZMQ_PUB_MAX_MESSAGE_BYTES")
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|value| *value > 0);
+        let wire_format = env::var("C2_ZMQ_PUB_WIRE_FORMAT")
+            .ok()
+            .and_then(|value| WireFormat::parse(&value))
+            .unwrap_or_default();
         Self {
             endpoint,
             bind,
             high_water_mark,
             linger_ms,
+            max_message_bytes,
+            signing_key: signing_key_from_env(),
+            wire_format,
         }
     }
 }
 
 pub struct ZmqPublisher {
     socket: zmq::Socket,
+    max_message_bytes: Option<usize>,
+    signing_key: Option<SigningKey>,
+    wire_format: WireFormat,
 }
 
 impl ZmqPublisher {
@@ -96,18 +134,55 @@ impl ZmqPublisher {
         } else {
             socket.connect(&config.endpoint)?;
         }
-        Ok(Self { socket })
+        Ok(Self {
+            socket,
+            max_message_bytes: config.max_message_bytes,
+            signing_key: config.signing_key.clone(),
+            wire_format: config.wire_format,
+        })
     }
 
+    /// Sends `[topic, envelope]`, with a wire format tag frame between the
+    /// two for formats other than JSON and a signature frame after them when
+    /// a signing key is set. Default JSON frames thus keep the layout that
+    /// subscribers predating wire formats expect.
     pub fn publish<T: Serialize>(
         &self,
         topic: &str,
         envelope: &MessageEnvelope<T>,
     ) -> Result<(), MessagingError> {
-        let payload = serde_json::to_vec(envelope)?;
-        self.socket.send_multipart([topic.as_bytes(), payload.as_slice()], 0)?;
+        let payload = self.wire_format.encode(envelope)?;
+        if let Some(limit) = self.max_message_bytes
+            && payload.len() > limit
+        {
+            return Err(MessagingError::TooLarge {
+                size: payload.len(),
+                limit,
+            });
+        }
+        let signature = self.signing_key.as_ref().map(|key| key.sign(&payload));
+        let mut frames = vec![topic.as_bytes()];
+        if self.wire_format != WireFormat::Json {
+            frames.push(self.wire_format.tag().as_bytes());
+        }
+        frames.push(&payload);
+        if let Some(signature) = &signature {
+            frames.push(signature.as_bytes());
+        }
+        self.socket.send_multipart(frames, 0)?;
         Ok(())
     }
+
+    /// Like [`Self::publish`], but first rejects envelopes labelled below
+    /// their payload's classification.
+    pub fn publish_checked<T: Serialize + Classified>(
+        &self,
+        topic: &str,
+        envelope: &MessageEnvelope<T>,
+    ) -> Result<(), MessagingError> {
+        envelope.check_classification()?;
+        self.publish(topic, envelope)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -117,6 +192,8 @@ pub struct ZmqSubscriberConfig {
     pub topics: Vec<String>,
     pub high_water_mark: Option<i32>,
     pub linger_ms: Option<i32>,
+    /// When set, frames without a valid signature are dropped.
+    pub signing_key: Option<SigningKey>,
 }
 
 impl ZmqSubscriberConfig {
@@ -127,6 +204,7 @@ impl ZmqSubscriberConfig {
             topics,
             high_water_mark: None,
             linger_ms: Some(0),
+            signing_key: None,
         }
     }
 
@@ -137,6 +215,7 @@ impl ZmqSubscriberConfig {
             topics,
             high_water_mark: None,
             linger_ms: Some(0),
+            signing_key: None,
         }
     }
 
@@ -158,12 +237,14 @@ impl ZmqSubscriberConfig {
             topics,
             high_water_mark,
             linger_ms,
+            signing_key: signing_key_from_env(),
         }
     }
 }
 
 pub struct ZmqSubscriber {
     socket: zmq::Socket,
+    signing_key: Option<SigningKey>,
 }
 
 impl ZmqSubscriber {
@@ -188,21 +269,74 @@ impl ZmqSubscriber {
                 socket.set_subscribe(topic.as_bytes())?;
             }
         }
-        Ok(Self { socket })
+        Ok(Self {
+            socket,
+            signing_key: config.signing_key.clone(),
+        })
     }
 
+    /// Waits for the next message, decoding it in the format named by its
+    /// tag frame (untagged `[topic, envelope]` frames are JSON). With a
+    /// signing key, frames with a missing or bad signature, or that do not
+    /// decode, are counted in `c2_messaging_rejected_frames_total` and
+    /// skipped.
     pub fn recv<T: DeserializeOwned>(&self) -> Result<(String, MessageEnvelope<T>), MessagingError> {
-        let frames = self.socket.recv_multipart(0)?;
-        if frames.len() < 2 {
+        loop {
+            let frames = self.socket.recv_multipart(0)?;
+            let Some(key) = &self.signing_key else {
+                return decode_frames(&frames);
+            };
+            let (message, signature) = match frames.split_last() {
+                Some((signature, message)) if message.len() >= 2 => {
+                    (message, Some(signature.as_slice()))
+                }
+                _ => (frames.as_slice(), None),
+            };
+            let Some(payload) = message.last() else {
+                continue;
+            };
+            let rejected = match verify_frame(key, payload, signature) {
+                Ok(()) => match decode_frames(message) {
+                    Ok(message) => return Ok(message),
+                    Err(_) => MALFORMED,
+                },
+                Err(err) => err.as_str(),
+            };
+            counter!(REJECTED_FRAMES_TOTAL, "reason" => rejected).increment(1);
+        }
+    }
+}
+
+/// Reason label for signed frames that verify but do not decode.
+const MALFORMED: &str = "malformed";
+
+fn decode_frames<T: DeserializeOwned>(
+    frames: &[Vec<u8>],
+) -> Result<(String, MessageEnvelope<T>), MessagingError> {
+    let (topic, format, payload) = match frames {
+        [topic, payload] => (topic, WireFormat::Json, payload),
+        [topic, tag, payload] => {
+            let format = WireFormat::from_tag(tag).ok_or_else(|| {
+                MessagingError::InvalidFrame("unknown wire format".to_string())
+            })?;
+            (topic, format, payload)
+        }
+        _ => {
             return Err(MessagingError::InvalidFrame(
                 "expected topic and payload frames".to_string(),
             ));
         }
-        let topic = String::from_utf8(frames[0].clone())
-            .map_err(|_| MessagingError::Utf8("invalid topic utf8".to_string()))?;
-        let envelope = serde_json::from_slice(&frames[1])?;
-        Ok((topic, envelope))
-    }
+    };
+    let topic = String::from_utf8(topic.clone())
+        .map_err(|_| MessagingError::Utf8("invalid topic utf8".to_string()))?;
+    Ok((topic, format.decode(payload)?))
+}
+
+fn signing_key_from_env() -> Option<SigningKey> {
+    env::var("C2_ZMQ_SIGNING_KEY")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(SigningKey::new)
 }
 
 fn env_var_bool(key: &str, default: bool) -> bool {
@@ -219,3 +353,147 @@ fn env_var_bool(key: &str, default: bool) -> bool {
 fn env_var_i32(key: &str) -> Option<i32> {
     env::var(key).ok().and_then(|value| value.parse::<i32>().ok())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageMetadata;
+    use c2_core::{
+        MessageId, Mission, MissionId, MissionStatus, OperationalPriority, SecurityClassification,
+        TenantId,
+    };
+    use serde_json::{json, Value};
+    use std::thread;
+    use std::time::Duration;
+
+    fn envelope(name: &str) -> MessageEnvelope<Value> {
+        MessageEnvelope {
+            metadata: MessageMetadata::new(
+                MessageId::new(),
+                TenantId::new(),
+                SecurityClassification::Unclassified,
+                1,
+                "test".to_string(),
+            ),
+            payload: json!({ "name": name }),
+        }
+    }
+
+    fn subscriber(endpoint: String, key: Option<&SigningKey>) -> ZmqSubscriber {
+        let mut config = ZmqSubscriberConfig::connect(endpoint, Vec::new());
+        config.signing_key = key.cloned();
+        let subscriber = ZmqSubscriber::new(&config).unwrap();
+        subscriber.socket.set_rcvtimeo(5_000).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        subscriber
+    }
+
+    #[test]
+    fn signed_subscriber_skips_tampered_and_malformed_frames() {
+        let key = SigningKey::new("secret");
+        let raw = zmq::Context::new().socket(zmq::PUB).unwrap();
+        raw.bind("tcp://127.0.0.1:*").unwrap();
+        let subscriber = subscriber(raw.get_last_endpoint().unwrap().unwrap(), Some(&key));
+
+        let send = |frames: &[&[u8]]| raw.send_multipart(frames, 0).unwrap();
+        let signed = serde_json::to_vec(&envelope("Alpha")).unwrap();
+        let tampered = serde_json::to_vec(&envelope("Bravo")).unwrap();
+        let signature = key.sign(&signed);
+        send(&[b"missions", b"json", &signed]);
+        send(&[b"missions", b"json", &tampered, signature.as_bytes()]);
+        send(&[b"missions", b"json", b"not json", key.sign(b"not json").as_bytes()]);
+        send(&[b"missions", b"json", &signed, signature.as_bytes()]);
+
+        let (topic, received) = subscriber.recv::<Value>().unwrap();
+        assert_eq!(topic, "missions");
+        assert_eq!(received.payload, json!({ "name": "Alpha" }));
+    }
+
+    #[test]
+    fn signed_publisher_frames_verify_in_any_wire_format() {
+        let key = SigningKey::new("secret");
+        for format in [WireFormat::Json, WireFormat::MessagePack] {
+            let mut config = ZmqPublisherConfig::bind("tcp://127.0.0.1:*");
+            config.signing_key = Some(key.clone());
+            config.wire_format = format;
+            let publisher = ZmqPublisher::new(&config).unwrap();
+            let endpoint = publisher.socket.get_last_endpoint().unwrap().unwrap();
+            let subscriber = subscriber(endpoint, Some(&key));
+
+            publisher.publish("missions", &envelope("Charlie")).unwrap();
+            let (_, received) = subscriber.recv::<Value>().unwrap();
+            assert_eq!(received.payload, json!({ "name": "Charlie" }), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn oversized_and_under_labelled_envelopes_are_not_published() {
+        let mut config = ZmqPublisherConfig::bind("tcp://127.0.0.1:*");
+        config.max_message_bytes = Some(512);
+        let publisher = ZmqPublisher::new(&config).unwrap();
+        let endpoint = publisher.socket.get_last_endpoint().unwrap().unwrap();
+        let subscriber = subscriber(endpoint, None);
+
+        let oversized = envelope(&"x".repeat(1024));
+        let err = publisher.publish("missions", &oversized).unwrap_err();
+        assert!(matches!(err, MessagingError::TooLarge { limit: 512, size } if size > 512));
+
+        let tenant_id = TenantId::new();
+        let mut mission = MessageEnvelope {
+            metadata: MessageMetadata::new(
+                MessageId::new(),
+                tenant_id,
+                SecurityClassification::Unclassified,
+                1,
+                "test".to_string(),
+            ),
+            payload: Mission {
+                id: MissionId::new(),
+                tenant_id,
+                name: "Night crossing".to_string(),
+                status: MissionStatus::Planned,
+                priority: OperationalPriority::Routine,
+                classification: SecurityClassification::Secret,
+                created_at_ms: 1,
+                updated_at_ms: 1,
+                archived_at_ms: None,
+            },
+        };
+        let err = publisher.publish_checked("missions", &mission).unwrap_err();
+        assert!(matches!(err, MessagingError::Classification(_)));
+
+        mission.metadata.classification = SecurityClassification::Secret;
+        publisher.publish_checked("missions", &mission).unwrap();
+        let (topic, received) = subscriber.recv::<Mission>().unwrap();
+        assert_eq!(topic, "missions");
+        assert_eq!(received.payload.id, mission.payload.id);
+    }
+
+    #[test]
+    fn only_non_json_frames_carry_a_format_tag() {
+        for (format, tagged) in [(WireFormat::Json, false), (WireFormat::Cbor, true)] {
+            let mut config = ZmqPublisherConfig::bind("tcp://127.0.0.1:*");
+            config.wire_format = format;
+            let publisher = ZmqPublisher::new(&config).unwrap();
+            let raw = zmq::Context::new().socket(zmq::SUB).unwrap();
+            raw.connect(&publisher.socket.get_last_endpoint().unwrap().unwrap()).unwrap();
+            raw.set_subscribe(b"").unwrap();
+            raw.set_rcvtimeo(5_000).unwrap();
+            thread::sleep(Duration::from_millis(200));
+
+            publisher.publish("missions", &envelope("Delta")).unwrap();
+            let frames = raw.recv_multipart(0).unwrap();
+            let (_, received) = decode_frames::<Value>(&frames).unwrap();
+            assert_eq!(received.payload, json!({ "name": "Delta" }));
+            if tagged {
+                assert_eq!(frames.len(), 3);
+                assert_eq!(frames[1], format.tag().as_bytes());
+            } else {
+                // The layout subscribers that predate wire formats read.
+                assert_eq!(frames.len(), 2);
+                let envelope: MessageEnvelope<Value> = serde_json::from_slice(&frames[1]).unwrap();
+                assert_eq!(envelope.payload, json!({ "name": "Delta" }));
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! JSON log lines for `C2_LOG_FORMAT=json`: one object per event with
+//! `timestamp`, `level`, `target`, `service`, `environment` and `message`,
+//! plus the fields of the event and of every span it happened in (such as
+//! `correlation_id` from the request span) as top-level keys.
+
+use serde_json::{Map, Value};
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::{FormatTime, SystemTime};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::registry::LookupSpan;
+
+pub(crate) struct JsonFormat {
+    pub(crate) service: String,
+    pub(crate) environment: String,
+}
+
+impl<S> FormatEvent<S, JsonFields> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, JsonFields>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut object = Map::new();
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                if let Some(fields) = extensions.get::<FormattedFields<JsonFields>>() {
+                    object.extend(parse_object(&fields.fields));
+                }
+            }
+        }
+        event.record(&mut JsonVisitor(&mut object));
+
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+        let metadata = event.metadata();
+        object.insert("timestamp".to_string(), Value::String(timestamp));
+        object.insert("level".to_string(), Value::String(metadata.level().to_string()));
+        object.insert("target".to_string(), Value::String(metadata.target().to_string()));
+        object.insert("service".to_string(), Value::String(self.service.clone()));
+        object.insert("environment".to_string(), Value::String(self.environment.clone()));
+
+        let line = serde_json::to_string(&object).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+/// Records span fields as a JSON object, so [`JsonFormat`] can lift them
+/// into each event's line.
+pub(crate) struct JsonFields;
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: RecordFields>(&self, mut writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut object = Map::new();
+        fields.record(&mut JsonVisitor(&mut object));
+        let text = serde_json::to_string(&object).map_err(|_| fmt::Error)?;
+        writer.write_str(&text)
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &tracing::span::Record<'_>,
+    ) -> fmt::Result {
+        let mut object = parse_object(&current.fields);
+        fields.record(&mut JsonVisitor(&mut object));
+        current.fields = serde_json::to_string(&object).map_err(|_| fmt::Error)?;
+        Ok(())
+    }
+}
+
+fn parse_object(text: &str) -> Map<String, Value> {
+    serde_json::from_str(text).unwrap_or_default()
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl JsonVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        self.0.insert(field.name().to_string(), value);
+    }
+}
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::String(format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_event_carries_service_and_span_fields() {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .fmt_fields(JsonFields)
+            .event_format(JsonFormat {
+                service: "c2-api".to_string(),
+                environment: "prod".to_string(),
+            })
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", correlation_id = "abc-123");
+            let _entered = span.enter();
+            tracing::warn!(status = 503, "storage unavailable");
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        let object = line.as_object().expect("a JSON object per line");
+        assert_eq!(object["message"], "storage unavailable");
+        assert_eq!(object["level"], "WARN");
+        assert_eq!(object["service"], "c2-api");
+        assert_eq!(object["environment"], "prod");
+        assert_eq!(object["correlation_id"], "abc-123");
+        assert_eq!(object["status"], 503);
+        assert!(object["timestamp"].is_string());
+        assert!(object.contains_key("target"));
+    }
+}
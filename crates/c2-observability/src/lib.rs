@@ -1,37 +1,79 @@
-use metrics_exporter_prometheus::PrometheusBuilder;
+mod json_log;
+pub mod telemetry;
+
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+use std::fmt;
 use std::net::SocketAddr;
+use std::thread;
+use tokio::runtime::{self, Handle};
+use json_log::{JsonFields, JsonFormat};
 use tracing_subscriber::EnvFilter;
 
+pub use telemetry::TelemetryConfig;
+
+const DURATION_BUCKETS_SECONDS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
 #[derive(Debug, Clone)]
 pub struct ObservabilityConfig {
     pub service_name: String,
     pub environment: String,
     pub log_level: String,
+    /// `json` for one JSON object per line; anything else is human text.
+    pub log_format: String,
     pub metrics_addr: Option<String>,
+    pub telemetry: TelemetryConfig,
 }
 
 #[derive(Debug, Clone)]
 pub struct ObservabilityHandle {
     pub service_name: String,
     pub metrics_enabled: bool,
+    pub telemetry_enabled: bool,
 }
 
 pub fn init(config: &ObservabilityConfig) -> ObservabilityHandle {
     init_tls_provider();
 
     let filter = EnvFilter::try_new(&config.log_level).unwrap_or_else(|_| EnvFilter::new("info"));
-    let subscriber = tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(true)
-        .finish();
-
-    let _ = tracing::subscriber::set_global_default(subscriber);
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    let _ = if config.log_format.eq_ignore_ascii_case("json") {
+        let subscriber = builder
+            .fmt_fields(JsonFields)
+            .event_format(JsonFormat {
+                service: config.service_name.clone(),
+                environment: config.environment.clone(),
+            })
+            .finish();
+        tracing::subscriber::set_global_default(subscriber)
+    } else {
+        tracing::subscriber::set_global_default(builder.with_target(true).finish())
+    };
 
-    let metrics_enabled = init_metrics(config);
+    let mut metrics_enabled = false;
+    let mut telemetry_enabled = false;
+    if config.metrics_addr.is_some() || config.telemetry.enabled {
+        match background_runtime() {
+            Ok(runtime) => {
+                if let Some((metrics, served)) = init_metrics(config, &runtime) {
+                    metrics_enabled = served;
+                    telemetry_enabled = config.telemetry.enabled;
+                    telemetry::spawn(&config.telemetry, &config.service_name, metrics, &runtime);
+                }
+            }
+            Err(err) => tracing::warn!(
+                service = %config.service_name,
+                error = %err,
+                "Failed to start metrics runtime"
+            ),
+        }
+    }
 
     ObservabilityHandle {
         service_name: config.service_name.clone(),
         metrics_enabled,
+        telemetry_enabled,
     }
 }
 
@@ -40,44 +82,174 @@ pub fn log_startup(handle: &ObservabilityHandle, environment: &str) {
         service = %handle.service_name,
         environment = %environment,
         metrics_enabled = handle.metrics_enabled,
+        telemetry_enabled = handle.telemetry_enabled,
         "C2 service starting"
     );
 }
 
+pub fn check_requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--check")
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    pub service_name: String,
+    pub results: Vec<CheckResult>,
+}
+
+impl CheckReport {
+    pub fn new(service_name: impl Into<String>) -> Self {
+        init_tls_provider();
+        Self {
+            service_name: service_name.into(),
+            results: Vec::new(),
+        }
+    }
+
+    pub fn pass(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.results.push(CheckResult {
+            name: name.into(),
+            ok: true,
+            detail: detail.into(),
+        });
+    }
+
+    pub fn record<T: fmt::Display, E: fmt::Display>(
+        &mut self,
+        name: impl Into<String>,
+        result: Result<T, E>,
+    ) -> bool {
+        let (ok, detail) = match result {
+            Ok(detail) => (true, detail.to_string()),
+            Err(err) => (false, err.to_string()),
+        };
+        self.results.push(CheckResult {
+            name: name.into(),
+            ok,
+            detail,
+        });
+        ok
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(|result| result.ok)
+    }
+
+    pub fn exit(self) -> ! {
+        println!("{self}");
+        std::process::exit(if self.is_ok() { 0 } else { 1 });
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.is_ok() { "OK" } else { "FAIL" };
+        write!(f, "service={} status={}", self.service_name, status)?;
+        for result in &self.results {
+            let status = if result.ok { "OK" } else { "FAIL" };
+            write!(
+                f,
+                "\ncheck={} status={} detail={:?}",
+                result.name, status, result.detail
+            )?;
+        }
+        Ok(())
+    }
+}
+
 fn init_tls_provider() {
     let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
 }
 
-fn init_metrics(config: &ObservabilityConfig) -> bool {
-    let Some(addr) = config.metrics_addr.as_ref() else {
-        return false;
-    };
-    let addr: SocketAddr = match addr.parse() {
-        Ok(parsed) => parsed,
-        Err(err) => {
+/// The current Tokio runtime, or a dedicated single-threaded one for
+/// services that initialize observability outside of one.
+fn background_runtime() -> std::io::Result<Handle> {
+    if let Ok(handle) = Handle::try_current() {
+        return Ok(handle);
+    }
+    let runtime = runtime::Builder::new_current_thread().enable_all().build()?;
+    let handle = runtime.handle().clone();
+    thread::Builder::new()
+        .name("c2-observability".to_string())
+        .spawn(move || runtime.block_on(std::future::pending::<()>()))?;
+    Ok(handle)
+}
+
+/// Installs the global Prometheus recorder, serving it on `C2_METRICS_ADDR`
+/// when set, and reports whether it is served. Telemetry reads the same
+/// recorder through the returned handle.
+fn init_metrics(
+    config: &ObservabilityConfig,
+    runtime: &Handle,
+) -> Option<(PrometheusHandle, bool)> {
+    let listener = match config.metrics_addr.as_ref().map(|addr| addr.parse::<SocketAddr>()) {
+        Some(Ok(addr)) => Some(addr),
+        Some(Err(err)) => {
             tracing::warn!(
                 service = %config.service_name,
                 error = %err,
                 "Invalid C2_METRICS_ADDR value"
             );
-            return false;
+            None
         }
+        None => None,
     };
+    if listener.is_none() && !config.telemetry.enabled {
+        return None;
+    }
 
-    let builder = PrometheusBuilder::new()
-        .with_http_listener(addr)
+    let builder = match PrometheusBuilder::new()
         .add_global_label("service", config.service_name.clone())
-        .add_global_label("environment", config.environment.clone());
+        .add_global_label("environment", config.environment.clone())
+        .set_buckets_for_metric(
+            Matcher::Suffix("_duration_seconds".to_string()),
+            DURATION_BUCKETS_SECONDS,
+        ) {
+        Ok(builder) => builder,
+        Err(err) => {
+            tracing::warn!(
+                service = %config.service_name,
+                error = %err,
+                "Invalid Prometheus histogram buckets"
+            );
+            return None;
+        }
+    };
 
-    match builder.install() {
-        Ok(()) => true,
+    let installed = match listener {
+        Some(addr) => install_exporter(builder.with_http_listener(addr), runtime),
+        None => builder.install_recorder(),
+    };
+    match installed {
+        Ok(handle) => Some((handle, listener.is_some())),
         Err(err) => {
             tracing::warn!(
                 service = %config.service_name,
                 error = %err,
                 "Failed to initialize Prometheus exporter"
             );
-            false
+            None
         }
     }
 }
+
+fn install_exporter(
+    builder: PrometheusBuilder,
+    runtime: &Handle,
+) -> Result<PrometheusHandle, BuildError> {
+    let (recorder, exporter) = {
+        let _guard = runtime.enter();
+        builder.build()?
+    };
+    runtime.spawn(exporter);
+    let handle = recorder.handle();
+    metrics::set_global_recorder(recorder)?;
+    Ok(handle)
+}
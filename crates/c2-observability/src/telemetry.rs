@@ -0,0 +1,250 @@
+//! Opt-in anonymized usage telemetry.
+//!
+//! Off by default. When enabled, a background task periodically reduces the
+//! metrics this process already records for Prometheus to plain counts and
+//! either logs them or posts them to a configured endpoint. Only counters and
+//! the sample counts of histograms and summaries are reported, and only a
+//! fixed set of low-cardinality labels (tool, entity, operation, ...) is kept,
+//! so ids, tenant or user names and other label values never leave the
+//! process. Gauges, sums and bucket boundaries are dropped.
+
+use metrics_exporter_prometheus::PrometheusHandle;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use tokio::runtime::Handle;
+
+/// Labels whose values name a kind of thing rather than a particular record.
+const COUNT_LABELS: &[&str] = &[
+    "backend",
+    "entity",
+    "operation",
+    "permission",
+    "reason",
+    "resource_type",
+    "result",
+    "tool",
+];
+
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    pub interval: Duration,
+    /// Reports are posted here as JSON; without one they are logged.
+    pub endpoint: Option<String>,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(3_600),
+            endpoint: None,
+        }
+    }
+}
+
+/// One emitted aggregate: a service name and counts keyed by metric and
+/// retained labels, e.g. `c2_mcp_tool_calls_total{result=ok,tool=c2.get_mission}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TelemetryReport {
+    pub service: String,
+    pub counts: BTreeMap<String, u64>,
+}
+
+/// The report for one rendering of the Prometheus registry, or `None` when
+/// telemetry is disabled.
+pub fn report(config: &TelemetryConfig, service: &str, rendered: &str) -> Option<TelemetryReport> {
+    if !config.enabled {
+        return None;
+    }
+    Some(TelemetryReport {
+        service: service.to_string(),
+        counts: aggregate(rendered),
+    })
+}
+
+/// Sums the counter samples of a Prometheus text exposition, keeping only
+/// [`COUNT_LABELS`].
+pub fn aggregate(rendered: &str) -> BTreeMap<String, u64> {
+    let mut kinds = HashMap::new();
+    let mut counts = BTreeMap::new();
+    for line in rendered.lines() {
+        if let Some(declaration) = line.strip_prefix("# TYPE ") {
+            let mut parts = declaration.split_whitespace();
+            if let (Some(name), Some(kind)) = (parts.next(), parts.next()) {
+                kinds.insert(name.to_string(), kind.to_string());
+            }
+            continue;
+        }
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let Some((series, value)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let (name, labels) = match series.split_once('{') {
+            Some((name, labels)) => (name, labels.strip_suffix('}').unwrap_or(labels)),
+            None => (series, ""),
+        };
+        let counted = match kinds.get(name).map(String::as_str) {
+            Some("counter") => true,
+            _ => name
+                .strip_suffix("_count")
+                .and_then(|base| kinds.get(base))
+                .is_some_and(|kind| kind == "histogram" || kind == "summary"),
+        };
+        if !counted {
+            continue;
+        }
+        let Ok(value) = value.parse::<f64>() else {
+            continue;
+        };
+        let mut kept: Vec<String> = parse_labels(labels)
+            .into_iter()
+            .filter(|(key, _)| COUNT_LABELS.contains(&key.as_str()))
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        kept.sort();
+        let key = if kept.is_empty() {
+            name.to_string()
+        } else {
+            format!("{name}{{{}}}", kept.join(","))
+        };
+        *counts.entry(key).or_insert(0) += value.max(0.0).round() as u64;
+    }
+    counts
+}
+
+/// Starts the periodic emitter on `runtime`. Does nothing when disabled.
+pub(crate) fn spawn(
+    config: &TelemetryConfig,
+    service: &str,
+    metrics: PrometheusHandle,
+    runtime: &Handle,
+) {
+    if !config.enabled {
+        return;
+    }
+    let config = config.clone();
+    let service = service.to_string();
+    runtime.spawn(async move {
+        let client = reqwest::Client::new();
+        let mut ticker = tokio::time::interval(config.interval);
+        // The first tick completes immediately and there is nothing to report yet.
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            metrics.run_upkeep();
+            if let Some(report) = report(&config, &service, &metrics.render()) {
+                emit(&client, config.endpoint.as_deref(), &report).await;
+            }
+        }
+    });
+}
+
+async fn emit(client: &reqwest::Client, endpoint: Option<&str>, report: &TelemetryReport) {
+    let Some(endpoint) = endpoint else {
+        let payload = serde_json::to_string(report).unwrap_or_default();
+        tracing::info!(service = %report.service, payload = %payload, "Usage telemetry");
+        return;
+    };
+    let result = client
+        .post(endpoint)
+        .json(report)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    if let Err(err) = result {
+        tracing::warn!(
+            service = %report.service,
+            error = %err,
+            "Failed to post usage telemetry"
+        );
+    }
+}
+
+fn parse_labels(raw: &str) -> Vec<(String, String)> {
+    let mut labels = Vec::new();
+    let mut chars = raw.chars();
+    loop {
+        let key: String = chars.by_ref().take_while(|ch| *ch != '=').collect();
+        let key = key.trim_start_matches(',').trim().to_string();
+        if key.is_empty() || chars.next() != Some('"') {
+            break;
+        }
+        let mut value = String::new();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => value.extend(chars.next()),
+                '"' => break,
+                _ => value.push(ch),
+            }
+        }
+        labels.push((key, value));
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    fn rendered() -> String {
+        let recorder = PrometheusBuilder::new()
+            .add_global_label("service", "c2-mcp")
+            .add_global_label("environment", "local")
+            .build_recorder();
+        metrics::with_local_recorder(&recorder, || {
+            let calls = |mission_id: &'static str| {
+                metrics::counter!(
+                    "c2_mcp_tool_calls_total",
+                    "tool" => "c2.get_mission",
+                    "result" => "ok",
+                    "mission_id" => mission_id
+                )
+            };
+            calls("7d3c2a10-mission-alpha").increment(2);
+            calls("9f1e0b22-mission-bravo").increment(1);
+            metrics::histogram!(
+                "c2_storage_operation_duration_seconds",
+                "entity" => "mission",
+                "operation" => "get",
+                "tenant_id" => "tenant-secret"
+            )
+            .record(0.25);
+            metrics::gauge!("c2_open_sessions", "user" => "operator-jane").set(4.0);
+        });
+        recorder.handle().render()
+    }
+
+    #[test]
+    fn disabled_telemetry_emits_nothing() {
+        assert_eq!(report(&TelemetryConfig::default(), "c2-mcp", &rendered()), None);
+    }
+
+    #[test]
+    fn enabled_report_contains_only_counts() {
+        let config = TelemetryConfig {
+            enabled: true,
+            ..TelemetryConfig::default()
+        };
+        let report = report(&config, "c2-mcp", &rendered()).expect("telemetry is enabled");
+
+        let expected = BTreeMap::from([
+            ("c2_mcp_tool_calls_total{result=ok,tool=c2.get_mission}".to_string(), 3),
+            (
+                "c2_storage_operation_duration_seconds_count{entity=mission,operation=get}"
+                    .to_string(),
+                1,
+            ),
+        ]);
+        assert_eq!(report.counts, expected);
+
+        let payload = serde_json::to_string(&report).unwrap();
+        for leaked in ["mission-alpha", "tenant-secret", "operator-jane", "local"] {
+            assert!(!payload.contains(leaked), "{leaked} leaked into {payload}");
+        }
+    }
+}
@@ -1,6 +1,14 @@
-use c2_core::{IncidentId, MissionId, SecurityClassification, TenantId};
+use c2_core::{CorrelationId, IncidentId, MissionId, SecurityClassification, TenantId};
+use c2_geo::Coordinate;
 use c2_identity::{Permission, Role, Subject};
+use metrics::counter;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -10,6 +18,25 @@ pub enum PolicyDecision {
     Indeterminate,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyOutcome {
+    pub decision: PolicyDecision,
+    pub matched_rule: Option<String>,
+    pub reason: String,
+}
+
+impl PolicyOutcome {
+    /// The denial for a subject that lacks `permission` outright, which
+    /// enforcement points check before consulting the rules.
+    pub fn missing_permission(permission: Permission) -> Self {
+        Self {
+            decision: PolicyDecision::Deny,
+            matched_rule: None,
+            reason: format!("subject does not hold permission {permission:?}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceDescriptor {
     pub resource_type: String,
@@ -22,6 +49,9 @@ pub struct PolicyContext {
     pub mission_id: Option<MissionId>,
     pub incident_id: Option<IncidentId>,
     pub tags: Vec<String>,
+    /// The request being authorized, so denials can be traced back to it.
+    #[serde(default)]
+    pub correlation_id: Option<CorrelationId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,8 +72,56 @@ pub struct PolicyRule {
     pub minimum_clearance: SecurityClassification,
 }
 
+pub const AUTHZ_DENIED_TOTAL: &str = "c2_authz_denied_total";
+
 pub trait PolicyEngine {
     fn evaluate(&self, request: &PolicyRequest) -> PolicyDecision;
+
+    /// [`Self::explain`] for enforcement points: anything but a permit is
+    /// passed to [`record_denial`] with the explanation. The rules are
+    /// evaluated once.
+    fn authorize(&self, request: &PolicyRequest) -> PolicyDecision {
+        let outcome = self.explain(request);
+        if outcome.decision != PolicyDecision::Permit {
+            record_denial(request, outcome.decision, &outcome);
+        }
+        outcome.decision
+    }
+
+    fn explain(&self, request: &PolicyRequest) -> PolicyOutcome {
+        PolicyOutcome {
+            decision: self.evaluate(request),
+            matched_rule: None,
+            reason: String::new(),
+        }
+    }
+}
+
+/// Logs a denied or indeterminate decision at warn level, with the subject,
+/// action, resource and the rule and reason from `outcome`, and counts it in
+/// `c2_authz_denied_total{permission,resource_type}`. Denials are routine
+/// (probing clients, stale tokens), so this deliberately stays below error.
+pub fn record_denial(request: &PolicyRequest, decision: PolicyDecision, outcome: &PolicyOutcome) {
+    tracing::warn!(
+        tenant_id = %request.subject.tenant_id,
+        user_id = %request.subject.user_id,
+        action = request.action.as_str(),
+        resource_type = %request.resource.resource_type,
+        resource_id = request.resource.resource_id.as_deref(),
+        classification = request.classification.marking(),
+        clearance = request.subject.clearance.marking(),
+        decision = ?decision,
+        matched_rule = outcome.matched_rule.as_deref(),
+        reason = %outcome.reason,
+        correlation_id = request.context.correlation_id.map(tracing::field::display),
+        "authorization denied"
+    );
+    counter!(
+        AUTHZ_DENIED_TOTAL,
+        "permission" => request.action.as_str(),
+        "resource_type" => request.resource.resource_type.clone()
+    )
+    .increment(1);
 }
 
 #[derive(Debug, Clone)]
@@ -61,16 +139,23 @@ impl BasicPolicyEngine {
     }
 
     fn matches_rule(&self, request: &PolicyRequest, rule: &PolicyRule) -> bool {
+        self.rule_mismatch(request, rule).is_none()
+    }
+
+    fn rule_mismatch(&self, request: &PolicyRequest, rule: &PolicyRule) -> Option<String> {
         if request.subject.clearance < rule.minimum_clearance {
-            return false;
+            return Some(format!(
+                "clearance {:?} is below rule minimum {:?}",
+                request.subject.clearance, rule.minimum_clearance
+            ));
         }
         if request.subject.clearance < request.classification {
-            return false;
+            return Some(clearance_reason(request));
         }
         if !rule.required_permissions.is_empty()
             && !rule.required_permissions.contains(&request.action)
         {
-            return false;
+            return Some(format!("rule does not grant {:?}", request.action));
         }
         if !rule.required_roles.is_empty()
             && !rule
@@ -78,12 +163,22 @@ impl BasicPolicyEngine {
                 .iter()
                 .any(|role| request.subject.has_role(*role))
         {
-            return false;
+            return Some(format!(
+                "subject holds none of the required roles {:?}",
+                rule.required_roles
+            ));
         }
-        true
+        None
     }
 }
 
+fn clearance_reason(request: &PolicyRequest) -> String {
+    format!(
+        "clearance {:?} is below resource classification {:?}",
+        request.subject.clearance, request.classification
+    )
+}
+
 impl PolicyEngine for BasicPolicyEngine {
     fn evaluate(&self, request: &PolicyRequest) -> PolicyDecision {
         if self.rules.is_empty() {
@@ -105,6 +200,362 @@ impl PolicyEngine for BasicPolicyEngine {
             PolicyDecision::Deny
         }
     }
+
+    fn explain(&self, request: &PolicyRequest) -> PolicyOutcome {
+        if request.subject.clearance < request.classification {
+            return PolicyOutcome {
+                decision: PolicyDecision::Deny,
+                matched_rule: None,
+                reason: clearance_reason(request),
+            };
+        }
+        if self.rules.is_empty() {
+            return PolicyOutcome {
+                decision: PolicyDecision::Permit,
+                matched_rule: None,
+                reason: "no rules configured; clearance check passed".to_string(),
+            };
+        }
+        if let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| self.matches_rule(request, rule))
+        {
+            return PolicyOutcome {
+                decision: PolicyDecision::Permit,
+                matched_rule: Some(rule.id.clone()),
+                reason: rule.description.clone(),
+            };
+        }
+
+        let candidate = self.rules.iter().find(|rule| {
+            rule.required_permissions.is_empty()
+                || rule.required_permissions.contains(&request.action)
+        });
+        match candidate {
+            Some(rule) => PolicyOutcome {
+                decision: PolicyDecision::Deny,
+                matched_rule: Some(rule.id.clone()),
+                reason: self
+                    .rule_mismatch(request, rule)
+                    .unwrap_or_else(|| "no rule permits the request".to_string()),
+            },
+            None => PolicyOutcome {
+                decision: PolicyDecision::Deny,
+                matched_rule: None,
+                reason: format!("no rule grants {:?}", request.action),
+            },
+        }
+    }
+}
+
+/// Highest classification each tenant may store. Tenants without an entry
+/// are not capped.
+#[derive(Debug, Clone, Default)]
+pub struct TenantCeilings {
+    ceilings: HashMap<TenantId, SecurityClassification>,
+}
+
+impl TenantCeilings {
+    pub fn new(ceilings: HashMap<TenantId, SecurityClassification>) -> Self {
+        Self { ceilings }
+    }
+
+    /// Reads `C2_TENANT_CLASSIFICATION_CEILINGS`, a comma-separated list of
+    /// `<tenant-uuid>=<classification>` pairs.
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("C2_TENANT_CLASSIFICATION_CEILINGS") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        parse_tenant_classifications(value, "ceiling").map(Self::new)
+    }
+
+    pub fn ceiling(&self, tenant_id: TenantId) -> Option<SecurityClassification> {
+        self.ceilings.get(&tenant_id).copied()
+    }
+
+    pub fn check(
+        &self,
+        tenant_id: TenantId,
+        classification: SecurityClassification,
+    ) -> Result<(), String> {
+        match self.ceiling(tenant_id) {
+            Some(ceiling) if classification > ceiling => Err(format!(
+                "classification {:?} exceeds tenant ceiling {:?}",
+                classification, ceiling
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Lowest classification each tenant may store, which is also what a new
+/// record gets when the caller leaves its classification out. Tenants
+/// without an entry use the deployment-wide default floor.
+#[derive(Debug, Clone, Default)]
+pub struct TenantFloors {
+    default_floor: SecurityClassification,
+    floors: HashMap<TenantId, SecurityClassification>,
+}
+
+impl TenantFloors {
+    pub fn new(
+        default_floor: SecurityClassification,
+        floors: HashMap<TenantId, SecurityClassification>,
+    ) -> Self {
+        Self {
+            default_floor,
+            floors,
+        }
+    }
+
+    /// Reads `C2_CLASSIFICATION_FLOOR`, the default floor (unclassified when
+    /// unset), and `C2_TENANT_CLASSIFICATION_FLOORS`, a comma-separated list
+    /// of `<tenant-uuid>=<classification>` pairs.
+    pub fn from_env() -> Result<Self, String> {
+        let default_floor = match env::var("C2_CLASSIFICATION_FLOOR") {
+            Ok(value) => SecurityClassification::from_str(value.trim())
+                .map_err(|_| format!("invalid C2_CLASSIFICATION_FLOOR {value:?}"))?,
+            Err(_) => SecurityClassification::default(),
+        };
+        let floors = match env::var("C2_TENANT_CLASSIFICATION_FLOORS") {
+            Ok(value) => parse_tenant_classifications(&value, "floor")?,
+            Err(_) => HashMap::new(),
+        };
+        Ok(Self::new(default_floor, floors))
+    }
+
+    pub fn floor(&self, tenant_id: TenantId) -> SecurityClassification {
+        self.floors.get(&tenant_id).copied().unwrap_or(self.default_floor)
+    }
+
+    pub fn check(
+        &self,
+        tenant_id: TenantId,
+        classification: SecurityClassification,
+    ) -> Result<(), String> {
+        let floor = self.floor(tenant_id);
+        if classification < floor {
+            return Err(format!(
+                "classification {:?} is below tenant floor {:?}",
+                classification, floor
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `--check` entries for the tenant policy settings read from the
+/// environment: ceilings, floors and the rate limit.
+pub fn env_checks() -> Vec<(&'static str, Result<&'static str, String>)> {
+    vec![
+        ("tenant_ceilings", TenantCeilings::from_env().map(|_| "parsed")),
+        ("classification_floors", TenantFloors::from_env().map(|_| "parsed")),
+        (
+            "tenant_rate_limit",
+            TenantRateLimiter::from_env().map(|limiter| {
+                if limiter.is_enabled() { "enabled" } else { "disabled" }
+            }),
+        ),
+    ]
+}
+
+/// Parses `<tenant-uuid>=<classification>` pairs separated by commas; `kind`
+/// names the setting in errors.
+fn parse_tenant_classifications(
+    value: &str,
+    kind: &str,
+) -> Result<HashMap<TenantId, SecurityClassification>, String> {
+    let mut classifications = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (tenant, classification) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("invalid tenant {kind} entry {entry:?}"))?;
+        let tenant_id = Uuid::parse_str(tenant.trim())
+            .map(TenantId::from_uuid)
+            .map_err(|_| format!("invalid tenant id in {kind} entry {entry:?}"))?;
+        let classification = SecurityClassification::from_str(classification.trim())
+            .map_err(|_| format!("invalid classification in {kind} entry {entry:?}"))?;
+        classifications.insert(tenant_id, classification);
+    }
+    Ok(classifications)
+}
+
+/// Decimal places kept in positions of records classified above the viewer's
+/// clearance, by how many levels above: the first entry applies one level
+/// up, the second two levels up, and so on. Gaps past the end get no
+/// position. Records at or below the clearance keep full precision.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionPrecision {
+    decimals: Vec<u8>,
+}
+
+impl PositionPrecision {
+    /// Finer grids than this do not hide anything.
+    const MAX_DECIMALS: u8 = 9;
+
+    pub fn new(decimals: Vec<u8>) -> Self {
+        Self { decimals }
+    }
+
+    /// Reads `C2_POSITION_PRECISION`, a comma-separated list of decimal
+    /// places such as `2,0`. Unset keeps no positions above clearance.
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("C2_POSITION_PRECISION") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut decimals = Vec::new();
+        for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let places = entry
+                .parse::<u8>()
+                .ok()
+                .filter(|places| *places <= Self::MAX_DECIMALS)
+                .ok_or_else(|| format!("invalid position precision entry {entry:?}"))?;
+            decimals.push(places);
+        }
+        Ok(Self { decimals })
+    }
+
+    /// `position` as a viewer with `clearance` may see it on a record
+    /// classified `classification`, or `None` when it must be withheld.
+    pub fn apply(
+        &self,
+        position: Coordinate,
+        classification: SecurityClassification,
+        clearance: SecurityClassification,
+    ) -> Option<Coordinate> {
+        if classification <= clearance {
+            return Some(position);
+        }
+        self.decimals_for(classification, clearance)
+            .map(|decimals| position.quantize(decimals))
+    }
+
+    fn decimals_for(
+        &self,
+        classification: SecurityClassification,
+        clearance: SecurityClassification,
+    ) -> Option<u8> {
+        let gap = classification.level().saturating_sub(clearance.level());
+        usize::from(gap)
+            .checked_sub(1)
+            .and_then(|index| self.decimals.get(index).copied())
+    }
+}
+
+/// Token bucket per tenant, so one tenant's burst of calls cannot starve the
+/// others. Each tenant may make `burst` calls at once and `rate_per_sec`
+/// more per second after that. A zero rate disables limiting.
+///
+/// ```
+/// use c2_core::TenantId;
+/// use c2_policy::TenantRateLimiter;
+/// use std::time::{Duration, Instant};
+///
+/// let limiter = TenantRateLimiter::new(1.0, 2);
+/// let (noisy, quiet) = (TenantId::new(), TenantId::new());
+/// let now = Instant::now();
+/// assert!(limiter.check_at(noisy, now).is_ok());
+/// assert!(limiter.check_at(noisy, now).is_ok());
+/// assert_eq!(limiter.check_at(noisy, now), Err(Duration::from_secs(1)));
+/// assert!(limiter.check_at(quiet, now).is_ok());
+/// assert!(limiter.check_at(noisy, now + Duration::from_secs(1)).is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct TenantRateLimiter {
+    rate_per_sec: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<TenantId, TokenBucket>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TenantRateLimiter {
+    /// Full buckets are dropped once this many tenants are tracked; a
+    /// missing bucket starts full, so this loses nothing.
+    const PRUNE_AT: usize = 1024;
+
+    pub fn new(rate_per_sec: f64, burst: u32) -> Self {
+        Self {
+            rate_per_sec,
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reads `C2_TENANT_RATE_LIMIT_RPS` (unset or `0` disables) and
+    /// `C2_TENANT_RATE_LIMIT_BURST`, which defaults to the rate rounded up.
+    pub fn from_env() -> Result<Self, String> {
+        let rate_per_sec = match env::var("C2_TENANT_RATE_LIMIT_RPS") {
+            Ok(value) => value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| rate.is_finite() && *rate >= 0.0)
+                .ok_or_else(|| format!("invalid C2_TENANT_RATE_LIMIT_RPS {value:?}"))?,
+            Err(_) => return Ok(Self::default()),
+        };
+        let burst = match env::var("C2_TENANT_RATE_LIMIT_BURST") {
+            Ok(value) => value
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|burst| *burst > 0)
+                .ok_or_else(|| format!("invalid C2_TENANT_RATE_LIMIT_BURST {value:?}"))?,
+            Err(_) => rate_per_sec.ceil().min(f64::from(u32::MAX)) as u32,
+        };
+        Ok(Self::new(rate_per_sec, burst))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.rate_per_sec > 0.0
+    }
+
+    /// Takes one call from `tenant_id`'s bucket, or returns how long until
+    /// the next call would be allowed.
+    pub fn check(&self, tenant_id: TenantId) -> Result<(), Duration> {
+        self.check_at(tenant_id, Instant::now())
+    }
+
+    /// [`Self::check`] at a given instant.
+    pub fn check_at(&self, tenant_id: TenantId, now: Instant) -> Result<(), Duration> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let mut buckets = self.buckets.lock().unwrap_or_else(|err| err.into_inner());
+        if buckets.len() >= Self::PRUNE_AT {
+            buckets.retain(|_, bucket| self.refilled(bucket, now) < self.burst);
+        }
+        let bucket = buckets.entry(tenant_id).or_insert(TokenBucket {
+            tokens: self.burst,
+            refilled_at: now,
+        });
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate_per_sec))
+        }
+    }
+
+    fn refilled(&self, bucket: &TokenBucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+        (bucket.tokens + elapsed * self.rate_per_sec).min(self.burst)
+    }
 }
 
 fn default_rules() -> Vec<PolicyRule> {
@@ -227,6 +678,13 @@ fn default_rules() -> Vec<PolicyRule> {
             required_permissions: vec![Permission::AccessClassified],
             minimum_clearance: SecurityClassification::Secret,
         },
+        PolicyRule {
+            id: "manage_users".to_string(),
+            description: "Manage users and API keys".to_string(),
+            required_roles: vec![Role::SystemAdmin],
+            required_permissions: vec![Permission::ManageUsers],
+            minimum_clearance: SecurityClassification::Restricted,
+        },
         PolicyRule {
             id: "admin".to_string(),
             description: "Administrative actions".to_string(),
@@ -236,3 +694,28 @@ fn default_rules() -> Vec<PolicyRule> {
         },
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_coarsen_with_each_level_above_clearance() {
+        let precision = PositionPrecision::parse("1, 0").unwrap();
+        let position = Coordinate {
+            altitude_m: Some(310.0),
+            ..Coordinate::new(45.8153, 15.9819)
+        };
+        let seen = |classification| {
+            precision
+                .apply(position, classification, SecurityClassification::Restricted)
+                .map(|coord| (coord.latitude, coord.longitude, coord.altitude_m))
+        };
+
+        assert_eq!(seen(SecurityClassification::Restricted), Some((45.8153, 15.9819, Some(310.0))));
+        assert_eq!(seen(SecurityClassification::Confidential), Some((45.8, 16.0, Some(310.0))));
+        assert_eq!(seen(SecurityClassification::Secret), Some((46.0, 16.0, Some(310.0))));
+        assert_eq!(seen(SecurityClassification::TopSecret), None);
+        assert!(PositionPrecision::parse("10").is_err());
+    }
+}
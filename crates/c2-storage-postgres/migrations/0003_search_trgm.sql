@@ -0,0 +1,6 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_missions_name_trgm
+    ON missions USING GIN (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_incidents_summary_trgm
+    ON incidents USING GIN (summary gin_trgm_ops);
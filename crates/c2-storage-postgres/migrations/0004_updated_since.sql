@@ -0,0 +1,14 @@
+CREATE INDEX IF NOT EXISTS idx_missions_tenant_updated
+    ON missions (tenant_id, updated_at_ms);
+CREATE INDEX IF NOT EXISTS idx_assets_tenant_updated
+    ON assets (tenant_id, updated_at_ms);
+CREATE INDEX IF NOT EXISTS idx_units_tenant_updated
+    ON units (tenant_id, updated_at_ms);
+CREATE INDEX IF NOT EXISTS idx_teams_tenant_updated
+    ON teams (tenant_id, updated_at_ms);
+CREATE INDEX IF NOT EXISTS idx_capabilities_tenant_updated
+    ON capabilities (tenant_id, updated_at_ms);
+CREATE INDEX IF NOT EXISTS idx_incidents_tenant_updated
+    ON incidents (tenant_id, updated_at_ms);
+CREATE INDEX IF NOT EXISTS idx_tasks_tenant_updated
+    ON tasks (tenant_id, updated_at_ms);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS asset_status_events (
+    id BIGSERIAL,
+    asset_id UUID NOT NULL,
+    tenant_id UUID NOT NULL,
+    readiness TEXT NOT NULL,
+    comms_status TEXT NOT NULL,
+    maintenance_state TEXT NOT NULL,
+    recorded_at_ms BIGINT NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    payload JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_status_events_asset_recorded
+    ON asset_status_events (asset_id, recorded_at_ms, id);
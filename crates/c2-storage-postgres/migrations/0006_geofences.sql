@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS geofences (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    active BOOLEAN NOT NULL,
+    classification TEXT NOT NULL,
+    created_at_ms BIGINT NOT NULL,
+    updated_at_ms BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    payload JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_geofences_tenant_created
+    ON geofences (tenant_id, created_at_ms DESC);
+CREATE INDEX IF NOT EXISTS idx_geofences_active
+    ON geofences (active);
@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS idx_assets_maintenance_pending
+    ON assets ((payload->>'maintenance_state'))
+    WHERE jsonb_typeof(payload->'maintenance_window') = 'array';
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL,
+    hashed_secret TEXT NOT NULL UNIQUE,
+    revoked BOOLEAN NOT NULL,
+    created_at_ms BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    payload JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_tenant_created
+    ON api_keys (tenant_id, created_at_ms DESC);
@@ -0,0 +1,8 @@
+ALTER TABLE incidents ADD COLUMN IF NOT EXISTS priority TEXT NOT NULL DEFAULT 'routine';
+
+UPDATE incidents
+    SET payload = jsonb_set(payload, '{priority}', '"routine"')
+    WHERE NOT payload ? 'priority';
+
+CREATE INDEX IF NOT EXISTS idx_incidents_tenant_priority
+    ON incidents (tenant_id, priority);
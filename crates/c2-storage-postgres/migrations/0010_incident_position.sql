@@ -0,0 +1,6 @@
+ALTER TABLE incidents ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION;
+ALTER TABLE incidents ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION;
+
+CREATE INDEX IF NOT EXISTS idx_incidents_tenant_position
+    ON incidents (tenant_id, latitude, longitude)
+    WHERE latitude IS NOT NULL;
@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS idx_tasks_with_dependencies
+    ON tasks (status)
+    WHERE jsonb_array_length(COALESCE(payload->'depends_on', '[]'::jsonb)) > 0;
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS mission_assignments (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL,
+    mission_id UUID NOT NULL,
+    unit_id UUID,
+    team_id UUID,
+    role TEXT NOT NULL,
+    classification TEXT NOT NULL,
+    assigned_at_ms BIGINT NOT NULL,
+    assigned_at TIMESTAMPTZ NOT NULL,
+    payload JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mission_assignments_mission
+    ON mission_assignments (mission_id, assigned_at_ms);
+CREATE INDEX IF NOT EXISTS idx_mission_assignments_unit
+    ON mission_assignments (unit_id, assigned_at_ms DESC)
+    WHERE unit_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_mission_assignments_tenant
+    ON mission_assignments (tenant_id);
@@ -0,0 +1,5 @@
+ALTER TABLE missions ADD COLUMN IF NOT EXISTS archived_at_ms BIGINT;
+
+CREATE INDEX IF NOT EXISTS idx_missions_tenant_archived
+    ON missions (tenant_id, archived_at_ms DESC)
+    WHERE archived_at_ms IS NOT NULL;
@@ -0,0 +1,21 @@
+DROP INDEX IF EXISTS idx_missions_tenant_updated;
+CREATE INDEX IF NOT EXISTS idx_missions_tenant_updated_id
+    ON missions (tenant_id, updated_at_ms, id);
+DROP INDEX IF EXISTS idx_assets_tenant_updated;
+CREATE INDEX IF NOT EXISTS idx_assets_tenant_updated_id
+    ON assets (tenant_id, updated_at_ms, id);
+DROP INDEX IF EXISTS idx_units_tenant_updated;
+CREATE INDEX IF NOT EXISTS idx_units_tenant_updated_id
+    ON units (tenant_id, updated_at_ms, id);
+DROP INDEX IF EXISTS idx_teams_tenant_updated;
+CREATE INDEX IF NOT EXISTS idx_teams_tenant_updated_id
+    ON teams (tenant_id, updated_at_ms, id);
+DROP INDEX IF EXISTS idx_capabilities_tenant_updated;
+CREATE INDEX IF NOT EXISTS idx_capabilities_tenant_updated_id
+    ON capabilities (tenant_id, updated_at_ms, id);
+DROP INDEX IF EXISTS idx_incidents_tenant_updated;
+CREATE INDEX IF NOT EXISTS idx_incidents_tenant_updated_id
+    ON incidents (tenant_id, updated_at_ms, id);
+DROP INDEX IF EXISTS idx_tasks_tenant_updated;
+CREATE INDEX IF NOT EXISTS idx_tasks_tenant_updated_id
+    ON tasks (tenant_id, updated_at_ms, id);
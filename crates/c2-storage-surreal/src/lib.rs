@@ -1,32 +1,162 @@
 use async_trait::async_trait;
 use c2_core::{
-    Asset, AssetId, AssetKind, AssetStatus, Capability, CapabilityId, CommsStatus, Incident,
-    IncidentId, IncidentStatus, IncidentType, MaintenanceState, Mission, MissionId, MissionStatus,
-    OperationalPriority, ReadinessState, SecurityClassification, Task, TaskId, TaskStatus, Team,
-    TeamId, TenantId, Unit, UnitId,
+    ApiKeyId, Asset, AssetId, AssetKind, AssetStatus, AssetStatusEvent, AssignmentId, Capability,
+    CapabilityCode, CapabilityId, CommsStatus, EpochMillis, Geofence, GeofenceId, GroupCount,
+    Incident, IncidentId, IncidentStatus, IncidentType, MaintenanceState, Mission,
+    MissionAssignment, MissionId, MissionProgress, MissionStatus, OperationalPriority,
+    ReadinessState, SecurityClassification, Task, TaskId, TaskStatus, Team, TeamId, TenantId, Unit,
+    UnitId, UserId, now_epoch_millis,
 };
+use c2_geo::{BoundingBox, Coordinate, GeoFence};
+use c2_identity::{ApiKey, Permission, Role};
 use c2_storage::{
-    AssetRepository, CapabilityRepository, IncidentRepository, MissionRepository, StorageError,
-    TaskRepository, TeamRepository, UnitRepository,
+    ApiKeyRepository, AssetFilter, AssetRepository, AssignmentRepository, CapabilityRepository,
+    ChangeCursor, GeofenceRepository, IncidentFilter, IncidentRepository, IncidentSort,
+    MissionFilter, MissionRepository, StorageBackend, StorageError, TaskRepository, TeamRepository,
+    UnitRepository, UpsertOutcome,
 };
+use serde::de::{DeserializeOwned, IgnoredAny};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
-use surrealdb::engine::remote::ws::{Client, Ws, Wss};
+use std::str::FromStr;
+use surrealdb::engine::any::{self, Any};
 use surrealdb::opt::auth::Root;
-use surrealdb::sql::{Id, Thing};
+use surrealdb::sql::{Id, Table, Thing};
 use surrealdb::Surreal;
 use tokio::time::{sleep, Duration};
-use tracing::warn;
+use tracing::{info, warn};
 use uuid::Uuid;
 
 const TABLE_MISSION: &str = "mission";
 const TABLE_ASSET: &str = "asset";
+const TABLE_ASSET_STATUS_EVENT: &str = "asset_status_event";
 const TABLE_UNIT: &str = "unit";
 const TABLE_TEAM: &str = "team";
 const TABLE_CAPABILITY: &str = "capability";
 const TABLE_INCIDENT: &str = "incident";
+const TABLE_GEOFENCE: &str = "geofence";
 const TABLE_TASK: &str = "task";
-const SURREAL_SCHEMA: &str = include_str!("../schema/c2.surql");
+const TABLE_ASSIGNMENT: &str = "mission_assignment";
+const TABLE_API_KEY: &str = "api_key";
+/// Tables the repositories read and write; warmup fails when one is missing.
+const EXPECTED_TABLES: &[&str] = &[
+    TABLE_MISSION,
+    TABLE_ASSET,
+    TABLE_ASSET_STATUS_EVENT,
+    TABLE_UNIT,
+    TABLE_TEAM,
+    TABLE_CAPABILITY,
+    TABLE_INCIDENT,
+    TABLE_GEOFENCE,
+    TABLE_TASK,
+    TABLE_ASSIGNMENT,
+    TABLE_API_KEY,
+];
+/// Writes `$record` unless the id is held by another tenant, keeping the stored
+/// `created_at_ms`, and returns the previous owner and creation time. The block
+/// runs as one statement, so the check and the write share a transaction.
+const INSERT_OR_UPDATE: &str = "RETURN {
+    LET $rid = type::thing($table, $id);
+    LET $before = (SELECT tenant_id, created_at_ms FROM ONLY $rid);
+    IF $before = NONE {
+        UPSERT $rid CONTENT $record RETURN NONE;
+    } ELSE IF $before.tenant_id = $record.tenant_id {
+        UPSERT $rid CONTENT $record RETURN NONE;
+        UPDATE $rid SET created_at_ms = $before.created_at_ms RETURN NONE;
+    };
+    RETURN $before;
+};";
+/// Reads the records in `$ids`; ids without a record are skipped.
+const SELECT_MANY: &str = "SELECT * FROM $ids";
+const DELETE_MANY: &str = "DELETE $ids";
+/// Writes `$record` to `$id` and removes the copies in `$others` together.
+const UPSERT_PARTITIONED: &str = "BEGIN TRANSACTION;
+UPSERT $id CONTENT $record;
+DELETE $others;
+COMMIT TRANSACTION;";
+/// Definitions for an extra mission table holding one or more classifications,
+/// with `{table}` standing for its name. Keep in step with the mission
+/// migrations.
+const MISSION_PARTITION_SCHEMA: &str = include_str!("../schema/mission_partition.surql");
+/// Deletes the tenant's rows in `$table` and returns how many went.
+const DELETE_BY_TENANT: &str = "RETURN array::len(
+    (DELETE type::table($table) WHERE tenant_id = $tenant_id RETURN BEFORE)
+);";
+const SCHEMA_VERSION_BOOTSTRAP: &str = "\
+DEFINE TABLE IF NOT EXISTS schema_version SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS version ON schema_version TYPE int;
+DEFINE FIELD IF NOT EXISTS name ON schema_version TYPE string;
+DEFINE FIELD IF NOT EXISTS applied_at_ms ON schema_version TYPE int;";
+
+struct SchemaMigration {
+    version: u32,
+    name: &'static str,
+    statements: &'static str,
+}
+
+const SCHEMA_MIGRATIONS: &[SchemaMigration] = &[
+    SchemaMigration {
+        version: 1,
+        name: "init",
+        statements: include_str!("../schema/0001_init.surql"),
+    },
+    SchemaMigration {
+        version: 2,
+        name: "search",
+        statements: include_str!("../schema/0002_search.surql"),
+    },
+    SchemaMigration {
+        version: 3,
+        name: "updated_since",
+        statements: include_str!("../schema/0003_updated_since.surql"),
+    },
+    SchemaMigration {
+        version: 4,
+        name: "asset_status_events",
+        statements: include_str!("../schema/0004_asset_status_events.surql"),
+    },
+    SchemaMigration {
+        version: 5,
+        name: "geofences",
+        statements: include_str!("../schema/0005_geofences.surql"),
+    },
+    SchemaMigration {
+        version: 6,
+        name: "maintenance_windows",
+        statements: include_str!("../schema/0006_maintenance_windows.surql"),
+    },
+    SchemaMigration {
+        version: 7,
+        name: "api_keys",
+        statements: include_str!("../schema/0007_api_keys.surql"),
+    },
+    SchemaMigration {
+        version: 8,
+        name: "incident_priority",
+        statements: include_str!("../schema/0008_incident_priority.surql"),
+    },
+    SchemaMigration {
+        version: 9,
+        name: "incident_position",
+        statements: include_str!("../schema/0009_incident_position.surql"),
+    },
+    SchemaMigration {
+        version: 10,
+        name: "task_dependencies",
+        statements: include_str!("../schema/0010_task_dependencies.surql"),
+    },
+    SchemaMigration {
+        version: 11,
+        name: "mission_assignments",
+        statements: include_str!("../schema/0011_mission_assignments.surql"),
+    },
+    SchemaMigration {
+        version: 12,
+        name: "mission_archive",
+        statements: include_str!("../schema/0012_mission_archive.surql"),
+    },
+];
 
 #[derive(Debug, Clone)]
 pub struct SurrealConfig {
@@ -38,6 +168,10 @@ pub struct SurrealConfig {
     pub connect_retry_initial_ms: u64,
     pub connect_retry_max_ms: u64,
     pub connect_retry_max_attempts: u32,
+    /// Run [`SurrealStore::warmup`] once connected.
+    pub warmup: bool,
+    /// `<classification>=<table>` pairs parsed by [`MissionPartitions::parse`].
+    pub mission_partitions: String,
 }
 
 impl SurrealConfig {
@@ -51,20 +185,120 @@ impl SurrealConfig {
             connect_retry_initial_ms: env_var_u64("C2_SURREAL_CONNECT_RETRY_INITIAL_MS", 500),
             connect_retry_max_ms: env_var_u64("C2_SURREAL_CONNECT_RETRY_MAX_MS", 5000),
             connect_retry_max_attempts: env_var_u32("C2_SURREAL_CONNECT_RETRY_MAX_ATTEMPTS", 0),
+            warmup: env_var_bool("C2_SURREAL_WARMUP", true),
+            mission_partitions: env::var("C2_SURREAL_MISSION_PARTITIONS").unwrap_or_default(),
+        }
+    }
+}
+
+/// Which table holds missions of each classification, for deployments that
+/// keep higher classifications physically apart. Unmapped classifications
+/// stay in `mission`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MissionPartitions {
+    tables: BTreeMap<SecurityClassification, String>,
+}
+
+impl MissionPartitions {
+    const CLASSIFICATIONS: [SecurityClassification; 6] = [
+        SecurityClassification::Unclassified,
+        SecurityClassification::Controlled,
+        SecurityClassification::Restricted,
+        SecurityClassification::Confidential,
+        SecurityClassification::Secret,
+        SecurityClassification::TopSecret,
+    ];
+
+    /// Parses a comma-separated list of `<classification>=<table>` pairs,
+    /// such as `top_secret=mission_top_secret`. Table names are lowercase
+    /// letters, digits and underscores, and may not be another entity's table.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut tables = BTreeMap::new();
+        for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (classification, table) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("invalid mission partition entry {entry:?}"))?;
+            let classification = SecurityClassification::from_str(classification.trim())
+                .map_err(|_| format!("invalid classification in mission partition {entry:?}"))?;
+            let table = table.trim();
+            let valid_name = !table.is_empty()
+                && table
+                    .chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_');
+            if !valid_name || (table != TABLE_MISSION && EXPECTED_TABLES.contains(&table)) {
+                return Err(format!("invalid table in mission partition {entry:?}"));
+            }
+            tables.insert(classification, table.to_string());
+        }
+        Ok(Self { tables })
+    }
+
+    /// The table missions of `classification` are written to.
+    pub fn table(&self, classification: SecurityClassification) -> &str {
+        self.tables
+            .get(&classification)
+            .map(String::as_str)
+            .unwrap_or(TABLE_MISSION)
+    }
+
+    /// Every mission table, `mission` first.
+    fn all(&self) -> Vec<&str> {
+        self.readable(None)
+    }
+
+    /// The tables holding at least one classification at or below
+    /// `clearance`; all of them when there is no clearance.
+    fn readable(&self, clearance: Option<SecurityClassification>) -> Vec<&str> {
+        let mut tables = Vec::new();
+        for classification in Self::CLASSIFICATIONS {
+            if clearance.is_some_and(|clearance| classification > clearance) {
+                continue;
+            }
+            let table = self.table(classification);
+            if !tables.contains(&table) {
+                tables.push(table);
+            }
+        }
+        tables
+    }
+
+    /// Tables other than `mission`, which the migrations do not define.
+    fn extra(&self) -> Vec<&str> {
+        let mut tables = Vec::new();
+        for table in self.tables.values().map(String::as_str) {
+            if table != TABLE_MISSION && !tables.contains(&table) {
+                tables.push(table);
+            }
         }
+        tables
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct SurrealStore {
     #[allow(dead_code)]
-    db: Surreal<Client>,
+    db: Surreal<Any>,
+    partitions: MissionPartitions,
+    /// Set on views from [`SurrealStore::with_clearance`].
+    clearance: Option<SecurityClassification>,
+}
+
+impl StorageBackend for SurrealStore {
+    const NAME: &'static str = "surreal";
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SurrealScheme {
     Ws,
     Wss,
+    Memory,
+    RocksDb,
+}
+
+impl SurrealScheme {
+    fn is_embedded(self) -> bool {
+        matches!(self, SurrealScheme::Memory | SurrealScheme::RocksDb)
+    }
 }
 
 fn normalize_endpoint(raw: &str) -> Result<(SurrealScheme, String), StorageError> {
@@ -73,19 +307,28 @@ fn normalize_endpoint(raw: &str) -> Result<(SurrealScheme, String), StorageError
         return Err(StorageError::new("C2_SURREAL_ENDPOINT is empty"));
     }
     if let Some(stripped) = trimmed.strip_prefix("ws://") {
-        return Ok((SurrealScheme::Ws, stripped.to_string()));
+        return Ok((SurrealScheme::Ws, format!("ws://{stripped}")));
     }
     if let Some(stripped) = trimmed.strip_prefix("wss://") {
-        return Ok((SurrealScheme::Wss, stripped.to_string()));
+        return Ok((SurrealScheme::Wss, format!("wss://{stripped}")));
+    }
+    if trimmed == "mem://" || trimmed == "memory" {
+        return Ok((SurrealScheme::Memory, "mem://".to_string()));
+    }
+    if let Some(path) = trimmed.strip_prefix("rocksdb://") {
+        if path.is_empty() {
+            return Err(StorageError::new("rocksdb:// endpoint requires a path"));
+        }
+        return Ok((SurrealScheme::RocksDb, format!("rocksdb://{path}")));
     }
     if let Some((scheme, rest)) = trimmed.split_once("://") {
         warn!(
             scheme = scheme,
             "Unsupported SurrealDB endpoint scheme, defaulting to ws"
         );
-        return Ok((SurrealScheme::Ws, rest.to_string()));
+        return Ok((SurrealScheme::Ws, format!("ws://{rest}")));
     }
-    Ok((SurrealScheme::Ws, trimmed.to_string()))
+    Ok((SurrealScheme::Ws, format!("ws://{trimmed}")))
 }
 
 #[derive(Debug, Deserialize)]
@@ -98,6 +341,8 @@ struct SurrealMissionRecord {
     classification: SecurityClassification,
     created_at_ms: u64,
     updated_at_ms: u64,
+    #[serde(default)]
+    archived_at_ms: Option<u64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -109,6 +354,7 @@ struct SurrealMissionWrite {
     classification: SecurityClassification,
     created_at_ms: u64,
     updated_at_ms: u64,
+    archived_at_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -125,9 +371,13 @@ struct SurrealAssetRecord {
     #[serde(default)]
     maintenance_state: MaintenanceState,
     #[serde(default)]
+    maintenance_window: Option<(u64, u64)>,
+    #[serde(default)]
     unit_id: Option<String>,
     #[serde(default)]
     capability_ids: Vec<String>,
+    #[serde(default)]
+    position: Option<Coordinate>,
     classification: SecurityClassification,
     created_at_ms: u64,
     updated_at_ms: u64,
@@ -142,13 +392,25 @@ struct SurrealAssetWrite {
     readiness: ReadinessState,
     comms_status: CommsStatus,
     maintenance_state: MaintenanceState,
+    maintenance_window: Option<(u64, u64)>,
     unit_id: Option<String>,
     capability_ids: Vec<String>,
+    position: Option<Coordinate>,
     classification: SecurityClassification,
     created_at_ms: u64,
     updated_at_ms: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct SurrealAssetStatusEventRecord {
+    asset_id: String,
+    tenant_id: String,
+    readiness: ReadinessState,
+    comms_status: CommsStatus,
+    maintenance_state: MaintenanceState,
+    recorded_at_ms: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct SurrealUnitRecord {
     id: Thing,
@@ -205,6 +467,79 @@ struct SurrealTeamWrite {
     updated_at_ms: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct SurrealGeofenceRecord {
+    id: Thing,
+    tenant_id: String,
+    name: String,
+    fence: GeoFence,
+    active: bool,
+    classification: SecurityClassification,
+    created_at_ms: u64,
+    updated_at_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct SurrealGeofenceWrite {
+    tenant_id: String,
+    name: String,
+    fence: GeoFence,
+    active: bool,
+    classification: SecurityClassification,
+    created_at_ms: u64,
+    updated_at_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SurrealAssignmentRecord {
+    id: Thing,
+    tenant_id: String,
+    mission_id: String,
+    unit_id: Option<String>,
+    team_id: Option<String>,
+    role: String,
+    classification: SecurityClassification,
+    assigned_at_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct SurrealAssignmentWrite {
+    tenant_id: String,
+    mission_id: String,
+    unit_id: Option<String>,
+    team_id: Option<String>,
+    role: String,
+    classification: SecurityClassification,
+    assigned_at_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SurrealApiKeyRecord {
+    id: Thing,
+    tenant_id: String,
+    label: String,
+    hashed_secret: String,
+    scopes: Vec<Permission>,
+    roles: Vec<Role>,
+    clearance: SecurityClassification,
+    created_by: String,
+    created_at_ms: u64,
+    revoked: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct SurrealApiKeyWrite {
+    tenant_id: String,
+    label: String,
+    hashed_secret: String,
+    scopes: Vec<Permission>,
+    roles: Vec<Role>,
+    clearance: SecurityClassification,
+    created_by: String,
+    created_at_ms: u64,
+    revoked: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct SurrealCapabilityRecord {
     id: Thing,
@@ -238,7 +573,13 @@ struct SurrealIncidentRecord {
     tenant_id: String,
     incident_type: IncidentType,
     status: IncidentStatus,
+    #[serde(default)]
+    priority: OperationalPriority,
     summary: String,
+    #[serde(default)]
+    position: Option<Coordinate>,
+    #[serde(default)]
+    affected_radius_m: Option<f64>,
     classification: SecurityClassification,
     created_at_ms: u64,
     updated_at_ms: u64,
@@ -249,7 +590,10 @@ struct SurrealIncidentWrite {
     tenant_id: String,
     incident_type: IncidentType,
     status: IncidentStatus,
+    priority: OperationalPriority,
     summary: String,
+    position: Option<Coordinate>,
+    affected_radius_m: Option<f64>,
     classification: SecurityClassification,
     created_at_ms: u64,
     updated_at_ms: u64,
@@ -264,6 +608,7 @@ struct SurrealTaskRecord {
     status: TaskStatus,
     priority: OperationalPriority,
     classification: SecurityClassification,
+    depends_on: Option<Vec<String>>,
     created_at_ms: u64,
     updated_at_ms: u64,
 }
@@ -276,32 +621,116 @@ struct SurrealTaskWrite {
     status: TaskStatus,
     priority: OperationalPriority,
     classification: SecurityClassification,
+    depends_on: Vec<String>,
     created_at_ms: u64,
     updated_at_ms: u64,
 }
 
-impl SurrealStore {
-    pub async fn connect(config: &SurrealConfig) -> Result<Self, StorageError> {
-        let (scheme, endpoint) = normalize_endpoint(&config.endpoint)?;
-        let db = match scheme {
-            SurrealScheme::Ws => Surreal::new::<Ws>(&endpoint).await.map_err(map_err)?,
-            SurrealScheme::Wss => Surreal::new::<Wss>(&endpoint).await.map_err(map_err)?,
-        };
+/// Connects, signs in unless embedded, and selects the namespace and
+/// database.
+async fn open(config: &SurrealConfig) -> Result<Surreal<Any>, StorageError> {
+    let (scheme, endpoint) = normalize_endpoint(&config.endpoint)?;
+    let db = any::connect(endpoint).await.map_err(map_err)?;
+    if scheme.is_embedded() {
+        info!(endpoint = %config.endpoint, "Using embedded SurrealDB engine");
+    } else {
         db.signin(Root {
             username: &config.username,
             password: &config.password,
         })
         .await
         .map_err(map_err)?;
-        db.use_ns(&config.namespace)
-            .use_db(&config.database)
-            .await
-            .map_err(map_err)?;
+    }
+    db.use_ns(&config.namespace)
+        .use_db(&config.database)
+        .await
+        .map_err(map_err)?;
+    Ok(db)
+}
+
+impl SurrealStore {
+    pub async fn connect(config: &SurrealConfig) -> Result<Self, StorageError> {
+        let db = open(config).await?;
+        let partitions = MissionPartitions::parse(&config.mission_partitions)
+            .map_err(|err| StorageError::new(format!("C2_SURREAL_MISSION_PARTITIONS: {err}")))?;
         apply_schema(&db).await?;
-        Ok(Self { db })
+        apply_partition_schema(&db, &partitions).await?;
+        Ok(Self {
+            db,
+            partitions,
+            clearance: None,
+        })
+    }
+
+    /// Signs in and pings the server without touching the schema, for
+    /// `--check` runs that must not change the database. Returns the
+    /// endpoint on success.
+    pub async fn check(config: &SurrealConfig) -> Result<String, StorageError> {
+        open(config).await?.health().await.map_err(map_err)?;
+        Ok(config.endpoint.clone())
+    }
+
+    /// A view of the store whose mission reads only touch the partitions
+    /// holding classifications at or below `clearance`, so higher partitions
+    /// are never queried on the caller's behalf. Writes are not restricted.
+    pub fn with_clearance(&self, clearance: SecurityClassification) -> Self {
+        Self {
+            clearance: Some(clearance),
+            ..self.clone()
+        }
+    }
+
+    fn mission_tables(&self) -> Vec<Table> {
+        self.partitions
+            .readable(self.clearance)
+            .into_iter()
+            .map(Table::from)
+            .collect()
     }
 
+    pub async fn health(&self) -> Result<(), StorageError> {
+        self.db.health().await.map_err(map_err)
+    }
+
+    /// Checks the store is usable before it takes traffic: a first query
+    /// over the connection, and every table the repositories use defined.
+    /// A missing table is reported by name.
+    pub async fn warmup(&self) -> Result<(), StorageError> {
+        #[derive(Deserialize)]
+        struct DatabaseInfo {
+            tables: HashMap<String, IgnoredAny>,
+        }
+
+        let mut response = self.db.query("INFO FOR DB").await.map_err(map_err)?;
+        let info: Option<DatabaseInfo> = response.take(0).map_err(map_err)?;
+        let tables = info.map(|info| info.tables).unwrap_or_default();
+        let missing: Vec<&str> = EXPECTED_TABLES
+            .iter()
+            .copied()
+            .chain(self.partitions.extra())
+            .filter(|table| !tables.contains_key(*table))
+            .collect();
+        if !missing.is_empty() {
+            return Err(StorageError::new(format!(
+                "SurrealDB schema is missing tables: {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Connects, retrying per `config`, then warms up when enabled. A failed
+    /// warmup is not retried: the schema will not fix itself.
     pub async fn connect_with_retry(config: &SurrealConfig) -> Result<Self, StorageError> {
+        let store = Self::connect_retrying(config).await?;
+        if config.warmup {
+            store.warmup().await?;
+            info!("SurrealDB warmup complete");
+        }
+        Ok(store)
+    }
+
+    async fn connect_retrying(config: &SurrealConfig) -> Result<Self, StorageError> {
         let mut attempt: u32 = 0;
         let mut delay_ms = config.connect_retry_initial_ms.max(1);
         let max_delay = config.connect_retry_max_ms.max(delay_ms);
@@ -328,25 +757,225 @@ impl SurrealStore {
             }
         }
     }
+
+    async fn select_many<R: DeserializeOwned>(
+        &self,
+        table: &'static str,
+        ids: &[impl ToString],
+    ) -> Result<Vec<R>, StorageError> {
+        self.select_many_in(&[table], ids).await
+    }
+
+    /// Like `select_many`, looking for each id in every one of `tables`.
+    async fn select_many_in<R: DeserializeOwned>(
+        &self,
+        tables: &[&str],
+        ids: &[impl ToString],
+    ) -> Result<Vec<R>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            ids: Vec<Thing>,
+        }
+
+        let ids = record_ids(tables, ids);
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut response = self
+            .db
+            .query(SELECT_MANY)
+            .bind(Bindings { ids })
+            .await
+            .map_err(map_err)?;
+        response.take(0).map_err(map_err)
+    }
+
+    /// Deletes the record `id` from each of `tables` that holds it.
+    async fn delete_in(&self, tables: &[&str], id: &str) -> Result<(), StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            ids: Vec<Thing>,
+        }
+
+        let ids = record_ids(tables, &[id]);
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.db
+            .query(DELETE_MANY)
+            .bind(Bindings { ids })
+            .await
+            .map_err(map_err)?
+            .check()
+            .map_err(map_err)?;
+        Ok(())
+    }
+
+    async fn delete_by_tenant(
+        &self,
+        table: &str,
+        tenant_id: TenantId,
+    ) -> Result<u64, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            table: String,
+            tenant_id: String,
+        }
+
+        let mut response = self
+            .db
+            .query(DELETE_BY_TENANT)
+            .bind(Bindings {
+                table: table.to_string(),
+                tenant_id: tenant_id.to_string(),
+            })
+            .await
+            .map_err(map_err)?;
+        let deleted: Option<u64> = response.take(0).map_err(map_err)?;
+        Ok(deleted.unwrap_or(0))
+    }
+
+    async fn insert_or_update_record<W: Serialize + 'static>(
+        &self,
+        table: &'static str,
+        id: String,
+        tenant_id: &str,
+        created_at_ms: u64,
+        record: W,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings<W> {
+            table: &'static str,
+            id: String,
+            record: W,
+        }
+
+        let mut response = self
+            .db
+            .query(INSERT_OR_UPDATE)
+            .bind(Bindings { table, id, record })
+            .await
+            .map_err(map_err)?;
+        let before: Option<SurrealExistingRecord> = response.take(0).map_err(map_err)?;
+        Ok(match before {
+            None => Some(UpsertOutcome {
+                inserted: true,
+                created_at_ms,
+            }),
+            Some(before) if before.tenant_id == tenant_id => Some(UpsertOutcome {
+                inserted: false,
+                created_at_ms: before.created_at_ms,
+            }),
+            Some(_) => None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SurrealExistingRecord {
+    tenant_id: String,
+    created_at_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SurrealSchemaVersionRecord {
+    version: u32,
+}
+
+async fn apply_schema(db: &Surreal<Any>) -> Result<(), StorageError> {
+    db.query(SCHEMA_VERSION_BOOTSTRAP)
+        .await
+        .map_err(map_err)?
+        .check()
+        .map_err(map_err)?;
+    let current = current_schema_version(db).await?;
+
+    for migration in SCHEMA_MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > current)
+    {
+        #[derive(Serialize)]
+        struct Bindings {
+            version: u32,
+            name: &'static str,
+            applied_at_ms: u64,
+        }
+
+        let statement = format!(
+            "BEGIN TRANSACTION;\n{}\nUPSERT type::thing('schema_version', $version) CONTENT {{ version: $version, name: $name, applied_at_ms: $applied_at_ms }};\nCOMMIT TRANSACTION;",
+            migration.statements
+        );
+        db.query(statement)
+            .bind(Bindings {
+                version: migration.version,
+                name: migration.name,
+                applied_at_ms: now_epoch_millis(),
+            })
+            .await
+            .map_err(map_err)?
+            .check()
+            .map_err(|err| {
+                StorageError::new(format!(
+                    "schema migration {} ({}) failed: {}",
+                    migration.version, migration.name, err
+                ))
+            })?;
+        info!(
+            version = migration.version,
+            name = migration.name,
+            "Applied SurrealDB schema migration"
+        );
+    }
+    Ok(())
 }
 
-async fn apply_schema(db: &Surreal<Client>) -> Result<(), StorageError> {
-    db.query(SURREAL_SCHEMA).await.map_err(map_err)?;
+/// Defines the extra mission tables named in `partitions`. These sit outside
+/// the versioned migrations since they depend on configuration.
+async fn apply_partition_schema(
+    db: &Surreal<Any>,
+    partitions: &MissionPartitions,
+) -> Result<(), StorageError> {
+    for table in partitions.extra() {
+        db.query(MISSION_PARTITION_SCHEMA.replace("{table}", table))
+            .await
+            .map_err(map_err)?
+            .check()
+            .map_err(|err| {
+                StorageError::new(format!("mission partition {table} schema failed: {err}"))
+            })?;
+    }
     Ok(())
 }
 
+fn record_ids(tables: &[&str], ids: &[impl ToString]) -> Vec<Thing> {
+    tables
+        .iter()
+        .flat_map(|table| {
+            ids.iter()
+                .map(move |id| Thing::from((*table, Id::String(id.to_string()))))
+        })
+        .collect()
+}
+
+async fn current_schema_version(db: &Surreal<Any>) -> Result<u32, StorageError> {
+    let mut response = db
+        .query("SELECT version FROM schema_version ORDER BY version DESC LIMIT 1")
+        .await
+        .map_err(map_err)?;
+    let record: Option<SurrealSchemaVersionRecord> = response.take(0).map_err(map_err)?;
+    Ok(record.map(|record| record.version).unwrap_or(0))
+}
+
 #[async_trait]
 impl MissionRepository for SurrealStore {
     async fn get(&self, id: MissionId) -> Result<Option<Mission>, StorageError> {
-        let record: Option<SurrealMissionRecord> = self
-            .db
-            .select((TABLE_MISSION, id.to_string()))
-            .await
-            .map_err(map_err)?;
-        match record {
-            Some(record) => Ok(Some(record.try_into()?)),
-            None => Ok(None),
-        }
+        Ok(MissionRepository::get_many(self, &[id]).await?.pop())
+    }
+
+    async fn get_many(&self, ids: &[MissionId]) -> Result<Vec<Mission>, StorageError> {
+        let tables = self.partitions.readable(self.clearance);
+        let records: Vec<SurrealMissionRecord> = self.select_many_in(&tables, ids).await?;
+        records.into_iter().map(Mission::try_from).collect()
     }
 
     async fn list_by_tenant(
@@ -357,6 +986,7 @@ impl MissionRepository for SurrealStore {
     ) -> Result<Vec<Mission>, StorageError> {
         #[derive(Serialize)]
         struct Bindings {
+            tables: Vec<Table>,
             tenant_id: String,
             limit: usize,
             offset: usize,
@@ -365,9 +995,10 @@ impl MissionRepository for SurrealStore {
         let mut response = self
             .db
             .query(
-                "SELECT * FROM mission WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+                "SELECT * FROM $tables WHERE tenant_id = $tenant_id AND archived_at_ms = NONE ORDER BY created_at_ms DESC LIMIT $limit START $offset",
             )
             .bind(Bindings {
+                tables: self.mission_tables(),
                 tenant_id: tenant_id.to_string(),
                 limit,
                 offset,
@@ -382,49 +1013,15 @@ impl MissionRepository for SurrealStore {
             .collect()
     }
 
-    async fn upsert(&self, mission: Mission) -> Result<(), StorageError> {
-        let record = SurrealMissionWrite::from(&mission);
-        let _: Option<SurrealMissionRecord> = self
-            .db
-            .upsert((TABLE_MISSION, mission.id.to_string()))
-            .content(record)
-            .await
-            .map_err(map_err)?;
-        Ok(())
-    }
-
-    async fn delete(&self, id: MissionId) -> Result<(), StorageError> {
-        let _: Option<SurrealMissionRecord> = self
-            .db
-            .delete((TABLE_MISSION, id.to_string()))
-            .await
-            .map_err(map_err)?;
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl AssetRepository for SurrealStore {
-    async fn get(&self, id: AssetId) -> Result<Option<Asset>, StorageError> {
-        let record: Option<SurrealAssetRecord> = self
-            .db
-            .select((TABLE_ASSET, id.to_string()))
-            .await
-            .map_err(map_err)?;
-        match record {
-            Some(record) => Ok(Some(record.try_into()?)),
-            None => Ok(None),
-        }
-    }
-
-    async fn list_by_tenant(
+    async fn list_archived_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
-    ) -> Result<Vec<Asset>, StorageError> {
+    ) -> Result<Vec<Mission>, StorageError> {
         #[derive(Serialize)]
         struct Bindings {
+            tables: Vec<Table>,
             tenant_id: String,
             limit: usize,
             offset: usize,
@@ -433,9 +1030,10 @@ impl AssetRepository for SurrealStore {
         let mut response = self
             .db
             .query(
-                "SELECT * FROM asset WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+                "SELECT * FROM $tables WHERE tenant_id = $tenant_id AND archived_at_ms != NONE ORDER BY archived_at_ms DESC LIMIT $limit START $offset",
             )
             .bind(Bindings {
+                tables: self.mission_tables(),
                 tenant_id: tenant_id.to_string(),
                 limit,
                 offset,
@@ -443,54 +1041,824 @@ impl AssetRepository for SurrealStore {
             .await
             .map_err(map_err)?;
 
-        let records: Vec<SurrealAssetRecord> = response.take(0).map_err(map_err)?;
-        records.into_iter().map(Asset::try_from).collect()
-    }
-
-    async fn upsert(&self, asset: Asset) -> Result<(), StorageError> {
+        let records: Vec<SurrealMissionRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Mission::try_from)
+            .collect()
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &MissionFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tables: Vec<Table>,
+            tenant_id: String,
+            status: Option<MissionStatus>,
+            priority: Option<OperationalPriority>,
+            classification: Option<SecurityClassification>,
+            search: Option<String>,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM $tables WHERE tenant_id = $tenant_id AND archived_at_ms = NONE AND (!$status OR status = $status) AND (!$priority OR priority = $priority) AND (!$classification OR classification = $classification) AND (!$search OR string::contains(string::lowercase(name), $search)) ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                tables: self.mission_tables(),
+                tenant_id: tenant_id.to_string(),
+                status: filter.status,
+                priority: filter.priority,
+                classification: filter.classification,
+                search: search_term(filter.search.as_deref()),
+                limit,
+                offset,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealMissionRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Mission::try_from).collect()
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tables: Vec<Table>,
+            tenant_id: String,
+            query: String,
+            limit: usize,
+        }
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, search::score(1) AS score FROM $tables WHERE tenant_id = $tenant_id AND archived_at_ms = NONE AND name @1@ $query ORDER BY score DESC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tables: self.mission_tables(),
+                tenant_id: tenant_id.to_string(),
+                query: query.to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealMissionRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Mission::try_from)
+            .collect()
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tables: Vec<Table>,
+            tenant_id: String,
+            since_ms: EpochMillis,
+            after_id: String,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, record::id(id) AS record_key FROM $tables WHERE tenant_id = $tenant_id AND (updated_at_ms > $since_ms OR (updated_at_ms = $since_ms AND record::id(id) > $after_id)) ORDER BY updated_at_ms ASC, record_key ASC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tables: self.mission_tables(),
+                tenant_id: tenant_id.to_string(),
+                since_ms: after.updated_at_ms,
+                after_id: after.after_id().to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealMissionRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Mission::try_from)
+            .collect()
+    }
+
+    async fn count_by_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<MissionStatus>>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tables: Vec<Table>,
+            tenant_id: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT status AS key, classification, count() AS count FROM $tables WHERE tenant_id = $tenant_id GROUP BY key, classification",
+            )
+            .bind(Bindings {
+                tables: self.mission_tables(),
+                tenant_id: tenant_id.to_string(),
+            })
+            .await
+            .map_err(map_err)?;
+
+        response.take(0).map_err(map_err)
+    }
+
+    /// Writes to the mission's partition and drops any copy left in another
+    /// one by an earlier classification, in one transaction so a mission is
+    /// never stored in two partitions.
+    async fn upsert(&self, mission: Mission) -> Result<(), StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            id: Thing,
+            record: SurrealMissionWrite,
+            others: Vec<Thing>,
+        }
+
+        let table = self.partitions.table(mission.classification);
+        let others: Vec<&str> =
+            self.partitions.all().into_iter().filter(|other| *other != table).collect();
+        self.db
+            .query(UPSERT_PARTITIONED)
+            .bind(Bindings {
+                id: Thing::from((table, Id::String(mission.id.to_string()))),
+                record: SurrealMissionWrite::from(&mission),
+                others: record_ids(&others, &[mission.id]),
+            })
+            .await
+            .map_err(map_err)?
+            .check()
+            .map_err(map_err)?;
+        Ok(())
+    }
+
+    /// With partitions configured the tenant check looks across all of them
+    /// and, unlike the single-table write, is not atomic with the write.
+    async fn insert_or_update(
+        &self,
+        mut mission: Mission,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        if self.partitions.extra().is_empty() {
+            return self
+                .insert_or_update_record(
+                    TABLE_MISSION,
+                    mission.id.to_string(),
+                    &mission.tenant_id.to_string(),
+                    mission.created_at_ms,
+                    SurrealMissionWrite::from(&mission),
+                )
+                .await;
+        }
+        let existing: Vec<SurrealExistingRecord> =
+            self.select_many_in(&self.partitions.all(), &[mission.id]).await?;
+        let outcome = match existing.first() {
+            Some(before) if before.tenant_id != mission.tenant_id.to_string() => return Ok(None),
+            Some(before) => UpsertOutcome {
+                inserted: false,
+                created_at_ms: before.created_at_ms,
+            },
+            None => UpsertOutcome {
+                inserted: true,
+                created_at_ms: mission.created_at_ms,
+            },
+        };
+        mission.created_at_ms = outcome.created_at_ms;
+        MissionRepository::upsert(self, mission).await?;
+        Ok(Some(outcome))
+    }
+
+    async fn delete(&self, id: MissionId) -> Result<(), StorageError> {
+        self.delete_in(&self.partitions.all(), &id.to_string()).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mut deleted = 0;
+        for table in self.partitions.all() {
+            deleted += self.delete_by_tenant(table, tenant_id).await?;
+        }
+        Ok(deleted)
+    }
+}
+
+#[async_trait]
+impl AssetRepository for SurrealStore {
+    async fn get(&self, id: AssetId) -> Result<Option<Asset>, StorageError> {
+        let record: Option<SurrealAssetRecord> = self
+            .db
+            .select((TABLE_ASSET, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        match record {
+            Some(record) => Ok(Some(record.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_many(&self, ids: &[AssetId]) -> Result<Vec<Asset>, StorageError> {
+        let records: Vec<SurrealAssetRecord> = self.select_many(TABLE_ASSET, ids).await?;
+        records.into_iter().map(Asset::try_from).collect()
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM asset WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                limit,
+                offset,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealAssetRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Asset::try_from).collect()
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &AssetFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            status: Option<AssetStatus>,
+            classification: Option<SecurityClassification>,
+            search: Option<String>,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM asset WHERE tenant_id = $tenant_id AND (!$status OR status = $status) AND (!$classification OR classification = $classification) AND (!$search OR string::contains(string::lowercase(name), $search)) ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                status: filter.status,
+                classification: filter.classification,
+                search: search_term(filter.search.as_deref()),
+                limit,
+                offset,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealAssetRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Asset::try_from).collect()
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            since_ms: EpochMillis,
+            after_id: String,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, record::id(id) AS record_key FROM asset WHERE tenant_id = $tenant_id AND (updated_at_ms > $since_ms OR (updated_at_ms = $since_ms AND record::id(id) > $after_id)) ORDER BY updated_at_ms ASC, record_key ASC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                since_ms: after.updated_at_ms,
+                after_id: after.after_id().to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealAssetRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Asset::try_from)
+            .collect()
+    }
+
+    async fn list_status_history(
+        &self,
+        asset_id: AssetId,
+        from_ms: EpochMillis,
+        to_ms: EpochMillis,
+    ) -> Result<Vec<AssetStatusEvent>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            asset_id: String,
+            from_ms: EpochMillis,
+            to_ms: EpochMillis,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT asset_id, tenant_id, readiness, comms_status, maintenance_state, recorded_at_ms FROM asset_status_event WHERE asset_id = $asset_id AND recorded_at_ms >= $from_ms AND recorded_at_ms <= $to_ms ORDER BY recorded_at_ms ASC",
+            )
+            .bind(Bindings {
+                asset_id: asset_id.to_string(),
+                from_ms,
+                to_ms,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealAssetStatusEventRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(AssetStatusEvent::try_from)
+            .collect()
+    }
+
+    async fn list_maintenance_due(&self, now_ms: EpochMillis) -> Result<Vec<Asset>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            now_ms: EpochMillis,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM asset WHERE maintenance_window != NONE AND ((maintenance_state = 'scheduled' AND maintenance_window[0] <= $now_ms) OR (maintenance_state IN ['scheduled', 'in_progress'] AND maintenance_window[1] <= $now_ms))",
+            )
+            .bind(Bindings { now_ms })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealAssetRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Asset::try_from).collect()
+    }
+
+    async fn count_by_readiness(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<ReadinessState>>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT readiness AS key, classification, count() AS count FROM asset WHERE tenant_id = $tenant_id GROUP BY key, classification",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+            })
+            .await
+            .map_err(map_err)?;
+
+        response.take(0).map_err(map_err)
+    }
+
+    async fn upsert(&self, asset: Asset) -> Result<(), StorageError> {
+        let previous = AssetRepository::get(self, asset.id).await?;
         let record = SurrealAssetWrite::from(&asset);
         let _: Option<SurrealAssetRecord> = self
             .db
-            .upsert((TABLE_ASSET, asset.id.to_string()))
+            .upsert((TABLE_ASSET, asset.id.to_string()))
+            .content(record)
+            .await
+            .map_err(map_err)?;
+        if previous.is_some_and(|previous| previous.status_differs(&asset)) {
+            let event = SurrealAssetStatusEventRecord::from(&AssetStatusEvent::from(&asset));
+            let _: Option<SurrealAssetStatusEventRecord> = self
+                .db
+                .create(TABLE_ASSET_STATUS_EVENT)
+                .content(event)
+                .await
+                .map_err(map_err)?;
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: AssetId) -> Result<(), StorageError> {
+        let _: Option<SurrealAssetRecord> = self
+            .db
+            .delete((TABLE_ASSET, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        Ok(())
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_ASSET_STATUS_EVENT, tenant_id).await?;
+        self.delete_by_tenant(TABLE_ASSET, tenant_id).await
+    }
+}
+
+#[async_trait]
+impl UnitRepository for SurrealStore {
+    async fn get(&self, id: UnitId) -> Result<Option<Unit>, StorageError> {
+        let record: Option<SurrealUnitRecord> = self
+            .db
+            .select((TABLE_UNIT, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        match record {
+            Some(record) => Ok(Some(record.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_many(&self, ids: &[UnitId]) -> Result<Vec<Unit>, StorageError> {
+        let records: Vec<SurrealUnitRecord> = self.select_many(TABLE_UNIT, ids).await?;
+        records.into_iter().map(Unit::try_from).collect()
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM unit WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                limit,
+                offset,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealUnitRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Unit::try_from).collect()
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            since_ms: EpochMillis,
+            after_id: String,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, record::id(id) AS record_key FROM unit WHERE tenant_id = $tenant_id AND (updated_at_ms > $since_ms OR (updated_at_ms = $since_ms AND record::id(id) > $after_id)) ORDER BY updated_at_ms ASC, record_key ASC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                since_ms: after.updated_at_ms,
+                after_id: after.after_id().to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealUnitRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Unit::try_from)
+            .collect()
+    }
+
+    async fn upsert(&self, unit: Unit) -> Result<(), StorageError> {
+        let record = SurrealUnitWrite::from(&unit);
+        let _: Option<SurrealUnitRecord> = self
+            .db
+            .upsert((TABLE_UNIT, unit.id.to_string()))
             .content(record)
             .await
             .map_err(map_err)?;
         Ok(())
     }
 
-    async fn delete(&self, id: AssetId) -> Result<(), StorageError> {
-        let _: Option<SurrealAssetRecord> = self
+    async fn delete(&self, id: UnitId) -> Result<(), StorageError> {
+        let _: Option<SurrealUnitRecord> = self
             .db
-            .delete((TABLE_ASSET, id.to_string()))
+            .delete((TABLE_UNIT, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        Ok(())
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_UNIT, tenant_id).await
+    }
+}
+
+#[async_trait]
+impl TeamRepository for SurrealStore {
+    async fn get(&self, id: TeamId) -> Result<Option<Team>, StorageError> {
+        let record: Option<SurrealTeamRecord> = self
+            .db
+            .select((TABLE_TEAM, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        match record {
+            Some(record) => Ok(Some(record.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_many(&self, ids: &[TeamId]) -> Result<Vec<Team>, StorageError> {
+        let records: Vec<SurrealTeamRecord> = self.select_many(TABLE_TEAM, ids).await?;
+        records.into_iter().map(Team::try_from).collect()
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM team WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                limit,
+                offset,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealTeamRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Team::try_from).collect()
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            since_ms: EpochMillis,
+            after_id: String,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, record::id(id) AS record_key FROM team WHERE tenant_id = $tenant_id AND (updated_at_ms > $since_ms OR (updated_at_ms = $since_ms AND record::id(id) > $after_id)) ORDER BY updated_at_ms ASC, record_key ASC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                since_ms: after.updated_at_ms,
+                after_id: after.after_id().to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealTeamRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Team::try_from)
+            .collect()
+    }
+
+    async fn upsert(&self, team: Team) -> Result<(), StorageError> {
+        let record = SurrealTeamWrite::from(&team);
+        let _: Option<SurrealTeamRecord> = self
+            .db
+            .upsert((TABLE_TEAM, team.id.to_string()))
+            .content(record)
+            .await
+            .map_err(map_err)?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: TeamId) -> Result<(), StorageError> {
+        let _: Option<SurrealTeamRecord> = self
+            .db
+            .delete((TABLE_TEAM, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        Ok(())
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_TEAM, tenant_id).await
+    }
+}
+
+#[async_trait]
+impl GeofenceRepository for SurrealStore {
+    async fn get(&self, id: GeofenceId) -> Result<Option<Geofence>, StorageError> {
+        let record: Option<SurrealGeofenceRecord> = self
+            .db
+            .select((TABLE_GEOFENCE, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        match record {
+            Some(record) => Ok(Some(record.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_many(&self, ids: &[GeofenceId]) -> Result<Vec<Geofence>, StorageError> {
+        let records: Vec<SurrealGeofenceRecord> = self.select_many(TABLE_GEOFENCE, ids).await?;
+        records.into_iter().map(Geofence::try_from).collect()
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Geofence>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM geofence WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                limit,
+                offset,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealGeofenceRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Geofence::try_from).collect()
+    }
+
+    async fn list_active(&self) -> Result<Vec<Geofence>, StorageError> {
+        let mut response = self
+            .db
+            .query("SELECT * FROM geofence WHERE active = true")
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealGeofenceRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Geofence::try_from).collect()
+    }
+
+    async fn upsert(&self, geofence: Geofence) -> Result<(), StorageError> {
+        let record = SurrealGeofenceWrite::from(&geofence);
+        let _: Option<SurrealGeofenceRecord> = self
+            .db
+            .upsert((TABLE_GEOFENCE, geofence.id.to_string()))
+            .content(record)
+            .await
+            .map_err(map_err)?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: GeofenceId) -> Result<(), StorageError> {
+        let _: Option<SurrealGeofenceRecord> = self
+            .db
+            .delete((TABLE_GEOFENCE, id.to_string()))
             .await
             .map_err(map_err)?;
         Ok(())
     }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_GEOFENCE, tenant_id).await
+    }
 }
 
-#[async_trait]
-impl UnitRepository for SurrealStore {
-    async fn get(&self, id: UnitId) -> Result<Option<Unit>, StorageError> {
-        let record: Option<SurrealUnitRecord> = self
+#[async_trait]
+impl AssignmentRepository for SurrealStore {
+    async fn get(&self, id: AssignmentId) -> Result<Option<MissionAssignment>, StorageError> {
+        let record: Option<SurrealAssignmentRecord> = self
+            .db
+            .select((TABLE_ASSIGNMENT, id.to_string()))
+            .await
+            .map_err(map_err)?;
+        match record {
+            Some(record) => Ok(Some(record.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_many(&self, ids: &[AssignmentId]) -> Result<Vec<MissionAssignment>, StorageError> {
+        let records: Vec<SurrealAssignmentRecord> = self.select_many(TABLE_ASSIGNMENT, ids).await?;
+        records.into_iter().map(MissionAssignment::try_from).collect()
+    }
+
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            mission_id: String,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
             .db
-            .select((TABLE_UNIT, id.to_string()))
+            .query(
+                "SELECT * FROM mission_assignment WHERE mission_id = $mission_id ORDER BY assigned_at_ms ASC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                mission_id: mission_id.to_string(),
+                limit,
+                offset,
+            })
             .await
             .map_err(map_err)?;
-        match record {
-            Some(record) => Ok(Some(record.try_into()?)),
-            None => Ok(None),
-        }
+
+        let records: Vec<SurrealAssignmentRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(MissionAssignment::try_from).collect()
     }
 
-    async fn list_by_tenant(
+    async fn list_by_unit(
         &self,
-        tenant_id: TenantId,
+        unit_id: UnitId,
         limit: usize,
         offset: usize,
-    ) -> Result<Vec<Unit>, StorageError> {
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
         #[derive(Serialize)]
         struct Bindings {
-            tenant_id: String,
+            unit_id: String,
             limit: usize,
             offset: usize,
         }
@@ -498,47 +1866,51 @@ impl UnitRepository for SurrealStore {
         let mut response = self
             .db
             .query(
-                "SELECT * FROM unit WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+                "SELECT * FROM mission_assignment WHERE unit_id = $unit_id ORDER BY assigned_at_ms DESC LIMIT $limit START $offset",
             )
             .bind(Bindings {
-                tenant_id: tenant_id.to_string(),
+                unit_id: unit_id.to_string(),
                 limit,
                 offset,
             })
             .await
             .map_err(map_err)?;
 
-        let records: Vec<SurrealUnitRecord> = response.take(0).map_err(map_err)?;
-        records.into_iter().map(Unit::try_from).collect()
+        let records: Vec<SurrealAssignmentRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(MissionAssignment::try_from).collect()
     }
 
-    async fn upsert(&self, unit: Unit) -> Result<(), StorageError> {
-        let record = SurrealUnitWrite::from(&unit);
-        let _: Option<SurrealUnitRecord> = self
+    async fn upsert(&self, assignment: MissionAssignment) -> Result<(), StorageError> {
+        let record = SurrealAssignmentWrite::from(&assignment);
+        let _: Option<SurrealAssignmentRecord> = self
             .db
-            .upsert((TABLE_UNIT, unit.id.to_string()))
+            .upsert((TABLE_ASSIGNMENT, assignment.id.to_string()))
             .content(record)
             .await
             .map_err(map_err)?;
         Ok(())
     }
 
-    async fn delete(&self, id: UnitId) -> Result<(), StorageError> {
-        let _: Option<SurrealUnitRecord> = self
+    async fn delete(&self, id: AssignmentId) -> Result<(), StorageError> {
+        let _: Option<SurrealAssignmentRecord> = self
             .db
-            .delete((TABLE_UNIT, id.to_string()))
+            .delete((TABLE_ASSIGNMENT, id.to_string()))
             .await
             .map_err(map_err)?;
         Ok(())
     }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_ASSIGNMENT, tenant_id).await
+    }
 }
 
 #[async_trait]
-impl TeamRepository for SurrealStore {
-    async fn get(&self, id: TeamId) -> Result<Option<Team>, StorageError> {
-        let record: Option<SurrealTeamRecord> = self
+impl ApiKeyRepository for SurrealStore {
+    async fn get(&self, id: ApiKeyId) -> Result<Option<ApiKey>, StorageError> {
+        let record: Option<SurrealApiKeyRecord> = self
             .db
-            .select((TABLE_TEAM, id.to_string()))
+            .select((TABLE_API_KEY, id.to_string()))
             .await
             .map_err(map_err)?;
         match record {
@@ -547,12 +1919,36 @@ impl TeamRepository for SurrealStore {
         }
     }
 
+    async fn get_many(&self, ids: &[ApiKeyId]) -> Result<Vec<ApiKey>, StorageError> {
+        let records: Vec<SurrealApiKeyRecord> = self.select_many(TABLE_API_KEY, ids).await?;
+        records.into_iter().map(ApiKey::try_from).collect()
+    }
+
+    async fn find_by_hash(&self, hashed_secret: &str) -> Result<Option<ApiKey>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            hashed_secret: String,
+        }
+
+        let mut response = self
+            .db
+            .query("SELECT * FROM api_key WHERE hashed_secret = $hashed_secret LIMIT 1")
+            .bind(Bindings {
+                hashed_secret: hashed_secret.to_string(),
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealApiKeyRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().next().map(ApiKey::try_from).transpose()
+    }
+
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
-    ) -> Result<Vec<Team>, StorageError> {
+    ) -> Result<Vec<ApiKey>, StorageError> {
         #[derive(Serialize)]
         struct Bindings {
             tenant_id: String,
@@ -563,7 +1959,7 @@ impl TeamRepository for SurrealStore {
         let mut response = self
             .db
             .query(
-                "SELECT * FROM team WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
+                "SELECT * FROM api_key WHERE tenant_id = $tenant_id ORDER BY created_at_ms DESC LIMIT $limit START $offset",
             )
             .bind(Bindings {
                 tenant_id: tenant_id.to_string(),
@@ -573,28 +1969,23 @@ impl TeamRepository for SurrealStore {
             .await
             .map_err(map_err)?;
 
-        let records: Vec<SurrealTeamRecord> = response.take(0).map_err(map_err)?;
-        records.into_iter().map(Team::try_from).collect()
+        let records: Vec<SurrealApiKeyRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(ApiKey::try_from).collect()
     }
 
-    async fn upsert(&self, team: Team) -> Result<(), StorageError> {
-        let record = SurrealTeamWrite::from(&team);
-        let _: Option<SurrealTeamRecord> = self
+    async fn upsert(&self, key: ApiKey) -> Result<(), StorageError> {
+        let record = SurrealApiKeyWrite::from(&key);
+        let _: Option<SurrealApiKeyRecord> = self
             .db
-            .upsert((TABLE_TEAM, team.id.to_string()))
+            .upsert((TABLE_API_KEY, key.id.to_string()))
             .content(record)
             .await
             .map_err(map_err)?;
         Ok(())
     }
 
-    async fn delete(&self, id: TeamId) -> Result<(), StorageError> {
-        let _: Option<SurrealTeamRecord> = self
-            .db
-            .delete((TABLE_TEAM, id.to_string()))
-            .await
-            .map_err(map_err)?;
-        Ok(())
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_API_KEY, tenant_id).await
     }
 }
 
@@ -612,6 +2003,11 @@ impl CapabilityRepository for SurrealStore {
         }
     }
 
+    async fn get_many(&self, ids: &[CapabilityId]) -> Result<Vec<Capability>, StorageError> {
+        let records: Vec<SurrealCapabilityRecord> = self.select_many(TABLE_CAPABILITY, ids).await?;
+        records.into_iter().map(Capability::try_from).collect()
+    }
+
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
@@ -645,6 +2041,64 @@ impl CapabilityRepository for SurrealStore {
             .collect()
     }
 
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Capability>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            since_ms: EpochMillis,
+            after_id: String,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, record::id(id) AS record_key FROM capability WHERE tenant_id = $tenant_id AND (updated_at_ms > $since_ms OR (updated_at_ms = $since_ms AND record::id(id) > $after_id)) ORDER BY updated_at_ms ASC, record_key ASC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                since_ms: after.updated_at_ms,
+                after_id: after.after_id().to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealCapabilityRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Capability::try_from)
+            .collect()
+    }
+
+    async fn distinct_codes(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<CapabilityCode>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT code, category, classification FROM capability WHERE tenant_id = $tenant_id GROUP BY code, category, classification ORDER BY code ASC",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+            })
+            .await
+            .map_err(map_err)?;
+
+        response.take(0).map_err(map_err)
+    }
+
     async fn upsert(&self, capability: Capability) -> Result<(), StorageError> {
         let record = SurrealCapabilityWrite::from(&capability);
         let _: Option<SurrealCapabilityRecord> = self
@@ -664,6 +2118,10 @@ impl CapabilityRepository for SurrealStore {
             .map_err(map_err)?;
         Ok(())
     }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_CAPABILITY, tenant_id).await
+    }
 }
 
 #[async_trait]
@@ -680,6 +2138,11 @@ impl IncidentRepository for SurrealStore {
         }
     }
 
+    async fn get_many(&self, ids: &[IncidentId]) -> Result<Vec<Incident>, StorageError> {
+        let records: Vec<SurrealIncidentRecord> = self.select_many(TABLE_INCIDENT, ids).await?;
+        records.into_iter().map(Incident::try_from).collect()
+    }
+
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
@@ -713,6 +2176,178 @@ impl IncidentRepository for SurrealStore {
             .collect()
     }
 
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &IncidentFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            status: Option<IncidentStatus>,
+            priority: Option<OperationalPriority>,
+            classification: Option<SecurityClassification>,
+            search: Option<String>,
+            by_priority: bool,
+            limit: usize,
+            offset: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, (IF $by_priority THEN array::find_index(['routine', 'elevated', 'urgent', 'critical'], priority) ELSE 0 END) AS priority_rank FROM incident WHERE tenant_id = $tenant_id AND (!$status OR status = $status) AND (!$priority OR priority = $priority) AND (!$classification OR classification = $classification) AND (!$search OR string::contains(string::lowercase(summary), $search)) ORDER BY priority_rank DESC, created_at_ms DESC LIMIT $limit START $offset",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                status: filter.status,
+                priority: filter.priority,
+                classification: filter.classification,
+                search: search_term(filter.search.as_deref()),
+                by_priority: filter.sort == IncidentSort::Priority,
+                limit,
+                offset,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealIncidentRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Incident::try_from).collect()
+    }
+
+    async fn list_in_bbox(
+        &self,
+        tenant_id: TenantId,
+        bbox: BoundingBox,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            north: f64,
+            south: f64,
+            east: f64,
+            west: f64,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM incident WHERE tenant_id = $tenant_id AND position != NONE AND position.latitude >= $south AND position.latitude <= $north AND position.longitude >= $west AND position.longitude <= $east ORDER BY created_at_ms DESC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                north: bbox.north,
+                south: bbox.south,
+                east: bbox.east,
+                west: bbox.west,
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealIncidentRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Incident::try_from).collect()
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            query: String,
+            limit: usize,
+        }
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, search::score(1) AS score FROM incident WHERE tenant_id = $tenant_id AND summary @1@ $query ORDER BY score DESC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                query: query.to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealIncidentRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Incident::try_from)
+            .collect()
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            since_ms: EpochMillis,
+            after_id: String,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, record::id(id) AS record_key FROM incident WHERE tenant_id = $tenant_id AND (updated_at_ms > $since_ms OR (updated_at_ms = $since_ms AND record::id(id) > $after_id)) ORDER BY updated_at_ms ASC, record_key ASC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                since_ms: after.updated_at_ms,
+                after_id: after.after_id().to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealIncidentRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Incident::try_from)
+            .collect()
+    }
+
+    async fn count_by_type_and_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<(IncidentType, IncidentStatus)>>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT [incident_type, status] AS key, classification, count() AS count FROM incident WHERE tenant_id = $tenant_id GROUP BY key, classification",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+            })
+            .await
+            .map_err(map_err)?;
+
+        response.take(0).map_err(map_err)
+    }
+
     async fn upsert(&self, incident: Incident) -> Result<(), StorageError> {
         let record = SurrealIncidentWrite::from(&incident);
         let _: Option<SurrealIncidentRecord> = self
@@ -724,6 +2359,20 @@ impl IncidentRepository for SurrealStore {
         Ok(())
     }
 
+    async fn insert_or_update(
+        &self,
+        incident: Incident,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        self.insert_or_update_record(
+            TABLE_INCIDENT,
+            incident.id.to_string(),
+            &incident.tenant_id.to_string(),
+            incident.created_at_ms,
+            SurrealIncidentWrite::from(&incident),
+        )
+        .await
+    }
+
     async fn delete(&self, id: IncidentId) -> Result<(), StorageError> {
         let _: Option<SurrealIncidentRecord> = self
             .db
@@ -732,6 +2381,10 @@ impl IncidentRepository for SurrealStore {
             .map_err(map_err)?;
         Ok(())
     }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_INCIDENT, tenant_id).await
+    }
 }
 
 #[async_trait]
@@ -748,6 +2401,11 @@ impl TaskRepository for SurrealStore {
         }
     }
 
+    async fn get_many(&self, ids: &[TaskId]) -> Result<Vec<Task>, StorageError> {
+        let records: Vec<SurrealTaskRecord> = self.select_many(TABLE_TASK, ids).await?;
+        records.into_iter().map(Task::try_from).collect()
+    }
+
     async fn list_by_mission(
         &self,
         mission_id: MissionId,
@@ -778,6 +2436,85 @@ impl TaskRepository for SurrealStore {
         records.into_iter().map(Task::try_from).collect()
     }
 
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Task>, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            tenant_id: String,
+            since_ms: EpochMillis,
+            after_id: String,
+            limit: usize,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT *, record::id(id) AS record_key FROM task WHERE tenant_id = $tenant_id AND (updated_at_ms > $since_ms OR (updated_at_ms = $since_ms AND record::id(id) > $after_id)) ORDER BY updated_at_ms ASC, record_key ASC LIMIT $limit",
+            )
+            .bind(Bindings {
+                tenant_id: tenant_id.to_string(),
+                since_ms: after.updated_at_ms,
+                after_id: after.after_id().to_string(),
+                limit,
+            })
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealTaskRecord> = response.take(0).map_err(map_err)?;
+        records
+            .into_iter()
+            .map(Task::try_from)
+            .collect()
+    }
+
+    async fn mission_progress(
+        &self,
+        mission_id: MissionId,
+    ) -> Result<MissionProgress, StorageError> {
+        #[derive(Serialize)]
+        struct Bindings {
+            mission_id: String,
+        }
+        #[derive(Deserialize)]
+        struct StatusCount {
+            status: TaskStatus,
+            count: u64,
+        }
+
+        let mut response = self
+            .db
+            .query(
+                "SELECT status, count() AS count FROM task WHERE mission_id = $mission_id GROUP BY status",
+            )
+            .bind(Bindings {
+                mission_id: mission_id.to_string(),
+            })
+            .await
+            .map_err(map_err)?;
+
+        let counts: Vec<StatusCount> = response.take(0).map_err(map_err)?;
+        Ok(MissionProgress::from_counts(
+            counts.into_iter().map(|row| (row.status, row.count)),
+        ))
+    }
+
+    async fn list_with_dependencies(&self) -> Result<Vec<Task>, StorageError> {
+        let mut response = self
+            .db
+            .query(
+                "SELECT * FROM task WHERE depends_on != NONE AND array::len(depends_on) > 0 AND status NOT IN ['completed', 'cancelled']",
+            )
+            .await
+            .map_err(map_err)?;
+
+        let records: Vec<SurrealTaskRecord> = response.take(0).map_err(map_err)?;
+        records.into_iter().map(Task::try_from).collect()
+    }
+
     async fn upsert(&self, task: Task) -> Result<(), StorageError> {
         let record = SurrealTaskWrite::from(&task);
         let _: Option<SurrealTaskRecord> = self
@@ -789,6 +2526,20 @@ impl TaskRepository for SurrealStore {
         Ok(())
     }
 
+    async fn insert_or_update(
+        &self,
+        task: Task,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        self.insert_or_update_record(
+            TABLE_TASK,
+            task.id.to_string(),
+            &task.tenant_id.to_string(),
+            task.created_at_ms,
+            SurrealTaskWrite::from(&task),
+        )
+        .await
+    }
+
     async fn delete(&self, id: TaskId) -> Result<(), StorageError> {
         let _: Option<SurrealTaskRecord> = self
             .db
@@ -797,6 +2548,10 @@ impl TaskRepository for SurrealStore {
             .map_err(map_err)?;
         Ok(())
     }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.delete_by_tenant(TABLE_TASK, tenant_id).await
+    }
 }
 
 fn env_var(key: &str, default: String) -> String {
@@ -810,6 +2565,17 @@ fn env_var_u64(key: &str, default: u64) -> u64 {
         .unwrap_or(default)
 }
 
+fn env_var_bool(key: &str, default: bool) -> bool {
+    env::var(key)
+        .ok()
+        .map(|value| match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => default,
+        })
+        .unwrap_or(default)
+}
+
 fn env_var_u32(key: &str, default: u32) -> u32 {
     env::var(key)
         .ok()
@@ -817,14 +2583,32 @@ fn env_var_u32(key: &str, default: u32) -> u32 {
         .unwrap_or(default)
 }
 
-fn map_err(err: impl std::fmt::Display) -> StorageError {
-    StorageError::new(err.to_string())
+/// Transport failures of a remote endpoint are reported as connection
+/// errors, so a fallback store can retry the read elsewhere.
+fn map_err(err: surrealdb::Error) -> StorageError {
+    match err {
+        surrealdb::Error::Api(
+            surrealdb::error::Api::Http(_)
+            | surrealdb::error::Api::Ws(_)
+            | surrealdb::error::Api::ConnectionUninitialised,
+        ) => StorageError::connection(err.to_string()),
+        _ => StorageError::new(err.to_string()),
+    }
+}
+
+/// Lowercased search term for a filter; blank means no filter.
+fn search_term(term: Option<&str>) -> Option<String> {
+    term.map(str::trim)
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
 }
 
 fn thing_uuid(thing: &Thing) -> Result<Uuid, StorageError> {
     match &thing.id {
         Id::Uuid(value) => Ok((*value).into()),
-        Id::String(value) => Uuid::parse_str(value).map_err(map_err),
+        Id::String(value) => {
+            Uuid::parse_str(value).map_err(|err| StorageError::new(err.to_string()))
+        }
         _ => Err(StorageError::new("unsupported SurrealDB record id type")),
     }
 }
@@ -846,6 +2630,7 @@ impl TryFrom<SurrealMissionRecord> for Mission {
             classification: value.classification,
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
+            archived_at_ms: value.archived_at_ms,
         })
     }
 }
@@ -860,6 +2645,7 @@ impl From<&Mission> for SurrealMissionWrite {
             classification: value.classification,
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
+            archived_at_ms: value.archived_at_ms,
         }
     }
 }
@@ -886,8 +2672,10 @@ impl TryFrom<SurrealAssetRecord> for Asset {
             readiness: value.readiness,
             comms_status: value.comms_status,
             maintenance_state: value.maintenance_state,
+            maintenance_window: value.maintenance_window,
             unit_id,
             capability_ids,
+            position: value.position,
             classification: value.classification,
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
@@ -895,6 +2683,34 @@ impl TryFrom<SurrealAssetRecord> for Asset {
     }
 }
 
+impl TryFrom<SurrealAssetStatusEventRecord> for AssetStatusEvent {
+    type Error = StorageError;
+
+    fn try_from(value: SurrealAssetStatusEventRecord) -> Result<Self, Self::Error> {
+        Ok(AssetStatusEvent {
+            asset_id: AssetId::from_uuid(parse_uuid(&value.asset_id, "asset_id")?),
+            tenant_id: TenantId::from_uuid(parse_uuid(&value.tenant_id, "tenant_id")?),
+            readiness: value.readiness,
+            comms_status: value.comms_status,
+            maintenance_state: value.maintenance_state,
+            recorded_at_ms: value.recorded_at_ms,
+        })
+    }
+}
+
+impl From<&AssetStatusEvent> for SurrealAssetStatusEventRecord {
+    fn from(value: &AssetStatusEvent) -> Self {
+        Self {
+            asset_id: value.asset_id.to_string(),
+            tenant_id: value.tenant_id.to_string(),
+            readiness: value.readiness,
+            comms_status: value.comms_status,
+            maintenance_state: value.maintenance_state,
+            recorded_at_ms: value.recorded_at_ms,
+        }
+    }
+}
+
 impl From<&Asset> for SurrealAssetWrite {
     fn from(value: &Asset) -> Self {
         Self {
@@ -905,12 +2721,14 @@ impl From<&Asset> for SurrealAssetWrite {
             readiness: value.readiness,
             comms_status: value.comms_status,
             maintenance_state: value.maintenance_state,
+            maintenance_window: value.maintenance_window,
             unit_id: value.unit_id.map(|id| id.to_string()),
             capability_ids: value
                 .capability_ids
                 .iter()
                 .map(ToString::to_string)
                 .collect(),
+            position: value.position,
             classification: value.classification,
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
@@ -997,6 +2815,111 @@ impl From<&Team> for SurrealTeamWrite {
     }
 }
 
+impl TryFrom<SurrealGeofenceRecord> for Geofence {
+    type Error = StorageError;
+
+    fn try_from(value: SurrealGeofenceRecord) -> Result<Self, Self::Error> {
+        Ok(Geofence {
+            id: GeofenceId::from_uuid(thing_uuid(&value.id)?),
+            tenant_id: TenantId::from_uuid(parse_uuid(&value.tenant_id, "tenant_id")?),
+            name: value.name,
+            fence: value.fence,
+            active: value.active,
+            classification: value.classification,
+            created_at_ms: value.created_at_ms,
+            updated_at_ms: value.updated_at_ms,
+        })
+    }
+}
+
+impl From<&Geofence> for SurrealGeofenceWrite {
+    fn from(value: &Geofence) -> Self {
+        Self {
+            tenant_id: value.tenant_id.to_string(),
+            name: value.name.clone(),
+            fence: value.fence.clone(),
+            active: value.active,
+            classification: value.classification,
+            created_at_ms: value.created_at_ms,
+            updated_at_ms: value.updated_at_ms,
+        }
+    }
+}
+
+impl TryFrom<SurrealAssignmentRecord> for MissionAssignment {
+    type Error = StorageError;
+
+    fn try_from(value: SurrealAssignmentRecord) -> Result<Self, Self::Error> {
+        let unit_id = match value.unit_id {
+            Some(raw) => Some(UnitId::from_uuid(parse_uuid(&raw, "unit_id")?)),
+            None => None,
+        };
+        let team_id = match value.team_id {
+            Some(raw) => Some(TeamId::from_uuid(parse_uuid(&raw, "team_id")?)),
+            None => None,
+        };
+        Ok(MissionAssignment {
+            id: AssignmentId::from_uuid(thing_uuid(&value.id)?),
+            tenant_id: TenantId::from_uuid(parse_uuid(&value.tenant_id, "tenant_id")?),
+            mission_id: MissionId::from_uuid(parse_uuid(&value.mission_id, "mission_id")?),
+            unit_id,
+            team_id,
+            role: value.role,
+            classification: value.classification,
+            assigned_at_ms: value.assigned_at_ms,
+        })
+    }
+}
+
+impl From<&MissionAssignment> for SurrealAssignmentWrite {
+    fn from(value: &MissionAssignment) -> Self {
+        Self {
+            tenant_id: value.tenant_id.to_string(),
+            mission_id: value.mission_id.to_string(),
+            unit_id: value.unit_id.map(|id| id.to_string()),
+            team_id: value.team_id.map(|id| id.to_string()),
+            role: value.role.clone(),
+            classification: value.classification,
+            assigned_at_ms: value.assigned_at_ms,
+        }
+    }
+}
+
+impl TryFrom<SurrealApiKeyRecord> for ApiKey {
+    type Error = StorageError;
+
+    fn try_from(value: SurrealApiKeyRecord) -> Result<Self, Self::Error> {
+        Ok(ApiKey {
+            id: ApiKeyId::from_uuid(thing_uuid(&value.id)?),
+            tenant_id: TenantId::from_uuid(parse_uuid(&value.tenant_id, "tenant_id")?),
+            label: value.label,
+            hashed_secret: value.hashed_secret,
+            scopes: value.scopes,
+            roles: value.roles,
+            clearance: value.clearance,
+            created_by: UserId::from_uuid(parse_uuid(&value.created_by, "created_by")?),
+            created_at_ms: value.created_at_ms,
+            revoked: value.revoked,
+        })
+    }
+}
+
+impl From<&ApiKey> for SurrealApiKeyWrite {
+    fn from(value: &ApiKey) -> Self {
+        Self {
+            tenant_id: value.tenant_id.to_string(),
+            label: value.label.clone(),
+            hashed_secret: value.hashed_secret.clone(),
+            scopes: value.scopes.clone(),
+            roles: value.roles.clone(),
+            clearance: value.clearance,
+            created_by: value.created_by.to_string(),
+            created_at_ms: value.created_at_ms,
+            revoked: value.revoked,
+        }
+    }
+}
+
 impl TryFrom<SurrealCapabilityRecord> for Capability {
     type Error = StorageError;
 
@@ -1039,7 +2962,10 @@ impl TryFrom<SurrealIncidentRecord> for Incident {
             tenant_id: TenantId::from_uuid(parse_uuid(&value.tenant_id, "tenant_id")?),
             incident_type: value.incident_type,
             status: value.status,
+            priority: value.priority,
             summary: value.summary,
+            position: value.position,
+            affected_radius_m: value.affected_radius_m,
             classification: value.classification,
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
@@ -1053,7 +2979,10 @@ impl From<&Incident> for SurrealIncidentWrite {
             tenant_id: value.tenant_id.to_string(),
             incident_type: value.incident_type,
             status: value.status,
+            priority: value.priority,
             summary: value.summary.clone(),
+            position: value.position,
+            affected_radius_m: value.affected_radius_m,
             classification: value.classification,
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
@@ -1073,6 +3002,12 @@ impl TryFrom<SurrealTaskRecord> for Task {
             status: value.status,
             priority: value.priority,
             classification: value.classification,
+            depends_on: value
+                .depends_on
+                .unwrap_or_default()
+                .into_iter()
+                .map(|id| parse_uuid(&id, "depends_on").map(TaskId::from_uuid))
+                .collect::<Result<_, _>>()?,
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
         })
@@ -1088,8 +3023,512 @@ impl From<&Task> for SurrealTaskWrite {
             status: value.status,
             priority: value.priority,
             classification: value.classification,
+            depends_on: value.depends_on.iter().map(ToString::to_string).collect(),
             created_at_ms: value.created_at_ms,
             updated_at_ms: value.updated_at_ms,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn get_many_returns_only_existing_records() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let tenant_id = TenantId::new();
+        let mission = |name: &str| Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: name.to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        let (first, second) = (mission("Alpha"), mission("Bravo"));
+        MissionRepository::upsert(&store, first.clone()).await.unwrap();
+        MissionRepository::upsert(&store, second.clone()).await.unwrap();
+
+        let ids = [first.id, MissionId::new(), second.id];
+        let mut found: Vec<MissionId> = MissionRepository::get_many(&store, &ids)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|mission| mission.id)
+            .collect();
+        found.sort_by_key(|id| id.to_string());
+        let mut expected = vec![first.id, second.id];
+        expected.sort_by_key(|id| id.to_string());
+        assert_eq!(found, expected);
+        assert!(MissionRepository::get_many(&store, &[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn check_connects_without_applying_the_schema() {
+        let config = SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        };
+        assert_eq!(SurrealStore::check(&config).await.unwrap(), "mem://");
+
+        #[derive(Deserialize)]
+        struct DatabaseInfo {
+            tables: HashMap<String, IgnoredAny>,
+        }
+        let db = open(&config).await.unwrap();
+        let mut response = db.query("INFO FOR DB").await.unwrap();
+        let info: Option<DatabaseInfo> = response.take(0).unwrap();
+        assert!(info.unwrap().tables.is_empty());
+    }
+
+    #[tokio::test]
+    async fn check_fails_against_an_unreachable_store() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config = SurrealConfig {
+            endpoint: format!("ws://127.0.0.1:{port}"),
+            ..SurrealConfig::from_env()
+        };
+        let result = SurrealStore::check(&config).await;
+        assert!(result.is_err());
+
+        let mut report = c2_observability::CheckReport::new("c2-worker");
+        assert!(!report.record("surreal", result));
+        assert!(!report.is_ok());
+    }
+
+    #[tokio::test]
+    async fn warmup_reports_missing_tables() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        store.warmup().await.unwrap();
+
+        store.db.query("REMOVE TABLE geofence").await.unwrap();
+        let err = store.warmup().await.unwrap_err();
+        assert_eq!(err.message, "SurrealDB schema is missing tables: geofence");
+    }
+
+    #[tokio::test]
+    async fn archived_mission_leaves_default_listing_only() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let tenant_id = TenantId::new();
+        let mission = Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: "Harbour clearance".to_string(),
+            status: MissionStatus::Completed,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        MissionRepository::upsert(&store, mission.clone()).await.unwrap();
+
+        let archived = MissionRepository::archive(&store, mission.id, 50).await.unwrap().unwrap();
+        assert_eq!(archived.archived_at_ms, Some(50));
+        let again = MissionRepository::archive(&store, mission.id, 60).await.unwrap().unwrap();
+        assert_eq!(again.archived_at_ms, Some(50));
+
+        let listed = MissionRepository::list_by_tenant(&store, tenant_id, 10, 0).await.unwrap();
+        assert!(listed.is_empty());
+        let filtered = MissionRepository::list_filtered(
+            &store,
+            tenant_id,
+            &MissionFilter::default(),
+            10,
+            0,
+        )
+        .await
+        .unwrap();
+        assert!(filtered.is_empty());
+        let stored = MissionRepository::get(&store, mission.id).await.unwrap().unwrap();
+        assert!(stored.is_archived());
+        let archive = MissionRepository::list_archived_by_tenant(&store, tenant_id, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive[0].id, mission.id);
+
+        MissionRepository::unarchive(&store, mission.id, 70).await.unwrap();
+        let listed = MissionRepository::list_by_tenant(&store, tenant_id, 10, 0).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].archived_at_ms, None);
+        let archive = MissionRepository::list_archived_by_tenant(&store, tenant_id, 10, 0)
+            .await
+            .unwrap();
+        assert!(archive.is_empty());
+        assert!(MissionRepository::archive(&store, MissionId::new(), 80).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn secret_clearance_never_reads_top_secret_partition() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            mission_partitions: "top_secret=mission_top_secret".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        store.warmup().await.unwrap();
+        let tenant_id = TenantId::new();
+        let mission = |name: &str, classification| Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: name.to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        let secret = mission("Harbor watch", SecurityClassification::Secret);
+        let top_secret = mission("Harbor strike", SecurityClassification::TopSecret);
+        MissionRepository::upsert(&store, secret.clone()).await.unwrap();
+        MissionRepository::upsert(&store, top_secret.clone()).await.unwrap();
+
+        let mut response = store.db.query("SELECT * FROM mission_top_secret").await.unwrap();
+        let partition: Vec<SurrealMissionRecord> = response.take(0).unwrap();
+        assert_eq!(partition.len(), 1);
+        assert_eq!(store.partitions.all(), vec!["mission", "mission_top_secret"]);
+
+        let cleared = store.with_clearance(SecurityClassification::Secret);
+        assert_eq!(cleared.mission_tables(), vec![Table::from("mission")]);
+        let listed = MissionRepository::list_by_tenant(&cleared, tenant_id, 10, 0).await.unwrap();
+        assert_eq!(listed.iter().map(|mission| mission.id).collect::<Vec<_>>(), [secret.id]);
+        let searched = MissionRepository::search_by_tenant(&cleared, tenant_id, "harbor", 10)
+            .await
+            .unwrap();
+        assert_eq!(searched.len(), 1);
+        assert!(MissionRepository::get(&cleared, top_secret.id).await.unwrap().is_none());
+
+        let all = MissionRepository::list_by_tenant(&store, tenant_id, 10, 0).await.unwrap();
+        assert_eq!(all.len(), 2);
+        let reclassified = Mission {
+            classification: SecurityClassification::Secret,
+            ..top_secret.clone()
+        };
+        MissionRepository::upsert(&store, reclassified).await.unwrap();
+        let listed = MissionRepository::list_by_tenant(&cleared, tenant_id, 10, 0).await.unwrap();
+        assert_eq!(listed.len(), 2);
+        let all = MissionRepository::list_by_tenant(&store, tenant_id, 10, 0).await.unwrap();
+        assert_eq!(all.len(), 2);
+        let mut response = store.db.query("SELECT * FROM mission_top_secret").await.unwrap();
+        let partition: Vec<SurrealMissionRecord> = response.take(0).unwrap();
+        assert!(partition.is_empty());
+    }
+
+    #[tokio::test]
+    async fn backfill_copies_archived_missions_too() {
+        let connect = || async {
+            SurrealStore::connect(&SurrealConfig {
+                endpoint: "mem://".to_string(),
+                ..SurrealConfig::from_env()
+            })
+            .await
+            .unwrap()
+        };
+        let (from, to) = (connect().await, connect().await);
+        let tenant_id = TenantId::new();
+        let mission = |name: &str, archived_at_ms| Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: name.to_string(),
+            status: MissionStatus::Completed,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms,
+        };
+        let (active, archived) = (mission("Alpha", None), mission("Bravo", Some(2)));
+        MissionRepository::upsert(&from, active.clone()).await.unwrap();
+        MissionRepository::upsert(&from, archived.clone()).await.unwrap();
+
+        let store = c2_storage::DualWriteStore::new(from, to.clone());
+        let report = store.backfill(tenant_id).await.unwrap();
+        assert_eq!(report.missions, 2);
+        let listed = MissionRepository::list_by_tenant(&to, tenant_id, 10, 0).await.unwrap();
+        assert_eq!(listed.iter().map(|mission| mission.id).collect::<Vec<_>>(), [active.id]);
+        let copied = MissionRepository::get(&to, archived.id).await.unwrap().unwrap();
+        assert_eq!(copied.archived_at_ms, Some(2));
+    }
+
+    #[tokio::test]
+    async fn backfill_copies_each_entity_once_and_can_run_again() {
+        let connect = || async {
+            SurrealStore::connect(&SurrealConfig {
+                endpoint: "mem://".to_string(),
+                ..SurrealConfig::from_env()
+            })
+            .await
+            .unwrap()
+        };
+        let (from, to) = (connect().await, connect().await);
+        let tenant_id = TenantId::new();
+        let unit = Unit {
+            id: UnitId::new(),
+            tenant_id,
+            classification: SecurityClassification::Unclassified,
+            callsign: Some("BRAVO-2".to_string()),
+            display_name: "Bravo Platoon".to_string(),
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            team_id: None,
+            capability_ids: Vec::new(),
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        let mission = Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: "Bridge survey".to_string(),
+            status: MissionStatus::Active,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        let task = Task {
+            id: TaskId::new(),
+            mission_id: mission.id,
+            tenant_id,
+            title: "Inspect the pylons".to_string(),
+            status: TaskStatus::Pending,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            depends_on: Vec::new(),
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        UnitRepository::upsert(&from, unit.clone()).await.unwrap();
+        MissionRepository::upsert(&from, mission.clone()).await.unwrap();
+        TaskRepository::upsert(&from, task.clone()).await.unwrap();
+
+        let store = c2_storage::DualWriteStore::new(from, to.clone());
+        let report = store.backfill(tenant_id).await.unwrap();
+        assert_eq!((report.units, report.missions, report.tasks), (1, 1, 1));
+        assert_eq!(report.total(), 3);
+        assert_eq!(store.backfill(tenant_id).await.unwrap(), report);
+        let tasks = TaskRepository::list_by_mission(&to, mission.id, 10, 0).await.unwrap();
+        assert_eq!(tasks.iter().map(|task| task.id).collect::<Vec<_>>(), [task.id]);
+        let copied = UnitRepository::get(&to, unit.id).await.unwrap().unwrap();
+        assert_eq!(copied.callsign, unit.callsign);
+    }
+
+    #[tokio::test]
+    async fn reapplying_the_schema_runs_each_migration_once() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Applied {
+            version: u32,
+            applied_at_ms: u64,
+        }
+
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let applied = || async {
+            let mut response = store
+                .db
+                .query("SELECT version, applied_at_ms FROM schema_version ORDER BY version")
+                .await
+                .unwrap();
+            response.take::<Vec<Applied>>(0).unwrap()
+        };
+        let first = applied().await;
+        let versions: Vec<u32> = first.iter().map(|row| row.version).collect();
+        let expected: Vec<u32> =
+            SCHEMA_MIGRATIONS.iter().map(|migration| migration.version).collect();
+        assert_eq!(versions, expected);
+
+        apply_schema(&store.db).await.unwrap();
+        assert_eq!(applied().await, first);
+
+        // Forgetting the newest migration makes the next connect run only that one.
+        let latest = expected[expected.len() - 1];
+        store
+            .db
+            .query("DELETE type::thing('schema_version', $version)")
+            .bind(("version", latest))
+            .await
+            .unwrap();
+        apply_schema(&store.db).await.unwrap();
+        let again = applied().await;
+        assert_eq!(again.len(), first.len());
+        assert_eq!(again[..first.len() - 1], first[..first.len() - 1]);
+    }
+
+    #[tokio::test]
+    async fn mem_endpoint_runs_in_process() {
+        let endpoints = [
+            (" memory ", SurrealScheme::Memory, "mem://"),
+            ("rocksdb:///var/lib/c2", SurrealScheme::RocksDb, "rocksdb:///var/lib/c2"),
+            ("localhost:8000", SurrealScheme::Ws, "ws://localhost:8000"),
+        ];
+        for (raw, scheme, url) in endpoints {
+            assert_eq!(normalize_endpoint(raw).unwrap(), (scheme, url.to_string()));
+        }
+        assert!(normalize_endpoint("rocksdb://").is_err());
+
+        // Embedded engines skip sign-in, so credentials do not matter.
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            username: "nobody".to_string(),
+            password: "wrong".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let mission = Mission {
+            id: MissionId::new(),
+            tenant_id: TenantId::new(),
+            name: "Local dev".to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        MissionRepository::upsert(&store, mission.clone()).await.unwrap();
+        let stored = MissionRepository::get(&store, mission.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, mission.name);
+    }
+
+    #[tokio::test]
+    async fn search_matches_stemmed_variants_within_the_tenant() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let tenant_id = TenantId::new();
+        let mission = |tenant_id, name: &str| Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: name.to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        let escorts = mission(tenant_id, "Convoy escorting north");
+        let missions = [
+            escorts.clone(),
+            mission(tenant_id, "Bridge survey"),
+            mission(TenantId::new(), "Escorted resupply"),
+        ];
+        for mission in missions {
+            MissionRepository::upsert(&store, mission).await.unwrap();
+        }
+        let incident = Incident {
+            id: IncidentId::new(),
+            tenant_id,
+            incident_type: IncidentType::Infrastructure,
+            status: IncidentStatus::Reported,
+            priority: OperationalPriority::Elevated,
+            summary: "Flooding near the depot".to_string(),
+            position: None,
+            affected_radius_m: None,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        IncidentRepository::upsert(&store, incident.clone()).await.unwrap();
+
+        let found = MissionRepository::search_by_tenant(&store, tenant_id, "escorted", 10);
+        let found: Vec<MissionId> =
+            found.await.unwrap().into_iter().map(|mission| mission.id).collect();
+        assert_eq!(found, [escorts.id]);
+        let found = IncidentRepository::search_by_tenant(&store, tenant_id, "flooded", 10);
+        let found: Vec<IncidentId> =
+            found.await.unwrap().into_iter().map(|incident| incident.id).collect();
+        assert_eq!(found, [incident.id]);
+        let blank = MissionRepository::search_by_tenant(&store, tenant_id, "  ", 10).await;
+        assert!(blank.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn insert_or_update_reports_inserts_and_keeps_created_at_on_update() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let created = Mission {
+            id: MissionId::new(),
+            tenant_id: TenantId::new(),
+            name: "Harbor watch".to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 10,
+            updated_at_ms: 10,
+            archived_at_ms: None,
+        };
+
+        let outcome = MissionRepository::insert_or_update(&store, created.clone()).await.unwrap();
+        assert_eq!(
+            outcome,
+            Some(UpsertOutcome {
+                inserted: true,
+                created_at_ms: 10,
+            })
+        );
+
+        let renamed = Mission {
+            name: "Harbor patrol".to_string(),
+            created_at_ms: 20,
+            updated_at_ms: 20,
+            ..created.clone()
+        };
+        let outcome = MissionRepository::insert_or_update(&store, renamed).await.unwrap();
+        assert_eq!(
+            outcome,
+            Some(UpsertOutcome {
+                inserted: false,
+                created_at_ms: 10,
+            })
+        );
+
+        let hijack = Mission {
+            tenant_id: TenantId::new(),
+            name: "Taken".to_string(),
+            ..created.clone()
+        };
+        assert_eq!(MissionRepository::insert_or_update(&store, hijack).await.unwrap(), None);
+        let stored = MissionRepository::get(&store, created.id).await.unwrap().unwrap();
+        assert_eq!((stored.name.as_str(), stored.created_at_ms), ("Harbor patrol", 10));
+        assert_eq!(stored.tenant_id, created.tenant_id);
+    }
+}
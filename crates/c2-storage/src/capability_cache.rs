@@ -0,0 +1,240 @@
+use c2_core::{Capability, CapabilityId, TenantId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{CapabilityRepository, StorageError};
+
+/// Records read per call when loading a tenant's catalog.
+const LOAD_PAGE_SIZE: usize = 500;
+
+/// One tenant's capabilities by id.
+pub type CapabilityCatalog = HashMap<CapabilityId, Capability>;
+
+/// Short-lived copy of each tenant's capability catalog, so checking the
+/// capability references of many assets reads the catalog once per TTL
+/// instead of once per reference. Writers in this process call
+/// [`Self::invalidate`]; changes made elsewhere show up once the TTL runs
+/// out. A zero TTL turns caching off.
+pub struct CapabilityCache {
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<TenantId, Entry>,
+    /// Bumped on every invalidation, so a load that started before one does
+    /// not put the old catalog back.
+    generation: u64,
+}
+
+struct Entry {
+    catalog: Arc<CapabilityCatalog>,
+    expires_at: Instant,
+}
+
+impl CapabilityCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// The tenant's catalog, read from `store` when not cached or expired.
+    pub async fn catalog<S>(
+        &self,
+        store: &S,
+        tenant_id: TenantId,
+    ) -> Result<Arc<CapabilityCatalog>, StorageError>
+    where
+        S: CapabilityRepository + ?Sized,
+    {
+        let now = Instant::now();
+        {
+            let state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+            if let Some(entry) = state.entries.get(&tenant_id)
+                && entry.expires_at > now
+            {
+                return Ok(entry.catalog.clone());
+            }
+        }
+        self.refresh(store, tenant_id).await
+    }
+
+    /// The first of `ids` that is not one of the tenant's capabilities. A
+    /// cached catalog missing one is read again once, since the capability
+    /// may have been created after it was cached.
+    pub async fn first_unknown<S>(
+        &self,
+        store: &S,
+        tenant_id: TenantId,
+        ids: &[CapabilityId],
+    ) -> Result<Option<CapabilityId>, StorageError>
+    where
+        S: CapabilityRepository + ?Sized,
+    {
+        if ids.is_empty() {
+            return Ok(None);
+        }
+        let catalog = self.catalog(store, tenant_id).await?;
+        if ids.iter().all(|id| catalog.contains_key(id)) {
+            return Ok(None);
+        }
+        let catalog = self.refresh(store, tenant_id).await?;
+        Ok(ids.iter().copied().find(|id| !catalog.contains_key(id)))
+    }
+
+    /// Reads the tenant's catalog from `store` and caches it.
+    pub async fn refresh<S>(
+        &self,
+        store: &S,
+        tenant_id: TenantId,
+    ) -> Result<Arc<CapabilityCatalog>, StorageError>
+    where
+        S: CapabilityRepository + ?Sized,
+    {
+        let generation = self.state.lock().unwrap_or_else(|err| err.into_inner()).generation;
+        let mut catalog = CapabilityCatalog::new();
+        let mut offset = 0;
+        loop {
+            let page = store.list_by_tenant(tenant_id, LOAD_PAGE_SIZE, offset).await?;
+            let fetched = page.len();
+            catalog.extend(page.into_iter().map(|capability| (capability.id, capability)));
+            if fetched < LOAD_PAGE_SIZE {
+                break;
+            }
+            offset += fetched;
+        }
+        let catalog = Arc::new(catalog);
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        if state.generation == generation {
+            state.entries.insert(
+                tenant_id,
+                Entry {
+                    catalog: catalog.clone(),
+                    expires_at: Instant::now() + self.ttl,
+                },
+            );
+        }
+        Ok(catalog)
+    }
+
+    /// Drops the tenant's cached catalog after a capability write.
+    pub fn invalidate(&self, tenant_id: TenantId) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.entries.remove(&tenant_id);
+        state.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChangeCursor;
+    use async_trait::async_trait;
+    use c2_core::{CapabilityCode, SecurityClassification};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// One tenant's catalog that counts how often it is listed.
+    struct CountingStore {
+        capabilities: Vec<Capability>,
+        lists: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CapabilityRepository for CountingStore {
+        async fn get(&self, id: CapabilityId) -> Result<Option<Capability>, StorageError> {
+            Ok(self.capabilities.iter().find(|capability| capability.id == id).cloned())
+        }
+
+        async fn get_many(&self, ids: &[CapabilityId]) -> Result<Vec<Capability>, StorageError> {
+            let found = self.capabilities.iter().filter(|capability| ids.contains(&capability.id));
+            Ok(found.cloned().collect())
+        }
+
+        async fn list_by_tenant(
+            &self,
+            _tenant_id: TenantId,
+            limit: usize,
+            offset: usize,
+        ) -> Result<Vec<Capability>, StorageError> {
+            self.lists.fetch_add(1, Ordering::SeqCst);
+            Ok(self.capabilities.iter().skip(offset).take(limit).cloned().collect())
+        }
+
+        async fn list_updated_since(
+            &self,
+            _tenant_id: TenantId,
+            _after: ChangeCursor,
+            _limit: usize,
+        ) -> Result<Vec<Capability>, StorageError> {
+            Ok(Vec::new())
+        }
+
+        async fn distinct_codes(
+            &self,
+            _tenant_id: TenantId,
+        ) -> Result<Vec<CapabilityCode>, StorageError> {
+            Ok(Vec::new())
+        }
+
+        async fn upsert(&self, _capability: Capability) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: CapabilityId) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        async fn delete_all_by_tenant(&self, _tenant_id: TenantId) -> Result<u64, StorageError> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn catalog_is_read_once_per_ttl_until_invalidated() {
+        let tenant_id = TenantId::new();
+        let capability = Capability {
+            id: CapabilityId::new(),
+            tenant_id,
+            code: "medevac".to_string(),
+            name: "Medical evacuation".to_string(),
+            category: None,
+            description: None,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        let store = CountingStore {
+            capabilities: vec![capability.clone()],
+            lists: AtomicUsize::new(0),
+        };
+        let cache = CapabilityCache::new(Duration::from_secs(60));
+
+        let first = cache.catalog(&store, tenant_id).await.unwrap();
+        let second = cache.catalog(&store, tenant_id).await.unwrap();
+        assert!(first.contains_key(&capability.id));
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(store.lists.load(Ordering::SeqCst), 1);
+
+        cache.invalidate(tenant_id);
+        cache.catalog(&store, tenant_id).await.unwrap();
+        assert_eq!(store.lists.load(Ordering::SeqCst), 2);
+
+        // Known ids come from the cache; an unknown one forces one re-read.
+        let known = cache.first_unknown(&store, tenant_id, &[capability.id]).await.unwrap();
+        assert_eq!(known, None);
+        assert_eq!(store.lists.load(Ordering::SeqCst), 2);
+        let missing = CapabilityId::new();
+        let unknown = cache.first_unknown(&store, tenant_id, &[capability.id, missing]).await;
+        assert_eq!(unknown.unwrap(), Some(missing));
+        assert_eq!(store.lists.load(Ordering::SeqCst), 3);
+
+        let uncached = CapabilityCache::new(Duration::ZERO);
+        uncached.catalog(&store, tenant_id).await.unwrap();
+        uncached.catalog(&store, tenant_id).await.unwrap();
+        assert_eq!(store.lists.load(Ordering::SeqCst), 5);
+    }
+}
@@ -0,0 +1,1375 @@
+//! [`DualWriteStore`] keeps two backends in step while moving a deployment
+//! from one to the other (for example Surreal to Postgres) without downtime:
+//! every write goes to both stores, reads go to the side picked with
+//! [`DualWriteStore::with_reads_from`], and [`DualWriteStore::backfill`]
+//! copies what the `from` store held before dual writes started.
+//!
+//! The usual sequence is to deploy with reads on `from`, backfill each
+//! tenant, compare, switch reads to `to`, and finally drop the wrapper.
+
+use async_trait::async_trait;
+use c2_core::{
+    ApiKeyId, Asset, AssetId, AssetStatusEvent, AssignmentId, Capability, CapabilityCode,
+    CapabilityId, EpochMillis, Geofence, GeofenceId, GroupCount, Incident, IncidentId,
+    IncidentStatus, IncidentType, Mission, MissionAssignment, MissionId, MissionProgress,
+    MissionStatus, ReadinessState, Task, TaskId, Team, TeamId, TenantId, Unit, UnitId,
+};
+use c2_geo::BoundingBox;
+use c2_identity::ApiKey;
+use std::future::Future;
+
+use crate::{
+    ApiKeyRepository, AssetFilter, AssetRepository, AssignmentRepository, CapabilityRepository,
+    ChangeCursor, GeofenceRepository, IncidentFilter, IncidentRepository, MissionFilter,
+    MissionRepository, StorageBackend, StorageError, TaskRepository, TeamRepository, UnitRepository,
+    UpsertOutcome,
+};
+
+/// Records read from the `from` store per call during a backfill.
+const BACKFILL_PAGE_SIZE: usize = 500;
+
+/// Which store of a [`DualWriteStore`] serves reads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadSide {
+    #[default]
+    From,
+    To,
+}
+
+#[derive(Debug, Clone)]
+pub struct DualWriteStore<F, T> {
+    from: F,
+    to: T,
+    reads: ReadSide,
+}
+
+/// Records copied by [`DualWriteStore::backfill`], per entity type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackfillReport {
+    pub capabilities: u64,
+    pub teams: u64,
+    pub units: u64,
+    pub assets: u64,
+    pub geofences: u64,
+    pub missions: u64,
+    pub tasks: u64,
+    pub assignments: u64,
+    pub incidents: u64,
+    pub api_keys: u64,
+}
+
+impl BackfillReport {
+    pub fn total(&self) -> u64 {
+        self.capabilities
+            + self.teams
+            + self.units
+            + self.assets
+            + self.geofences
+            + self.missions
+            + self.tasks
+            + self.assignments
+            + self.incidents
+            + self.api_keys
+    }
+}
+
+impl<F, T> DualWriteStore<F, T> {
+    /// Reads from `from` until [`Self::with_reads_from`] says otherwise.
+    pub fn new(from: F, to: T) -> Self {
+        Self {
+            from,
+            to,
+            reads: ReadSide::default(),
+        }
+    }
+
+    pub fn with_reads_from(mut self, reads: ReadSide) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    pub fn reads_from(&self) -> ReadSide {
+        self.reads
+    }
+
+    pub fn from_store(&self) -> &F {
+        &self.from
+    }
+
+    pub fn to_store(&self) -> &T {
+        &self.to
+    }
+
+    /// Awaits the call on the read side. Both futures are built up front but
+    /// the other one is dropped without running.
+    async fn primary<R>(
+        &self,
+        from: impl Future<Output = Result<R, StorageError>>,
+        to: impl Future<Output = Result<R, StorageError>>,
+    ) -> Result<R, StorageError> {
+        match self.reads {
+            ReadSide::From => from.await,
+            ReadSide::To => to.await,
+        }
+    }
+
+    /// Awaits the call on the side that does not serve reads.
+    async fn secondary<R>(
+        &self,
+        from: impl Future<Output = Result<R, StorageError>>,
+        to: impl Future<Output = Result<R, StorageError>>,
+    ) -> Result<R, StorageError> {
+        match self.reads {
+            ReadSide::From => to.await,
+            ReadSide::To => from.await,
+        }
+    }
+
+    /// Writes the read side first, so a write it rejects is not applied to
+    /// the other store either. An error from either side fails the call; the
+    /// caller can retry, as writes are upserts. Returns the read side's result.
+    async fn write<R>(
+        &self,
+        from: impl Future<Output = Result<R, StorageError>>,
+        to: impl Future<Output = Result<R, StorageError>>,
+    ) -> Result<R, StorageError> {
+        match self.reads {
+            ReadSide::From => {
+                let result = from.await?;
+                to.await?;
+                Ok(result)
+            }
+            ReadSide::To => {
+                let result = to.await?;
+                from.await?;
+                Ok(result)
+            }
+        }
+    }
+}
+
+impl<F, T> DualWriteStore<F, T>
+where
+    F: MissionRepository
+        + AssetRepository
+        + UnitRepository
+        + TeamRepository
+        + CapabilityRepository
+        + GeofenceRepository
+        + ApiKeyRepository
+        + IncidentRepository
+        + TaskRepository
+        + AssignmentRepository,
+    T: MissionRepository
+        + AssetRepository
+        + UnitRepository
+        + TeamRepository
+        + CapabilityRepository
+        + GeofenceRepository
+        + ApiKeyRepository
+        + IncidentRepository
+        + TaskRepository
+        + AssignmentRepository,
+{
+    /// Copies every record of `tenant_id` from `from` to `to`, a page at a
+    /// time, upserting each page in one batch over anything already there.
+    /// Referenced records go before what references them. Run it with dual
+    /// writes already in place; running it again is harmless and picks up
+    /// records that moved between pages while it ran. Asset status history
+    /// is not copied.
+    pub async fn backfill(&self, tenant_id: TenantId) -> Result<BackfillReport, StorageError> {
+        let (from, to) = (&self.from, &self.to);
+        let capabilities = copy_pages(
+            |limit, offset| CapabilityRepository::list_by_tenant(from, tenant_id, limit, offset),
+            |page| CapabilityRepository::upsert_many(to, page),
+        )
+        .await?;
+        let teams = copy_pages(
+            |limit, offset| TeamRepository::list_by_tenant(from, tenant_id, limit, offset),
+            |page| TeamRepository::upsert_many(to, page),
+        )
+        .await?;
+        let units = copy_pages(
+            |limit, offset| UnitRepository::list_by_tenant(from, tenant_id, limit, offset),
+            |page| UnitRepository::upsert_many(to, page),
+        )
+        .await?;
+        let assets = copy_pages(
+            |limit, offset| AssetRepository::list_by_tenant(from, tenant_id, limit, offset),
+            |page| AssetRepository::upsert_many(to, page),
+        )
+        .await?;
+        let geofences = copy_pages(
+            |limit, offset| GeofenceRepository::list_by_tenant(from, tenant_id, limit, offset),
+            |page| GeofenceRepository::upsert_many(to, page),
+        )
+        .await?;
+
+        // Archived missions are left out of the default listing, so they are
+        // paged separately.
+        let (mut missions, mut tasks, mut assignments) = (0, 0, 0);
+        for archived in [false, true] {
+            let mut offset = 0;
+            loop {
+                let page = if archived {
+                    MissionRepository::list_archived_by_tenant(
+                        from,
+                        tenant_id,
+                        BACKFILL_PAGE_SIZE,
+                        offset,
+                    )
+                    .await?
+                } else {
+                    MissionRepository::list_by_tenant(from, tenant_id, BACKFILL_PAGE_SIZE, offset)
+                        .await?
+                };
+                let fetched = page.len();
+                let mission_ids: Vec<MissionId> = page.iter().map(|mission| mission.id).collect();
+                if !page.is_empty() {
+                    MissionRepository::upsert_many(to, page).await?;
+                }
+                missions += fetched as u64;
+                for mission_id in mission_ids {
+                    tasks += copy_pages(
+                        |limit, offset| {
+                            TaskRepository::list_by_mission(from, mission_id, limit, offset)
+                        },
+                        |page| TaskRepository::upsert_many(to, page),
+                    )
+                    .await?;
+                    assignments += copy_pages(
+                        |limit, offset| {
+                            AssignmentRepository::list_by_mission(from, mission_id, limit, offset)
+                        },
+                        |page| AssignmentRepository::upsert_many(to, page),
+                    )
+                    .await?;
+                }
+                if fetched < BACKFILL_PAGE_SIZE {
+                    break;
+                }
+                offset += fetched;
+            }
+        }
+
+        let incidents = copy_pages(
+            |limit, offset| IncidentRepository::list_by_tenant(from, tenant_id, limit, offset),
+            |page| IncidentRepository::upsert_many(to, page),
+        )
+        .await?;
+        let api_keys = copy_pages(
+            |limit, offset| ApiKeyRepository::list_by_tenant(from, tenant_id, limit, offset),
+            |page| ApiKeyRepository::upsert_many(to, page),
+        )
+        .await?;
+
+        Ok(BackfillReport {
+            capabilities,
+            teams,
+            units,
+            assets,
+            geofences,
+            missions,
+            tasks,
+            assignments,
+            incidents,
+            api_keys,
+        })
+    }
+}
+
+/// Reads pages from offset 0 until one comes back short, writing each page
+/// as one batch. Returns the number of records written.
+async fn copy_pages<R, L, LF, W, WF>(list: L, write: W) -> Result<u64, StorageError>
+where
+    L: Fn(usize, usize) -> LF,
+    LF: Future<Output = Result<Vec<R>, StorageError>>,
+    W: Fn(Vec<R>) -> WF,
+    WF: Future<Output = Result<(), StorageError>>,
+{
+    let mut copied = 0;
+    let mut offset = 0;
+    loop {
+        let page = list(BACKFILL_PAGE_SIZE, offset).await?;
+        let fetched = page.len();
+        if fetched > 0 {
+            write(page).await?;
+            copied += fetched as u64;
+        }
+        if fetched < BACKFILL_PAGE_SIZE {
+            return Ok(copied);
+        }
+        offset += fetched;
+    }
+}
+
+impl<F, T> StorageBackend for DualWriteStore<F, T> {
+    const NAME: &'static str = "dual_write";
+}
+
+#[async_trait]
+impl<F: MissionRepository, T: MissionRepository> MissionRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: MissionId) -> Result<Option<Mission>, StorageError> {
+        self.primary(
+            MissionRepository::get(&self.from, id),
+            MissionRepository::get(&self.to, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[MissionId]) -> Result<Vec<Mission>, StorageError> {
+        self.primary(
+            MissionRepository::get_many(&self.from, ids),
+            MissionRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.primary(
+            MissionRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            MissionRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_archived_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.primary(
+            MissionRepository::list_archived_by_tenant(&self.from, tenant_id, limit, offset),
+            MissionRepository::list_archived_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &MissionFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.primary(
+            MissionRepository::list_filtered(&self.from, tenant_id, filter, limit, offset),
+            MissionRepository::list_filtered(&self.to, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.primary(
+            MissionRepository::search_by_tenant(&self.from, tenant_id, query, limit),
+            MissionRepository::search_by_tenant(&self.to, tenant_id, query, limit),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.primary(
+            MissionRepository::list_updated_since(&self.from, tenant_id, after, limit),
+            MissionRepository::list_updated_since(&self.to, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn count_by_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<MissionStatus>>, StorageError> {
+        self.primary(
+            MissionRepository::count_by_status(&self.from, tenant_id),
+            MissionRepository::count_by_status(&self.to, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, mission: Mission) -> Result<(), StorageError> {
+        self.write(
+            MissionRepository::upsert(&self.from, mission.clone()),
+            MissionRepository::upsert(&self.to, mission),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, missions: Vec<Mission>) -> Result<(), StorageError> {
+        self.write(
+            MissionRepository::upsert_many(&self.from, missions.clone()),
+            MissionRepository::upsert_many(&self.to, missions),
+        )
+        .await
+    }
+
+    async fn insert_or_update(
+        &self,
+        mut mission: Mission,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let outcome = self
+            .primary(
+                MissionRepository::insert_or_update(&self.from, mission.clone()),
+                MissionRepository::insert_or_update(&self.to, mission.clone()),
+            )
+            .await?;
+        if let Some(outcome) = outcome {
+            mission.created_at_ms = outcome.created_at_ms;
+            self.secondary(
+                MissionRepository::upsert(&self.from, mission.clone()),
+                MissionRepository::upsert(&self.to, mission),
+            )
+            .await?;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: MissionId) -> Result<(), StorageError> {
+        self.write(
+            MissionRepository::delete(&self.from, id),
+            MissionRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            MissionRepository::delete_all_by_tenant(&self.from, tenant_id),
+            MissionRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: AssetRepository, T: AssetRepository> AssetRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: AssetId) -> Result<Option<Asset>, StorageError> {
+        self.primary(AssetRepository::get(&self.from, id), AssetRepository::get(&self.to, id)).await
+    }
+
+    async fn get_many(&self, ids: &[AssetId]) -> Result<Vec<Asset>, StorageError> {
+        self.primary(
+            AssetRepository::get_many(&self.from, ids),
+            AssetRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.primary(
+            AssetRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            AssetRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &AssetFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.primary(
+            AssetRepository::list_filtered(&self.from, tenant_id, filter, limit, offset),
+            AssetRepository::list_filtered(&self.to, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.primary(
+            AssetRepository::list_updated_since(&self.from, tenant_id, after, limit),
+            AssetRepository::list_updated_since(&self.to, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn list_status_history(
+        &self,
+        asset_id: AssetId,
+        from_ms: EpochMillis,
+        to_ms: EpochMillis,
+    ) -> Result<Vec<AssetStatusEvent>, StorageError> {
+        self.primary(
+            AssetRepository::list_status_history(&self.from, asset_id, from_ms, to_ms),
+            AssetRepository::list_status_history(&self.to, asset_id, from_ms, to_ms),
+        )
+        .await
+    }
+
+    async fn list_maintenance_due(&self, now_ms: EpochMillis) -> Result<Vec<Asset>, StorageError> {
+        self.primary(
+            AssetRepository::list_maintenance_due(&self.from, now_ms),
+            AssetRepository::list_maintenance_due(&self.to, now_ms),
+        )
+        .await
+    }
+
+    async fn count_by_readiness(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<ReadinessState>>, StorageError> {
+        self.primary(
+            AssetRepository::count_by_readiness(&self.from, tenant_id),
+            AssetRepository::count_by_readiness(&self.to, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, asset: Asset) -> Result<(), StorageError> {
+        self.write(
+            AssetRepository::upsert(&self.from, asset.clone()),
+            AssetRepository::upsert(&self.to, asset),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, assets: Vec<Asset>) -> Result<(), StorageError> {
+        self.write(
+            AssetRepository::upsert_many(&self.from, assets.clone()),
+            AssetRepository::upsert_many(&self.to, assets),
+        )
+        .await
+    }
+
+    async fn insert_or_update(
+        &self,
+        mut asset: Asset,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let outcome = self
+            .primary(
+                AssetRepository::insert_or_update(&self.from, asset.clone()),
+                AssetRepository::insert_or_update(&self.to, asset.clone()),
+            )
+            .await?;
+        if let Some(outcome) = outcome {
+            asset.created_at_ms = outcome.created_at_ms;
+            self.secondary(
+                AssetRepository::upsert(&self.from, asset.clone()),
+                AssetRepository::upsert(&self.to, asset),
+            )
+            .await?;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: AssetId) -> Result<(), StorageError> {
+        self.write(
+            AssetRepository::delete(&self.from, id),
+            AssetRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            AssetRepository::delete_all_by_tenant(&self.from, tenant_id),
+            AssetRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: UnitRepository, T: UnitRepository> UnitRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: UnitId) -> Result<Option<Unit>, StorageError> {
+        self.primary(UnitRepository::get(&self.from, id), UnitRepository::get(&self.to, id)).await
+    }
+
+    async fn get_many(&self, ids: &[UnitId]) -> Result<Vec<Unit>, StorageError> {
+        self.primary(
+            UnitRepository::get_many(&self.from, ids),
+            UnitRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        self.primary(
+            UnitRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            UnitRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        self.primary(
+            UnitRepository::list_updated_since(&self.from, tenant_id, after, limit),
+            UnitRepository::list_updated_since(&self.to, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn upsert(&self, unit: Unit) -> Result<(), StorageError> {
+        self.write(
+            UnitRepository::upsert(&self.from, unit.clone()),
+            UnitRepository::upsert(&self.to, unit),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, units: Vec<Unit>) -> Result<(), StorageError> {
+        self.write(
+            UnitRepository::upsert_many(&self.from, units.clone()),
+            UnitRepository::upsert_many(&self.to, units),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: UnitId) -> Result<(), StorageError> {
+        self.write(
+            UnitRepository::delete(&self.from, id),
+            UnitRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            UnitRepository::delete_all_by_tenant(&self.from, tenant_id),
+            UnitRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: TeamRepository, T: TeamRepository> TeamRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: TeamId) -> Result<Option<Team>, StorageError> {
+        self.primary(TeamRepository::get(&self.from, id), TeamRepository::get(&self.to, id)).await
+    }
+
+    async fn get_many(&self, ids: &[TeamId]) -> Result<Vec<Team>, StorageError> {
+        self.primary(
+            TeamRepository::get_many(&self.from, ids),
+            TeamRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        self.primary(
+            TeamRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            TeamRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        self.primary(
+            TeamRepository::list_updated_since(&self.from, tenant_id, after, limit),
+            TeamRepository::list_updated_since(&self.to, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn upsert(&self, team: Team) -> Result<(), StorageError> {
+        self.write(
+            TeamRepository::upsert(&self.from, team.clone()),
+            TeamRepository::upsert(&self.to, team),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, teams: Vec<Team>) -> Result<(), StorageError> {
+        self.write(
+            TeamRepository::upsert_many(&self.from, teams.clone()),
+            TeamRepository::upsert_many(&self.to, teams),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: TeamId) -> Result<(), StorageError> {
+        self.write(
+            TeamRepository::delete(&self.from, id),
+            TeamRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            TeamRepository::delete_all_by_tenant(&self.from, tenant_id),
+            TeamRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: CapabilityRepository, T: CapabilityRepository> CapabilityRepository
+    for DualWriteStore<F, T>
+{
+    async fn get(&self, id: CapabilityId) -> Result<Option<Capability>, StorageError> {
+        self.primary(
+            CapabilityRepository::get(&self.from, id),
+            CapabilityRepository::get(&self.to, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[CapabilityId]) -> Result<Vec<Capability>, StorageError> {
+        self.primary(
+            CapabilityRepository::get_many(&self.from, ids),
+            CapabilityRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Capability>, StorageError> {
+        self.primary(
+            CapabilityRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            CapabilityRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Capability>, StorageError> {
+        self.primary(
+            CapabilityRepository::list_updated_since(&self.from, tenant_id, after, limit),
+            CapabilityRepository::list_updated_since(&self.to, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn distinct_codes(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<CapabilityCode>, StorageError> {
+        self.primary(
+            CapabilityRepository::distinct_codes(&self.from, tenant_id),
+            CapabilityRepository::distinct_codes(&self.to, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, capability: Capability) -> Result<(), StorageError> {
+        self.write(
+            CapabilityRepository::upsert(&self.from, capability.clone()),
+            CapabilityRepository::upsert(&self.to, capability),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, capabilities: Vec<Capability>) -> Result<(), StorageError> {
+        self.write(
+            CapabilityRepository::upsert_many(&self.from, capabilities.clone()),
+            CapabilityRepository::upsert_many(&self.to, capabilities),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: CapabilityId) -> Result<(), StorageError> {
+        self.write(
+            CapabilityRepository::delete(&self.from, id),
+            CapabilityRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            CapabilityRepository::delete_all_by_tenant(&self.from, tenant_id),
+            CapabilityRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: GeofenceRepository, T: GeofenceRepository> GeofenceRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: GeofenceId) -> Result<Option<Geofence>, StorageError> {
+        self.primary(
+            GeofenceRepository::get(&self.from, id),
+            GeofenceRepository::get(&self.to, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[GeofenceId]) -> Result<Vec<Geofence>, StorageError> {
+        self.primary(
+            GeofenceRepository::get_many(&self.from, ids),
+            GeofenceRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Geofence>, StorageError> {
+        self.primary(
+            GeofenceRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            GeofenceRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_active(&self) -> Result<Vec<Geofence>, StorageError> {
+        self.primary(
+            GeofenceRepository::list_active(&self.from),
+            GeofenceRepository::list_active(&self.to),
+        )
+        .await
+    }
+
+    async fn upsert(&self, geofence: Geofence) -> Result<(), StorageError> {
+        self.write(
+            GeofenceRepository::upsert(&self.from, geofence.clone()),
+            GeofenceRepository::upsert(&self.to, geofence),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, geofences: Vec<Geofence>) -> Result<(), StorageError> {
+        self.write(
+            GeofenceRepository::upsert_many(&self.from, geofences.clone()),
+            GeofenceRepository::upsert_many(&self.to, geofences),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: GeofenceId) -> Result<(), StorageError> {
+        self.write(
+            GeofenceRepository::delete(&self.from, id),
+            GeofenceRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            GeofenceRepository::delete_all_by_tenant(&self.from, tenant_id),
+            GeofenceRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: AssignmentRepository, T: AssignmentRepository> AssignmentRepository
+    for DualWriteStore<F, T>
+{
+    async fn get(&self, id: AssignmentId) -> Result<Option<MissionAssignment>, StorageError> {
+        self.primary(
+            AssignmentRepository::get(&self.from, id),
+            AssignmentRepository::get(&self.to, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[AssignmentId]) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.primary(
+            AssignmentRepository::get_many(&self.from, ids),
+            AssignmentRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.primary(
+            AssignmentRepository::list_by_mission(&self.from, mission_id, limit, offset),
+            AssignmentRepository::list_by_mission(&self.to, mission_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_by_unit(
+        &self,
+        unit_id: UnitId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.primary(
+            AssignmentRepository::list_by_unit(&self.from, unit_id, limit, offset),
+            AssignmentRepository::list_by_unit(&self.to, unit_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn upsert(&self, assignment: MissionAssignment) -> Result<(), StorageError> {
+        self.write(
+            AssignmentRepository::upsert(&self.from, assignment.clone()),
+            AssignmentRepository::upsert(&self.to, assignment),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, assignments: Vec<MissionAssignment>) -> Result<(), StorageError> {
+        self.write(
+            AssignmentRepository::upsert_many(&self.from, assignments.clone()),
+            AssignmentRepository::upsert_many(&self.to, assignments),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: AssignmentId) -> Result<(), StorageError> {
+        self.write(
+            AssignmentRepository::delete(&self.from, id),
+            AssignmentRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            AssignmentRepository::delete_all_by_tenant(&self.from, tenant_id),
+            AssignmentRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: ApiKeyRepository, T: ApiKeyRepository> ApiKeyRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: ApiKeyId) -> Result<Option<ApiKey>, StorageError> {
+        self.primary(
+            ApiKeyRepository::get(&self.from, id),
+            ApiKeyRepository::get(&self.to, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[ApiKeyId]) -> Result<Vec<ApiKey>, StorageError> {
+        self.primary(
+            ApiKeyRepository::get_many(&self.from, ids),
+            ApiKeyRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn find_by_hash(&self, hashed_secret: &str) -> Result<Option<ApiKey>, StorageError> {
+        self.primary(
+            ApiKeyRepository::find_by_hash(&self.from, hashed_secret),
+            ApiKeyRepository::find_by_hash(&self.to, hashed_secret),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ApiKey>, StorageError> {
+        self.primary(
+            ApiKeyRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            ApiKeyRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn upsert(&self, key: ApiKey) -> Result<(), StorageError> {
+        self.write(
+            ApiKeyRepository::upsert(&self.from, key.clone()),
+            ApiKeyRepository::upsert(&self.to, key),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, keys: Vec<ApiKey>) -> Result<(), StorageError> {
+        self.write(
+            ApiKeyRepository::upsert_many(&self.from, keys.clone()),
+            ApiKeyRepository::upsert_many(&self.to, keys),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            ApiKeyRepository::delete_all_by_tenant(&self.from, tenant_id),
+            ApiKeyRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: IncidentRepository, T: IncidentRepository> IncidentRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: IncidentId) -> Result<Option<Incident>, StorageError> {
+        self.primary(
+            IncidentRepository::get(&self.from, id),
+            IncidentRepository::get(&self.to, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[IncidentId]) -> Result<Vec<Incident>, StorageError> {
+        self.primary(
+            IncidentRepository::get_many(&self.from, ids),
+            IncidentRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.primary(
+            IncidentRepository::list_by_tenant(&self.from, tenant_id, limit, offset),
+            IncidentRepository::list_by_tenant(&self.to, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &IncidentFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.primary(
+            IncidentRepository::list_filtered(&self.from, tenant_id, filter, limit, offset),
+            IncidentRepository::list_filtered(&self.to, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_in_bbox(
+        &self,
+        tenant_id: TenantId,
+        bbox: BoundingBox,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.primary(
+            IncidentRepository::list_in_bbox(&self.from, tenant_id, bbox, limit),
+            IncidentRepository::list_in_bbox(&self.to, tenant_id, bbox, limit),
+        )
+        .await
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.primary(
+            IncidentRepository::search_by_tenant(&self.from, tenant_id, query, limit),
+            IncidentRepository::search_by_tenant(&self.to, tenant_id, query, limit),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.primary(
+            IncidentRepository::list_updated_since(&self.from, tenant_id, after, limit),
+            IncidentRepository::list_updated_since(&self.to, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn count_by_type_and_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<(IncidentType, IncidentStatus)>>, StorageError> {
+        self.primary(
+            IncidentRepository::count_by_type_and_status(&self.from, tenant_id),
+            IncidentRepository::count_by_type_and_status(&self.to, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, incident: Incident) -> Result<(), StorageError> {
+        self.write(
+            IncidentRepository::upsert(&self.from, incident.clone()),
+            IncidentRepository::upsert(&self.to, incident),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, incidents: Vec<Incident>) -> Result<(), StorageError> {
+        self.write(
+            IncidentRepository::upsert_many(&self.from, incidents.clone()),
+            IncidentRepository::upsert_many(&self.to, incidents),
+        )
+        .await
+    }
+
+    async fn insert_or_update(
+        &self,
+        mut incident: Incident,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let outcome = self
+            .primary(
+                IncidentRepository::insert_or_update(&self.from, incident.clone()),
+                IncidentRepository::insert_or_update(&self.to, incident.clone()),
+            )
+            .await?;
+        if let Some(outcome) = outcome {
+            incident.created_at_ms = outcome.created_at_ms;
+            self.secondary(
+                IncidentRepository::upsert(&self.from, incident.clone()),
+                IncidentRepository::upsert(&self.to, incident),
+            )
+            .await?;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: IncidentId) -> Result<(), StorageError> {
+        self.write(
+            IncidentRepository::delete(&self.from, id),
+            IncidentRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            IncidentRepository::delete_all_by_tenant(&self.from, tenant_id),
+            IncidentRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<F: TaskRepository, T: TaskRepository> TaskRepository for DualWriteStore<F, T> {
+    async fn get(&self, id: TaskId) -> Result<Option<Task>, StorageError> {
+        self.primary(TaskRepository::get(&self.from, id), TaskRepository::get(&self.to, id)).await
+    }
+
+    async fn get_many(&self, ids: &[TaskId]) -> Result<Vec<Task>, StorageError> {
+        self.primary(
+            TaskRepository::get_many(&self.from, ids),
+            TaskRepository::get_many(&self.to, ids),
+        )
+        .await
+    }
+
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Task>, StorageError> {
+        self.primary(
+            TaskRepository::list_by_mission(&self.from, mission_id, limit, offset),
+            TaskRepository::list_by_mission(&self.to, mission_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Task>, StorageError> {
+        self.primary(
+            TaskRepository::list_updated_since(&self.from, tenant_id, after, limit),
+            TaskRepository::list_updated_since(&self.to, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn mission_progress(
+        &self,
+        mission_id: MissionId,
+    ) -> Result<MissionProgress, StorageError> {
+        self.primary(
+            TaskRepository::mission_progress(&self.from, mission_id),
+            TaskRepository::mission_progress(&self.to, mission_id),
+        )
+        .await
+    }
+
+    async fn list_with_dependencies(&self) -> Result<Vec<Task>, StorageError> {
+        self.primary(
+            TaskRepository::list_with_dependencies(&self.from),
+            TaskRepository::list_with_dependencies(&self.to),
+        )
+        .await
+    }
+
+    async fn upsert(&self, task: Task) -> Result<(), StorageError> {
+        self.write(
+            TaskRepository::upsert(&self.from, task.clone()),
+            TaskRepository::upsert(&self.to, task),
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, tasks: Vec<Task>) -> Result<(), StorageError> {
+        self.write(
+            TaskRepository::upsert_many(&self.from, tasks.clone()),
+            TaskRepository::upsert_many(&self.to, tasks),
+        )
+        .await
+    }
+
+    async fn insert_or_update(
+        &self,
+        mut task: Task,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let outcome = self
+            .primary(
+                TaskRepository::insert_or_update(&self.from, task.clone()),
+                TaskRepository::insert_or_update(&self.to, task.clone()),
+            )
+            .await?;
+        if let Some(outcome) = outcome {
+            task.created_at_ms = outcome.created_at_ms;
+            self.secondary(
+                TaskRepository::upsert(&self.from, task.clone()),
+                TaskRepository::upsert(&self.to, task),
+            )
+            .await?;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: TaskId) -> Result<(), StorageError> {
+        self.write(
+            TaskRepository::delete(&self.from, id),
+            TaskRepository::delete(&self.to, id),
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.write(
+            TaskRepository::delete_all_by_tenant(&self.from, tenant_id),
+            TaskRepository::delete_all_by_tenant(&self.to, tenant_id),
+        )
+        .await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StorageErrorKind;
+    use crate::testing::{MemoryMissions, mission};
+
+    #[tokio::test]
+    async fn writes_reach_both_stores_and_reads_follow_the_read_side() {
+        let tenant_id = TenantId::new();
+        let first = mission(tenant_id, "Harbor watch");
+        let store = DualWriteStore::new(MemoryMissions::default(), MemoryMissions::default());
+        MissionRepository::upsert(&store, first.clone()).await.unwrap();
+        assert!(store.from_store().stored(first.id).is_some());
+        assert!(store.to_store().stored(first.id).is_some());
+
+        let only_new = mission(tenant_id, "Harbor patrol");
+        MissionRepository::upsert(store.to_store(), only_new.clone()).await.unwrap();
+        assert!(MissionRepository::get(&store, only_new.id).await.unwrap().is_none());
+        let store = store.with_reads_from(ReadSide::To);
+        assert!(MissionRepository::get(&store, only_new.id).await.unwrap().is_some());
+
+        MissionRepository::delete(&store, first.id).await.unwrap();
+        assert!(store.from_store().stored(first.id).is_none());
+        assert!(store.to_store().stored(first.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn a_write_rejected_by_the_read_side_skips_the_other_store() {
+        let tenant_id = TenantId::new();
+        let first = mission(tenant_id, "Bridge survey");
+        let store = DualWriteStore::new(
+            MemoryMissions::default(),
+            MemoryMissions::failing(StorageErrorKind::Backend),
+        );
+        assert!(MissionRepository::upsert(&store, first.clone()).await.is_err());
+        assert!(store.from_store().stored(first.id).is_some());
+
+        let store = store.with_reads_from(ReadSide::To);
+        let second = mission(tenant_id, "Route clearance");
+        assert!(MissionRepository::upsert(&store, second.clone()).await.is_err());
+        assert!(store.from_store().stored(second.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn backfill_pages_are_written_in_one_batch_each() {
+        let records: Vec<usize> = (0..BACKFILL_PAGE_SIZE * 2 + 7).collect();
+        let batches = std::sync::Mutex::new(Vec::new());
+        let copied = copy_pages(
+            |limit, offset| {
+                let page = records.iter().copied().skip(offset).take(limit).collect();
+                async move { Ok(page) }
+            },
+            |page: Vec<usize>| {
+                batches.lock().unwrap().push(page.len());
+                async { Ok(()) }
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(copied, records.len() as u64);
+        assert_eq!(*batches.lock().unwrap(), [BACKFILL_PAGE_SIZE, BACKFILL_PAGE_SIZE, 7]);
+    }
+}
@@ -0,0 +1,1299 @@
+//! [`FallbackStore`] pairs a primary store with a secondary (for example a
+//! Postgres read replica behind a Surreal primary). Reads go to the primary
+//! and are retried on the secondary when the primary fails with a
+//! [`StorageErrorKind::Connection`] error; any other error is returned as-is,
+//! since the secondary would most likely fail the same way. Timeouts are not
+//! retried either: the primary may still be working on the call.
+//!
+//! Writes only go to the primary. With [`FallbackStore::with_dual_write`]
+//! each successful write is repeated on the secondary; a failed mirror write
+//! is logged and does not fail the call, as the primary holds the record.
+//!
+//! Reads served by the secondary are counted in
+//! `c2_storage_fallback_reads_total`, labelled by entity and operation.
+
+use async_trait::async_trait;
+use c2_core::{
+    ApiKeyId, Asset, AssetId, AssetStatusEvent, AssignmentId, Capability, CapabilityCode,
+    CapabilityId, EpochMillis, Geofence, GeofenceId, GroupCount, Incident, IncidentId,
+    IncidentStatus, IncidentType, Mission, MissionAssignment, MissionId, MissionProgress,
+    MissionStatus, ReadinessState, Task, TaskId, Team, TeamId, TenantId, Unit, UnitId,
+};
+use c2_geo::BoundingBox;
+use c2_identity::ApiKey;
+use metrics::counter;
+use std::future::Future;
+
+use crate::{
+    ApiKeyRepository, AssetFilter, AssetRepository, AssignmentRepository, CapabilityRepository,
+    ChangeCursor, GeofenceRepository, IncidentFilter, IncidentRepository, MissionFilter,
+    MissionRepository, StorageBackend, StorageError, StorageErrorKind, TaskRepository,
+    TeamRepository, UnitRepository, UpsertOutcome,
+};
+
+const FALLBACK_READS_TOTAL: &str = "c2_storage_fallback_reads_total";
+
+#[derive(Debug, Clone)]
+pub struct FallbackStore<P, S> {
+    primary: P,
+    secondary: S,
+    dual_write: bool,
+}
+
+impl<P, S> FallbackStore<P, S> {
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            primary,
+            secondary,
+            dual_write: false,
+        }
+    }
+
+    /// Also applies every successful write to the secondary.
+    pub fn with_dual_write(mut self, dual_write: bool) -> Self {
+        self.dual_write = dual_write;
+        self
+    }
+
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+
+    /// Awaits `secondary` only when `primary` could not reach its backend;
+    /// both futures are built up front but neither runs until awaited.
+    async fn read<T>(
+        &self,
+        operation: &'static str,
+        primary: impl Future<Output = Result<T, StorageError>>,
+        secondary: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        match primary.await {
+            Err(err) if err.kind == StorageErrorKind::Connection => {
+                tracing::warn!(
+                    operation,
+                    error = %err.message,
+                    "primary store unreachable, reading from secondary"
+                );
+                let (entity, action) = operation.split_once('.').unwrap_or(("store", operation));
+                counter!(FALLBACK_READS_TOTAL, "entity" => entity, "operation" => action)
+                    .increment(1);
+                secondary.await
+            }
+            result => result,
+        }
+    }
+
+    async fn write<T, M>(
+        &self,
+        operation: &'static str,
+        primary: impl Future<Output = Result<T, StorageError>>,
+        mirror: Option<impl Future<Output = Result<M, StorageError>>>,
+    ) -> Result<T, StorageError> {
+        let result = primary.await?;
+        if let Some(mirror) = mirror {
+            self.mirror(operation, mirror).await;
+        }
+        Ok(result)
+    }
+
+    async fn mirror<M>(
+        &self,
+        operation: &'static str,
+        call: impl Future<Output = Result<M, StorageError>>,
+    ) {
+        if let Err(err) = call.await {
+            tracing::warn!(
+                operation,
+                error = %err.message,
+                "secondary store write failed"
+            );
+        }
+    }
+}
+
+impl<P, S> StorageBackend for FallbackStore<P, S> {
+    const NAME: &'static str = "fallback";
+}
+
+#[async_trait]
+impl<P: MissionRepository, S: MissionRepository> MissionRepository for FallbackStore<P, S> {
+    async fn get(&self, id: MissionId) -> Result<Option<Mission>, StorageError> {
+        self.read(
+            "mission.get",
+            MissionRepository::get(&self.primary, id),
+            MissionRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[MissionId]) -> Result<Vec<Mission>, StorageError> {
+        self.read(
+            "mission.get_many",
+            MissionRepository::get_many(&self.primary, ids),
+            MissionRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.read(
+            "mission.list_by_tenant",
+            MissionRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            MissionRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_archived_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.read(
+            "mission.list_archived_by_tenant",
+            MissionRepository::list_archived_by_tenant(&self.primary, tenant_id, limit, offset),
+            MissionRepository::list_archived_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &MissionFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.read(
+            "mission.list_filtered",
+            MissionRepository::list_filtered(&self.primary, tenant_id, filter, limit, offset),
+            MissionRepository::list_filtered(&self.secondary, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.read(
+            "mission.search_by_tenant",
+            MissionRepository::search_by_tenant(&self.primary, tenant_id, query, limit),
+            MissionRepository::search_by_tenant(&self.secondary, tenant_id, query, limit),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.read(
+            "mission.list_updated_since",
+            MissionRepository::list_updated_since(&self.primary, tenant_id, after, limit),
+            MissionRepository::list_updated_since(&self.secondary, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn count_by_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<MissionStatus>>, StorageError> {
+        self.read(
+            "mission.count_by_status",
+            MissionRepository::count_by_status(&self.primary, tenant_id),
+            MissionRepository::count_by_status(&self.secondary, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, mission: Mission) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| MissionRepository::upsert(&self.secondary, mission.clone()));
+        self.write(
+            "mission.upsert",
+            MissionRepository::upsert(&self.primary, mission),
+            mirror,
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, missions: Vec<Mission>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| MissionRepository::upsert_many(&self.secondary, missions.clone()));
+        self.write(
+            "mission.upsert_many",
+            MissionRepository::upsert_many(&self.primary, missions),
+            mirror,
+        )
+        .await
+    }
+
+    async fn insert_or_update(
+        &self,
+        mission: Mission,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let mirrored = self.dual_write.then(|| mission.clone());
+        let outcome = MissionRepository::insert_or_update(&self.primary, mission).await?;
+        if let (Some(mut mission), Some(outcome)) = (mirrored, outcome) {
+            mission.created_at_ms = outcome.created_at_ms;
+            self.mirror(
+                "mission.insert_or_update",
+                MissionRepository::upsert(&self.secondary, mission),
+            )
+            .await;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: MissionId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| MissionRepository::delete(&self.secondary, id));
+        self.write("mission.delete", MissionRepository::delete(&self.primary, id), mirror).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| MissionRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "mission.delete_all_by_tenant",
+            MissionRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: AssetRepository, S: AssetRepository> AssetRepository for FallbackStore<P, S> {
+    async fn get(&self, id: AssetId) -> Result<Option<Asset>, StorageError> {
+        self.read(
+            "asset.get",
+            AssetRepository::get(&self.primary, id),
+            AssetRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[AssetId]) -> Result<Vec<Asset>, StorageError> {
+        self.read(
+            "asset.get_many",
+            AssetRepository::get_many(&self.primary, ids),
+            AssetRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.read(
+            "asset.list_by_tenant",
+            AssetRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            AssetRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &AssetFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.read(
+            "asset.list_filtered",
+            AssetRepository::list_filtered(&self.primary, tenant_id, filter, limit, offset),
+            AssetRepository::list_filtered(&self.secondary, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.read(
+            "asset.list_updated_since",
+            AssetRepository::list_updated_since(&self.primary, tenant_id, after, limit),
+            AssetRepository::list_updated_since(&self.secondary, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn list_status_history(
+        &self,
+        asset_id: AssetId,
+        from_ms: EpochMillis,
+        to_ms: EpochMillis,
+    ) -> Result<Vec<AssetStatusEvent>, StorageError> {
+        self.read(
+            "asset.list_status_history",
+            AssetRepository::list_status_history(&self.primary, asset_id, from_ms, to_ms),
+            AssetRepository::list_status_history(&self.secondary, asset_id, from_ms, to_ms),
+        )
+        .await
+    }
+
+    async fn list_maintenance_due(&self, now_ms: EpochMillis) -> Result<Vec<Asset>, StorageError> {
+        self.read(
+            "asset.list_maintenance_due",
+            AssetRepository::list_maintenance_due(&self.primary, now_ms),
+            AssetRepository::list_maintenance_due(&self.secondary, now_ms),
+        )
+        .await
+    }
+
+    async fn count_by_readiness(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<ReadinessState>>, StorageError> {
+        self.read(
+            "asset.count_by_readiness",
+            AssetRepository::count_by_readiness(&self.primary, tenant_id),
+            AssetRepository::count_by_readiness(&self.secondary, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, asset: Asset) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| AssetRepository::upsert(&self.secondary, asset.clone()));
+        self.write("asset.upsert", AssetRepository::upsert(&self.primary, asset), mirror).await
+    }
+
+    async fn upsert_many(&self, assets: Vec<Asset>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| AssetRepository::upsert_many(&self.secondary, assets.clone()));
+        self.write(
+            "asset.upsert_many",
+            AssetRepository::upsert_many(&self.primary, assets),
+            mirror,
+        )
+        .await
+    }
+
+    async fn insert_or_update(&self, asset: Asset) -> Result<Option<UpsertOutcome>, StorageError> {
+        let mirrored = self.dual_write.then(|| asset.clone());
+        let outcome = AssetRepository::insert_or_update(&self.primary, asset).await?;
+        if let (Some(mut asset), Some(outcome)) = (mirrored, outcome) {
+            asset.created_at_ms = outcome.created_at_ms;
+            self.mirror(
+                "asset.insert_or_update",
+                AssetRepository::upsert(&self.secondary, asset),
+            )
+            .await;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: AssetId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| AssetRepository::delete(&self.secondary, id));
+        self.write("asset.delete", AssetRepository::delete(&self.primary, id), mirror).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| AssetRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "asset.delete_all_by_tenant",
+            AssetRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: UnitRepository, S: UnitRepository> UnitRepository for FallbackStore<P, S> {
+    async fn get(&self, id: UnitId) -> Result<Option<Unit>, StorageError> {
+        self.read(
+            "unit.get",
+            UnitRepository::get(&self.primary, id),
+            UnitRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[UnitId]) -> Result<Vec<Unit>, StorageError> {
+        self.read(
+            "unit.get_many",
+            UnitRepository::get_many(&self.primary, ids),
+            UnitRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        self.read(
+            "unit.list_by_tenant",
+            UnitRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            UnitRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        self.read(
+            "unit.list_updated_since",
+            UnitRepository::list_updated_since(&self.primary, tenant_id, after, limit),
+            UnitRepository::list_updated_since(&self.secondary, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn upsert(&self, unit: Unit) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| UnitRepository::upsert(&self.secondary, unit.clone()));
+        self.write("unit.upsert", UnitRepository::upsert(&self.primary, unit), mirror).await
+    }
+
+    async fn upsert_many(&self, units: Vec<Unit>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| UnitRepository::upsert_many(&self.secondary, units.clone()));
+        self.write(
+            "unit.upsert_many",
+            UnitRepository::upsert_many(&self.primary, units),
+            mirror,
+        )
+        .await
+    }
+
+    async fn delete(&self, id: UnitId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| UnitRepository::delete(&self.secondary, id));
+        self.write("unit.delete", UnitRepository::delete(&self.primary, id), mirror).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| UnitRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "unit.delete_all_by_tenant",
+            UnitRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: TeamRepository, S: TeamRepository> TeamRepository for FallbackStore<P, S> {
+    async fn get(&self, id: TeamId) -> Result<Option<Team>, StorageError> {
+        self.read(
+            "team.get",
+            TeamRepository::get(&self.primary, id),
+            TeamRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[TeamId]) -> Result<Vec<Team>, StorageError> {
+        self.read(
+            "team.get_many",
+            TeamRepository::get_many(&self.primary, ids),
+            TeamRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        self.read(
+            "team.list_by_tenant",
+            TeamRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            TeamRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        self.read(
+            "team.list_updated_since",
+            TeamRepository::list_updated_since(&self.primary, tenant_id, after, limit),
+            TeamRepository::list_updated_since(&self.secondary, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn upsert(&self, team: Team) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| TeamRepository::upsert(&self.secondary, team.clone()));
+        self.write("team.upsert", TeamRepository::upsert(&self.primary, team), mirror).await
+    }
+
+    async fn upsert_many(&self, teams: Vec<Team>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| TeamRepository::upsert_many(&self.secondary, teams.clone()));
+        self.write(
+            "team.upsert_many",
+            TeamRepository::upsert_many(&self.primary, teams),
+            mirror,
+        )
+        .await
+    }
+
+    async fn delete(&self, id: TeamId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| TeamRepository::delete(&self.secondary, id));
+        self.write("team.delete", TeamRepository::delete(&self.primary, id), mirror).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| TeamRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "team.delete_all_by_tenant",
+            TeamRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: CapabilityRepository, S: CapabilityRepository> CapabilityRepository
+    for FallbackStore<P, S>
+{
+    async fn get(&self, id: CapabilityId) -> Result<Option<Capability>, StorageError> {
+        self.read(
+            "capability.get",
+            CapabilityRepository::get(&self.primary, id),
+            CapabilityRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[CapabilityId]) -> Result<Vec<Capability>, StorageError> {
+        self.read(
+            "capability.get_many",
+            CapabilityRepository::get_many(&self.primary, ids),
+            CapabilityRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Capability>, StorageError> {
+        self.read(
+            "capability.list_by_tenant",
+            CapabilityRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            CapabilityRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Capability>, StorageError> {
+        self.read(
+            "capability.list_updated_since",
+            CapabilityRepository::list_updated_since(&self.primary, tenant_id, after, limit),
+            CapabilityRepository::list_updated_since(&self.secondary, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn distinct_codes(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<CapabilityCode>, StorageError> {
+        self.read(
+            "capability.distinct_codes",
+            CapabilityRepository::distinct_codes(&self.primary, tenant_id),
+            CapabilityRepository::distinct_codes(&self.secondary, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, capability: Capability) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| CapabilityRepository::upsert(&self.secondary, capability.clone()));
+        self.write(
+            "capability.upsert",
+            CapabilityRepository::upsert(&self.primary, capability),
+            mirror,
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, capabilities: Vec<Capability>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| CapabilityRepository::upsert_many(&self.secondary, capabilities.clone()));
+        self.write(
+            "capability.upsert_many",
+            CapabilityRepository::upsert_many(&self.primary, capabilities),
+            mirror,
+        )
+        .await
+    }
+
+    async fn delete(&self, id: CapabilityId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| CapabilityRepository::delete(&self.secondary, id));
+        self.write(
+            "capability.delete",
+            CapabilityRepository::delete(&self.primary, id),
+            mirror,
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| CapabilityRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "capability.delete_all_by_tenant",
+            CapabilityRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: GeofenceRepository, S: GeofenceRepository> GeofenceRepository for FallbackStore<P, S> {
+    async fn get(&self, id: GeofenceId) -> Result<Option<Geofence>, StorageError> {
+        self.read(
+            "geofence.get",
+            GeofenceRepository::get(&self.primary, id),
+            GeofenceRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[GeofenceId]) -> Result<Vec<Geofence>, StorageError> {
+        self.read(
+            "geofence.get_many",
+            GeofenceRepository::get_many(&self.primary, ids),
+            GeofenceRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Geofence>, StorageError> {
+        self.read(
+            "geofence.list_by_tenant",
+            GeofenceRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            GeofenceRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_active(&self) -> Result<Vec<Geofence>, StorageError> {
+        self.read(
+            "geofence.list_active",
+            GeofenceRepository::list_active(&self.primary),
+            GeofenceRepository::list_active(&self.secondary),
+        )
+        .await
+    }
+
+    async fn upsert(&self, geofence: Geofence) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| GeofenceRepository::upsert(&self.secondary, geofence.clone()));
+        self.write(
+            "geofence.upsert",
+            GeofenceRepository::upsert(&self.primary, geofence),
+            mirror,
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, geofences: Vec<Geofence>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| GeofenceRepository::upsert_many(&self.secondary, geofences.clone()));
+        self.write(
+            "geofence.upsert_many",
+            GeofenceRepository::upsert_many(&self.primary, geofences),
+            mirror,
+        )
+        .await
+    }
+
+    async fn delete(&self, id: GeofenceId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| GeofenceRepository::delete(&self.secondary, id));
+        self.write("geofence.delete", GeofenceRepository::delete(&self.primary, id), mirror).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| GeofenceRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "geofence.delete_all_by_tenant",
+            GeofenceRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: AssignmentRepository, S: AssignmentRepository> AssignmentRepository
+    for FallbackStore<P, S>
+{
+    async fn get(&self, id: AssignmentId) -> Result<Option<MissionAssignment>, StorageError> {
+        self.read(
+            "assignment.get",
+            AssignmentRepository::get(&self.primary, id),
+            AssignmentRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[AssignmentId]) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.read(
+            "assignment.get_many",
+            AssignmentRepository::get_many(&self.primary, ids),
+            AssignmentRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.read(
+            "assignment.list_by_mission",
+            AssignmentRepository::list_by_mission(&self.primary, mission_id, limit, offset),
+            AssignmentRepository::list_by_mission(&self.secondary, mission_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_by_unit(
+        &self,
+        unit_id: UnitId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.read(
+            "assignment.list_by_unit",
+            AssignmentRepository::list_by_unit(&self.primary, unit_id, limit, offset),
+            AssignmentRepository::list_by_unit(&self.secondary, unit_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn upsert(&self, assignment: MissionAssignment) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| AssignmentRepository::upsert(&self.secondary, assignment.clone()));
+        self.write(
+            "assignment.upsert",
+            AssignmentRepository::upsert(&self.primary, assignment),
+            mirror,
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, assignments: Vec<MissionAssignment>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| AssignmentRepository::upsert_many(&self.secondary, assignments.clone()));
+        self.write(
+            "assignment.upsert_many",
+            AssignmentRepository::upsert_many(&self.primary, assignments),
+            mirror,
+        )
+        .await
+    }
+
+    async fn delete(&self, id: AssignmentId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| AssignmentRepository::delete(&self.secondary, id));
+        self.write("assignment.delete", AssignmentRepository::delete(&self.primary, id), mirror)
+            .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| AssignmentRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "assignment.delete_all_by_tenant",
+            AssignmentRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: ApiKeyRepository, S: ApiKeyRepository> ApiKeyRepository for FallbackStore<P, S> {
+    async fn get(&self, id: ApiKeyId) -> Result<Option<ApiKey>, StorageError> {
+        self.read(
+            "api_key.get",
+            ApiKeyRepository::get(&self.primary, id),
+            ApiKeyRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[ApiKeyId]) -> Result<Vec<ApiKey>, StorageError> {
+        self.read(
+            "api_key.get_many",
+            ApiKeyRepository::get_many(&self.primary, ids),
+            ApiKeyRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn find_by_hash(&self, hashed_secret: &str) -> Result<Option<ApiKey>, StorageError> {
+        self.read(
+            "api_key.find_by_hash",
+            ApiKeyRepository::find_by_hash(&self.primary, hashed_secret),
+            ApiKeyRepository::find_by_hash(&self.secondary, hashed_secret),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ApiKey>, StorageError> {
+        self.read(
+            "api_key.list_by_tenant",
+            ApiKeyRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            ApiKeyRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn upsert(&self, key: ApiKey) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| ApiKeyRepository::upsert(&self.secondary, key.clone()));
+        self.write("api_key.upsert", ApiKeyRepository::upsert(&self.primary, key), mirror).await
+    }
+
+    async fn upsert_many(&self, keys: Vec<ApiKey>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| ApiKeyRepository::upsert_many(&self.secondary, keys.clone()));
+        self.write(
+            "api_key.upsert_many",
+            ApiKeyRepository::upsert_many(&self.primary, keys),
+            mirror,
+        )
+        .await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| ApiKeyRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "api_key.delete_all_by_tenant",
+            ApiKeyRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: IncidentRepository, S: IncidentRepository> IncidentRepository for FallbackStore<P, S> {
+    async fn get(&self, id: IncidentId) -> Result<Option<Incident>, StorageError> {
+        self.read(
+            "incident.get",
+            IncidentRepository::get(&self.primary, id),
+            IncidentRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[IncidentId]) -> Result<Vec<Incident>, StorageError> {
+        self.read(
+            "incident.get_many",
+            IncidentRepository::get_many(&self.primary, ids),
+            IncidentRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.read(
+            "incident.list_by_tenant",
+            IncidentRepository::list_by_tenant(&self.primary, tenant_id, limit, offset),
+            IncidentRepository::list_by_tenant(&self.secondary, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &IncidentFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.read(
+            "incident.list_filtered",
+            IncidentRepository::list_filtered(&self.primary, tenant_id, filter, limit, offset),
+            IncidentRepository::list_filtered(&self.secondary, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_in_bbox(
+        &self,
+        tenant_id: TenantId,
+        bbox: BoundingBox,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.read(
+            "incident.list_in_bbox",
+            IncidentRepository::list_in_bbox(&self.primary, tenant_id, bbox, limit),
+            IncidentRepository::list_in_bbox(&self.secondary, tenant_id, bbox, limit),
+        )
+        .await
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.read(
+            "incident.search_by_tenant",
+            IncidentRepository::search_by_tenant(&self.primary, tenant_id, query, limit),
+            IncidentRepository::search_by_tenant(&self.secondary, tenant_id, query, limit),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.read(
+            "incident.list_updated_since",
+            IncidentRepository::list_updated_since(&self.primary, tenant_id, after, limit),
+            IncidentRepository::list_updated_since(&self.secondary, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn count_by_type_and_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<(IncidentType, IncidentStatus)>>, StorageError> {
+        self.read(
+            "incident.count_by_type_and_status",
+            IncidentRepository::count_by_type_and_status(&self.primary, tenant_id),
+            IncidentRepository::count_by_type_and_status(&self.secondary, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, incident: Incident) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| IncidentRepository::upsert(&self.secondary, incident.clone()));
+        self.write(
+            "incident.upsert",
+            IncidentRepository::upsert(&self.primary, incident),
+            mirror,
+        )
+        .await
+    }
+
+    async fn upsert_many(&self, incidents: Vec<Incident>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| IncidentRepository::upsert_many(&self.secondary, incidents.clone()));
+        self.write(
+            "incident.upsert_many",
+            IncidentRepository::upsert_many(&self.primary, incidents),
+            mirror,
+        )
+        .await
+    }
+
+    async fn insert_or_update(
+        &self,
+        incident: Incident,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let mirrored = self.dual_write.then(|| incident.clone());
+        let outcome = IncidentRepository::insert_or_update(&self.primary, incident).await?;
+        if let (Some(mut incident), Some(outcome)) = (mirrored, outcome) {
+            incident.created_at_ms = outcome.created_at_ms;
+            self.mirror(
+                "incident.insert_or_update",
+                IncidentRepository::upsert(&self.secondary, incident),
+            )
+            .await;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: IncidentId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| IncidentRepository::delete(&self.secondary, id));
+        self.write("incident.delete", IncidentRepository::delete(&self.primary, id), mirror).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| IncidentRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "incident.delete_all_by_tenant",
+            IncidentRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<P: TaskRepository, S: TaskRepository> TaskRepository for FallbackStore<P, S> {
+    async fn get(&self, id: TaskId) -> Result<Option<Task>, StorageError> {
+        self.read(
+            "task.get",
+            TaskRepository::get(&self.primary, id),
+            TaskRepository::get(&self.secondary, id),
+        )
+        .await
+    }
+
+    async fn get_many(&self, ids: &[TaskId]) -> Result<Vec<Task>, StorageError> {
+        self.read(
+            "task.get_many",
+            TaskRepository::get_many(&self.primary, ids),
+            TaskRepository::get_many(&self.secondary, ids),
+        )
+        .await
+    }
+
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Task>, StorageError> {
+        self.read(
+            "task.list_by_mission",
+            TaskRepository::list_by_mission(&self.primary, mission_id, limit, offset),
+            TaskRepository::list_by_mission(&self.secondary, mission_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Task>, StorageError> {
+        self.read(
+            "task.list_updated_since",
+            TaskRepository::list_updated_since(&self.primary, tenant_id, after, limit),
+            TaskRepository::list_updated_since(&self.secondary, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn mission_progress(
+        &self,
+        mission_id: MissionId,
+    ) -> Result<MissionProgress, StorageError> {
+        self.read(
+            "task.mission_progress",
+            TaskRepository::mission_progress(&self.primary, mission_id),
+            TaskRepository::mission_progress(&self.secondary, mission_id),
+        )
+        .await
+    }
+
+    async fn list_with_dependencies(&self) -> Result<Vec<Task>, StorageError> {
+        self.read(
+            "task.list_with_dependencies",
+            TaskRepository::list_with_dependencies(&self.primary),
+            TaskRepository::list_with_dependencies(&self.secondary),
+        )
+        .await
+    }
+
+    async fn upsert(&self, task: Task) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| TaskRepository::upsert(&self.secondary, task.clone()));
+        self.write("task.upsert", TaskRepository::upsert(&self.primary, task), mirror).await
+    }
+
+    async fn upsert_many(&self, tasks: Vec<Task>) -> Result<(), StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| TaskRepository::upsert_many(&self.secondary, tasks.clone()));
+        self.write(
+            "task.upsert_many",
+            TaskRepository::upsert_many(&self.primary, tasks),
+            mirror,
+        )
+        .await
+    }
+
+    async fn insert_or_update(&self, task: Task) -> Result<Option<UpsertOutcome>, StorageError> {
+        let mirrored = self.dual_write.then(|| task.clone());
+        let outcome = TaskRepository::insert_or_update(&self.primary, task).await?;
+        if let (Some(mut task), Some(outcome)) = (mirrored, outcome) {
+            task.created_at_ms = outcome.created_at_ms;
+            self.mirror(
+                "task.insert_or_update",
+                TaskRepository::upsert(&self.secondary, task),
+            )
+            .await;
+        }
+        Ok(outcome)
+    }
+
+    async fn delete(&self, id: TaskId) -> Result<(), StorageError> {
+        let mirror = self.dual_write.then(|| TaskRepository::delete(&self.secondary, id));
+        self.write("task.delete", TaskRepository::delete(&self.primary, id), mirror).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        let mirror = self
+            .dual_write
+            .then(|| TaskRepository::delete_all_by_tenant(&self.secondary, tenant_id));
+        self.write(
+            "task.delete_all_by_tenant",
+            TaskRepository::delete_all_by_tenant(&self.primary, tenant_id),
+            mirror,
+        )
+        .await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MemoryMissions, mission};
+
+    #[tokio::test]
+    async fn only_unreachable_primaries_fail_over_to_the_secondary() {
+        let tenant_id = TenantId::new();
+        let replica = mission(tenant_id, "Harbor watch");
+
+        let store = FallbackStore::new(
+            MemoryMissions::failing(StorageErrorKind::Connection),
+            MemoryMissions::holding([replica.clone()]),
+        );
+        let found = MissionRepository::get(&store, replica.id).await.unwrap();
+        assert_eq!(found.map(|found| found.name), Some(replica.name.clone()));
+        let listed = MissionRepository::list_by_tenant(&store, tenant_id, 10, 0).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(store.secondary().calls(), 2);
+
+        for kind in [StorageErrorKind::Backend, StorageErrorKind::Timeout] {
+            let store = FallbackStore::new(
+                MemoryMissions::failing(kind),
+                MemoryMissions::holding([replica.clone()]),
+            );
+            let err = MissionRepository::get(&store, replica.id).await.unwrap_err();
+            assert_eq!(err.kind, kind);
+            assert_eq!(store.secondary().calls(), 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_go_to_the_primary_and_mirror_failures_are_ignored() {
+        let tenant_id = TenantId::new();
+        let first = mission(tenant_id, "Bridge survey");
+        let store = FallbackStore::new(MemoryMissions::default(), MemoryMissions::default());
+        MissionRepository::upsert(&store, first.clone()).await.unwrap();
+        assert!(store.primary().stored(first.id).is_some());
+        assert_eq!(store.secondary().calls(), 0);
+
+        let store = FallbackStore::new(
+            MemoryMissions::default(),
+            MemoryMissions::failing(StorageErrorKind::Connection),
+        )
+        .with_dual_write(true);
+        MissionRepository::upsert(&store, first.clone()).await.unwrap();
+        assert!(store.primary().stored(first.id).is_some());
+        assert_eq!(store.secondary().calls(), 1);
+
+        let store = FallbackStore::new(
+            MemoryMissions::failing(StorageErrorKind::Connection),
+            MemoryMissions::default(),
+        )
+        .with_dual_write(true);
+        assert!(MissionRepository::upsert(&store, first.clone()).await.is_err());
+        assert_eq!(store.secondary().calls(), 0);
+    }
+}
@@ -1,21 +1,83 @@
 use async_trait::async_trait;
 use c2_core::{
-    Asset, AssetId, Capability, CapabilityId, Incident, IncidentId, Mission, MissionId, Task,
-    TaskId, Team, TeamId, TenantId, Unit, UnitId,
+    ApiKeyId, Asset, AssetId, AssetStatus, AssetStatusEvent, AssignmentId, Capability,
+    CapabilityCode, CapabilityId, EpochMillis, Geofence, GeofenceId, GroupCount, Incident,
+    IncidentId, IncidentStatus, IncidentType, Mission, MissionAssignment, MissionId,
+    MissionProgress, MissionStatus, OperationalPriority, ReadinessState, SecurityClassification,
+    Task, TaskId, Team, TeamId, TenantId, Unit, UnitId,
 };
+use c2_geo::BoundingBox;
+use c2_identity::ApiKey;
+use std::collections::HashSet;
 use std::fmt;
+use uuid::Uuid;
+
+mod capability_cache;
+mod dual_write;
+mod fallback;
+#[cfg(test)]
+mod testing;
+mod timeout;
+
+pub use capability_cache::{CapabilityCache, CapabilityCatalog};
+pub use dual_write::{BackfillReport, DualWriteStore, ReadSide};
+pub use fallback::FallbackStore;
+pub use timeout::TimeoutStore;
+
+/// Names a store in the `backend` label of storage metrics. Stores that wrap
+/// another backend report their own name.
+pub trait StorageBackend {
+    const NAME: &'static str;
+}
+
+/// Broad cause of a [`StorageError`], for callers that answer a slow backend
+/// differently from a failing one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StorageErrorKind {
+    #[default]
+    Backend,
+    /// The operation did not finish within the [`TimeoutStore`] limit.
+    Timeout,
+    /// The backend could not be reached: refused or dropped connections, an
+    /// exhausted pool. [`FallbackStore`] retries reads elsewhere on these.
+    Connection,
+}
 
 #[derive(Debug, Clone)]
 pub struct StorageError {
+    pub kind: StorageErrorKind,
     pub message: String,
 }
 
 impl StorageError {
     pub fn new(message: impl Into<String>) -> Self {
         Self {
+            kind: StorageErrorKind::Backend,
             message: message.into(),
         }
     }
+
+    pub fn timeout(message: impl Into<String>) -> Self {
+        Self {
+            kind: StorageErrorKind::Timeout,
+            message: message.into(),
+        }
+    }
+
+    pub fn connection(message: impl Into<String>) -> Self {
+        Self {
+            kind: StorageErrorKind::Connection,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        self.kind == StorageErrorKind::Timeout
+    }
+
+    pub fn is_connection(&self) -> bool {
+        self.kind == StorageErrorKind::Connection
+    }
 }
 
 impl fmt::Display for StorageError {
@@ -26,93 +88,693 @@ impl fmt::Display for StorageError {
 
 impl std::error::Error for StorageError {}
 
+/// Result of a single-round-trip write. `created_at_ms` is the stored value,
+/// which an update keeps from the existing record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpsertOutcome {
+    pub inserted: bool,
+    pub created_at_ms: EpochMillis,
+}
+
+impl UpsertOutcome {
+    fn resolve(existing: Option<EpochMillis>, created_at_ms: EpochMillis) -> Self {
+        Self {
+            inserted: existing.is_none(),
+            created_at_ms: existing.unwrap_or(created_at_ms),
+        }
+    }
+}
+
+/// Where a change feed resumes: after the record `id` updated at
+/// `updated_at_ms`. Records updated in the same millisecond are ordered by
+/// id, so a page that ends inside a millisecond goes on with the rest of it.
+/// Without an id everything updated at `updated_at_ms` counts as seen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChangeCursor {
+    pub updated_at_ms: EpochMillis,
+    pub id: Option<Uuid>,
+}
+
+impl ChangeCursor {
+    pub fn since(updated_at_ms: EpochMillis) -> Self {
+        Self {
+            updated_at_ms,
+            id: None,
+        }
+    }
+
+    pub fn after(updated_at_ms: EpochMillis, id: Uuid) -> Self {
+        Self {
+            updated_at_ms,
+            id: Some(id),
+        }
+    }
+
+    /// The id records at `updated_at_ms` must sort after; with no id set it
+    /// is the largest UUID, which no record passes.
+    pub fn after_id(&self) -> Uuid {
+        self.id.unwrap_or(Uuid::from_u128(u128::MAX))
+    }
+}
+
+/// Narrows a mission listing; unset fields match everything. `search` is a
+/// case-insensitive substring of the name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MissionFilter {
+    pub status: Option<MissionStatus>,
+    pub priority: Option<OperationalPriority>,
+    pub classification: Option<SecurityClassification>,
+    pub search: Option<String>,
+}
+
+/// Like [`MissionFilter`]; `search` matches the asset name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetFilter {
+    pub status: Option<AssetStatus>,
+    pub classification: Option<SecurityClassification>,
+    pub search: Option<String>,
+}
+
+/// Like [`MissionFilter`]; `search` matches the incident summary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IncidentFilter {
+    pub status: Option<IncidentStatus>,
+    pub priority: Option<OperationalPriority>,
+    pub classification: Option<SecurityClassification>,
+    pub search: Option<String>,
+    pub sort: IncidentSort,
+}
+
+/// Order of an incident listing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IncidentSort {
+    #[default]
+    Newest,
+    /// Critical first, newest first within a priority.
+    Priority,
+}
+
 #[async_trait]
 pub trait MissionRepository: Send + Sync {
     async fn get(&self, id: MissionId) -> Result<Option<Mission>, StorageError>;
+    /// The records among `ids` that exist, in no particular order.
+    async fn get_many(&self, ids: &[MissionId]) -> Result<Vec<Mission>, StorageError>;
+    /// The tenant's missions, newest first. Archived missions are left out
+    /// here and in `list_filtered` and `search_by_tenant`.
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Mission>, StorageError>;
+    /// The tenant's archived missions, most recently archived first.
+    async fn list_archived_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError>;
+    /// Missions matching `filter`, newest first, like `list_by_tenant`.
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &MissionFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError>;
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError>;
+    /// Records changed after `after`, ordered by `updated_at_ms` then id.
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError>;
+    /// The tenant's missions counted per status and classification.
+    async fn count_by_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<MissionStatus>>, StorageError>;
     async fn upsert(&self, mission: Mission) -> Result<(), StorageError>;
+    /// Writes `missions` in as few round trips as the backend allows, for
+    /// bulk copies such as a backfill. Each id may appear once. The default
+    /// upserts them one at a time.
+    async fn upsert_many(&self, missions: Vec<Mission>) -> Result<(), StorageError> {
+        for mission in missions {
+            self.upsert(mission).await?;
+        }
+        Ok(())
+    }
+    /// Writes `mission` unless its id belongs to another tenant, in which case
+    /// nothing is stored and `None` is returned. Backends override this to
+    /// check and write in one round trip.
+    async fn insert_or_update(
+        &self,
+        mut mission: Mission,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let existing = match self.get(mission.id).await? {
+            Some(existing) if existing.tenant_id != mission.tenant_id => return Ok(None),
+            Some(existing) => Some(existing.created_at_ms),
+            None => None,
+        };
+        let outcome = UpsertOutcome::resolve(existing, mission.created_at_ms);
+        mission.created_at_ms = outcome.created_at_ms;
+        self.upsert(mission).await?;
+        Ok(Some(outcome))
+    }
+    /// Marks the mission archived and returns it, or `None` when there is no
+    /// such mission. Archiving an archived mission keeps its first archive
+    /// time.
+    async fn archive(
+        &self,
+        id: MissionId,
+        now: EpochMillis,
+    ) -> Result<Option<Mission>, StorageError> {
+        let Some(mut mission) = self.get(id).await? else {
+            return Ok(None);
+        };
+        if mission.archived_at_ms.is_none() {
+            mission.archived_at_ms = Some(now);
+            mission.updated_at_ms = now;
+            self.upsert(mission.clone()).await?;
+        }
+        Ok(Some(mission))
+    }
+    /// Returns an archived mission to the default listings. See [`Self::archive`].
+    async fn unarchive(
+        &self,
+        id: MissionId,
+        now: EpochMillis,
+    ) -> Result<Option<Mission>, StorageError> {
+        let Some(mut mission) = self.get(id).await? else {
+            return Ok(None);
+        };
+        if mission.archived_at_ms.is_some() {
+            mission.archived_at_ms = None;
+            mission.updated_at_ms = now;
+            self.upsert(mission.clone()).await?;
+        }
+        Ok(Some(mission))
+    }
     async fn delete(&self, id: MissionId) -> Result<(), StorageError>;
+    /// Deletes every mission owned by `tenant_id` and returns how many were removed.
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
 }
 
 #[async_trait]
 pub trait AssetRepository: Send + Sync {
     async fn get(&self, id: AssetId) -> Result<Option<Asset>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[AssetId]) -> Result<Vec<Asset>, StorageError>;
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Asset>, StorageError>;
+    /// See [`MissionRepository::list_filtered`].
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &AssetFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError>;
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Asset>, StorageError>;
+    async fn list_status_history(
+        &self,
+        asset_id: AssetId,
+        from_ms: EpochMillis,
+        to_ms: EpochMillis,
+    ) -> Result<Vec<AssetStatusEvent>, StorageError>;
+    /// Assets across all tenants whose maintenance window start (when
+    /// scheduled) or end (when in progress) is at or before `now_ms`.
+    async fn list_maintenance_due(&self, now_ms: EpochMillis) -> Result<Vec<Asset>, StorageError>;
+    /// See [`MissionRepository::count_by_status`].
+    async fn count_by_readiness(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<ReadinessState>>, StorageError>;
+    /// Also appends an `AssetStatusEvent` when readiness, comms status or
+    /// maintenance state differ from the stored asset.
     async fn upsert(&self, asset: Asset) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, assets: Vec<Asset>) -> Result<(), StorageError> {
+        for asset in assets {
+            self.upsert(asset).await?;
+        }
+        Ok(())
+    }
+    /// See [`MissionRepository::insert_or_update`].
+    async fn insert_or_update(
+        &self,
+        mut asset: Asset,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let existing = match self.get(asset.id).await? {
+            Some(existing) if existing.tenant_id != asset.tenant_id => return Ok(None),
+            Some(existing) => Some(existing.created_at_ms),
+            None => None,
+        };
+        let outcome = UpsertOutcome::resolve(existing, asset.created_at_ms);
+        asset.created_at_ms = outcome.created_at_ms;
+        self.upsert(asset).await?;
+        Ok(Some(outcome))
+    }
     async fn delete(&self, id: AssetId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`]. The tenant's status
+    /// history goes with its assets but is not counted.
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
 }
 
 #[async_trait]
 pub trait UnitRepository: Send + Sync {
     async fn get(&self, id: UnitId) -> Result<Option<Unit>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[UnitId]) -> Result<Vec<Unit>, StorageError>;
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Unit>, StorageError>;
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Unit>, StorageError>;
     async fn upsert(&self, unit: Unit) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, units: Vec<Unit>) -> Result<(), StorageError> {
+        for unit in units {
+            self.upsert(unit).await?;
+        }
+        Ok(())
+    }
     async fn delete(&self, id: UnitId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
 }
 
 #[async_trait]
 pub trait TeamRepository: Send + Sync {
     async fn get(&self, id: TeamId) -> Result<Option<Team>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[TeamId]) -> Result<Vec<Team>, StorageError>;
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Team>, StorageError>;
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Team>, StorageError>;
     async fn upsert(&self, team: Team) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, teams: Vec<Team>) -> Result<(), StorageError> {
+        for team in teams {
+            self.upsert(team).await?;
+        }
+        Ok(())
+    }
     async fn delete(&self, id: TeamId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
 }
 
 #[async_trait]
 pub trait CapabilityRepository: Send + Sync {
     async fn get(&self, id: CapabilityId) -> Result<Option<Capability>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[CapabilityId]) -> Result<Vec<Capability>, StorageError>;
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Capability>, StorageError>;
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Capability>, StorageError>;
+    /// Distinct code/category/classification combinations, ordered by code.
+    async fn distinct_codes(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<CapabilityCode>, StorageError>;
     async fn upsert(&self, capability: Capability) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, capabilities: Vec<Capability>) -> Result<(), StorageError> {
+        for capability in capabilities {
+            self.upsert(capability).await?;
+        }
+        Ok(())
+    }
     async fn delete(&self, id: CapabilityId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
+}
+
+#[async_trait]
+pub trait GeofenceRepository: Send + Sync {
+    async fn get(&self, id: GeofenceId) -> Result<Option<Geofence>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[GeofenceId]) -> Result<Vec<Geofence>, StorageError>;
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Geofence>, StorageError>;
+    /// Active fences across all tenants, for background evaluation.
+    async fn list_active(&self) -> Result<Vec<Geofence>, StorageError>;
+    async fn upsert(&self, geofence: Geofence) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, geofences: Vec<Geofence>) -> Result<(), StorageError> {
+        for geofence in geofences {
+            self.upsert(geofence).await?;
+        }
+        Ok(())
+    }
+    async fn delete(&self, id: GeofenceId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
+}
+
+#[async_trait]
+pub trait AssignmentRepository: Send + Sync {
+    async fn get(&self, id: AssignmentId) -> Result<Option<MissionAssignment>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[AssignmentId]) -> Result<Vec<MissionAssignment>, StorageError>;
+    /// Assignments to one mission, in the order they were made.
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError>;
+    /// Assignments of one unit, newest first.
+    async fn list_by_unit(
+        &self,
+        unit_id: UnitId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError>;
+    async fn upsert(&self, assignment: MissionAssignment) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, assignments: Vec<MissionAssignment>) -> Result<(), StorageError> {
+        for assignment in assignments {
+            self.upsert(assignment).await?;
+        }
+        Ok(())
+    }
+    async fn delete(&self, id: AssignmentId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
+}
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn get(&self, id: ApiKeyId) -> Result<Option<ApiKey>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[ApiKeyId]) -> Result<Vec<ApiKey>, StorageError>;
+    /// Looks a key up by [`ApiKey::hashed_secret`], revoked or not.
+    async fn find_by_hash(&self, hashed_secret: &str) -> Result<Option<ApiKey>, StorageError>;
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ApiKey>, StorageError>;
+    async fn upsert(&self, key: ApiKey) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, keys: Vec<ApiKey>) -> Result<(), StorageError> {
+        for key in keys {
+            self.upsert(key).await?;
+        }
+        Ok(())
+    }
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
 }
 
 #[async_trait]
 pub trait IncidentRepository: Send + Sync {
     async fn get(&self, id: IncidentId) -> Result<Option<Incident>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[IncidentId]) -> Result<Vec<Incident>, StorageError>;
     async fn list_by_tenant(
         &self,
         tenant_id: TenantId,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Incident>, StorageError>;
+    /// Incidents matching `filter`, in `filter.sort` order.
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &IncidentFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError>;
+    /// Positioned incidents inside `bbox`, edges included, newest first.
+    /// Incidents without a position never match.
+    async fn list_in_bbox(
+        &self,
+        tenant_id: TenantId,
+        bbox: BoundingBox,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError>;
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError>;
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError>;
+    /// See [`MissionRepository::count_by_status`].
+    async fn count_by_type_and_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<(IncidentType, IncidentStatus)>>, StorageError>;
     async fn upsert(&self, incident: Incident) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, incidents: Vec<Incident>) -> Result<(), StorageError> {
+        for incident in incidents {
+            self.upsert(incident).await?;
+        }
+        Ok(())
+    }
+    /// See [`MissionRepository::insert_or_update`].
+    async fn insert_or_update(
+        &self,
+        mut incident: Incident,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let existing = match self.get(incident.id).await? {
+            Some(existing) if existing.tenant_id != incident.tenant_id => return Ok(None),
+            Some(existing) => Some(existing.created_at_ms),
+            None => None,
+        };
+        let outcome = UpsertOutcome::resolve(existing, incident.created_at_ms);
+        incident.created_at_ms = outcome.created_at_ms;
+        self.upsert(incident).await?;
+        Ok(Some(outcome))
+    }
     async fn delete(&self, id: IncidentId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
 }
 
 #[async_trait]
 pub trait TaskRepository: Send + Sync {
     async fn get(&self, id: TaskId) -> Result<Option<Task>, StorageError>;
+    /// See [`MissionRepository::get_many`].
+    async fn get_many(&self, ids: &[TaskId]) -> Result<Vec<Task>, StorageError>;
     async fn list_by_mission(
         &self,
         mission_id: MissionId,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Task>, StorageError>;
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Task>, StorageError>;
+    /// Task counts of one mission, for its progress and completion check.
+    async fn mission_progress(
+        &self,
+        mission_id: MissionId,
+    ) -> Result<MissionProgress, StorageError>;
+    /// Tasks across all tenants that have dependencies and are neither
+    /// completed nor cancelled.
+    async fn list_with_dependencies(&self) -> Result<Vec<Task>, StorageError>;
     async fn upsert(&self, task: Task) -> Result<(), StorageError>;
+    /// See [`MissionRepository::upsert_many`].
+    async fn upsert_many(&self, tasks: Vec<Task>) -> Result<(), StorageError> {
+        for task in tasks {
+            self.upsert(task).await?;
+        }
+        Ok(())
+    }
+    /// See [`MissionRepository::insert_or_update`].
+    async fn insert_or_update(
+        &self,
+        mut task: Task,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        let existing = match self.get(task.id).await? {
+            Some(existing) if existing.tenant_id != task.tenant_id => return Ok(None),
+            Some(existing) => Some(existing.created_at_ms),
+            None => None,
+        };
+        let outcome = UpsertOutcome::resolve(existing, task.created_at_ms);
+        task.created_at_ms = outcome.created_at_ms;
+        self.upsert(task).await?;
+        Ok(Some(outcome))
+    }
     async fn delete(&self, id: TaskId) -> Result<(), StorageError>;
+    /// See [`MissionRepository::delete_all_by_tenant`].
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError>;
+}
+
+/// Rows removed by [`delete_tenant_data`], per entity type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantDeletion {
+    pub assignments: u64,
+    pub tasks: u64,
+    pub missions: u64,
+    pub incidents: u64,
+    pub geofences: u64,
+    pub assets: u64,
+    pub capabilities: u64,
+    pub teams: u64,
+    pub units: u64,
+    pub api_keys: u64,
+}
+
+impl TenantDeletion {
+    pub fn total(&self) -> u64 {
+        self.assignments
+            + self.tasks
+            + self.missions
+            + self.incidents
+            + self.geofences
+            + self.assets
+            + self.capabilities
+            + self.teams
+            + self.units
+            + self.api_keys
+    }
+}
+
+/// Why [`check_task_dependencies`] refused a task's `depends_on`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskDependencyConflict {
+    /// The task lists itself.
+    SelfReference,
+    /// No task with this id exists in the task's tenant.
+    Unknown(TaskId),
+    /// This dependency already depends, directly or not, on the task.
+    Cycle(TaskId),
+}
+
+impl fmt::Display for TaskDependencyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SelfReference => write!(f, "task cannot depend on itself"),
+            Self::Unknown(id) => write!(f, "dependency {id} not found"),
+            Self::Cycle(id) => write!(f, "dependency {id} would create a cycle"),
+        }
+    }
+}
+
+/// Checks `task.depends_on` against the stored tasks before an upsert. Stored
+/// dependencies were checked when they were written, so only a path back to
+/// `task` itself can close a cycle; dependencies deleted since then are
+/// skipped.
+pub async fn check_task_dependencies<S>(
+    store: &S,
+    task: &Task,
+) -> Result<Option<TaskDependencyConflict>, StorageError>
+where
+    S: TaskRepository,
+{
+    if task.depends_on.contains(&task.id) {
+        return Ok(Some(TaskDependencyConflict::SelfReference));
+    }
+    let mut visited = HashSet::new();
+    for &dependency_id in &task.depends_on {
+        let dependency = match store.get(dependency_id).await? {
+            Some(dependency) if dependency.tenant_id == task.tenant_id => dependency,
+            _ => return Ok(Some(TaskDependencyConflict::Unknown(dependency_id))),
+        };
+        let mut pending = dependency.depends_on;
+        while let Some(next_id) = pending.pop() {
+            if next_id == task.id {
+                return Ok(Some(TaskDependencyConflict::Cycle(dependency_id)));
+            }
+            if !visited.insert(next_id) {
+                continue;
+            }
+            if let Some(next) = store.get(next_id).await? {
+                pending.extend(next.depends_on);
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Deletes everything owned by `tenant_id` in `store`, for offboarding and
+/// test teardown. Dependents go before what they reference, so a failure part
+/// way leaves no task pointing at a deleted mission. Not atomic across types.
+pub async fn delete_tenant_data<S>(
+    store: &S,
+    tenant_id: TenantId,
+) -> Result<TenantDeletion, StorageError>
+where
+    S: MissionRepository
+        + AssetRepository
+        + UnitRepository
+        + TeamRepository
+        + CapabilityRepository
+        + GeofenceRepository
+        + ApiKeyRepository
+        + IncidentRepository
+        + TaskRepository
+        + AssignmentRepository,
+{
+    Ok(TenantDeletion {
+        assignments: AssignmentRepository::delete_all_by_tenant(store, tenant_id).await?,
+        tasks: TaskRepository::delete_all_by_tenant(store, tenant_id).await?,
+        missions: MissionRepository::delete_all_by_tenant(store, tenant_id).await?,
+        incidents: IncidentRepository::delete_all_by_tenant(store, tenant_id).await?,
+        geofences: GeofenceRepository::delete_all_by_tenant(store, tenant_id).await?,
+        assets: AssetRepository::delete_all_by_tenant(store, tenant_id).await?,
+        capabilities: CapabilityRepository::delete_all_by_tenant(store, tenant_id).await?,
+        teams: TeamRepository::delete_all_by_tenant(store, tenant_id).await?,
+        units: UnitRepository::delete_all_by_tenant(store, tenant_id).await?,
+        api_keys: ApiKeyRepository::delete_all_by_tenant(store, tenant_id).await?,
+    })
 }
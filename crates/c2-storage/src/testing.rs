@@ -0,0 +1,214 @@
+//! An in-memory mission store for the tests of the wrapping stores. It can
+//! be told to fail or stall every call, and counts the calls it gets.
+
+use async_trait::async_trait;
+use c2_core::{
+    GroupCount, Mission, MissionId, MissionStatus, OperationalPriority, SecurityClassification,
+    TenantId,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use crate::{
+    ChangeCursor, MissionFilter, MissionRepository, StorageBackend, StorageError,
+    StorageErrorKind, UpsertOutcome,
+};
+
+#[derive(Default)]
+pub struct MemoryMissions {
+    missions: Mutex<HashMap<MissionId, Mission>>,
+    failure: Option<StorageErrorKind>,
+    delay: Duration,
+    calls: AtomicUsize,
+}
+
+impl MemoryMissions {
+    /// Fails every call with an error of `kind`.
+    pub fn failing(kind: StorageErrorKind) -> Self {
+        Self {
+            failure: Some(kind),
+            ..Self::default()
+        }
+    }
+
+    /// Waits `delay` before answering each call.
+    pub fn slow(delay: Duration) -> Self {
+        Self {
+            delay,
+            ..Self::default()
+        }
+    }
+
+    pub fn holding(missions: impl IntoIterator<Item = Mission>) -> Self {
+        let missions = missions.into_iter().map(|mission| (mission.id, mission)).collect();
+        Self {
+            missions: Mutex::new(missions),
+            ..Self::default()
+        }
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    pub fn stored(&self, id: MissionId) -> Option<Mission> {
+        self.missions.lock().unwrap().get(&id).cloned()
+    }
+
+    async fn enter(&self) -> Result<(), StorageError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        match self.failure {
+            Some(kind) => Err(StorageError {
+                kind,
+                message: "memory store failure".to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The tenant's missions with the given archive state, newest first.
+    fn listed(&self, tenant_id: TenantId, archived: bool) -> Vec<Mission> {
+        let mut missions: Vec<Mission> = self
+            .missions
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|mission| mission.tenant_id == tenant_id)
+            .filter(|mission| mission.archived_at_ms.is_some() == archived)
+            .cloned()
+            .collect();
+        missions.sort_by_key(|mission| std::cmp::Reverse(mission.created_at_ms));
+        missions
+    }
+}
+
+pub fn mission(tenant_id: TenantId, name: &str) -> Mission {
+    Mission {
+        id: MissionId::new(),
+        tenant_id,
+        name: name.to_string(),
+        status: MissionStatus::Planned,
+        priority: OperationalPriority::Routine,
+        classification: SecurityClassification::Unclassified,
+        created_at_ms: 1,
+        updated_at_ms: 1,
+        archived_at_ms: None,
+    }
+}
+
+impl StorageBackend for MemoryMissions {
+    const NAME: &'static str = "memory";
+}
+
+/// Filters, search and counts are not modelled; they list or count nothing.
+#[async_trait]
+impl MissionRepository for MemoryMissions {
+    async fn get(&self, id: MissionId) -> Result<Option<Mission>, StorageError> {
+        self.enter().await?;
+        Ok(self.stored(id))
+    }
+
+    async fn get_many(&self, ids: &[MissionId]) -> Result<Vec<Mission>, StorageError> {
+        self.enter().await?;
+        Ok(ids.iter().filter_map(|id| self.stored(*id)).collect())
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.enter().await?;
+        Ok(self.listed(tenant_id, false).into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn list_archived_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.enter().await?;
+        Ok(self.listed(tenant_id, true).into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn list_filtered(
+        &self,
+        _tenant_id: TenantId,
+        _filter: &MissionFilter,
+        _limit: usize,
+        _offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.enter().await?;
+        Ok(Vec::new())
+    }
+
+    async fn search_by_tenant(
+        &self,
+        _tenant_id: TenantId,
+        _query: &str,
+        _limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.enter().await?;
+        Ok(Vec::new())
+    }
+
+    async fn list_updated_since(
+        &self,
+        _tenant_id: TenantId,
+        _after: ChangeCursor,
+        _limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.enter().await?;
+        Ok(Vec::new())
+    }
+
+    async fn count_by_status(
+        &self,
+        _tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<MissionStatus>>, StorageError> {
+        self.enter().await?;
+        Ok(Vec::new())
+    }
+
+    async fn upsert(&self, mission: Mission) -> Result<(), StorageError> {
+        self.enter().await?;
+        self.missions.lock().unwrap().insert(mission.id, mission);
+        Ok(())
+    }
+
+    /// One call, like a backend that checks and writes in a single round trip.
+    async fn insert_or_update(
+        &self,
+        mut mission: Mission,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        self.enter().await?;
+        let mut missions = self.missions.lock().unwrap();
+        let existing = match missions.get(&mission.id) {
+            Some(existing) if existing.tenant_id != mission.tenant_id => return Ok(None),
+            existing => existing.map(|existing| existing.created_at_ms),
+        };
+        let outcome = UpsertOutcome::resolve(existing, mission.created_at_ms);
+        mission.created_at_ms = outcome.created_at_ms;
+        missions.insert(mission.id, mission);
+        Ok(Some(outcome))
+    }
+
+    async fn delete(&self, id: MissionId) -> Result<(), StorageError> {
+        self.enter().await?;
+        self.missions.lock().unwrap().remove(&id);
+        Ok(())
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.enter().await?;
+        let mut missions = self.missions.lock().unwrap();
+        let before = missions.len();
+        missions.retain(|_, mission| mission.tenant_id != tenant_id);
+        Ok((before - missions.len()) as u64)
+    }
+}
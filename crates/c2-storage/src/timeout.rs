@@ -0,0 +1,989 @@
+//! [`TimeoutStore`] bounds every repository call on a wrapped store, so a hung
+//! backend connection surfaces as a prompt timeout error instead of a request
+//! that never returns. It also records each call's duration, labelled with
+//! the backend, entity and operation:
+//!
+//! - `c2_storage_operation_duration_seconds`, a bucketed histogram for
+//!   aggregating across instances with `histogram_quantile`;
+//! - `c2_storage_operation_latency_seconds`, the same samples rendered as a
+//!   summary with per-instance quantiles (p95 among them) over the last minute.
+
+use async_trait::async_trait;
+use c2_core::{
+    ApiKeyId, Asset, AssetId, AssetStatusEvent, AssignmentId, Capability, CapabilityCode,
+    CapabilityId, EpochMillis, Geofence, GeofenceId, GroupCount, Incident, IncidentId,
+    IncidentStatus, IncidentType, Mission, MissionAssignment, MissionId, MissionProgress,
+    MissionStatus, ReadinessState, Task, TaskId, Team, TeamId, TenantId, Unit, UnitId,
+};
+use c2_geo::BoundingBox;
+use c2_identity::ApiKey;
+use metrics::histogram;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use crate::{
+    ApiKeyRepository, AssetFilter, AssetRepository, AssignmentRepository, CapabilityRepository,
+    ChangeCursor, GeofenceRepository, IncidentFilter, IncidentRepository, MissionFilter,
+    MissionRepository, StorageBackend, StorageError, TaskRepository, TeamRepository, UnitRepository,
+    UpsertOutcome,
+};
+
+const OPERATION_DURATION_SECONDS: &str = "c2_storage_operation_duration_seconds";
+/// Deliberately not matching the exporter's `_duration_seconds` bucket rule,
+/// so it renders as a summary.
+const OPERATION_LATENCY_SECONDS: &str = "c2_storage_operation_latency_seconds";
+
+#[derive(Debug, Clone)]
+pub struct TimeoutStore<S> {
+    inner: S,
+    timeout: Duration,
+    backend: &'static str,
+}
+
+impl<S> TimeoutStore<S> {
+    pub fn new(inner: S, timeout: Duration) -> Self
+    where
+        S: StorageBackend,
+    {
+        Self {
+            inner,
+            timeout,
+            backend: S::NAME,
+        }
+    }
+
+    /// The wrapped store, for calls outside the repository traits (health
+    /// checks, migrations). These are not bounded.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// The same bounds around a view of the wrapped store, such as one
+    /// restricted to a caller's clearance.
+    pub fn scoped(&self, scope: impl FnOnce(&S) -> S) -> Self {
+        Self {
+            inner: scope(&self.inner),
+            timeout: self.timeout,
+            backend: self.backend,
+        }
+    }
+
+    /// Drops `call` once the timeout expires; the backend may still finish
+    /// the operation, so a timed-out write can have been applied. The
+    /// recorded duration covers failed and timed-out calls too.
+    async fn run<T>(
+        &self,
+        operation: &'static str,
+        call: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        let started = Instant::now();
+        let result = tokio::time::timeout(self.timeout, call)
+            .await
+            .unwrap_or_else(|_| {
+                Err(StorageError::timeout(format!(
+                    "{operation} timed out after {}ms",
+                    self.timeout.as_millis()
+                )))
+            });
+        let elapsed = started.elapsed().as_secs_f64();
+        let (entity, action) = operation.split_once('.').unwrap_or(("store", operation));
+        let labels = [
+            ("backend", self.backend),
+            ("entity", entity),
+            ("operation", action),
+        ];
+        histogram!(OPERATION_DURATION_SECONDS, &labels).record(elapsed);
+        histogram!(OPERATION_LATENCY_SECONDS, &labels).record(elapsed);
+        result
+    }
+}
+
+#[async_trait]
+impl<S: MissionRepository> MissionRepository for TimeoutStore<S> {
+    async fn get(&self, id: MissionId) -> Result<Option<Mission>, StorageError> {
+        self.run("mission.get", MissionRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[MissionId]) -> Result<Vec<Mission>, StorageError> {
+        self.run("mission.get_many", MissionRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.run(
+            "mission.list_by_tenant",
+            MissionRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_archived_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.run(
+            "mission.list_archived_by_tenant",
+            MissionRepository::list_archived_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &MissionFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.run(
+            "mission.list_filtered",
+            MissionRepository::list_filtered(&self.inner, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.run(
+            "mission.search_by_tenant",
+            MissionRepository::search_by_tenant(&self.inner, tenant_id, query, limit),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Mission>, StorageError> {
+        self.run(
+            "mission.list_updated_since",
+            MissionRepository::list_updated_since(&self.inner, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn count_by_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<MissionStatus>>, StorageError> {
+        self.run(
+            "mission.count_by_status",
+            MissionRepository::count_by_status(&self.inner, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, mission: Mission) -> Result<(), StorageError> {
+        self.run("mission.upsert", MissionRepository::upsert(&self.inner, mission)).await
+    }
+
+    async fn upsert_many(&self, missions: Vec<Mission>) -> Result<(), StorageError> {
+        self.run("mission.upsert_many", MissionRepository::upsert_many(&self.inner, missions)).await
+    }
+
+    async fn insert_or_update(
+        &self,
+        mission: Mission,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        self.run(
+            "mission.insert_or_update",
+            MissionRepository::insert_or_update(&self.inner, mission),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: MissionId) -> Result<(), StorageError> {
+        self.run("mission.delete", MissionRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "mission.delete_all_by_tenant",
+            MissionRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: AssetRepository> AssetRepository for TimeoutStore<S> {
+    async fn get(&self, id: AssetId) -> Result<Option<Asset>, StorageError> {
+        self.run("asset.get", AssetRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[AssetId]) -> Result<Vec<Asset>, StorageError> {
+        self.run("asset.get_many", AssetRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.run(
+            "asset.list_by_tenant",
+            AssetRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &AssetFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.run(
+            "asset.list_filtered",
+            AssetRepository::list_filtered(&self.inner, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Asset>, StorageError> {
+        self.run(
+            "asset.list_updated_since",
+            AssetRepository::list_updated_since(&self.inner, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn list_status_history(
+        &self,
+        asset_id: AssetId,
+        from_ms: EpochMillis,
+        to_ms: EpochMillis,
+    ) -> Result<Vec<AssetStatusEvent>, StorageError> {
+        self.run(
+            "asset.list_status_history",
+            AssetRepository::list_status_history(&self.inner, asset_id, from_ms, to_ms),
+        )
+        .await
+    }
+
+    async fn list_maintenance_due(&self, now_ms: EpochMillis) -> Result<Vec<Asset>, StorageError> {
+        self.run(
+            "asset.list_maintenance_due",
+            AssetRepository::list_maintenance_due(&self.inner, now_ms),
+        )
+        .await
+    }
+
+    async fn count_by_readiness(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<ReadinessState>>, StorageError> {
+        self.run(
+            "asset.count_by_readiness",
+            AssetRepository::count_by_readiness(&self.inner, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, asset: Asset) -> Result<(), StorageError> {
+        self.run("asset.upsert", AssetRepository::upsert(&self.inner, asset)).await
+    }
+
+    async fn upsert_many(&self, assets: Vec<Asset>) -> Result<(), StorageError> {
+        self.run("asset.upsert_many", AssetRepository::upsert_many(&self.inner, assets)).await
+    }
+
+    async fn insert_or_update(&self, asset: Asset) -> Result<Option<UpsertOutcome>, StorageError> {
+        self.run(
+            "asset.insert_or_update",
+            AssetRepository::insert_or_update(&self.inner, asset),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: AssetId) -> Result<(), StorageError> {
+        self.run("asset.delete", AssetRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "asset.delete_all_by_tenant",
+            AssetRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: UnitRepository> UnitRepository for TimeoutStore<S> {
+    async fn get(&self, id: UnitId) -> Result<Option<Unit>, StorageError> {
+        self.run("unit.get", UnitRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[UnitId]) -> Result<Vec<Unit>, StorageError> {
+        self.run("unit.get_many", UnitRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        self.run(
+            "unit.list_by_tenant",
+            UnitRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Unit>, StorageError> {
+        self.run(
+            "unit.list_updated_since",
+            UnitRepository::list_updated_since(&self.inner, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn upsert(&self, unit: Unit) -> Result<(), StorageError> {
+        self.run("unit.upsert", UnitRepository::upsert(&self.inner, unit)).await
+    }
+
+    async fn upsert_many(&self, units: Vec<Unit>) -> Result<(), StorageError> {
+        self.run("unit.upsert_many", UnitRepository::upsert_many(&self.inner, units)).await
+    }
+
+    async fn delete(&self, id: UnitId) -> Result<(), StorageError> {
+        self.run("unit.delete", UnitRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "unit.delete_all_by_tenant",
+            UnitRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: TeamRepository> TeamRepository for TimeoutStore<S> {
+    async fn get(&self, id: TeamId) -> Result<Option<Team>, StorageError> {
+        self.run("team.get", TeamRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[TeamId]) -> Result<Vec<Team>, StorageError> {
+        self.run("team.get_many", TeamRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        self.run(
+            "team.list_by_tenant",
+            TeamRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Team>, StorageError> {
+        self.run(
+            "team.list_updated_since",
+            TeamRepository::list_updated_since(&self.inner, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn upsert(&self, team: Team) -> Result<(), StorageError> {
+        self.run("team.upsert", TeamRepository::upsert(&self.inner, team)).await
+    }
+
+    async fn upsert_many(&self, teams: Vec<Team>) -> Result<(), StorageError> {
+        self.run("team.upsert_many", TeamRepository::upsert_many(&self.inner, teams)).await
+    }
+
+    async fn delete(&self, id: TeamId) -> Result<(), StorageError> {
+        self.run("team.delete", TeamRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "team.delete_all_by_tenant",
+            TeamRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: CapabilityRepository> CapabilityRepository for TimeoutStore<S> {
+    async fn get(&self, id: CapabilityId) -> Result<Option<Capability>, StorageError> {
+        self.run("capability.get", CapabilityRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[CapabilityId]) -> Result<Vec<Capability>, StorageError> {
+        self.run("capability.get_many", CapabilityRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Capability>, StorageError> {
+        self.run(
+            "capability.list_by_tenant",
+            CapabilityRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Capability>, StorageError> {
+        self.run(
+            "capability.list_updated_since",
+            CapabilityRepository::list_updated_since(&self.inner, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn distinct_codes(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<CapabilityCode>, StorageError> {
+        self.run(
+            "capability.distinct_codes",
+            CapabilityRepository::distinct_codes(&self.inner, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, capability: Capability) -> Result<(), StorageError> {
+        self.run("capability.upsert", CapabilityRepository::upsert(&self.inner, capability)).await
+    }
+
+    async fn upsert_many(&self, capabilities: Vec<Capability>) -> Result<(), StorageError> {
+        self.run(
+            "capability.upsert_many",
+            CapabilityRepository::upsert_many(&self.inner, capabilities),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: CapabilityId) -> Result<(), StorageError> {
+        self.run("capability.delete", CapabilityRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "capability.delete_all_by_tenant",
+            CapabilityRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: GeofenceRepository> GeofenceRepository for TimeoutStore<S> {
+    async fn get(&self, id: GeofenceId) -> Result<Option<Geofence>, StorageError> {
+        self.run("geofence.get", GeofenceRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[GeofenceId]) -> Result<Vec<Geofence>, StorageError> {
+        self.run("geofence.get_many", GeofenceRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Geofence>, StorageError> {
+        self.run(
+            "geofence.list_by_tenant",
+            GeofenceRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_active(&self) -> Result<Vec<Geofence>, StorageError> {
+        self.run("geofence.list_active", GeofenceRepository::list_active(&self.inner)).await
+    }
+
+    async fn upsert(&self, geofence: Geofence) -> Result<(), StorageError> {
+        self.run("geofence.upsert", GeofenceRepository::upsert(&self.inner, geofence)).await
+    }
+
+    async fn upsert_many(&self, geofences: Vec<Geofence>) -> Result<(), StorageError> {
+        self.run(
+            "geofence.upsert_many",
+            GeofenceRepository::upsert_many(&self.inner, geofences),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: GeofenceId) -> Result<(), StorageError> {
+        self.run("geofence.delete", GeofenceRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "geofence.delete_all_by_tenant",
+            GeofenceRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: AssignmentRepository> AssignmentRepository for TimeoutStore<S> {
+    async fn get(&self, id: AssignmentId) -> Result<Option<MissionAssignment>, StorageError> {
+        self.run("assignment.get", AssignmentRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[AssignmentId]) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.run("assignment.get_many", AssignmentRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.run(
+            "assignment.list_by_mission",
+            AssignmentRepository::list_by_mission(&self.inner, mission_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_by_unit(
+        &self,
+        unit_id: UnitId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MissionAssignment>, StorageError> {
+        self.run(
+            "assignment.list_by_unit",
+            AssignmentRepository::list_by_unit(&self.inner, unit_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn upsert(&self, assignment: MissionAssignment) -> Result<(), StorageError> {
+        self.run("assignment.upsert", AssignmentRepository::upsert(&self.inner, assignment)).await
+    }
+
+    async fn upsert_many(&self, assignments: Vec<MissionAssignment>) -> Result<(), StorageError> {
+        self.run(
+            "assignment.upsert_many",
+            AssignmentRepository::upsert_many(&self.inner, assignments),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: AssignmentId) -> Result<(), StorageError> {
+        self.run("assignment.delete", AssignmentRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "assignment.delete_all_by_tenant",
+            AssignmentRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: ApiKeyRepository> ApiKeyRepository for TimeoutStore<S> {
+    async fn get(&self, id: ApiKeyId) -> Result<Option<ApiKey>, StorageError> {
+        self.run("api_key.get", ApiKeyRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[ApiKeyId]) -> Result<Vec<ApiKey>, StorageError> {
+        self.run("api_key.get_many", ApiKeyRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn find_by_hash(&self, hashed_secret: &str) -> Result<Option<ApiKey>, StorageError> {
+        self.run(
+            "api_key.find_by_hash",
+            ApiKeyRepository::find_by_hash(&self.inner, hashed_secret),
+        )
+        .await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ApiKey>, StorageError> {
+        self.run(
+            "api_key.list_by_tenant",
+            ApiKeyRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn upsert(&self, key: ApiKey) -> Result<(), StorageError> {
+        self.run("api_key.upsert", ApiKeyRepository::upsert(&self.inner, key)).await
+    }
+
+    async fn upsert_many(&self, keys: Vec<ApiKey>) -> Result<(), StorageError> {
+        self.run("api_key.upsert_many", ApiKeyRepository::upsert_many(&self.inner, keys)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "api_key.delete_all_by_tenant",
+            ApiKeyRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: IncidentRepository> IncidentRepository for TimeoutStore<S> {
+    async fn get(&self, id: IncidentId) -> Result<Option<Incident>, StorageError> {
+        self.run("incident.get", IncidentRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[IncidentId]) -> Result<Vec<Incident>, StorageError> {
+        self.run("incident.get_many", IncidentRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.run(
+            "incident.list_by_tenant",
+            IncidentRepository::list_by_tenant(&self.inner, tenant_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_filtered(
+        &self,
+        tenant_id: TenantId,
+        filter: &IncidentFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.run(
+            "incident.list_filtered",
+            IncidentRepository::list_filtered(&self.inner, tenant_id, filter, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_in_bbox(
+        &self,
+        tenant_id: TenantId,
+        bbox: BoundingBox,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.run(
+            "incident.list_in_bbox",
+            IncidentRepository::list_in_bbox(&self.inner, tenant_id, bbox, limit),
+        )
+        .await
+    }
+
+    async fn search_by_tenant(
+        &self,
+        tenant_id: TenantId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.run(
+            "incident.search_by_tenant",
+            IncidentRepository::search_by_tenant(&self.inner, tenant_id, query, limit),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Incident>, StorageError> {
+        self.run(
+            "incident.list_updated_since",
+            IncidentRepository::list_updated_since(&self.inner, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn count_by_type_and_status(
+        &self,
+        tenant_id: TenantId,
+    ) -> Result<Vec<GroupCount<(IncidentType, IncidentStatus)>>, StorageError> {
+        self.run(
+            "incident.count_by_type_and_status",
+            IncidentRepository::count_by_type_and_status(&self.inner, tenant_id),
+        )
+        .await
+    }
+
+    async fn upsert(&self, incident: Incident) -> Result<(), StorageError> {
+        self.run("incident.upsert", IncidentRepository::upsert(&self.inner, incident)).await
+    }
+
+    async fn upsert_many(&self, incidents: Vec<Incident>) -> Result<(), StorageError> {
+        self.run(
+            "incident.upsert_many",
+            IncidentRepository::upsert_many(&self.inner, incidents),
+        )
+        .await
+    }
+
+    async fn insert_or_update(
+        &self,
+        incident: Incident,
+    ) -> Result<Option<UpsertOutcome>, StorageError> {
+        self.run(
+            "incident.insert_or_update",
+            IncidentRepository::insert_or_update(&self.inner, incident),
+        )
+        .await
+    }
+
+    async fn delete(&self, id: IncidentId) -> Result<(), StorageError> {
+        self.run("incident.delete", IncidentRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "incident.delete_all_by_tenant",
+            IncidentRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<S: TaskRepository> TaskRepository for TimeoutStore<S> {
+    async fn get(&self, id: TaskId) -> Result<Option<Task>, StorageError> {
+        self.run("task.get", TaskRepository::get(&self.inner, id)).await
+    }
+
+    async fn get_many(&self, ids: &[TaskId]) -> Result<Vec<Task>, StorageError> {
+        self.run("task.get_many", TaskRepository::get_many(&self.inner, ids)).await
+    }
+
+    async fn list_by_mission(
+        &self,
+        mission_id: MissionId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Task>, StorageError> {
+        self.run(
+            "task.list_by_mission",
+            TaskRepository::list_by_mission(&self.inner, mission_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn list_updated_since(
+        &self,
+        tenant_id: TenantId,
+        after: ChangeCursor,
+        limit: usize,
+    ) -> Result<Vec<Task>, StorageError> {
+        self.run(
+            "task.list_updated_since",
+            TaskRepository::list_updated_since(&self.inner, tenant_id, after, limit),
+        )
+        .await
+    }
+
+    async fn mission_progress(
+        &self,
+        mission_id: MissionId,
+    ) -> Result<MissionProgress, StorageError> {
+        self.run(
+            "task.mission_progress",
+            TaskRepository::mission_progress(&self.inner, mission_id),
+        )
+        .await
+    }
+
+    async fn list_with_dependencies(&self) -> Result<Vec<Task>, StorageError> {
+        self.run(
+            "task.list_with_dependencies",
+            TaskRepository::list_with_dependencies(&self.inner),
+        )
+        .await
+    }
+
+    async fn upsert(&self, task: Task) -> Result<(), StorageError> {
+        self.run("task.upsert", TaskRepository::upsert(&self.inner, task)).await
+    }
+
+    async fn upsert_many(&self, tasks: Vec<Task>) -> Result<(), StorageError> {
+        self.run("task.upsert_many", TaskRepository::upsert_many(&self.inner, tasks)).await
+    }
+
+    async fn insert_or_update(&self, task: Task) -> Result<Option<UpsertOutcome>, StorageError> {
+        self.run("task.insert_or_update", TaskRepository::insert_or_update(&self.inner, task)).await
+    }
+
+    async fn delete(&self, id: TaskId) -> Result<(), StorageError> {
+        self.run("task.delete", TaskRepository::delete(&self.inner, id)).await
+    }
+
+    async fn delete_all_by_tenant(&self, tenant_id: TenantId) -> Result<u64, StorageError> {
+        self.run(
+            "task.delete_all_by_tenant",
+            TaskRepository::delete_all_by_tenant(&self.inner, tenant_id),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MemoryMissions, mission};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    #[tokio::test]
+    async fn calls_slower_than_the_limit_fail_with_a_timeout() {
+        let tenant_id = TenantId::new();
+        let first = mission(tenant_id, "Harbor watch");
+        let store = TimeoutStore::new(
+            MemoryMissions::slow(Duration::from_millis(200)),
+            Duration::from_millis(20),
+        );
+        let err = MissionRepository::upsert(&store, first.clone()).await.unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(err.message, "mission.upsert timed out after 20ms");
+
+        let err = MissionRepository::get(&store, first.id).await.unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(store.inner().calls(), 2);
+
+        let store = TimeoutStore::new(
+            MemoryMissions::slow(Duration::from_millis(5)),
+            Duration::from_secs(5),
+        );
+        MissionRepository::upsert(&store, first.clone()).await.unwrap();
+        assert!(MissionRepository::get(&store, first.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn insert_or_update_reaches_the_store_in_one_call() {
+        let tenant_id = TenantId::new();
+        let store = TimeoutStore::new(MemoryMissions::default(), Duration::from_secs(5));
+        let created = mission(tenant_id, "Harbor watch");
+
+        let outcome = MissionRepository::insert_or_update(&store, created.clone()).await.unwrap();
+        assert_eq!(
+            outcome,
+            Some(UpsertOutcome {
+                inserted: true,
+                created_at_ms: created.created_at_ms,
+            })
+        );
+        assert_eq!(store.inner().calls(), 1);
+
+        let renamed = Mission {
+            name: "Harbor patrol".to_string(),
+            created_at_ms: 50,
+            ..created.clone()
+        };
+        let outcome = MissionRepository::insert_or_update(&store, renamed).await.unwrap();
+        assert_eq!(
+            outcome,
+            Some(UpsertOutcome {
+                inserted: false,
+                created_at_ms: created.created_at_ms,
+            })
+        );
+        assert_eq!(store.inner().calls(), 2);
+
+        let hijack = Mission {
+            tenant_id: TenantId::new(),
+            ..created.clone()
+        };
+        assert_eq!(MissionRepository::insert_or_update(&store, hijack).await.unwrap(), None);
+        assert_eq!(store.inner().stored(created.id).unwrap().name, "Harbor patrol");
+    }
+
+    #[tokio::test]
+    async fn every_call_records_one_duration_sample() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _local = metrics::set_default_local_recorder(&recorder);
+        let tenant_id = TenantId::new();
+        let store = TimeoutStore::new(MemoryMissions::default(), Duration::from_secs(5));
+        let watch = mission(tenant_id, "Harbor watch");
+        for _ in 0..3 {
+            MissionRepository::upsert(&store, watch.clone()).await.unwrap();
+        }
+        MissionRepository::get(&store, watch.id).await.unwrap();
+        MissionRepository::get(&store, MissionId::new()).await.unwrap();
+
+        // Taking a snapshot drains the histograms, so take only one.
+        let snapshot = snapshotter.snapshot().into_vec();
+        let samples = |operation: &str| {
+            snapshot.iter().find_map(|(key, _, _, value)| {
+                let key = key.key();
+                let labels: Vec<_> =
+                    key.labels().map(|label| (label.key(), label.value())).collect();
+                let expected = [("backend", "memory"), ("entity", "mission")];
+                let matches = key.name() == OPERATION_DURATION_SECONDS
+                    && labels.contains(&("operation", operation))
+                    && expected.iter().all(|label| labels.contains(label));
+                match value {
+                    DebugValue::Histogram(values) if matches => Some(values.len()),
+                    _ => None,
+                }
+            })
+        };
+        assert_eq!(samples("upsert"), Some(3));
+        assert_eq!(samples("get"), Some(2));
+        assert_eq!(samples("delete"), None);
+    }
+}
@@ -1,17 +1,101 @@
-use actix_web::{HttpRequest, HttpResponse};
-use c2_core::{SecurityClassification, TenantId, UserId};
-use c2_identity::{Permission, Role, Subject};
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpMessage, HttpRequest, HttpResponse};
+use c2_core::{now_epoch_millis, CorrelationId, SecurityClassification, TenantId, UserId};
+use c2_identity::{hash_api_key_secret, AuthContext, Permission, Role, Subject};
 use c2_policy::{
-    BasicPolicyEngine, PolicyContext, PolicyDecision, PolicyEngine, PolicyRequest,
-    ResourceDescriptor,
+    record_denial, BasicPolicyEngine, PolicyContext, PolicyDecision, PolicyEngine,
+    PolicyOutcome, PolicyRequest, ResourceDescriptor,
 };
+use c2_storage::ApiKeyRepository;
 use std::str::FromStr;
 use uuid::Uuid;
 
-use crate::routes::common::{bad_request, forbidden, unauthorized};
+use crate::routes::common::{
+    bad_request, forbidden, internal_error, storage_error, too_many_requests, unauthorized,
+};
+use crate::state::AppState;
+
+pub const API_KEY_HEADER: &str = "x-c2-api-key";
 
 pub struct AuthInfo {
     pub subject: Subject,
+    pub permissions: Vec<Permission>,
+}
+
+/// Resolves an `x-c2-api-key` header to the key's [`AuthContext`], which
+/// [`authorize_request`] then uses instead of the `x-c2-*` identity headers.
+/// Unknown and revoked keys are rejected here.
+pub async fn api_key_auth(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let Some(secret) = req
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+    else {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_left_body);
+    };
+    let Some(state) = req.app_data::<web::Data<AppState>>().cloned() else {
+        return Ok(req
+            .into_response(internal_error("app state missing"))
+            .map_into_right_body());
+    };
+
+    let hashed_secret = hash_api_key_secret(&secret);
+    match ApiKeyRepository::find_by_hash(&state.store, &hashed_secret).await {
+        Ok(Some(key)) if !key.revoked => {
+            req.extensions_mut()
+                .insert(key.auth_context(now_epoch_millis()));
+            next.call(req)
+                .await
+                .map(ServiceResponse::map_into_left_body)
+        }
+        Ok(_) => Ok(req
+            .into_response(unauthorized("invalid or revoked api key"))
+            .map_into_right_body()),
+        Err(err) => Ok(req
+            .into_response(storage_error(err))
+            .map_into_right_body()),
+    }
+}
+
+/// Applies the per-tenant [`TenantRateLimiter`](c2_policy::TenantRateLimiter)
+/// to the caller's tenant, taken from the API key resolved by
+/// [`api_key_auth`] or else the `x-c2-tenant-id` header. Requests without a
+/// tenant pass through and are rejected later by [`authorize_request`].
+pub async fn tenant_rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let tenant_id = match req.extensions().get::<AuthContext>() {
+        Some(context) => Some(context.subject.tenant_id),
+        None => req
+            .headers()
+            .get("x-c2-tenant-id")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| Uuid::parse_str(value.trim()).ok())
+            .map(TenantId::from_uuid),
+    };
+    let limited = match (tenant_id, req.app_data::<web::Data<AppState>>()) {
+        (Some(tenant_id), Some(state)) => state.rate_limiter.check(tenant_id).err(),
+        _ => None,
+    };
+    match limited {
+        Some(retry_after) => Ok(req
+            .into_response(too_many_requests(retry_after))
+            .map_into_right_body()),
+        None => next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_left_body),
+    }
 }
 
 pub fn authorize_request(
@@ -20,6 +104,50 @@ pub fn authorize_request(
     permission: Permission,
     classification: SecurityClassification,
 ) -> Result<AuthInfo, HttpResponse> {
+    let api_key_context = req.extensions().get::<AuthContext>().cloned();
+    let (subject, permissions) = match api_key_context {
+        Some(context) => (context.subject, context.permissions),
+        None => header_identity(req)?,
+    };
+
+    let request = PolicyRequest {
+        subject: subject.clone(),
+        action: permission,
+        resource: ResourceDescriptor {
+            // The route pattern rather than the path, so ids stay out of
+            // denial metrics.
+            resource_type: req.match_pattern().unwrap_or_else(|| req.path().to_string()),
+            resource_id: None,
+        },
+        classification,
+        context: PolicyContext {
+            tenant_id: subject.tenant_id,
+            mission_id: None,
+            incident_id: None,
+            tags: vec![],
+            correlation_id: req.extensions().get::<CorrelationId>().copied(),
+        },
+    };
+    if !permissions.contains(&permission) {
+        record_denial(
+            &request,
+            PolicyDecision::Deny,
+            &PolicyOutcome::missing_permission(permission),
+        );
+        return Err(forbidden("permission denied"));
+    }
+
+    match engine.authorize(&request) {
+        PolicyDecision::Permit => Ok(AuthInfo {
+            subject,
+            permissions,
+        }),
+        PolicyDecision::Deny => Err(forbidden("policy denied")),
+        PolicyDecision::Indeterminate => Err(unauthorized("policy indeterminate")),
+    }
+}
+
+fn header_identity(req: &HttpRequest) -> Result<(Subject, Vec<Permission>), HttpResponse> {
     let tenant_id = parse_uuid_header(req, "x-c2-tenant-id")?;
     let user_id = parse_uuid_header(req, "x-c2-user-id")?;
     let roles = parse_list_header(req, "x-c2-roles")?
@@ -41,38 +169,13 @@ pub fn authorize_request(
         return Err(unauthorized("missing roles or permissions"));
     }
 
-    if !permissions.contains(&permission) {
-        return Err(forbidden("permission denied"));
-    }
-
     let subject = Subject {
         tenant_id: TenantId::from_uuid(tenant_id),
         user_id: UserId::from_uuid(user_id),
         roles,
         clearance,
     };
-
-    let request = PolicyRequest {
-        subject: subject.clone(),
-        action: permission,
-        resource: ResourceDescriptor {
-            resource_type: req.path().to_string(),
-            resource_id: None,
-        },
-        classification,
-        context: PolicyContext {
-            tenant_id: subject.tenant_id,
-            mission_id: None,
-            incident_id: None,
-            tags: vec![],
-        },
-    };
-
-    match engine.evaluate(&request) {
-        PolicyDecision::Permit => Ok(AuthInfo { subject }),
-        PolicyDecision::Deny => Err(forbidden("policy denied")),
-        PolicyDecision::Indeterminate => Err(unauthorized("policy indeterminate")),
-    }
+    Ok((subject, permissions))
 }
 
 fn parse_uuid_header(req: &HttpRequest, name: &str) -> Result<Uuid, HttpResponse> {
@@ -95,3 +198,70 @@ fn header_value(req: &HttpRequest, name: &str) -> Option<String> {
         .and_then(|value| value.to_str().ok())
         .map(|value| value.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Caller;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn denials_before_and_inside_the_policy_engine_are_logged() {
+        let engine = BasicPolicyEngine::with_default_rules();
+        let tenant_id = TenantId::new();
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = Caller::new(tenant_id, SecurityClassification::Unclassified)
+                .get("/v1/missions")
+                .insert_header(("x-c2-permissions", "view_units"))
+                .to_http_request();
+            assert!(authorize_request(
+                &request,
+                &engine,
+                Permission::ViewMissions,
+                SecurityClassification::Unclassified,
+            )
+            .is_err());
+
+            let request = Caller::new(tenant_id, SecurityClassification::Unclassified)
+                .get("/v1/missions")
+                .to_http_request();
+            assert!(authorize_request(
+                &request,
+                &engine,
+                Permission::ViewMissions,
+                SecurityClassification::Secret,
+            )
+            .is_err());
+        });
+
+        let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let denials: Vec<&str> =
+            logs.lines().filter(|line| line.contains("authorization denied")).collect();
+        assert_eq!(denials.len(), 2, "{logs}");
+        assert!(denials[0].contains("subject does not hold permission ViewMissions"));
+        assert!(denials[1].contains("below resource classification Secret"));
+        assert!(denials.iter().all(|line| line.contains(&tenant_id.to_string())));
+    }
+}
@@ -0,0 +1,101 @@
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, ContentEncoding};
+use actix_web::middleware::Next;
+use actix_web::Error;
+
+/// Smallest response body worth compressing; registered as app data.
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionMinBytes(pub usize);
+
+/// Content types that are already compressed, or must reach the client one
+/// event at a time. `Compress` itself skips images and video.
+const SKIPPED_CONTENT_TYPES: [&str; 7] = [
+    "application/gzip",
+    "application/x-protobuf",
+    "application/vnd.mapbox-vector-tile",
+    "application/zip",
+    "audio/",
+    "font/woff",
+    "text/event-stream",
+];
+
+/// Runs inside `actix_web::middleware::Compress` and marks responses it should
+/// leave alone with `Content-Encoding: identity`: bodies under
+/// [`CompressionMinBytes`] and the [`SKIPPED_CONTENT_TYPES`].
+pub async fn skip_uncompressible(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let min_bytes = req
+        .app_data::<CompressionMinBytes>()
+        .map_or(0, |min_bytes| min_bytes.0);
+    let mut response = next.call(req).await?;
+    let small = match response.response().body().size() {
+        BodySize::Sized(size) => size < min_bytes as u64,
+        BodySize::None => true,
+        BodySize::Stream => false,
+    };
+    let skipped_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            let content_type = content_type.to_ascii_lowercase();
+            SKIPPED_CONTENT_TYPES
+                .iter()
+                .any(|skipped| content_type.starts_with(skipped))
+        });
+    if (small || skipped_type) && !response.headers().contains_key(header::CONTENT_ENCODING) {
+        response.headers_mut().insert(
+            header::CONTENT_ENCODING,
+            ContentEncoding::Identity.to_header_value(),
+        );
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{middleware, test, web, App, HttpResponse};
+
+    async fn missions(count: web::Path<usize>) -> HttpResponse {
+        let missions: Vec<_> = (0..count.into_inner())
+            .map(|index| serde_json::json!({ "name": format!("Mission {index}") }))
+            .collect();
+        HttpResponse::Ok().json(missions)
+    }
+
+    #[actix_web::test]
+    async fn large_json_is_gzipped_and_small_json_is_not() {
+        let app = test::init_service(
+            App::new()
+                .app_data(CompressionMinBytes(1_024))
+                .wrap(middleware::from_fn(skip_uncompressible))
+                .wrap(middleware::Compress::default())
+                .route("/missions/{count}", web::get().to(missions)),
+        )
+        .await;
+        let get = |count: usize| {
+            test::TestRequest::get()
+                .uri(&format!("/missions/{count}"))
+                .insert_header((header::ACCEPT_ENCODING, "gzip"))
+                .to_request()
+        };
+
+        let response = test::call_service(&app, get(500)).await;
+        assert_eq!(response.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+        let body = test::read_body(response).await;
+        let mut json = String::new();
+        std::io::Read::read_to_string(&mut flate2::read::GzDecoder::new(&body[..]), &mut json)
+            .unwrap();
+        let missions: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(missions.len(), 500);
+
+        let response = test::call_service(&app, get(1)).await;
+        assert_ne!(response.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+        let body = test::read_body(response).await;
+        assert_eq!(&body[..], br#"[{"name":"Mission 0"}]"#);
+    }
+}
@@ -0,0 +1,97 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpMessage};
+use c2_config::DEFAULT_CORRELATION_HEADER;
+use c2_core::CorrelationId;
+use c2_messaging::correlation;
+use tracing::Instrument;
+
+use crate::state::AppState;
+
+/// Takes the request's correlation id from the configured header, or makes
+/// one up, and keeps it for the rest of the request: in the request
+/// extensions for authorization, on the tracing span, on every event the
+/// handler publishes, and echoed back in the response header.
+pub async fn correlation_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let header = req
+        .app_data::<web::Data<AppState>>()
+        .map(|state| state.config.correlation_header.as_str())
+        .unwrap_or(DEFAULT_CORRELATION_HEADER);
+    let header = HeaderName::try_from(header).ok();
+    let id = CorrelationId::from_header(
+        header
+            .as_ref()
+            .and_then(|name| req.headers().get(name))
+            .and_then(|value| value.to_str().ok()),
+    );
+    req.extensions_mut().insert(id);
+
+    let span = tracing::info_span!("request", correlation_id = %id);
+    let mut response = correlation::scope(id, next.call(req)).instrument(span).await?;
+    if let Some(name) = header
+        && let Ok(value) = HeaderValue::try_from(id.to_string())
+    {
+        response.headers_mut().insert(name, value);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{middleware, test, App, HttpResponse};
+    use c2_core::{SecurityClassification, TenantId};
+    use c2_messaging::{DomainChange, DomainEvent, EntityKind, EventBus};
+
+    async fn publish(events: web::Data<EventBus>, tenant: web::Path<TenantId>) -> HttpResponse {
+        events.publish(DomainEvent::delete(
+            EntityKind::Mission,
+            "m-1",
+            tenant.into_inner(),
+            SecurityClassification::Unclassified,
+        ));
+        HttpResponse::NoContent().finish()
+    }
+
+    #[actix_web::test]
+    async fn correlation_id_reaches_response_and_published_change() {
+        let events = EventBus::default();
+        let tenant = TenantId::new();
+        let mut receiver = events.subscribe(tenant);
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(events))
+                .wrap(middleware::from_fn(correlation_id))
+                .route("/{tenant}", web::post().to(publish)),
+        )
+        .await;
+
+        let incoming = CorrelationId::new();
+        let request = test::TestRequest::post()
+            .uri(&format!("/{tenant}"))
+            .insert_header((DEFAULT_CORRELATION_HEADER, incoming.to_tagged_string()))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        let echoed = response.headers().get(DEFAULT_CORRELATION_HEADER).unwrap();
+        assert_eq!(echoed.to_str().unwrap(), incoming.to_string());
+        let change = DomainChange::from_event(&receiver.try_recv().unwrap(), 1);
+        assert_eq!(change.correlation_id, Some(incoming));
+
+        // Without the header a fresh id is issued and used the same way.
+        let request = test::TestRequest::post().uri(&format!("/{tenant}")).to_request();
+        let response = test::call_service(&app, request).await;
+        let issued: CorrelationId = response
+            .headers()
+            .get(DEFAULT_CORRELATION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap();
+        assert_ne!(issued, incoming);
+        assert_eq!(receiver.try_recv().unwrap().correlation_id, Some(issued));
+    }
+}
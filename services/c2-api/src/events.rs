@@ -0,0 +1,28 @@
+use c2_core::SecurityClassification;
+use c2_identity::Permission;
+use c2_messaging::{DomainEvent, EntityKind};
+
+/// Permission needed to see events for `entity`, matching its list route.
+pub fn view_permission(entity: EntityKind) -> Permission {
+    match entity {
+        EntityKind::Mission
+        | EntityKind::Asset
+        | EntityKind::Task
+        | EntityKind::Geofence
+        | EntityKind::Assignment => Permission::ViewMissions,
+        EntityKind::Incident => Permission::ViewIncidents,
+        EntityKind::Unit => Permission::ViewUnits,
+        EntityKind::Team => Permission::ViewTeams,
+        EntityKind::Capability => Permission::ViewCapabilities,
+    }
+}
+
+/// Whether a subscriber with `clearance` and `permissions` may see `event`.
+/// Tenant scoping is already done by the bus.
+pub fn visible(
+    event: &DomainEvent,
+    clearance: SecurityClassification,
+    permissions: &[Permission],
+) -> bool {
+    event.classification <= clearance && permissions.contains(&view_permission(event.entity))
+}
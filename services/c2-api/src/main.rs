@@ -1,23 +1,45 @@
 mod auth;
+mod compression;
+mod correlation;
+mod events;
+mod read_only;
 mod routes;
 mod state;
+#[cfg(test)]
+mod testing;
 
-use actix_web::{web, App, HttpServer};
-use c2_config::ServiceConfig;
-use c2_observability::{init, log_startup, ObservabilityConfig};
-use c2_policy::BasicPolicyEngine;
+use actix_web::{middleware, web, App, HttpServer};
+use c2_config::{ReadOnlyMode, ServiceConfig};
+use c2_http::HttpPolicy;
+use c2_messaging::{ChangeRelay, EventBus};
+use c2_observability::{
+    check_requested, init, log_startup, CheckReport, ObservabilityConfig, TelemetryConfig,
+};
+use c2_policy::{BasicPolicyEngine, TenantCeilings, TenantFloors, TenantRateLimiter};
+use c2_storage::{CapabilityCache, TimeoutStore};
 use c2_storage_surreal::{SurrealConfig, SurrealStore};
+use compression::CompressionMinBytes;
 use state::AppState;
 use std::io;
+use tracing::info;
 
 #[actix_web::main]
 async fn main() -> io::Result<()> {
     let config = ServiceConfig::from_env("c2-api");
+    if check_requested() {
+        run_check(&config).await;
+    }
     let obs_config = ObservabilityConfig {
         service_name: config.service_name.clone(),
         environment: config.environment.to_string(),
         log_level: config.log_level.clone(),
+        log_format: config.log_format.clone(),
         metrics_addr: config.metrics_addr.clone(),
+        telemetry: TelemetryConfig {
+            enabled: config.telemetry,
+            interval: config.telemetry_interval(),
+            endpoint: config.telemetry_endpoint.clone(),
+        },
     };
     let handle = init(&obs_config);
     log_startup(&handle, &obs_config.environment);
@@ -26,19 +48,69 @@ async fn main() -> io::Result<()> {
     let store = SurrealStore::connect_with_retry(&SurrealConfig::from_env())
         .await
         .map_err(|err| io::Error::new(io::ErrorKind::Other, err.message))?;
+    let store = TimeoutStore::new(store, config.storage_timeout());
     let policy = BasicPolicyEngine::with_default_rules();
+    let ceilings = TenantCeilings::from_env()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let floors = TenantFloors::from_env()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let rate_limiter = TenantRateLimiter::from_env()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut events = EventBus::default();
+    if let Some(relay) = ChangeRelay::from_env(&config.service_name)
+        .map_err(io::Error::other)?
+    {
+        info!("c2-api relaying domain changes over ZeroMQ");
+        events = events.with_relay(relay);
+    }
+    if config.read_only {
+        info!("c2-api starting read-only");
+    }
+    let read_only = ReadOnlyMode::new(config.read_only);
+    let capabilities = CapabilityCache::new(config.capability_cache_ttl());
+    let compression_min_bytes = CompressionMinBytes(config.compression_min_bytes);
+    let http_policy = web::Data::new(HttpPolicy::from_config(&config));
     let state = web::Data::new(AppState {
         config,
         policy,
+        ceilings,
+        floors,
+        rate_limiter,
+        read_only,
         store,
+        capabilities,
+        events,
     });
 
     HttpServer::new(move || {
         App::new()
             .app_data(state.clone())
+            .app_data(compression_min_bytes)
+            .app_data(http_policy.clone())
+            .wrap(middleware::from_fn(read_only::reject_writes))
+            .wrap(middleware::from_fn(auth::tenant_rate_limit))
+            .wrap(middleware::from_fn(auth::api_key_auth))
+            .wrap(middleware::from_fn(compression::skip_uncompressible))
+            .wrap(middleware::Compress::default())
+            .wrap(middleware::from_fn(c2_http::trusted_origin))
+            .wrap(middleware::from_fn(c2_http::access_log))
+            .wrap(middleware::from_fn(correlation::correlation_id))
             .configure(routes::configure)
     })
     .bind(bind_addr)?
     .run()
     .await
 }
+
+async fn run_check(config: &ServiceConfig) -> ! {
+    let mut report = CheckReport::new(&config.service_name);
+    report.pass(
+        "config",
+        format!("environment={} bind_addr={}", config.environment, config.bind_addr),
+    );
+    for (name, result) in c2_policy::env_checks() {
+        report.record(name, result);
+    }
+    report.record("surreal", SurrealStore::check(&SurrealConfig::from_env()).await);
+    report.exit()
+}
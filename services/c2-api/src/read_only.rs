@@ -0,0 +1,37 @@
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+
+use crate::routes::common::service_read_only;
+use crate::state::AppState;
+
+/// POST routes that change nothing stored, plus the switch itself so it can
+/// be turned back off.
+const EXEMPT_PATHS: [&str; 4] = [
+    "/v1/admin/read-only",
+    "/v1/mcp/handshake",
+    "/v1/mcp/sessions",
+    "/v1/mcp/heartbeat",
+];
+
+/// Rejects every write with a 503 while [`AppState::read_only`] is enabled.
+/// Anything other than `GET`, `HEAD` and `OPTIONS` counts as a write.
+pub async fn reject_writes(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let read_only = req
+        .app_data::<web::Data<AppState>>()
+        .is_some_and(|state| state.read_only.is_enabled());
+    let is_write = !matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    if read_only && is_write && !EXEMPT_PATHS.contains(&req.path()) {
+        return Ok(req
+            .into_response(service_read_only())
+            .map_into_right_body());
+    }
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_left_body)
+}
@@ -0,0 +1,56 @@
+use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use c2_core::SecurityClassification;
+use c2_identity::Permission;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::authorize_request;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadOnlyView {
+    pub read_only: bool,
+}
+
+#[get("/v1/admin/read-only")]
+pub async fn get_read_only(req: HttpRequest, state: web::Data<AppState>) -> HttpResponse {
+    if let Err(response) = authorize_request(
+        &req,
+        &state.policy,
+        Permission::Admin,
+        SecurityClassification::Restricted,
+    ) {
+        return response;
+    }
+    HttpResponse::Ok().json(ReadOnlyView {
+        read_only: state.read_only.is_enabled(),
+    })
+}
+
+/// Switches this instance in or out of read-only mode. Other replicas keep
+/// their own setting.
+#[put("/v1/admin/read-only")]
+pub async fn set_read_only(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    payload: web::Json<ReadOnlyView>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::Admin,
+        SecurityClassification::Restricted,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let read_only = payload.read_only;
+    if state.read_only.set(read_only) != read_only {
+        tracing::warn!(
+            user_id = %auth.subject.user_id,
+            tenant_id = %auth.subject.tenant_id,
+            read_only,
+            "read-only mode changed"
+        );
+    }
+    HttpResponse::Ok().json(ReadOnlyView { read_only })
+}
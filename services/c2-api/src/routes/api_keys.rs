@@ -0,0 +1,178 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use c2_core::{now_epoch_millis, ApiKeyId, EpochMillis, SecurityClassification, TenantId, UserId};
+use c2_identity::{ApiKey, Permission, Role};
+use c2_storage::ApiKeyRepository;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::authorize_request;
+use crate::routes::common::{
+    bad_request, forbidden, not_found, parse_tenant_id, parse_uuid, storage_error,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    pub tenant_id: String,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub tenant_id: TenantId,
+    pub label: String,
+    pub scopes: Vec<Permission>,
+}
+
+/// An API key as returned to clients; the secret hash never leaves the store.
+#[derive(Debug, Serialize)]
+pub struct ApiKeyView {
+    pub id: ApiKeyId,
+    pub tenant_id: TenantId,
+    pub label: String,
+    pub scopes: Vec<Permission>,
+    pub roles: Vec<Role>,
+    pub clearance: SecurityClassification,
+    pub created_by: UserId,
+    pub created_at_ms: EpochMillis,
+    pub revoked: bool,
+}
+
+impl From<&ApiKey> for ApiKeyView {
+    fn from(value: &ApiKey) -> Self {
+        Self {
+            id: value.id,
+            tenant_id: value.tenant_id,
+            label: value.label.clone(),
+            scopes: value.scopes.clone(),
+            roles: value.roles.clone(),
+            clearance: value.clearance,
+            created_by: value.created_by,
+            created_at_ms: value.created_at_ms,
+            revoked: value.revoked,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub key: ApiKeyView,
+    /// Plaintext secret; only returned here, at creation.
+    pub secret: String,
+}
+
+#[get("/v1/api-keys")]
+pub async fn list_api_keys(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<ListQuery>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ManageUsers,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let tenant_id = match parse_tenant_id(&query.tenant_id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if auth.subject.tenant_id != tenant_id {
+        return bad_request("tenant mismatch");
+    }
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
+    let offset = query.offset.unwrap_or(0);
+
+    match ApiKeyRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
+        Ok(keys) => HttpResponse::Ok().json(keys.iter().map(ApiKeyView::from).collect::<Vec<_>>()),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[post("/v1/api-keys")]
+pub async fn create_api_key(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    payload: web::Json<CreateApiKeyRequest>,
+) -> HttpResponse {
+    let request = payload.into_inner();
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ManageUsers,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    if auth.subject.tenant_id != request.tenant_id {
+        return bad_request("tenant mismatch");
+    }
+    let label = request.label.trim();
+    if label.is_empty() {
+        return bad_request("api key label is required");
+    }
+    if request.scopes.is_empty() {
+        return bad_request("api key needs at least one scope");
+    }
+    if request
+        .scopes
+        .iter()
+        .any(|scope| !auth.permissions.contains(scope))
+    {
+        return forbidden("api key scopes exceed caller permissions");
+    }
+
+    let (key, secret) = ApiKey::issue(
+        request.tenant_id,
+        label.to_string(),
+        request.scopes,
+        &auth.subject,
+        now_epoch_millis(),
+    );
+    match ApiKeyRepository::upsert(&state.store, key.clone()).await {
+        Ok(()) => HttpResponse::Created().json(CreatedApiKey {
+            key: ApiKeyView::from(&key),
+            secret,
+        }),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[post("/v1/api-keys/{id}/revoke")]
+pub async fn revoke_api_key(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ManageUsers,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+
+    let mut key = match ApiKeyRepository::get(&state.store, ApiKeyId::from_uuid(uuid)).await {
+        Ok(Some(key)) if key.tenant_id == auth.subject.tenant_id => key,
+        Ok(_) => return not_found("api key not found"),
+        Err(err) => return storage_error(err),
+    };
+    key.revoked = true;
+    match ApiKeyRepository::upsert(&state.store, key.clone()).await {
+        Ok(()) => HttpResponse::Ok().json(ApiKeyView::from(&key)),
+        Err(err) => storage_error(err),
+    }
+}
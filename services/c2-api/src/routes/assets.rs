@@ -1,18 +1,28 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
-use c2_core::{Asset, SecurityClassification};
+use c2_core::{Asset, AssetStatus, EpochMillis, SecurityClassification};
 use c2_identity::Permission;
-use c2_storage::AssetRepository;
+use c2_messaging::{DomainEvent, EntityKind};
+use c2_storage::{AssetFilter, AssetRepository};
+use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_tenant_id, parse_uuid};
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found,
+    parse_tenant_id, parse_uuid, storage_error, Marked,
+};
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ListQuery {
     pub tenant_id: String,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    pub status: Option<AssetStatus>,
+    pub classification: Option<SecurityClassification>,
+    /// Case-insensitive substring of the name.
+    pub q: Option<String>,
 }
 
 #[get("/v1/assets")]
@@ -37,12 +47,19 @@ pub async fn list_assets(
     if auth.subject.tenant_id != tenant_id {
         return bad_request("tenant mismatch");
     }
-    let limit = query.limit.unwrap_or(100);
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
     let offset = query.offset.unwrap_or(0);
+    let filter = AssetFilter {
+        status: query.status,
+        classification: query.classification,
+        search: query.q.clone(),
+    };
 
-    match AssetRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
-        Ok(assets) => HttpResponse::Ok().json(assets),
-        Err(err) => internal_error(err.message),
+    match AssetRepository::list_filtered(&state.store, tenant_id, &filter, limit, offset).await {
+        Ok(assets) => HttpResponse::Ok().json(marked(assets)),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -67,9 +84,53 @@ pub async fn get_asset(
     let asset_id = c2_core::AssetId::from_uuid(uuid);
 
     match AssetRepository::get(&state.store, asset_id).await {
-        Ok(Some(asset)) => HttpResponse::Ok().json(asset),
+        Ok(Some(asset)) => conditional_json(&req, Marked::new(asset)),
         Ok(None) => not_found("asset not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct StatusHistoryQuery {
+    pub from_ms: Option<EpochMillis>,
+    pub to_ms: Option<EpochMillis>,
+}
+
+#[get("/v1/assets/{id}/status-history")]
+pub async fn asset_status_history(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    query: web::Query<StatusHistoryQuery>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let asset_id = c2_core::AssetId::from_uuid(uuid);
+    match AssetRepository::get(&state.store, asset_id).await {
+        Ok(Some(asset)) if asset.tenant_id == auth.subject.tenant_id => {}
+        Ok(_) => return not_found("asset not found"),
+        Err(err) => return storage_error(err),
+    }
+    let from_ms = query.from_ms.unwrap_or(0);
+    let to_ms = query.to_ms.unwrap_or(i64::MAX as EpochMillis);
+    if from_ms > to_ms {
+        return bad_request("from_ms must not exceed to_ms");
+    }
+
+    match AssetRepository::list_status_history(&state.store, asset_id, from_ms, to_ms).await {
+        Ok(events) => HttpResponse::Ok().json(events),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -77,9 +138,12 @@ pub async fn get_asset(
 pub async fn upsert_asset(
     req: HttpRequest,
     state: web::Data<AppState>,
-    payload: web::Json<Asset>,
+    payload: web::Json<Value>,
 ) -> HttpResponse {
-    let asset = payload.into_inner();
+    let asset: Asset = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
     if let Err(response) = authorize_request(
         &req,
         &state.policy,
@@ -88,13 +152,39 @@ pub async fn upsert_asset(
     ) {
         return response;
     }
+    if let Err(message) = state.ceilings.check(asset.tenant_id, asset.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(asset.tenant_id, asset.classification) {
+        return forbidden(message);
+    }
     if asset.name.trim().is_empty() {
         return bad_request("asset name is required");
     }
+    match state
+        .capabilities
+        .first_unknown(&state.store, asset.tenant_id, &asset.capability_ids)
+        .await
+    {
+        Ok(None) => {}
+        Ok(Some(capability_id)) => {
+            return bad_request(format!("unknown capability {capability_id}"));
+        }
+        Err(err) => return storage_error(err),
+    }
 
     match AssetRepository::upsert(&state.store, asset.clone()).await {
-        Ok(()) => HttpResponse::Ok().json(asset),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Asset,
+                asset.id,
+                asset.tenant_id,
+                asset.classification,
+                &asset,
+            ));
+            HttpResponse::Ok().json(Marked::new(asset))
+        }
+        Err(err) => storage_error(err),
     }
 }
 
@@ -118,8 +208,19 @@ pub async fn delete_asset(
     };
     let asset_id = c2_core::AssetId::from_uuid(uuid);
 
+    let existing = AssetRepository::get(&state.store, asset_id).await.ok().flatten();
     match AssetRepository::delete(&state.store, asset_id).await {
-        Ok(()) => HttpResponse::NoContent().finish(),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            if let Some(asset) = existing {
+                state.events.publish(DomainEvent::delete(
+                    EntityKind::Asset,
+                    asset.id,
+                    asset.tenant_id,
+                    asset.classification,
+                ));
+            }
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
     }
 }
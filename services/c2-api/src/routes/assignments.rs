@@ -0,0 +1,224 @@
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+use c2_core::{
+    now_epoch_millis, AssignmentId, MissionAssignment, MissionId, SecurityClassification, TeamId,
+    UnitId,
+};
+use c2_identity::Permission;
+use c2_messaging::{DomainEvent, EntityKind};
+use c2_storage::{AssignmentRepository, MissionRepository, TeamRepository, UnitRepository};
+use serde::Deserialize;
+
+use crate::auth::authorize_request;
+use crate::routes::common::{bad_request, marked, not_found, parse_uuid, storage_error, Marked};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+/// Body of an assignment: exactly one of `unit_id` and `team_id`.
+#[derive(Debug, Deserialize)]
+pub struct AssignRequest {
+    pub unit_id: Option<UnitId>,
+    pub team_id: Option<TeamId>,
+    pub role: String,
+}
+
+#[get("/v1/missions/{id}/assignments")]
+pub async fn list_mission_assignments(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    query: web::Query<ListQuery>,
+) -> HttpResponse {
+    if let Err(response) = authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        return response;
+    }
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
+    let offset = query.offset.unwrap_or(0);
+
+    let mission_id = MissionId::from_uuid(uuid);
+    match AssignmentRepository::list_by_mission(&state.store, mission_id, limit, offset).await {
+        Ok(assignments) => HttpResponse::Ok().json(marked(assignments)),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[get("/v1/units/{id}/assignments")]
+pub async fn list_unit_assignments(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    query: web::Query<ListQuery>,
+) -> HttpResponse {
+    if let Err(response) = authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        return response;
+    }
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
+    let offset = query.offset.unwrap_or(0);
+
+    let unit_id = UnitId::from_uuid(uuid);
+    match AssignmentRepository::list_by_unit(&state.store, unit_id, limit, offset).await {
+        Ok(assignments) => HttpResponse::Ok().json(marked(assignments)),
+        Err(err) => storage_error(err),
+    }
+}
+
+/// Puts a unit or team on the mission. Assigning the same one again replaces
+/// its role. Needs both mission editing and dispatch rights at the mission's
+/// classification.
+#[post("/v1/missions/{id}/assignments")]
+pub async fn assign_to_mission(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    payload: web::Json<AssignRequest>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::EditMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let request = payload.into_inner();
+    let role = request.role.trim().to_string();
+    if role.is_empty() {
+        return bad_request("assignment role is required");
+    }
+
+    let store = state.cleared_store(auth.subject.clearance);
+    let mission = match MissionRepository::get(&store, MissionId::from_uuid(uuid)).await {
+        Ok(Some(mission)) if mission.tenant_id == auth.subject.tenant_id => mission,
+        Ok(_) => return not_found("mission not found"),
+        Err(err) => return storage_error(err),
+    };
+    for permission in [Permission::EditMissions, Permission::DispatchAssets] {
+        if let Err(response) =
+            authorize_request(&req, &state.policy, permission, mission.classification)
+        {
+            return response;
+        }
+    }
+
+    match (request.unit_id, request.team_id) {
+        (Some(unit_id), None) => match UnitRepository::get(&state.store, unit_id).await {
+            Ok(Some(unit)) if unit.tenant_id == mission.tenant_id => {}
+            Ok(_) => return not_found("unit not found"),
+            Err(err) => return storage_error(err),
+        },
+        (None, Some(team_id)) => match TeamRepository::get(&state.store, team_id).await {
+            Ok(Some(team)) if team.tenant_id == mission.tenant_id => {}
+            Ok(_) => return not_found("team not found"),
+            Err(err) => return storage_error(err),
+        },
+        _ => return bad_request("set exactly one of unit_id and team_id"),
+    }
+
+    let assignment = MissionAssignment {
+        id: MissionAssignment::derive_id(
+            mission.tenant_id,
+            mission.id,
+            request.unit_id,
+            request.team_id,
+        ),
+        tenant_id: mission.tenant_id,
+        mission_id: mission.id,
+        unit_id: request.unit_id,
+        team_id: request.team_id,
+        role,
+        classification: mission.classification,
+        assigned_at_ms: now_epoch_millis(),
+    };
+    match AssignmentRepository::upsert(&state.store, assignment.clone()).await {
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Assignment,
+                assignment.id,
+                assignment.tenant_id,
+                assignment.classification,
+                &assignment,
+            ));
+            HttpResponse::Ok().json(Marked::new(assignment))
+        }
+        Err(err) => storage_error(err),
+    }
+}
+
+#[delete("/v1/assignments/{id}")]
+pub async fn unassign(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::EditMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let assignment_id = AssignmentId::from_uuid(uuid);
+
+    let assignment = match AssignmentRepository::get(&state.store, assignment_id).await {
+        Ok(Some(assignment)) if assignment.tenant_id == auth.subject.tenant_id => assignment,
+        Ok(_) => return not_found("assignment not found"),
+        Err(err) => return storage_error(err),
+    };
+    for permission in [Permission::EditMissions, Permission::DispatchAssets] {
+        if let Err(response) =
+            authorize_request(&req, &state.policy, permission, assignment.classification)
+        {
+            return response;
+        }
+    }
+
+    match AssignmentRepository::delete(&state.store, assignment_id).await {
+        Ok(()) => {
+            state.events.publish(DomainEvent::delete(
+                EntityKind::Assignment,
+                assignment.id,
+                assignment.tenant_id,
+                assignment.classification,
+            ));
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
+    }
+}
@@ -1,11 +1,16 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
 use c2_core::{Capability, SecurityClassification};
 use c2_identity::Permission;
+use c2_messaging::{DomainEvent, EntityKind};
 use c2_storage::CapabilityRepository;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_tenant_id, parse_uuid};
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found,
+    parse_tenant_id, parse_uuid, storage_error, Marked,
+};
 use crate::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -37,12 +42,14 @@ pub async fn list_capabilities(
     if auth.subject.tenant_id != tenant_id {
         return bad_request("tenant mismatch");
     }
-    let limit = query.limit.unwrap_or(100);
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
     let offset = query.offset.unwrap_or(0);
 
     match CapabilityRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
-        Ok(capabilities) => HttpResponse::Ok().json(capabilities),
-        Err(err) => internal_error(err.message),
+        Ok(capabilities) => HttpResponse::Ok().json(marked(capabilities)),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -67,9 +74,9 @@ pub async fn get_capability(
     let capability_id = c2_core::CapabilityId::from_uuid(uuid);
 
     match CapabilityRepository::get(&state.store, capability_id).await {
-        Ok(Some(capability)) => HttpResponse::Ok().json(capability),
+        Ok(Some(capability)) => conditional_json(&req, Marked::new(capability)),
         Ok(None) => not_found("capability not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -77,9 +84,12 @@ pub async fn get_capability(
 pub async fn upsert_capability(
     req: HttpRequest,
     state: web::Data<AppState>,
-    payload: web::Json<Capability>,
+    payload: web::Json<Value>,
 ) -> HttpResponse {
-    let capability = payload.into_inner();
+    let capability: Capability = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
     if let Err(response) = authorize_request(
         &req,
         &state.policy,
@@ -88,6 +98,12 @@ pub async fn upsert_capability(
     ) {
         return response;
     }
+    if let Err(message) = state.ceilings.check(capability.tenant_id, capability.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(capability.tenant_id, capability.classification) {
+        return forbidden(message);
+    }
     if capability.code.trim().is_empty() {
         return bad_request("capability code is required");
     }
@@ -96,8 +112,18 @@ pub async fn upsert_capability(
     }
 
     match CapabilityRepository::upsert(&state.store, capability.clone()).await {
-        Ok(()) => HttpResponse::Ok().json(capability),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.capabilities.invalidate(capability.tenant_id);
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Capability,
+                capability.id,
+                capability.tenant_id,
+                capability.classification,
+                &capability,
+            ));
+            HttpResponse::Ok().json(Marked::new(capability))
+        }
+        Err(err) => storage_error(err),
     }
 }
 
@@ -121,8 +147,20 @@ pub async fn delete_capability(
     };
     let capability_id = c2_core::CapabilityId::from_uuid(uuid);
 
+    let existing = CapabilityRepository::get(&state.store, capability_id).await.ok().flatten();
     match CapabilityRepository::delete(&state.store, capability_id).await {
-        Ok(()) => HttpResponse::NoContent().finish(),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            if let Some(capability) = existing {
+                state.capabilities.invalidate(capability.tenant_id);
+                state.events.publish(DomainEvent::delete(
+                    EntityKind::Capability,
+                    capability.id,
+                    capability.tenant_id,
+                    capability.classification,
+                ));
+            }
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
     }
 }
@@ -0,0 +1,224 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use c2_core::{EpochMillis, SecurityClassification};
+use c2_identity::Permission;
+use c2_storage::{
+    AssetRepository, CapabilityRepository, ChangeCursor, IncidentRepository, MissionRepository,
+    StorageError, TaskRepository, TeamRepository, UnitRepository,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::auth::authorize_request;
+use crate::routes::common::{
+    bad_request, not_found, parse_tenant_id, parse_uuid, storage_error,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    pub tenant_id: String,
+    pub since_ms: Option<EpochMillis>,
+    /// Id of the last record seen at `since_ms`.
+    pub after_id: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChangeFeed<T> {
+    items: Vec<T>,
+    next_watermark: EpochMillis,
+    next_after_id: Option<Uuid>,
+}
+
+/// Records updated after `since_ms`, oldest first, with records sharing a
+/// millisecond ordered by id. Clients pass the returned `next_watermark` and
+/// `next_after_id` back as `since_ms` and `after_id` to continue syncing, so
+/// a page ending inside a millisecond does not skip the rest of it. Mission
+/// partitions above the caller's clearance are not read.
+#[get("/v1/changes/{kind}")]
+pub async fn list_changes(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    kind: web::Path<String>,
+    query: web::Query<ChangesQuery>,
+) -> HttpResponse {
+    let permission = match kind.as_str() {
+        "missions" | "assets" | "tasks" => Permission::ViewMissions,
+        "units" => Permission::ViewUnits,
+        "teams" => Permission::ViewTeams,
+        "capabilities" => Permission::ViewCapabilities,
+        "incidents" => Permission::ViewIncidents,
+        _ => return not_found("unknown change feed"),
+    };
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        permission,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let tenant_id = match parse_tenant_id(&query.tenant_id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if auth.subject.tenant_id != tenant_id {
+        return bad_request("tenant mismatch");
+    }
+    let since_ms = query.since_ms.unwrap_or(0);
+    let after = match query.after_id.as_deref().map(parse_uuid).transpose() {
+        Ok(Some(id)) => ChangeCursor::after(since_ms, id),
+        Ok(None) => ChangeCursor::since(since_ms),
+        Err(response) => return response,
+    };
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
+    let store = &state.cleared_store(auth.subject.clearance);
+
+    match kind.as_str() {
+        "missions" => feed(
+            MissionRepository::list_updated_since(store, tenant_id, after, limit).await,
+            after,
+            |item| (item.updated_at_ms, item.id.as_uuid()),
+        ),
+        "assets" => feed(
+            AssetRepository::list_updated_since(store, tenant_id, after, limit).await,
+            after,
+            |item| (item.updated_at_ms, item.id.as_uuid()),
+        ),
+        "units" => feed(
+            UnitRepository::list_updated_since(store, tenant_id, after, limit).await,
+            after,
+            |item| (item.updated_at_ms, item.id.as_uuid()),
+        ),
+        "teams" => feed(
+            TeamRepository::list_updated_since(store, tenant_id, after, limit).await,
+            after,
+            |item| (item.updated_at_ms, item.id.as_uuid()),
+        ),
+        "capabilities" => feed(
+            CapabilityRepository::list_updated_since(store, tenant_id, after, limit).await,
+            after,
+            |item| (item.updated_at_ms, item.id.as_uuid()),
+        ),
+        "incidents" => feed(
+            IncidentRepository::list_updated_since(store, tenant_id, after, limit).await,
+            after,
+            |item| (item.updated_at_ms, item.id.as_uuid()),
+        ),
+        _ => feed(
+            TaskRepository::list_updated_since(store, tenant_id, after, limit).await,
+            after,
+            |item| (item.updated_at_ms, item.id.as_uuid()),
+        ),
+    }
+}
+
+fn feed<T: Serialize>(
+    result: Result<Vec<T>, StorageError>,
+    after: ChangeCursor,
+    position: impl Fn(&T) -> (EpochMillis, Uuid),
+) -> HttpResponse {
+    match result {
+        Ok(items) => {
+            // Items come ordered by (updated_at_ms, id), so the last is the
+            // new cursor.
+            let (next_watermark, next_after_id) = items
+                .last()
+                .map(|item| {
+                    let (updated_at_ms, id) = position(item);
+                    (updated_at_ms, Some(id))
+                })
+                .unwrap_or((after.updated_at_ms, after.id));
+            HttpResponse::Ok().json(ChangeFeed {
+                items,
+                next_watermark,
+                next_after_id,
+            })
+        }
+        Err(err) => storage_error(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state, Caller};
+    use actix_web::{test, App};
+    use c2_core::{Mission, MissionId, MissionStatus, OperationalPriority, TenantId};
+    use serde_json::Value;
+
+    #[actix_web::test]
+    async fn sync_from_a_watermark_returns_only_what_changed_since() {
+        let state = app_state().await;
+        let app = test::init_service(
+            App::new().app_data(state.clone()).service(list_changes),
+        )
+        .await;
+        let tenant_id = TenantId::new();
+        let caller = Caller::new(tenant_id, SecurityClassification::Secret);
+        let mission = |name: &str, classification, updated_at_ms| Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: name.to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification,
+            created_at_ms: 1,
+            updated_at_ms,
+            archived_at_ms: None,
+        };
+        let mut missions: Vec<Mission> = ["Alpha", "Bravo", "Charlie"]
+            .into_iter()
+            .map(|name| mission(name, SecurityClassification::Unclassified, 10))
+            .collect();
+        let above_clearance = mission("Delta", SecurityClassification::TopSecret, 10);
+        for mission in missions.iter().chain([&above_clearance]) {
+            MissionRepository::upsert(&state.store, mission.clone()).await.unwrap();
+        }
+        let sync = |query: String| {
+            let app = &app;
+            let request = caller
+                .get(&format!("/v1/changes/missions?tenant_id={tenant_id}&{query}"))
+                .to_request();
+            async move { test::call_and_read_body_json::<_, _, Value>(app, request).await }
+        };
+        let names = |feed: &Value| -> Vec<String> {
+            feed["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // All three share a millisecond; paging one at a time still sees
+        // each of them once, and never the top secret one.
+        let mut seen = Vec::new();
+        let mut query = "since_ms=0&limit=1".to_string();
+        loop {
+            let feed = sync(query.clone()).await;
+            if feed["items"].as_array().unwrap().is_empty() {
+                break;
+            }
+            seen.extend(names(&feed));
+            query = format!(
+                "since_ms={}&after_id={}&limit=1",
+                feed["next_watermark"],
+                feed["next_after_id"].as_str().unwrap()
+            );
+        }
+        seen.sort();
+        assert_eq!(seen, ["Alpha", "Bravo", "Charlie"]);
+        let watermark = sync("since_ms=0".to_string()).await["next_watermark"].clone();
+        assert_eq!(watermark, 10);
+
+        missions[1].updated_at_ms = 20;
+        MissionRepository::upsert(&state.store, missions[1].clone()).await.unwrap();
+        let feed = sync(format!("since_ms={watermark}")).await;
+        assert_eq!(names(&feed), ["Bravo"]);
+        assert_eq!(feed["next_watermark"], 20);
+    }
+}
@@ -1,13 +1,78 @@
-use actix_web::HttpResponse;
+use actix_web::http::header::{
+    CacheControl, CacheDirective, ContentType, ETag, EntityTag, IfNoneMatch,
+};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use c2_config::READ_ONLY_MESSAGE;
 use c2_core::TenantId;
+use c2_messaging::Classified;
+use c2_policy::TenantFloors;
+use c2_storage::StorageError;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::Duration;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ErrorResponse {
     pub error: String,
 }
 
+/// A record with the banner text for its classification, so clients can show
+/// the marking without their own mapping.
+#[derive(Debug, Serialize)]
+pub struct Marked<T> {
+    #[serde(flatten)]
+    pub record: T,
+    pub marking: &'static str,
+}
+
+impl<T: Classified> Marked<T> {
+    pub fn new(record: T) -> Self {
+        let marking = record.classification().marking();
+        Self { record, marking }
+    }
+}
+
+pub fn marked<T: Classified>(records: Vec<T>) -> Vec<Marked<T>> {
+    records.into_iter().map(Marked::new).collect()
+}
+
+/// 200 with `body` for a single record, or an empty 304 when the request's
+/// `If-None-Match` already names this version. The `ETag` is a hash of the
+/// serialized body, so it changes with any field of the response whatever
+/// timestamps the client wrote, and `Cache-Control: no-cache` makes clients
+/// revalidate instead of reusing the body unchecked.
+pub fn conditional_json<T: Serialize>(req: &HttpRequest, body: T) -> HttpResponse {
+    let body = match serde_json::to_vec(&body) {
+        Ok(body) => body,
+        Err(err) => return internal_error(err.to_string()),
+    };
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    let etag = EntityTag::new_strong(format!("{:016x}", hasher.finish()));
+    let unchanged = match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => false,
+    };
+    let mut response = if unchanged {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .insert_header(ETag(etag))
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]));
+    if unchanged {
+        response.finish()
+    } else {
+        response.content_type(ContentType::json()).body(body)
+    }
+}
+
 pub fn bad_request(message: impl Into<String>) -> HttpResponse {
     HttpResponse::BadRequest().json(ErrorResponse {
         error: message.into(),
@@ -32,12 +97,38 @@ pub fn not_found(message: impl Into<String>) -> HttpResponse {
     })
 }
 
+/// 429 with `Retry-After` in whole seconds, rounded up.
+pub fn too_many_requests(retry_after: Duration) -> HttpResponse {
+    let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    HttpResponse::TooManyRequests()
+        .insert_header(("Retry-After", seconds.max(1).to_string()))
+        .json(ErrorResponse {
+            error: "rate limit exceeded".to_string(),
+        })
+}
+
+/// 503 for writes while the service is read-only; reads keep working.
+pub fn service_read_only() -> HttpResponse {
+    HttpResponse::ServiceUnavailable().json(ErrorResponse {
+        error: READ_ONLY_MESSAGE.to_string(),
+    })
+}
+
 pub fn internal_error(message: impl Into<String>) -> HttpResponse {
     HttpResponse::InternalServerError().json(ErrorResponse {
         error: message.into(),
     })
 }
 
+/// 504 when the store timed out, so clients can retry; 500 otherwise.
+pub fn storage_error(err: StorageError) -> HttpResponse {
+    if err.is_timeout() {
+        HttpResponse::GatewayTimeout().json(ErrorResponse { error: err.message })
+    } else {
+        internal_error(err.message)
+    }
+}
+
 pub fn parse_uuid(value: &str) -> Result<Uuid, HttpResponse> {
     Uuid::parse_str(value).map_err(|_| bad_request("invalid UUID"))
 }
@@ -46,3 +137,69 @@ pub fn parse_tenant_id(value: &str) -> Result<TenantId, HttpResponse> {
     let uuid = parse_uuid(value)?;
     Ok(TenantId::from_uuid(uuid))
 }
+
+/// Parses a record body. A missing or null `classification` becomes the
+/// tenant's floor; anything set explicitly is left for the caller to check.
+pub fn classified_record<T: DeserializeOwned>(
+    mut body: Value,
+    floors: &TenantFloors,
+) -> Result<T, HttpResponse> {
+    let tenant_id = body
+        .get("tenant_id")
+        .and_then(Value::as_str)
+        .and_then(|value| value.parse::<TenantId>().ok());
+    if let (Some(tenant_id), Some(record)) = (tenant_id, body.as_object_mut())
+        && record.get("classification").is_none_or(Value::is_null)
+    {
+        let floor = serde_json::to_value(floors.floor(tenant_id)).unwrap_or_default();
+        record.insert("classification".to_string(), floor);
+    }
+    serde_json::from_value(body).map_err(|err| bad_request(format!("invalid record: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{Mission, SecurityClassification};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn omitted_classification_defaults_to_tenant_floor() {
+        let tenant_id = TenantId::new();
+        let floors = TenantFloors::new(
+            SecurityClassification::Unclassified,
+            HashMap::from([(tenant_id, SecurityClassification::Restricted)]),
+        );
+        let body = |classification: Option<&str>| {
+            let mut body = json!({
+                "id": c2_core::MissionId::new(),
+                "tenant_id": tenant_id,
+                "name": "Bridge survey",
+                "status": "planned",
+                "priority": "routine",
+                "created_at_ms": 1,
+                "updated_at_ms": 1,
+            });
+            if let Some(classification) = classification {
+                body["classification"] = json!(classification);
+            }
+            body
+        };
+
+        let mission: Mission = classified_record(body(None), &floors).unwrap();
+        assert_eq!(mission.classification, SecurityClassification::Restricted);
+        assert!(floors.check(tenant_id, mission.classification).is_ok());
+
+        let mission: Mission = classified_record(body(Some("unclassified")), &floors).unwrap();
+        assert_eq!(
+            floors.check(tenant_id, mission.classification),
+            Err("classification Unclassified is below tenant floor Restricted".to_string())
+        );
+
+        // Other tenants keep the deployment default.
+        let other = TenantFloors::default();
+        let mission: Mission = classified_record(body(None), &other).unwrap();
+        assert_eq!(mission.classification, SecurityClassification::Unclassified);
+    }
+}
@@ -0,0 +1,225 @@
+use actix_web::error::ErrorInternalServerError;
+use actix_web::web::Bytes;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use c2_core::{to_rfc3339, Incident, Mission, SecurityClassification, TenantId};
+use c2_identity::Permission;
+use c2_storage::{IncidentRepository, MissionRepository, StorageError};
+use futures_util::stream::{self, unfold, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+use crate::auth::authorize_request;
+use crate::routes::common::{bad_request, parse_tenant_id};
+use crate::state::AppState;
+
+/// Records fetched per storage round trip; each page becomes one body chunk.
+const EXPORT_PAGE_SIZE: usize = 500;
+const MISSION_COLUMNS: [&str; 6] =
+    ["id", "name", "status", "priority", "classification", "created_at"];
+const INCIDENT_COLUMNS: [&str; 7] = [
+    "id",
+    "incident_type",
+    "status",
+    "priority",
+    "summary",
+    "classification",
+    "created_at",
+];
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub tenant_id: String,
+}
+
+/// The tenant's missions at or below the caller's clearance as CSV, newest
+/// first. Classification is the banner marking.
+#[get("/v1/export/missions.csv")]
+pub async fn export_missions(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<ExportQuery>,
+) -> HttpResponse {
+    let (tenant_id, clearance) =
+        match authorize_export(&req, &state, &query, Permission::ViewMissions) {
+            Ok(value) => value,
+            Err(response) => return response,
+        };
+
+    let rows = paged_rows(move |offset| {
+        let state = state.clone();
+        async move {
+            let missions = MissionRepository::list_by_tenant(
+                &state.cleared_store(clearance),
+                tenant_id,
+                EXPORT_PAGE_SIZE,
+                offset,
+            )
+            .await?;
+            let fetched = missions.len();
+            let rows = missions
+                .into_iter()
+                .filter(|mission| mission.classification <= clearance)
+                .map(mission_row)
+                .collect();
+            Ok((fetched, rows))
+        }
+    });
+    csv_response("missions.csv", &MISSION_COLUMNS, rows)
+}
+
+/// The tenant's incidents at or below the caller's clearance as CSV, newest
+/// first. Classification is the banner marking.
+#[get("/v1/export/incidents.csv")]
+pub async fn export_incidents(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<ExportQuery>,
+) -> HttpResponse {
+    let (tenant_id, clearance) =
+        match authorize_export(&req, &state, &query, Permission::ViewIncidents) {
+            Ok(value) => value,
+            Err(response) => return response,
+        };
+
+    let rows = paged_rows(move |offset| {
+        let state = state.clone();
+        async move {
+            let incidents = IncidentRepository::list_by_tenant(
+                &state.store,
+                tenant_id,
+                EXPORT_PAGE_SIZE,
+                offset,
+            )
+            .await?;
+            let fetched = incidents.len();
+            let rows = incidents
+                .into_iter()
+                .filter(|incident| incident.classification <= clearance)
+                .map(incident_row)
+                .collect();
+            Ok((fetched, rows))
+        }
+    });
+    csv_response("incidents.csv", &INCIDENT_COLUMNS, rows)
+}
+
+fn authorize_export(
+    req: &HttpRequest,
+    state: &AppState,
+    query: &ExportQuery,
+    permission: Permission,
+) -> Result<(TenantId, SecurityClassification), HttpResponse> {
+    let auth = authorize_request(
+        req,
+        &state.policy,
+        permission,
+        SecurityClassification::Unclassified,
+    )?;
+    let tenant_id = parse_tenant_id(&query.tenant_id)?;
+    if auth.subject.tenant_id != tenant_id {
+        return Err(bad_request("tenant mismatch"));
+    }
+    Ok((tenant_id, auth.subject.clearance))
+}
+
+fn mission_row(mission: Mission) -> Vec<String> {
+    vec![
+        mission.id.to_string(),
+        mission.name,
+        enum_label(&mission.status),
+        enum_label(&mission.priority),
+        mission.classification.marking().to_string(),
+        to_rfc3339(mission.created_at_ms),
+    ]
+}
+
+fn incident_row(incident: Incident) -> Vec<String> {
+    vec![
+        incident.id.to_string(),
+        enum_label(&incident.incident_type),
+        enum_label(&incident.status),
+        enum_label(&incident.priority),
+        incident.summary,
+        incident.classification.marking().to_string(),
+        to_rfc3339(incident.created_at_ms),
+    ]
+}
+
+/// Fetches pages from offset 0 until one comes back short, yielding each as
+/// encoded CSV rows. `fetch` returns the number of records read alongside the
+/// rows kept, since filtering may drop some.
+fn paged_rows<F, Fut>(fetch: F) -> impl Stream<Item = Result<Bytes, StorageError>>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = Result<(usize, Vec<Vec<String>>), StorageError>>,
+{
+    unfold((fetch, Some(0)), |(fetch, offset)| async move {
+        let offset = offset?;
+        match fetch(offset).await {
+            Ok((fetched, rows)) => {
+                let next = (fetched == EXPORT_PAGE_SIZE).then_some(offset + fetched);
+                let chunk = rows.iter().map(|row| csv_line(row)).collect::<String>();
+                Some((Ok(Bytes::from(chunk)), (fetch, next)))
+            }
+            Err(err) => Some((Err(err), (fetch, None))),
+        }
+    })
+}
+
+fn csv_response<S>(filename: &str, columns: &[&str], rows: S) -> HttpResponse
+where
+    S: Stream<Item = Result<Bytes, StorageError>> + 'static,
+{
+    let header = Bytes::from(csv_line(columns));
+    let body = stream::once(async move { Ok(header) })
+        .chain(rows)
+        .map(|chunk| {
+            chunk.map_err(|err| {
+                tracing::warn!(error = %err.message, "csv export aborted");
+                ErrorInternalServerError(err.message)
+            })
+        });
+
+    HttpResponse::Ok()
+        .insert_header(("Content-Type", "text/csv; charset=utf-8"))
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"{filename}\""),
+        ))
+        .streaming(body)
+}
+
+/// One RFC 4180 line. Fields with separators, quotes or line breaks are
+/// quoted; fields that a spreadsheet would read as a formula get a leading
+/// apostrophe.
+fn csv_line<T: AsRef<str>>(fields: &[T]) -> String {
+    let mut line = String::new();
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            line.push(',');
+        }
+        let field = field.as_ref();
+        let field = if field.starts_with(['=', '+', '-', '@']) {
+            format!("'{field}")
+        } else {
+            field.to_string()
+        };
+        if field.contains([',', '"', '\r', '\n']) {
+            line.push('"');
+            line.push_str(&field.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(&field);
+        }
+    }
+    line.push_str("\r\n");
+    line
+}
+
+/// The serde name of a unit enum variant, as the JSON API spells it.
+fn enum_label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(label)) => label,
+        _ => String::new(),
+    }
+}
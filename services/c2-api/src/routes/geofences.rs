@@ -0,0 +1,169 @@
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+use c2_core::{Geofence, SecurityClassification};
+use c2_geo::GeoFence;
+use c2_identity::Permission;
+use c2_messaging::{DomainEvent, EntityKind};
+use c2_storage::GeofenceRepository;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::auth::authorize_request;
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found,
+    parse_tenant_id, parse_uuid, storage_error, Marked,
+};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    pub tenant_id: String,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[get("/v1/geofences")]
+pub async fn list_geofences(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<ListQuery>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let tenant_id = match parse_tenant_id(&query.tenant_id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if auth.subject.tenant_id != tenant_id {
+        return bad_request("tenant mismatch");
+    }
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
+    let offset = query.offset.unwrap_or(0);
+
+    match GeofenceRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
+        Ok(geofences) => HttpResponse::Ok().json(marked(geofences)),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[get("/v1/geofences/{id}")]
+pub async fn get_geofence(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> HttpResponse {
+    if let Err(response) = authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        return response;
+    }
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let geofence_id = c2_core::GeofenceId::from_uuid(uuid);
+
+    match GeofenceRepository::get(&state.store, geofence_id).await {
+        Ok(Some(geofence)) => conditional_json(&req, Marked::new(geofence)),
+        Ok(None) => not_found("geofence not found"),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[post("/v1/geofences")]
+pub async fn upsert_geofence(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    payload: web::Json<Value>,
+) -> HttpResponse {
+    let geofence: Geofence = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if let Err(response) = authorize_request(
+        &req,
+        &state.policy,
+        Permission::EditMissions,
+        geofence.classification,
+    ) {
+        return response;
+    }
+    if let Err(message) = state.ceilings.check(geofence.tenant_id, geofence.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(geofence.tenant_id, geofence.classification) {
+        return forbidden(message);
+    }
+    if geofence.name.trim().is_empty() {
+        return bad_request("geofence name is required");
+    }
+    let valid_shape = match &geofence.fence {
+        GeoFence::Circle { radius_m, .. } => radius_m.is_finite() && *radius_m > 0.0,
+        GeoFence::Polygon { vertices } => vertices.len() >= 3,
+    };
+    if !valid_shape {
+        return bad_request("geofence needs a positive radius or at least three vertices");
+    }
+
+    match GeofenceRepository::upsert(&state.store, geofence.clone()).await {
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Geofence,
+                geofence.id,
+                geofence.tenant_id,
+                geofence.classification,
+                &geofence,
+            ));
+            HttpResponse::Ok().json(Marked::new(geofence))
+        }
+        Err(err) => storage_error(err),
+    }
+}
+
+#[delete("/v1/geofences/{id}")]
+pub async fn delete_geofence(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> HttpResponse {
+    if let Err(response) = authorize_request(
+        &req,
+        &state.policy,
+        Permission::EditMissions,
+        SecurityClassification::Restricted,
+    ) {
+        return response;
+    }
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let geofence_id = c2_core::GeofenceId::from_uuid(uuid);
+
+    let existing = GeofenceRepository::get(&state.store, geofence_id).await.ok().flatten();
+    match GeofenceRepository::delete(&state.store, geofence_id).await {
+        Ok(()) => {
+            if let Some(geofence) = existing {
+                state.events.publish(DomainEvent::delete(
+                    EntityKind::Geofence,
+                    geofence.id,
+                    geofence.tenant_id,
+                    geofence.classification,
+                ));
+            }
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
+    }
+}
@@ -1,18 +1,57 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
-use c2_core::{Incident, SecurityClassification};
+use c2_core::{Incident, IncidentStatus, OperationalPriority, SecurityClassification};
+use c2_geo::BoundingBox;
 use c2_identity::Permission;
-use c2_storage::IncidentRepository;
+use c2_messaging::{DomainEvent, EntityKind};
+use c2_storage::{IncidentFilter, IncidentRepository, IncidentSort};
+use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_tenant_id, parse_uuid};
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found,
+    parse_tenant_id, parse_uuid, storage_error, Marked,
+};
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ListQuery {
     pub tenant_id: String,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    pub status: Option<IncidentStatus>,
+    pub priority: Option<OperationalPriority>,
+    pub classification: Option<SecurityClassification>,
+    /// Case-insensitive substring of the summary.
+    pub q: Option<String>,
+    pub sort: Option<SortOrder>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Newest,
+    Priority,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BboxQuery {
+    pub tenant_id: String,
+    pub north: f64,
+    pub south: f64,
+    pub east: f64,
+    pub west: f64,
+    pub limit: Option<usize>,
+}
+
+impl From<SortOrder> for IncidentSort {
+    fn from(value: SortOrder) -> Self {
+        match value {
+            SortOrder::Newest => Self::Newest,
+            SortOrder::Priority => Self::Priority,
+        }
+    }
 }
 
 #[get("/v1/incidents")]
@@ -37,12 +76,64 @@ pub async fn list_incidents(
     if auth.subject.tenant_id != tenant_id {
         return bad_request("tenant mismatch");
     }
-    let limit = query.limit.unwrap_or(100);
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
     let offset = query.offset.unwrap_or(0);
+    let filter = IncidentFilter {
+        status: query.status,
+        priority: query.priority,
+        classification: query.classification,
+        search: query.q.clone(),
+        sort: query.sort.map(IncidentSort::from).unwrap_or_default(),
+    };
+
+    match IncidentRepository::list_filtered(&state.store, tenant_id, &filter, limit, offset).await {
+        Ok(incidents) => HttpResponse::Ok().json(marked(incidents)),
+        Err(err) => storage_error(err),
+    }
+}
+
+/// Positioned incidents inside the box, newest first. Registered ahead of
+/// `/v1/incidents/{id}` so `bbox` is not read as an id.
+#[get("/v1/incidents/bbox")]
+pub async fn incidents_in_bbox(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<BboxQuery>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewIncidents,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let tenant_id = match parse_tenant_id(&query.tenant_id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if auth.subject.tenant_id != tenant_id {
+        return bad_request("tenant mismatch");
+    }
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
+    let bbox = BoundingBox {
+        north: query.north,
+        south: query.south,
+        east: query.east,
+        west: query.west,
+    };
+    if !bbox.is_valid() {
+        return bad_request("invalid bounding box");
+    }
 
-    match IncidentRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
-        Ok(incidents) => HttpResponse::Ok().json(incidents),
-        Err(err) => internal_error(err.message),
+    match IncidentRepository::list_in_bbox(&state.store, tenant_id, bbox, limit).await {
+        Ok(incidents) => HttpResponse::Ok().json(marked(incidents)),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -67,9 +158,9 @@ pub async fn get_incident(
     let incident_id = c2_core::IncidentId::from_uuid(uuid);
 
     match IncidentRepository::get(&state.store, incident_id).await {
-        Ok(Some(incident)) => HttpResponse::Ok().json(incident),
+        Ok(Some(incident)) => conditional_json(&req, Marked::new(incident)),
         Ok(None) => not_found("incident not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -77,9 +168,12 @@ pub async fn get_incident(
 pub async fn upsert_incident(
     req: HttpRequest,
     state: web::Data<AppState>,
-    payload: web::Json<Incident>,
+    payload: web::Json<Value>,
 ) -> HttpResponse {
-    let incident = payload.into_inner();
+    let incident: Incident = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
     if let Err(response) = authorize_request(
         &req,
         &state.policy,
@@ -88,13 +182,37 @@ pub async fn upsert_incident(
     ) {
         return response;
     }
+    if let Err(message) = state.ceilings.check(incident.tenant_id, incident.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(incident.tenant_id, incident.classification) {
+        return forbidden(message);
+    }
     if incident.summary.trim().is_empty() {
         return bad_request("incident summary is required");
     }
+    if incident.position.is_some_and(|position| !position.is_valid()) {
+        return bad_request("incident position is out of range");
+    }
+    if incident
+        .affected_radius_m
+        .is_some_and(|radius| !radius.is_finite() || radius < 0.0)
+    {
+        return bad_request("affected_radius_m must be a non-negative number");
+    }
 
     match IncidentRepository::upsert(&state.store, incident.clone()).await {
-        Ok(()) => HttpResponse::Ok().json(incident),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Incident,
+                incident.id,
+                incident.tenant_id,
+                incident.classification,
+                &incident,
+            ));
+            HttpResponse::Ok().json(Marked::new(incident))
+        }
+        Err(err) => storage_error(err),
     }
 }
 
@@ -118,8 +236,19 @@ pub async fn delete_incident(
     };
     let incident_id = c2_core::IncidentId::from_uuid(uuid);
 
+    let existing = IncidentRepository::get(&state.store, incident_id).await.ok().flatten();
     match IncidentRepository::delete(&state.store, incident_id).await {
-        Ok(()) => HttpResponse::NoContent().finish(),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            if let Some(incident) = existing {
+                state.events.publish(DomainEvent::delete(
+                    EntityKind::Incident,
+                    incident.id,
+                    incident.tenant_id,
+                    incident.classification,
+                ));
+            }
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
     }
 }
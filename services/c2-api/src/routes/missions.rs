@@ -1,18 +1,32 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
-use c2_core::{Mission, SecurityClassification};
+use c2_core::{
+    now_epoch_millis, Mission, MissionId, MissionStatus, OperationalPriority,
+    SecurityClassification,
+};
 use c2_identity::Permission;
-use c2_storage::MissionRepository;
-use serde::Deserialize;
+use c2_messaging::{DomainEvent, EntityKind};
+use c2_storage::{MissionFilter, MissionRepository, TaskRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_tenant_id, parse_uuid};
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found,
+    parse_tenant_id, parse_uuid, storage_error, Marked,
+};
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ListQuery {
     pub tenant_id: String,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    pub status: Option<MissionStatus>,
+    pub priority: Option<OperationalPriority>,
+    pub classification: Option<SecurityClassification>,
+    /// Case-insensitive substring of the name.
+    pub q: Option<String>,
 }
 
 #[get("/v1/missions")]
@@ -37,12 +51,64 @@ pub async fn list_missions(
     if auth.subject.tenant_id != tenant_id {
         return bad_request("tenant mismatch");
     }
-    let limit = query.limit.unwrap_or(100);
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
     let offset = query.offset.unwrap_or(0);
+    let filter = MissionFilter {
+        status: query.status,
+        priority: query.priority,
+        classification: query.classification,
+        search: query.q.clone(),
+    };
 
-    match MissionRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
-        Ok(missions) => HttpResponse::Ok().json(missions),
-        Err(err) => internal_error(err.message),
+    let store = state.cleared_store(auth.subject.clearance);
+    match MissionRepository::list_filtered(&store, tenant_id, &filter, limit, offset).await {
+        Ok(missions) => HttpResponse::Ok().json(marked(missions)),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ArchivedQuery {
+    pub tenant_id: String,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+/// Archived missions, most recently archived first. `/v1/missions` leaves
+/// them out.
+#[get("/v1/missions/archived")]
+pub async fn list_archived_missions(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<ArchivedQuery>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let tenant_id = match parse_tenant_id(&query.tenant_id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if auth.subject.tenant_id != tenant_id {
+        return bad_request("tenant mismatch");
+    }
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
+    let offset = query.offset.unwrap_or(0);
+
+    let store = state.cleared_store(auth.subject.clearance);
+    match MissionRepository::list_archived_by_tenant(&store, tenant_id, limit, offset).await {
+        Ok(missions) => HttpResponse::Ok().json(marked(missions)),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -52,24 +118,75 @@ pub async fn get_mission(
     state: web::Data<AppState>,
     id: web::Path<String>,
 ) -> HttpResponse {
-    if let Err(response) = authorize_request(
+    let auth = match authorize_request(
         &req,
         &state.policy,
         Permission::ViewMissions,
         SecurityClassification::Unclassified,
     ) {
-        return response;
-    }
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
     let uuid = match parse_uuid(&id) {
         Ok(value) => value,
         Err(response) => return response,
     };
     let mission_id = c2_core::MissionId::from_uuid(uuid);
 
-    match MissionRepository::get(&state.store, mission_id).await {
-        Ok(Some(mission)) => HttpResponse::Ok().json(mission),
+    let store = state.cleared_store(auth.subject.clearance);
+    match MissionRepository::get(&store, mission_id).await {
+        Ok(Some(mission)) => conditional_json(&req, Marked::new(mission)),
         Ok(None) => not_found("mission not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MissionProgressView {
+    pub mission_id: MissionId,
+    pub total: u64,
+    pub completed: u64,
+    pub open: u64,
+    /// Share of tasks completed; cancelled tasks are not counted.
+    pub progress: f64,
+}
+
+#[get("/v1/missions/{id}/progress")]
+pub async fn get_mission_progress(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::ViewMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let uuid = match parse_uuid(&id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let mission_id = MissionId::from_uuid(uuid);
+    let store = state.cleared_store(auth.subject.clearance);
+
+    match MissionRepository::get(&store, mission_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return not_found("mission not found"),
+        Err(err) => return storage_error(err),
+    }
+    match TaskRepository::mission_progress(&store, mission_id).await {
+        Ok(progress) => HttpResponse::Ok().json(MissionProgressView {
+            mission_id,
+            total: progress.total,
+            completed: progress.completed,
+            open: progress.open(),
+            progress: progress.fraction(),
+        }),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -77,24 +194,72 @@ pub async fn get_mission(
 pub async fn upsert_mission(
     req: HttpRequest,
     state: web::Data<AppState>,
-    payload: web::Json<Mission>,
+    payload: web::Json<Value>,
 ) -> HttpResponse {
-    let mission = payload.into_inner();
-    if let Err(response) = authorize_request(
+    let mut mission: Mission = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let auth = match authorize_request(
         &req,
         &state.policy,
         Permission::EditMissions,
         mission.classification,
     ) {
-        return response;
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    if let Err(message) = state.ceilings.check(mission.tenant_id, mission.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(mission.tenant_id, mission.classification) {
+        return forbidden(message);
     }
     if mission.name.trim().is_empty() {
         return bad_request("mission name is required");
     }
+    if mission.status == MissionStatus::Completed {
+        let store = state.cleared_store(auth.subject.clearance);
+        match TaskRepository::mission_progress(&store, mission.id).await {
+            Ok(progress) if progress.open() > 0 => {
+                return bad_request(format!(
+                    "mission still has {} open task(s)",
+                    progress.open()
+                ));
+            }
+            Ok(_) => {}
+            Err(err) => return storage_error(err),
+        }
+    }
+
+    // Looks across every partition: a write moves the mission out of the
+    // one it was in, so a copy above the caller's clearance must not be
+    // replaced by one they can see. Nothing of it is returned.
+    let existing = match MissionRepository::get(&state.store, mission.id).await {
+        Ok(existing) => existing,
+        Err(err) => return storage_error(err),
+    };
+    if existing
+        .as_ref()
+        .is_some_and(|existing| existing.classification > auth.subject.clearance)
+    {
+        return forbidden("permission denied");
+    }
+    // Archiving goes through its own endpoints; a replace keeps the stored state.
+    mission.archived_at_ms = existing.and_then(|existing| existing.archived_at_ms);
 
     match MissionRepository::upsert(&state.store, mission.clone()).await {
-        Ok(()) => HttpResponse::Ok().json(mission),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Mission,
+                mission.id,
+                mission.tenant_id,
+                mission.classification,
+                &mission,
+            ));
+            HttpResponse::Ok().json(Marked::new(mission))
+        }
+        Err(err) => storage_error(err),
     }
 }
 
@@ -104,22 +269,275 @@ pub async fn delete_mission(
     state: web::Data<AppState>,
     id: web::Path<String>,
 ) -> HttpResponse {
-    if let Err(response) = authorize_request(
+    let auth = match authorize_request(
         &req,
         &state.policy,
         Permission::EditMissions,
         SecurityClassification::Restricted,
     ) {
-        return response;
-    }
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
     let uuid = match parse_uuid(&id) {
         Ok(value) => value,
         Err(response) => return response,
     };
     let mission_id = c2_core::MissionId::from_uuid(uuid);
 
+    // The delete reaches every partition, so only a mission the caller can
+    // see is deleted; anything else is already gone as far as they know.
+    let store = state.cleared_store(auth.subject.clearance);
+    let mission = match MissionRepository::get(&store, mission_id).await {
+        Ok(Some(mission)) if mission.tenant_id == auth.subject.tenant_id => mission,
+        Ok(_) => return HttpResponse::NoContent().finish(),
+        Err(err) => return storage_error(err),
+    };
+    if mission.is_archived() {
+        return bad_request("archived missions are kept; unarchive the mission to delete it");
+    }
     match MissionRepository::delete(&state.store, mission_id).await {
-        Ok(()) => HttpResponse::NoContent().finish(),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.events.publish(DomainEvent::delete(
+                EntityKind::Mission,
+                mission.id,
+                mission.tenant_id,
+                mission.classification,
+            ));
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
+    }
+}
+
+/// Takes a completed mission out of the default listings. It stays readable
+/// by id and under `/v1/missions/archived`, and cannot be deleted until it is
+/// unarchived.
+#[post("/v1/missions/{id}/archive")]
+pub async fn archive_mission(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> HttpResponse {
+    set_archived(&req, &state, &id, true).await
+}
+
+#[post("/v1/missions/{id}/unarchive")]
+pub async fn unarchive_mission(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> HttpResponse {
+    set_archived(&req, &state, &id, false).await
+}
+
+async fn set_archived(
+    req: &HttpRequest,
+    state: &AppState,
+    id: &str,
+    archived: bool,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        req,
+        &state.policy,
+        Permission::EditMissions,
+        SecurityClassification::Unclassified,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let uuid = match parse_uuid(id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let store = state.cleared_store(auth.subject.clearance);
+    let mission = match MissionRepository::get(&store, MissionId::from_uuid(uuid)).await {
+        Ok(Some(mission)) if mission.tenant_id == auth.subject.tenant_id => mission,
+        Ok(_) => return not_found("mission not found"),
+        Err(err) => return storage_error(err),
+    };
+    if let Err(response) =
+        authorize_request(req, &state.policy, Permission::EditMissions, mission.classification)
+    {
+        return response;
+    }
+    if archived && mission.status != MissionStatus::Completed {
+        return bad_request("only completed missions can be archived");
+    }
+
+    let result = if archived {
+        MissionRepository::archive(&state.store, mission.id, now_epoch_millis()).await
+    } else {
+        MissionRepository::unarchive(&state.store, mission.id, now_epoch_millis()).await
+    };
+    match result {
+        Ok(Some(updated)) => {
+            if updated.updated_at_ms != mission.updated_at_ms {
+                state.events.publish(DomainEvent::upsert(
+                    EntityKind::Mission,
+                    updated.id,
+                    updated.tenant_id,
+                    updated.classification,
+                    &updated,
+                ));
+            }
+            HttpResponse::Ok().json(Marked::new(updated))
+        }
+        Ok(None) => not_found("mission not found"),
+        Err(err) => storage_error(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state, app_state_with_events, Caller};
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::{test, App};
+    use c2_core::TenantId;
+    use c2_messaging::{
+        ChangeRelay, DomainChange, EventBus, EventOp, ZmqPublisherConfig, ZmqSubscriber,
+        ZmqSubscriberConfig, DOMAIN_CHANGE_SCHEMA, DOMAIN_CHANGE_TOPIC,
+    };
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[actix_web::test]
+    async fn missions_above_clearance_can_be_neither_read_nor_moved() {
+        let state = app_state().await;
+        let app = test::init_service(
+            App::new()
+                .app_data(state.clone())
+                .service(get_mission_progress)
+                .service(upsert_mission),
+        )
+        .await;
+        let tenant_id = TenantId::new();
+        let caller = Caller::new(tenant_id, SecurityClassification::Secret);
+        let mission = Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: "Delta".to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::TopSecret,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        MissionRepository::upsert(&state.store, mission.clone()).await.unwrap();
+
+        let request = caller.get(&format!("/v1/missions/{}/progress", mission.id)).to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let downgrade = Mission {
+            classification: SecurityClassification::Unclassified,
+            ..mission.clone()
+        };
+        let request = caller
+            .identify(TestRequest::post().uri("/v1/missions").set_json(&downgrade))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let stored = MissionRepository::get(&state.store, mission.id).await.unwrap().unwrap();
+        assert_eq!(stored.classification, SecurityClassification::TopSecret);
+    }
+
+    #[actix_web::test]
+    async fn upserts_publish_a_domain_change_on_the_bus() {
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let endpoint = format!("tcp://127.0.0.1:{port}");
+        let relay = ChangeRelay::new(&ZmqPublisherConfig::bind(endpoint.clone()), "c2-api");
+        let state = app_state_with_events(EventBus::default().with_relay(relay.unwrap())).await;
+        let topics = vec![DOMAIN_CHANGE_TOPIC.to_string()];
+        let subscriber = ZmqSubscriber::new(&ZmqSubscriberConfig::connect(endpoint, topics));
+        let subscriber = subscriber.unwrap();
+        let (sender, received) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(subscriber.recv::<DomainChange>());
+        });
+        thread::sleep(Duration::from_millis(200));
+
+        let app = test::init_service(App::new().app_data(state).service(upsert_mission)).await;
+        let tenant_id = TenantId::new();
+        let caller = Caller::new(tenant_id, SecurityClassification::Secret);
+        let mission = Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: "Harbor watch".to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Restricted,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        let request = caller
+            .identify(TestRequest::post().uri("/v1/missions").set_json(&mission))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+
+        let (topic, envelope) = received.recv_timeout(Duration::from_secs(5)).unwrap().unwrap();
+        assert_eq!(topic, DOMAIN_CHANGE_TOPIC);
+        assert_eq!(envelope.metadata.schema.as_deref(), Some(DOMAIN_CHANGE_SCHEMA));
+        assert_eq!(envelope.metadata.source_service, "c2-api");
+        let change = envelope.payload;
+        assert_eq!(change.id, mission.id.to_string());
+        assert_eq!(change.op, EventOp::Upsert);
+        assert_eq!(change.tenant_id, tenant_id);
+        assert_eq!(change.classification, SecurityClassification::Restricted);
+    }
+
+    #[actix_web::test]
+    async fn etags_change_with_the_record_even_when_updated_at_does_not() {
+        let state = app_state().await;
+        let app = test::init_service(
+            App::new().app_data(state.clone()).service(get_mission).service(upsert_mission),
+        )
+        .await;
+        let tenant_id = TenantId::new();
+        let caller = Caller::new(tenant_id, SecurityClassification::Secret);
+        let mission = Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: "Revalidate".to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        let uri = format!("/v1/missions/{}", mission.id);
+        let revalidate = |etag: &str| {
+            caller.get(&uri).insert_header(("If-None-Match", etag.to_string())).to_request()
+        };
+        MissionRepository::upsert(&state.store, mission.clone()).await.unwrap();
+
+        let response = test::call_service(&app, caller.get(&uri).to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers().get("ETag").unwrap().to_str().unwrap().to_string();
+        let response = test::call_service(&app, revalidate(&etag)).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let renamed = Mission {
+            name: "Revalidated".to_string(),
+            ..mission.clone()
+        };
+        let request = caller
+            .identify(TestRequest::post().uri("/v1/missions").set_json(&renamed))
+            .to_request();
+        assert!(test::call_service(&app, request).await.status().is_success());
+        let stored = MissionRepository::get(&state.store, mission.id).await.unwrap().unwrap();
+        assert_eq!(stored.updated_at_ms, 1);
+        let response = test::call_service(&app, revalidate(&etag)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers().get("ETag").unwrap().to_str().unwrap(), etag);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["name"], "Revalidated");
     }
 }
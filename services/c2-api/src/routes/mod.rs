@@ -1,15 +1,23 @@
 pub mod health;
+pub mod admin;
+pub mod api_keys;
 pub mod capabilities;
 pub mod assets;
+pub mod assignments;
+pub mod changes;
 pub mod common;
+pub mod export;
+pub mod geofences;
 pub mod incidents;
 pub mod missions;
 pub mod mcp;
+pub mod openapi;
 pub mod protobuf;
 pub mod sse;
 pub mod status;
 pub mod teams;
 pub mod tasks;
+pub mod tenants;
 pub mod units;
 pub mod ws;
 
@@ -17,13 +25,21 @@ use actix_web::web;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(health::health)
+        .service(openapi::openapi)
         .service(status::status)
+        .service(admin::get_read_only)
+        .service(admin::set_read_only)
         .service(missions::list_missions)
+        .service(missions::list_archived_missions)
         .service(missions::get_mission)
+        .service(missions::get_mission_progress)
         .service(missions::upsert_mission)
         .service(missions::delete_mission)
+        .service(missions::archive_mission)
+        .service(missions::unarchive_mission)
         .service(assets::list_assets)
         .service(assets::get_asset)
+        .service(assets::asset_status_history)
         .service(assets::upsert_asset)
         .service(assets::delete_asset)
         .service(units::list_units)
@@ -38,7 +54,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(capabilities::get_capability)
         .service(capabilities::upsert_capability)
         .service(capabilities::delete_capability)
+        .service(geofences::list_geofences)
+        .service(geofences::get_geofence)
+        .service(geofences::upsert_geofence)
+        .service(geofences::delete_geofence)
         .service(incidents::list_incidents)
+        .service(incidents::incidents_in_bbox)
         .service(incidents::get_incident)
         .service(incidents::upsert_incident)
         .service(incidents::delete_incident)
@@ -46,6 +67,17 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(tasks::get_task)
         .service(tasks::upsert_task)
         .service(tasks::delete_task)
+        .service(assignments::list_mission_assignments)
+        .service(assignments::list_unit_assignments)
+        .service(assignments::assign_to_mission)
+        .service(assignments::unassign)
+        .service(changes::list_changes)
+        .service(export::export_missions)
+        .service(export::export_incidents)
+        .service(tenants::delete_tenant)
+        .service(api_keys::list_api_keys)
+        .service(api_keys::create_api_key)
+        .service(api_keys::revoke_api_key)
         .service(protobuf::mission_proto)
         .service(protobuf::task_proto)
         .service(sse::sse)
@@ -0,0 +1,420 @@
+use actix_web::{get, HttpResponse};
+use c2_core::{Asset, AssetStatusEvent, Incident, Mission, Task};
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+use std::sync::OnceLock;
+
+use crate::auth::API_KEY_HEADER;
+use crate::routes::common::ErrorResponse;
+use crate::routes::{assets, incidents, missions, tasks};
+
+/// OpenAPI 3 description of the mission, asset, incident and task endpoints,
+/// built once from the route query types and the c2-core records.
+#[get("/openapi.json")]
+pub async fn openapi() -> HttpResponse {
+    static DOCUMENT: OnceLock<Value> = OnceLock::new();
+    HttpResponse::Ok().json(DOCUMENT.get_or_init(document))
+}
+
+pub fn document() -> Value {
+    let mut spec = Spec::new();
+
+    let query = spec.query::<missions::ListQuery>();
+    let missions = spec.list_of::<Mission>();
+    spec.operation("/v1/missions", "get", list("List a tenant's missions.", query, missions));
+    let (body, mission) = (spec.schema::<Mission>(), spec.marked::<Mission>());
+    spec.operation("/v1/missions", "post", upsert("Create or replace a mission.", body, mission));
+    let mission = spec.marked::<Mission>();
+    spec.operation("/v1/missions/{id}", "get", get_record("Fetch a mission.", mission));
+    spec.operation("/v1/missions/{id}", "delete", delete_by_id("Delete a mission."));
+    let query = spec.query::<missions::ArchivedQuery>();
+    let archived = spec.list_of::<Mission>();
+    let summary = "List a tenant's archived missions.";
+    spec.operation("/v1/missions/archived", "get", list(summary, query, archived));
+    let mission = spec.marked::<Mission>();
+    let archive = action_by_id("Archive a completed mission.", mission);
+    spec.operation("/v1/missions/{id}/archive", "post", archive);
+    let mission = spec.marked::<Mission>();
+    let unarchive = action_by_id("Return an archived mission to the listings.", mission);
+    spec.operation("/v1/missions/{id}/unarchive", "post", unarchive);
+    let progress = spec.schema::<missions::MissionProgressView>();
+    spec.operation(
+        "/v1/missions/{id}/progress",
+        "get",
+        get_by_id("Share of the mission's tasks completed.", progress),
+    );
+
+    let query = spec.query::<tasks::ListQuery>();
+    let tasks = spec.list_of::<Task>();
+    let mut list_tasks = list("List a mission's tasks.", query, tasks);
+    push_parameter(&mut list_tasks, path_id("mission_id"));
+    spec.operation("/v1/missions/{mission_id}/tasks", "get", list_tasks);
+    let (body, task) = (spec.schema::<Task>(), spec.marked::<Task>());
+    spec.operation("/v1/tasks", "post", upsert("Create or replace a task.", body, task));
+    let task = spec.marked::<Task>();
+    spec.operation("/v1/tasks/{id}", "get", get_record("Fetch a task.", task));
+    spec.operation("/v1/tasks/{id}", "delete", delete_by_id("Delete a task."));
+
+    let query = spec.query::<assets::ListQuery>();
+    let assets = spec.list_of::<Asset>();
+    spec.operation("/v1/assets", "get", list("List a tenant's assets.", query, assets));
+    let (body, asset) = (spec.schema::<Asset>(), spec.marked::<Asset>());
+    spec.operation("/v1/assets", "post", upsert("Create or replace an asset.", body, asset));
+    let asset = spec.marked::<Asset>();
+    spec.operation("/v1/assets/{id}", "get", get_record("Fetch an asset.", asset));
+    spec.operation("/v1/assets/{id}", "delete", delete_by_id("Delete an asset."));
+    let query = spec.query::<assets::StatusHistoryQuery>();
+    let history = json!({ "type": "array", "items": spec.schema::<AssetStatusEvent>() });
+    let mut status_history = list("An asset's recorded status changes.", query, history);
+    push_parameter(&mut status_history, path_id("id"));
+    spec.operation("/v1/assets/{id}/status-history", "get", status_history);
+
+    let query = spec.query::<incidents::ListQuery>();
+    let incidents = spec.list_of::<Incident>();
+    spec.operation(
+        "/v1/incidents",
+        "get",
+        list("List a tenant's incidents.", query, incidents),
+    );
+    let (body, incident) = (spec.schema::<Incident>(), spec.marked::<Incident>());
+    spec.operation(
+        "/v1/incidents",
+        "post",
+        upsert("Create or replace an incident.", body, incident),
+    );
+    let query = spec.query::<incidents::BboxQuery>();
+    let incidents = spec.list_of::<Incident>();
+    spec.operation(
+        "/v1/incidents/bbox",
+        "get",
+        list("List a tenant's incidents inside a bounding box.", query, incidents),
+    );
+    let incident = spec.marked::<Incident>();
+    spec.operation("/v1/incidents/{id}", "get", get_record("Fetch an incident.", incident));
+    spec.operation("/v1/incidents/{id}", "delete", delete_by_id("Delete an incident."));
+
+    spec.finish()
+}
+
+struct Spec {
+    generator: SchemaGenerator,
+    paths: Map<String, Value>,
+}
+
+impl Spec {
+    fn new() -> Self {
+        Self {
+            generator: SchemaSettings::openapi3().into_generator(),
+            paths: Map::new(),
+        }
+    }
+
+    /// A reference to `T` under `components/schemas`.
+    fn schema<T: JsonSchema>(&mut self) -> Value {
+        self.generator.subschema_for::<T>().to_value()
+    }
+
+    /// `T` with the `marking` banner the API adds to every record.
+    fn marked<T: JsonSchema>(&mut self) -> Value {
+        json!({
+            "allOf": [
+                self.schema::<T>(),
+                {
+                    "type": "object",
+                    "required": ["marking"],
+                    "properties": {
+                        "marking": {
+                            "type": "string",
+                            "description": "Banner text for the record's classification.",
+                        },
+                    },
+                },
+            ],
+        })
+    }
+
+    fn list_of<T: JsonSchema>(&mut self) -> Value {
+        json!({ "type": "array", "items": self.marked::<T>() })
+    }
+
+    /// One query parameter per field of `Q`, required unless optional.
+    fn query<Q: JsonSchema>(&mut self) -> Vec<Value> {
+        let schema = Q::json_schema(&mut self.generator);
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, schema)| {
+                        let mut schema = non_null(schema.clone());
+                        let description = schema
+                            .as_object_mut()
+                            .and_then(|schema| schema.remove("description"));
+                        let mut parameter = json!({
+                            "name": name,
+                            "in": "query",
+                            "required": required.contains(&name.as_str()),
+                            "schema": schema,
+                        });
+                        if let Some(description) = description {
+                            parameter["description"] = description;
+                        }
+                        parameter
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn operation(&mut self, path: &str, method: &str, operation: Value) {
+        let item = self
+            .paths
+            .entry(path.to_string())
+            .or_insert_with(|| json!({}));
+        item[method] = operation;
+    }
+
+    fn finish(mut self) -> Value {
+        // Registered here since the operations only refer to it by name.
+        self.schema::<ErrorResponse>();
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "c2-api",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": self.paths,
+            "components": {
+                "schemas": self.generator.take_definitions(true),
+                "securitySchemes": {
+                    "apiKey": {
+                        "type": "apiKey",
+                        "in": "header",
+                        "name": API_KEY_HEADER,
+                    },
+                    "tenantId": identity_header("x-c2-tenant-id", "Caller's tenant UUID."),
+                    "userId": identity_header("x-c2-user-id", "Caller's user UUID."),
+                    "roles": identity_header("x-c2-roles", "Comma-separated roles."),
+                    "permissions": identity_header(
+                        "x-c2-permissions",
+                        "Comma-separated permissions.",
+                    ),
+                },
+            },
+            // An API key, or the identity headers set by a trusted gateway.
+            "security": [
+                { "apiKey": [] },
+                { "tenantId": [], "userId": [], "roles": [], "permissions": [] },
+            ],
+        })
+    }
+}
+
+/// `schema` without the `null` an `Option` field allows; an omitted query
+/// parameter already says that.
+fn non_null(mut schema: Value) -> Value {
+    let is_null = |variant: &Value| {
+        variant.get("type") == Some(&json!("null")) || variant.get("const") == Some(&Value::Null)
+    };
+    if let Some(variants) = schema.get("anyOf").and_then(Value::as_array)
+        && variants.len() == 2
+        && let Some(position) = variants.iter().position(is_null)
+    {
+        let mut inner = variants[1 - position].clone();
+        if let (Some(inner), Some(outer)) = (inner.as_object_mut(), schema.as_object()) {
+            for (key, value) in outer.iter().filter(|(key, _)| *key != "anyOf") {
+                inner.insert(key.clone(), value.clone());
+            }
+        }
+        return inner;
+    }
+    if let Some(types) = schema.get_mut("type").and_then(Value::as_array_mut) {
+        types.retain(|value| value != "null");
+        if types.len() == 1 {
+            schema["type"] = types.remove(0);
+        }
+    }
+    schema
+}
+
+fn identity_header(name: &str, description: &str) -> Value {
+    json!({ "type": "apiKey", "in": "header", "name": name, "description": description })
+}
+
+fn path_id(name: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "schema": { "type": "string", "format": "uuid" },
+    })
+}
+
+fn push_parameter(operation: &mut Value, parameter: Value) {
+    if let Some(parameters) = operation["parameters"].as_array_mut() {
+        parameters.push(parameter);
+    }
+}
+
+fn json_content(schema: Value) -> Value {
+    json!({ "application/json": { "schema": schema } })
+}
+
+/// The error responses every operation shares, plus `extra`.
+fn responses(success: (&str, Value), extra: &[(&str, &str)]) -> Value {
+    let error = json_content(json!({ "$ref": "#/components/schemas/ErrorResponse" }));
+    let mut responses = json!({
+        "400": { "description": "Invalid request.", "content": error },
+        "401": { "description": "Missing or invalid credentials.", "content": error },
+        "403": { "description": "Permission or policy denied.", "content": error },
+        "429": { "description": "Tenant rate limit exceeded.", "content": error },
+        "500": { "description": "Storage failure.", "content": error },
+        "504": { "description": "Storage timed out.", "content": error },
+    });
+    responses[success.0] = success.1;
+    for (status, description) in extra {
+        responses[*status] = json!({ "description": description, "content": error });
+    }
+    responses
+}
+
+fn list(summary: &str, parameters: Vec<Value>, items: Value) -> Value {
+    json!({
+        "summary": summary,
+        "parameters": parameters,
+        "responses": responses(
+            ("200", json!({ "description": "OK", "content": json_content(items) })),
+            &[],
+        ),
+    })
+}
+
+fn get_by_id(summary: &str, record: Value) -> Value {
+    json!({
+        "summary": summary,
+        "parameters": [path_id("id")],
+        "responses": responses(
+            ("200", json!({ "description": "OK", "content": json_content(record) })),
+            &[("404", "Not found.")],
+        ),
+    })
+}
+
+/// [`get_by_id`] with a weak `ETag` from the record's `updated_at_ms`, so
+/// pollers can revalidate with `If-None-Match`.
+fn get_record(summary: &str, record: Value) -> Value {
+    let mut operation = get_by_id(summary, record);
+    push_parameter(
+        &mut operation,
+        json!({
+            "name": "If-None-Match",
+            "in": "header",
+            "required": false,
+            "schema": { "type": "string" },
+            "description": "ETag of a previous response; an unchanged record returns 304.",
+        }),
+    );
+    operation["responses"]["304"] = json!({ "description": "Not modified since that ETag." });
+    operation
+}
+
+fn upsert(summary: &str, body: Value, record: Value) -> Value {
+    json!({
+        "summary": summary,
+        "requestBody": { "required": true, "content": json_content(body) },
+        "responses": responses(
+            ("200", json!({ "description": "Stored.", "content": json_content(record) })),
+            &[("503", "Writes are paused in read-only mode.")],
+        ),
+    })
+}
+
+fn action_by_id(summary: &str, record: Value) -> Value {
+    json!({
+        "summary": summary,
+        "parameters": [path_id("id")],
+        "responses": responses(
+            ("200", json!({ "description": "OK", "content": json_content(record) })),
+            &[("404", "Not found."), ("503", "Writes are paused in read-only mode.")],
+        ),
+    })
+}
+
+fn delete_by_id(summary: &str) -> Value {
+    json!({
+        "summary": summary,
+        "parameters": [path_id("id")],
+        "responses": responses(
+            ("204", json!({ "description": "Deleted, or already absent." })),
+            &[("503", "Writes are paused in read-only mode.")],
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, App};
+
+    /// Every `$ref` in `value`.
+    fn refs<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(target) = map.get("$ref").and_then(Value::as_str) {
+                    found.push(target);
+                }
+                map.values().for_each(|value| refs(value, found));
+            }
+            Value::Array(values) => values.iter().for_each(|value| refs(value, found)),
+            _ => {}
+        }
+    }
+
+    #[actix_web::test]
+    async fn served_document_is_openapi_and_lists_missions() {
+        let app = test::init_service(App::new().service(openapi)).await;
+        let request = test::TestRequest::get().uri("/openapi.json").to_request();
+        let document: Value = test::call_and_read_body_json(&app, request).await;
+
+        assert!(document["openapi"].as_str().unwrap().starts_with("3."));
+        assert!(document["info"]["title"].is_string());
+        assert!(document["info"]["version"].is_string());
+        let list_missions = &document["paths"]["/v1/missions"]["get"]["responses"]["200"];
+        assert_eq!(list_missions["content"]["application/json"]["schema"]["type"], "array");
+
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        let mut found = Vec::new();
+        refs(&document, &mut found);
+        for target in found {
+            let name = target.strip_prefix("#/components/schemas/").unwrap();
+            assert!(schemas.contains_key(name), "unresolved {target}");
+        }
+
+        let schemes = document["components"]["securitySchemes"].as_object().unwrap();
+        for requirement in document["security"].as_array().unwrap() {
+            for scheme in requirement.as_object().unwrap().keys() {
+                assert!(schemes.contains_key(scheme), "undeclared scheme {scheme}");
+            }
+        }
+        for (path, item) in document["paths"].as_object().unwrap() {
+            for (method, operation) in item.as_object().unwrap() {
+                assert!(operation["responses"].is_object(), "{method} {path}");
+                let parameters = operation["parameters"].as_array().cloned().unwrap_or_default();
+                for segment in path.split('/').filter(|segment| segment.starts_with('{')) {
+                    let name = segment.trim_matches(|c| c == '{' || c == '}');
+                    assert!(
+                        parameters.iter().any(|p| p["in"] == "path" && p["name"] == name),
+                        "{method} {path} lacks path parameter {name}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -6,7 +6,7 @@ use c2_storage::{MissionRepository, TaskRepository};
 use prost::Message;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_uuid};
+use crate::routes::common::{bad_request, not_found, parse_uuid, storage_error};
 use crate::state::AppState;
 
 #[get("/v1/missions/{id}/proto")]
@@ -15,21 +15,23 @@ pub async fn mission_proto(
     state: web::Data<AppState>,
     id: web::Path<String>,
 ) -> HttpResponse {
-    if let Err(response) = authorize_request(
+    let auth = match authorize_request(
         &req,
         &state.policy,
         Permission::ViewMissions,
         SecurityClassification::Unclassified,
     ) {
-        return response;
-    }
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
     let uuid = match parse_uuid(&id) {
         Ok(value) => value,
         Err(response) => return response,
     };
     let mission_id = c2_core::MissionId::from_uuid(uuid);
 
-    match MissionRepository::get(&state.store, mission_id).await {
+    let store = state.cleared_store(auth.subject.clearance);
+    match MissionRepository::get(&store, mission_id).await {
         Ok(Some(mission)) => {
             let message = mission_to_proto(&mission);
             HttpResponse::Ok()
@@ -37,7 +39,7 @@ pub async fn mission_proto(
                 .body(message.encode_to_vec())
         }
         Ok(None) => not_found("mission not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -69,7 +71,7 @@ pub async fn task_proto(
                 .body(message.encode_to_vec())
         }
         Ok(None) => not_found("task not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
     }
 }
 
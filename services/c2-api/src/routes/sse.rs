@@ -3,29 +3,82 @@ use actix_web::rt::time::interval;
 use actix_web::web::Bytes;
 use c2_core::SecurityClassification;
 use c2_identity::Permission;
-use futures_util::stream::unfold;
+use c2_messaging::{DomainEvent, Replay};
+use futures_util::stream::{self, unfold, StreamExt};
 use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::auth::authorize_request;
+use crate::events;
 use crate::state::AppState;
 
+/// Streams heartbeats plus a `change` event per write visible to the caller,
+/// with the tenant's event sequence as the SSE id. A reconnect sending
+/// `Last-Event-ID` first gets the buffered changes after that id. A `resync`
+/// event means changes were dropped and the client should refetch.
 #[get("/v1/stream/sse")]
 pub async fn sse(req: HttpRequest, state: web::Data<AppState>) -> HttpResponse {
-    if let Err(response) = authorize_request(
+    let auth = match authorize_request(
         &req,
         &state.policy,
         Permission::ViewMissions,
         SecurityClassification::Unclassified,
     ) {
-        return response;
-    }
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
 
     let interval = interval(Duration::from_secs(2));
-    let stream = unfold((interval, 0u64), |(mut interval, counter)| async move {
+    let heartbeats = unfold((interval, 0u64), |(mut interval, counter)| async move {
         interval.tick().await;
         let payload = format!("event: heartbeat\ndata: {}\n\n", counter);
-        let bytes = Bytes::from(payload);
-        Some((Ok::<Bytes, actix_web::Error>(bytes), (interval, counter + 1)))
+        Some((Some(payload), (interval, counter + 1)))
+    });
+
+    let clearance = auth.subject.clearance;
+    let permissions = auth.permissions;
+    let tenant_id = auth.subject.tenant_id;
+    let last_event_id = req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    let (replayed, receiver) = match last_event_id {
+        Some(sequence) => match state.events.resume(tenant_id, sequence) {
+            (Replay::Events(events), receiver) => (
+                events
+                    .iter()
+                    .filter(|event| events::visible(event, clearance, &permissions))
+                    .map(change_payload)
+                    .collect(),
+                receiver,
+            ),
+            (Replay::Gap, receiver) => {
+                (vec![Some("event: resync\ndata: {}\n\n".to_string())], receiver)
+            }
+        },
+        None => (Vec::new(), state.events.subscribe(tenant_id)),
+    };
+    let live = unfold(receiver, move |mut receiver| {
+        let permissions = permissions.clone();
+        async move {
+            let payload = match receiver.recv().await {
+                Ok(event) if events::visible(&event, clearance, &permissions) => {
+                    change_payload(&event)
+                }
+                Ok(_) => None,
+                Err(RecvError::Lagged(missed)) => {
+                    Some(format!("event: resync\ndata: {{\"missed\":{}}}\n\n", missed))
+                }
+                Err(RecvError::Closed) => return None,
+            };
+            Some((payload, receiver))
+        }
+    });
+
+    let changes = stream::iter(replayed).chain(live);
+    let stream = stream::select(heartbeats, changes).filter_map(|payload| async move {
+        payload.map(|payload| Ok::<Bytes, actix_web::Error>(Bytes::from(payload)))
     });
 
     HttpResponse::Ok()
@@ -34,3 +87,66 @@ pub async fn sse(req: HttpRequest, state: web::Data<AppState>) -> HttpResponse {
         .insert_header(("Connection", "keep-alive"))
         .streaming(stream)
 }
+
+fn change_payload(event: &DomainEvent) -> Option<String> {
+    serde_json::to_string(event)
+        .ok()
+        .map(|data| format!("id: {}\nevent: change\ndata: {}\n\n", event.sequence, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state_with_events, Caller};
+    use actix_web::body::MessageBody;
+    use actix_web::{test, App};
+    use c2_core::{MissionId, TenantId};
+    use c2_messaging::{EntityKind, EventBus};
+    use futures_util::future::poll_fn;
+    use std::pin::Pin;
+
+    /// Reads the stream until `until` shows up in it.
+    async fn read_until(body: &mut (impl MessageBody + Unpin), until: &str) -> String {
+        let mut text = String::new();
+        while !text.contains(until) {
+            let chunk = poll_fn(|cx| Pin::new(&mut *body).poll_next(cx));
+            let chunk = actix_web::rt::time::timeout(Duration::from_secs(5), chunk).await;
+            let chunk = chunk.unwrap().unwrap().ok().unwrap();
+            text.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+        text
+    }
+
+    #[actix_web::test]
+    async fn reconnects_replay_only_the_buffered_changes_after_last_event_id() {
+        // Two buffered events per tenant, so the first of three is dropped.
+        let state = app_state_with_events(EventBus::new(2)).await;
+        let app = test::init_service(App::new().app_data(state.clone()).service(sse)).await;
+        let tenant_id = TenantId::new();
+        let caller = Caller::new(tenant_id, SecurityClassification::Secret);
+        for _ in 0..3 {
+            state.events.publish(DomainEvent::delete(
+                EntityKind::Mission,
+                MissionId::new(),
+                tenant_id,
+                SecurityClassification::Unclassified,
+            ));
+        }
+        let connect = |last_event_id: &str| {
+            let request = caller
+                .get("/v1/stream/sse")
+                .insert_header(("Last-Event-ID", last_event_id.to_string()));
+            test::call_service(&app, request.to_request())
+        };
+
+        let mut body = connect("1").await.into_body();
+        let text = read_until(&mut body, "id: 3\n").await;
+        assert!(text.contains("id: 2\nevent: change\n"));
+        assert!(!text.contains("id: 1\n"));
+        assert!(!text.contains("event: resync"));
+
+        let mut body = connect("0").await.into_body();
+        let text = read_until(&mut body, "event: resync\n").await;
+        assert!(!text.contains("event: change"));
+    }
+}
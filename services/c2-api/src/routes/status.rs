@@ -9,6 +9,7 @@ struct StatusResponse {
     service: String,
     environment: String,
     region: Option<String>,
+    read_only: bool,
     timestamp_ms: u64,
 }
 
@@ -18,6 +19,7 @@ pub async fn status(state: web::Data<AppState>) -> HttpResponse {
         service: state.config.service_name.clone(),
         environment: state.config.environment.to_string(),
         region: state.config.region.clone(),
+        read_only: state.read_only.is_enabled(),
         timestamp_ms: now_epoch_millis(),
     };
 
@@ -1,14 +1,20 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
 use c2_core::{SecurityClassification, Task};
 use c2_identity::Permission;
-use c2_storage::TaskRepository;
+use c2_messaging::{DomainEvent, EntityKind};
+use c2_storage::{check_task_dependencies, TaskRepository};
+use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_uuid};
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found, parse_uuid,
+    storage_error, Marked,
+};
 use crate::state::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ListQuery {
     pub limit: Option<usize>,
     pub offset: Option<usize>,
@@ -34,12 +40,14 @@ pub async fn list_tasks(
         Err(response) => return response,
     };
     let mission_id = c2_core::MissionId::from_uuid(uuid);
-    let limit = query.limit.unwrap_or(100);
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
     let offset = query.offset.unwrap_or(0);
 
     match TaskRepository::list_by_mission(&state.store, mission_id, limit, offset).await {
-        Ok(tasks) => HttpResponse::Ok().json(tasks),
-        Err(err) => internal_error(err.message),
+        Ok(tasks) => HttpResponse::Ok().json(marked(tasks)),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -64,9 +72,9 @@ pub async fn get_task(
     let task_id = c2_core::TaskId::from_uuid(uuid);
 
     match TaskRepository::get(&state.store, task_id).await {
-        Ok(Some(task)) => HttpResponse::Ok().json(task),
+        Ok(Some(task)) => conditional_json(&req, Marked::new(task)),
         Ok(None) => not_found("task not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -74,9 +82,12 @@ pub async fn get_task(
 pub async fn upsert_task(
     req: HttpRequest,
     state: web::Data<AppState>,
-    payload: web::Json<Task>,
+    payload: web::Json<Value>,
 ) -> HttpResponse {
-    let task = payload.into_inner();
+    let task: Task = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
     if let Err(response) = authorize_request(
         &req,
         &state.policy,
@@ -85,13 +96,33 @@ pub async fn upsert_task(
     ) {
         return response;
     }
+    if let Err(message) = state.ceilings.check(task.tenant_id, task.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(task.tenant_id, task.classification) {
+        return forbidden(message);
+    }
     if task.title.trim().is_empty() {
         return bad_request("task title is required");
     }
+    match check_task_dependencies(&state.store, &task).await {
+        Ok(None) => {}
+        Ok(Some(conflict)) => return bad_request(conflict.to_string()),
+        Err(err) => return storage_error(err),
+    }
 
     match TaskRepository::upsert(&state.store, task.clone()).await {
-        Ok(()) => HttpResponse::Ok().json(task),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Task,
+                task.id,
+                task.tenant_id,
+                task.classification,
+                &task,
+            ));
+            HttpResponse::Ok().json(Marked::new(task))
+        }
+        Err(err) => storage_error(err),
     }
 }
 
@@ -115,8 +146,19 @@ pub async fn delete_task(
     };
     let task_id = c2_core::TaskId::from_uuid(uuid);
 
+    let existing = TaskRepository::get(&state.store, task_id).await.ok().flatten();
     match TaskRepository::delete(&state.store, task_id).await {
-        Ok(()) => HttpResponse::NoContent().finish(),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            if let Some(task) = existing {
+                state.events.publish(DomainEvent::delete(
+                    EntityKind::Task,
+                    task.id,
+                    task.tenant_id,
+                    task.classification,
+                ));
+            }
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
     }
 }
@@ -1,11 +1,16 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
 use c2_core::{SecurityClassification, Team};
 use c2_identity::Permission;
+use c2_messaging::{DomainEvent, EntityKind};
 use c2_storage::TeamRepository;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_tenant_id, parse_uuid};
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found,
+    parse_tenant_id, parse_uuid, storage_error, Marked,
+};
 use crate::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -37,12 +42,14 @@ pub async fn list_teams(
     if auth.subject.tenant_id != tenant_id {
         return bad_request("tenant mismatch");
     }
-    let limit = query.limit.unwrap_or(100);
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
     let offset = query.offset.unwrap_or(0);
 
     match TeamRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
-        Ok(teams) => HttpResponse::Ok().json(teams),
-        Err(err) => internal_error(err.message),
+        Ok(teams) => HttpResponse::Ok().json(marked(teams)),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -67,9 +74,9 @@ pub async fn get_team(
     let team_id = c2_core::TeamId::from_uuid(uuid);
 
     match TeamRepository::get(&state.store, team_id).await {
-        Ok(Some(team)) => HttpResponse::Ok().json(team),
+        Ok(Some(team)) => conditional_json(&req, Marked::new(team)),
         Ok(None) => not_found("team not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -77,9 +84,12 @@ pub async fn get_team(
 pub async fn upsert_team(
     req: HttpRequest,
     state: web::Data<AppState>,
-    payload: web::Json<Team>,
+    payload: web::Json<Value>,
 ) -> HttpResponse {
-    let team = payload.into_inner();
+    let team: Team = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
     if let Err(response) = authorize_request(
         &req,
         &state.policy,
@@ -88,13 +98,28 @@ pub async fn upsert_team(
     ) {
         return response;
     }
+    if let Err(message) = state.ceilings.check(team.tenant_id, team.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(team.tenant_id, team.classification) {
+        return forbidden(message);
+    }
     if team.name.trim().is_empty() {
         return bad_request("team name is required");
     }
 
     match TeamRepository::upsert(&state.store, team.clone()).await {
-        Ok(()) => HttpResponse::Ok().json(team),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Team,
+                team.id,
+                team.tenant_id,
+                team.classification,
+                &team,
+            ));
+            HttpResponse::Ok().json(Marked::new(team))
+        }
+        Err(err) => storage_error(err),
     }
 }
 
@@ -118,8 +143,19 @@ pub async fn delete_team(
     };
     let team_id = c2_core::TeamId::from_uuid(uuid);
 
+    let existing = TeamRepository::get(&state.store, team_id).await.ok().flatten();
     match TeamRepository::delete(&state.store, team_id).await {
-        Ok(()) => HttpResponse::NoContent().finish(),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            if let Some(team) = existing {
+                state.events.publish(DomainEvent::delete(
+                    EntityKind::Team,
+                    team.id,
+                    team.tenant_id,
+                    team.classification,
+                ));
+            }
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
     }
 }
@@ -0,0 +1,76 @@
+use actix_web::{delete, web, HttpRequest, HttpResponse};
+use c2_core::SecurityClassification;
+use c2_identity::Permission;
+use c2_storage::{delete_tenant_data, TenantDeletion};
+use serde::Serialize;
+
+use crate::auth::authorize_request;
+use crate::routes::common::{bad_request, parse_tenant_id, storage_error};
+use crate::state::AppState;
+
+#[derive(Debug, Serialize)]
+pub struct TenantDeletionView {
+    pub assignments: u64,
+    pub tasks: u64,
+    pub missions: u64,
+    pub incidents: u64,
+    pub geofences: u64,
+    pub assets: u64,
+    pub capabilities: u64,
+    pub teams: u64,
+    pub units: u64,
+    pub api_keys: u64,
+    pub total: u64,
+}
+
+impl From<TenantDeletion> for TenantDeletionView {
+    fn from(value: TenantDeletion) -> Self {
+        Self {
+            assignments: value.assignments,
+            tasks: value.tasks,
+            missions: value.missions,
+            incidents: value.incidents,
+            geofences: value.geofences,
+            assets: value.assets,
+            capabilities: value.capabilities,
+            teams: value.teams,
+            units: value.units,
+            api_keys: value.api_keys,
+            total: value.total(),
+        }
+    }
+}
+
+/// Removes all of the caller's tenant data, for offboarding. Change streams
+/// get no per-record delete events.
+#[delete("/v1/tenants/{tenant_id}/data")]
+pub async fn delete_tenant(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    tenant_id: web::Path<String>,
+) -> HttpResponse {
+    let auth = match authorize_request(
+        &req,
+        &state.policy,
+        Permission::Admin,
+        SecurityClassification::Restricted,
+    ) {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+    let tenant_id = match parse_tenant_id(&tenant_id) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if auth.subject.tenant_id != tenant_id {
+        return bad_request("tenant mismatch");
+    }
+
+    match delete_tenant_data(&state.store, tenant_id).await {
+        Ok(deleted) => {
+            state.capabilities.invalidate(tenant_id);
+            HttpResponse::Ok().json(TenantDeletionView::from(deleted))
+        }
+        Err(err) => storage_error(err),
+    }
+}
@@ -1,11 +1,16 @@
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
 use c2_core::{SecurityClassification, Unit};
 use c2_identity::Permission;
+use c2_messaging::{DomainEvent, EntityKind};
 use c2_storage::UnitRepository;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::auth::authorize_request;
-use crate::routes::common::{bad_request, internal_error, not_found, parse_tenant_id, parse_uuid};
+use crate::routes::common::{
+    bad_request, classified_record, conditional_json, forbidden, marked, not_found,
+    parse_tenant_id, parse_uuid, storage_error, Marked,
+};
 use crate::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -37,12 +42,14 @@ pub async fn list_units(
     if auth.subject.tenant_id != tenant_id {
         return bad_request("tenant mismatch");
     }
-    let limit = query.limit.unwrap_or(100);
+    let Some(limit) = state.config.page_limit(query.limit) else {
+        return bad_request("limit exceeds maximum page size");
+    };
     let offset = query.offset.unwrap_or(0);
 
     match UnitRepository::list_by_tenant(&state.store, tenant_id, limit, offset).await {
-        Ok(units) => HttpResponse::Ok().json(units),
-        Err(err) => internal_error(err.message),
+        Ok(units) => HttpResponse::Ok().json(marked(units)),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -67,9 +74,9 @@ pub async fn get_unit(
     let unit_id = c2_core::UnitId::from_uuid(uuid);
 
     match UnitRepository::get(&state.store, unit_id).await {
-        Ok(Some(unit)) => HttpResponse::Ok().json(unit),
+        Ok(Some(unit)) => conditional_json(&req, Marked::new(unit)),
         Ok(None) => not_found("unit not found"),
-        Err(err) => internal_error(err.message),
+        Err(err) => storage_error(err),
     }
 }
 
@@ -77,9 +84,12 @@ pub async fn get_unit(
 pub async fn upsert_unit(
     req: HttpRequest,
     state: web::Data<AppState>,
-    payload: web::Json<Unit>,
+    payload: web::Json<Value>,
 ) -> HttpResponse {
-    let unit = payload.into_inner();
+    let unit: Unit = match classified_record(payload.into_inner(), &state.floors) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
     if let Err(response) = authorize_request(
         &req,
         &state.policy,
@@ -88,13 +98,28 @@ pub async fn upsert_unit(
     ) {
         return response;
     }
+    if let Err(message) = state.ceilings.check(unit.tenant_id, unit.classification) {
+        return forbidden(message);
+    }
+    if let Err(message) = state.floors.check(unit.tenant_id, unit.classification) {
+        return forbidden(message);
+    }
     if unit.display_name.trim().is_empty() {
         return bad_request("unit display_name is required");
     }
 
     match UnitRepository::upsert(&state.store, unit.clone()).await {
-        Ok(()) => HttpResponse::Ok().json(unit),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            state.events.publish(DomainEvent::upsert(
+                EntityKind::Unit,
+                unit.id,
+                unit.tenant_id,
+                unit.classification,
+                &unit,
+            ));
+            HttpResponse::Ok().json(Marked::new(unit))
+        }
+        Err(err) => storage_error(err),
     }
 }
 
@@ -118,8 +143,19 @@ pub async fn delete_unit(
     };
     let unit_id = c2_core::UnitId::from_uuid(uuid);
 
+    let existing = UnitRepository::get(&state.store, unit_id).await.ok().flatten();
     match UnitRepository::delete(&state.store, unit_id).await {
-        Ok(()) => HttpResponse::NoContent().finish(),
-        Err(err) => internal_error(err.message),
+        Ok(()) => {
+            if let Some(unit) = existing {
+                state.events.publish(DomainEvent::delete(
+                    EntityKind::Unit,
+                    unit.id,
+                    unit.tenant_id,
+                    unit.classification,
+                ));
+            }
+            HttpResponse::NoContent().finish()
+        }
+        Err(err) => storage_error(err),
     }
 }
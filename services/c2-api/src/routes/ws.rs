@@ -1,27 +1,146 @@
-use actix::{Actor, ActorContext, AsyncContext, StreamHandler};
+use actix::{
+    Actor, ActorContext, ActorFutureExt, AsyncContext, Handler, Message, StreamHandler,
+    WrapFuture,
+};
 use actix_web::{get, web, Error, HttpRequest, HttpResponse};
 use actix_web_actors::ws;
-use c2_core::SecurityClassification;
+use c2_core::{Incident, Mission, SecurityClassification, Task, TenantId};
 use c2_identity::Permission;
+use c2_messaging::{DomainEvent, EntityKind};
+use c2_storage::{
+    ChangeCursor, IncidentRepository, MissionRepository, StorageError, TaskRepository,
+};
+use futures_util::stream::unfold;
+use serde::Serialize;
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::auth::authorize_request;
+use crate::events;
 use crate::state::AppState;
 
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
 
+/// Frames pushed to subscribers. `resync` replaces any state the client
+/// built from deltas after it fell too far behind to receive them all.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Frame<'a> {
+    Delta(&'a DomainEvent),
+    Resync {
+        missed: u64,
+        missions: Vec<Mission>,
+        incidents: Vec<Incident>,
+        tasks: Vec<Task>,
+    },
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+enum Update {
+    Delta(DomainEvent),
+    Lagged(u64),
+}
+
 pub struct C2WsSession {
     last_heartbeat: Instant,
+    state: web::Data<AppState>,
+    tenant_id: TenantId,
+    clearance: SecurityClassification,
+    permissions: Vec<Permission>,
 }
 
 impl C2WsSession {
-    pub fn new() -> Self {
+    pub fn new(
+        state: web::Data<AppState>,
+        tenant_id: TenantId,
+        clearance: SecurityClassification,
+        permissions: Vec<Permission>,
+    ) -> Self {
         Self {
             last_heartbeat: Instant::now(),
+            state,
+            tenant_id,
+            clearance,
+            permissions,
         }
     }
 
+    fn subscribe(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        let receiver = self.state.events.subscribe(self.tenant_id);
+        ctx.add_message_stream(unfold(receiver, |mut receiver| async move {
+            match receiver.recv().await {
+                Ok(delta) => Some((Update::Delta(delta), receiver)),
+                Err(RecvError::Lagged(missed)) => Some((Update::Lagged(missed), receiver)),
+                Err(RecvError::Closed) => None,
+            }
+        }));
+    }
+
+    /// Missions, incidents and tasks only; the resync snapshot covers the same set.
+    fn visible(&self, event: &DomainEvent) -> bool {
+        matches!(
+            event.entity,
+            EntityKind::Mission | EntityKind::Incident | EntityKind::Task
+        ) && events::visible(event, self.clearance, &self.permissions)
+    }
+
+    /// Sends a snapshot of the tenant's missions, incidents and tasks,
+    /// holding back further deltas until it is out.
+    fn resync(&self, missed: u64, ctx: &mut ws::WebsocketContext<Self>) {
+        let state = self.state.clone();
+        let tenant_id = self.tenant_id;
+        let clearance = self.clearance;
+        let view_incidents = self.permissions.contains(&Permission::ViewIncidents);
+        let snapshot = async move {
+            let limit = state.config.max_page_size;
+            let store = &state.cleared_store(clearance);
+            let start = ChangeCursor::default();
+            let missions =
+                MissionRepository::list_updated_since(store, tenant_id, start, limit).await?;
+            let incidents = if view_incidents {
+                IncidentRepository::list_updated_since(store, tenant_id, start, limit).await?
+            } else {
+                Vec::new()
+            };
+            let tasks = TaskRepository::list_updated_since(store, tenant_id, start, limit).await?;
+            Ok::<_, StorageError>((missions, incidents, tasks))
+        };
+        ctx.wait(snapshot.into_actor(self).map(move |result, _, ctx| {
+            match result {
+                Ok((missions, incidents, tasks)) => {
+                    let frame = Frame::Resync {
+                        missed,
+                        missions: missions
+                            .into_iter()
+                            .filter(|item| item.classification <= clearance)
+                            .collect(),
+                        incidents: incidents
+                            .into_iter()
+                            .filter(|item| item.classification <= clearance)
+                            .collect(),
+                        tasks: tasks
+                            .into_iter()
+                            .filter(|item| item.classification <= clearance)
+                            .collect(),
+                    };
+                    if let Ok(text) = serde_json::to_string(&frame) {
+                        ctx.text(text);
+                    }
+                }
+                Err(err) => {
+                    // Without a snapshot the client cannot catch up; make it reconnect.
+                    ctx.close(Some(ws::CloseReason {
+                        code: ws::CloseCode::Error,
+                        description: Some(err.message),
+                    }));
+                    ctx.stop();
+                }
+            }
+        }));
+    }
+
     fn start_heartbeat(&self, ctx: &mut ws::WebsocketContext<Self>) {
         ctx.run_interval(HEARTBEAT_INTERVAL, |actor, ctx| {
             if Instant::now().duration_since(actor.last_heartbeat) > CLIENT_TIMEOUT {
@@ -38,6 +157,25 @@ impl Actor for C2WsSession {
 
     fn started(&mut self, ctx: &mut Self::Context) {
         self.start_heartbeat(ctx);
+        self.subscribe(ctx);
+    }
+}
+
+impl Handler<Update> for C2WsSession {
+    type Result = ();
+
+    fn handle(&mut self, update: Update, ctx: &mut Self::Context) {
+        match update {
+            Update::Delta(delta) => {
+                if !self.visible(&delta) {
+                    return;
+                }
+                if let Ok(text) = serde_json::to_string(&Frame::Delta(&delta)) {
+                    ctx.text(text);
+                }
+            }
+            Update::Lagged(missed) => self.resync(missed, ctx),
+        }
     }
 }
 
@@ -74,14 +212,104 @@ pub async fn ws_route(
     stream: web::Payload,
     state: web::Data<AppState>,
 ) -> Result<HttpResponse, Error> {
-    if let Err(response) = authorize_request(
+    let auth = match authorize_request(
         &req,
         &state.policy,
         Permission::ViewMissions,
         SecurityClassification::Unclassified,
     ) {
-        return Ok(response);
+        Ok(auth) => auth,
+        Err(response) => return Ok(response),
+    };
+
+    let session = C2WsSession::new(
+        state.clone(),
+        auth.subject.tenant_id,
+        auth.subject.clearance,
+        auth.permissions,
+    );
+    ws::start(session, &req, stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::missions::upsert_mission;
+    use crate::testing::{app_state, Caller};
+    use actix_web::test::TestRequest;
+    use actix_web::{test, App, HttpServer};
+    use c2_core::{MissionId, MissionStatus, OperationalPriority};
+    use futures_util::{SinkExt, Stream, StreamExt};
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+    use tokio_tungstenite::tungstenite::{Error as WsError, Message as WsMessage};
+
+    /// The next text frame, skipping pings.
+    async fn next_text<S>(socket: &mut S) -> String
+    where
+        S: Stream<Item = Result<WsMessage, WsError>> + Unpin,
+    {
+        loop {
+            let frame = actix_web::rt::time::timeout(Duration::from_secs(5), socket.next());
+            if let WsMessage::Text(text) = frame.await.unwrap().unwrap().unwrap() {
+                return text;
+            }
+        }
     }
 
-    ws::start(C2WsSession::new(), &req, stream)
+    #[actix_web::test]
+    async fn upserts_after_subscribing_arrive_as_delta_frames() {
+        let state = app_state().await;
+        let server_state = state.clone();
+        let server =
+            HttpServer::new(move || App::new().app_data(server_state.clone()).service(ws_route))
+                .workers(1)
+                .bind(("127.0.0.1", 0))
+                .unwrap();
+        let port = server.addrs()[0].port();
+        actix_web::rt::spawn(server.run());
+
+        let tenant_id = TenantId::new();
+        let caller = Caller::new(tenant_id, SecurityClassification::Secret);
+        let mut request =
+            format!("ws://127.0.0.1:{port}/v1/stream/ws").into_client_request().unwrap();
+        for (name, value) in caller.get("/").to_http_request().headers() {
+            request.headers_mut().insert(
+                HeaderName::from_bytes(name.as_str().as_bytes()).unwrap(),
+                HeaderValue::from_bytes(value.as_bytes()).unwrap(),
+            );
+        }
+        let (mut socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+        // The session subscribes before it handles any message, so the ack
+        // means later writes reach it.
+        socket.send(WsMessage::text("hello")).await.unwrap();
+        assert_eq!(next_text(&mut socket).await, "ack: hello");
+
+        let app = test::init_service(App::new().app_data(state).service(upsert_mission)).await;
+        let mission = |tenant_id| Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: "Harbor watch".to_string(),
+            status: MissionStatus::Planned,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Restricted,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        };
+        let other_tenant = Caller::new(TenantId::new(), SecurityClassification::Secret);
+        let theirs = mission(other_tenant.tenant_id);
+        let ours = mission(tenant_id);
+        for (caller, mission) in [(other_tenant, &theirs), (caller, &ours)] {
+            let request = TestRequest::post().uri("/v1/missions").set_json(mission);
+            let response = test::call_service(&app, caller.identify(request).to_request()).await;
+            assert!(response.status().is_success());
+        }
+
+        let frame: serde_json::Value = serde_json::from_str(&next_text(&mut socket).await).unwrap();
+        assert_eq!(frame["type"], "delta");
+        assert_eq!(frame["entity"], "mission");
+        assert_eq!(frame["op"], "upsert");
+        assert_eq!(frame["id"], ours.id.to_string());
+    }
 }
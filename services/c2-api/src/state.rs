@@ -1,9 +1,26 @@
-use c2_config::ServiceConfig;
-use c2_policy::BasicPolicyEngine;
+use c2_core::SecurityClassification;
+use c2_config::{ReadOnlyMode, ServiceConfig};
+use c2_messaging::EventBus;
+use c2_policy::{BasicPolicyEngine, TenantCeilings, TenantFloors, TenantRateLimiter};
+use c2_storage::{CapabilityCache, TimeoutStore};
 use c2_storage_surreal::SurrealStore;
 
 pub struct AppState {
     pub config: ServiceConfig,
     pub policy: BasicPolicyEngine,
-    pub store: SurrealStore,
+    pub ceilings: TenantCeilings,
+    pub floors: TenantFloors,
+    pub rate_limiter: TenantRateLimiter,
+    pub read_only: ReadOnlyMode,
+    pub store: TimeoutStore<SurrealStore>,
+    pub capabilities: CapabilityCache,
+    pub events: EventBus,
+}
+
+impl AppState {
+    /// The store as seen by a caller with `clearance`: mission reads skip
+    /// classification partitions above it.
+    pub fn cleared_store(&self, clearance: SecurityClassification) -> TimeoutStore<SurrealStore> {
+        self.store.scoped(|store| store.with_clearance(clearance))
+    }
 }
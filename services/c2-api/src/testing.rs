@@ -0,0 +1,79 @@
+//! Setup shared by route tests: app state over an in-memory store, and the
+//! `x-c2-*` identity headers of a caller.
+
+use actix_web::test::TestRequest;
+use actix_web::web;
+use c2_config::{ReadOnlyMode, ServiceConfig};
+use c2_core::{SecurityClassification, TenantId, UserId};
+use c2_messaging::EventBus;
+use c2_policy::{BasicPolicyEngine, TenantCeilings, TenantFloors, TenantRateLimiter};
+use c2_storage::{CapabilityCache, TimeoutStore};
+use c2_storage_surreal::{SurrealConfig, SurrealStore};
+use std::time::Duration;
+
+use crate::state::AppState;
+
+/// Top secret missions live in their own partition, as in a deployment
+/// that splits them out.
+pub async fn app_state() -> web::Data<AppState> {
+    app_state_with_events(EventBus::default()).await
+}
+
+/// Like [`app_state`], publishing writes on `events`.
+pub async fn app_state_with_events(events: EventBus) -> web::Data<AppState> {
+    let config = ServiceConfig::from_env("c2-api");
+    let store = SurrealStore::connect(&SurrealConfig {
+        endpoint: "mem://".to_string(),
+        mission_partitions: "top_secret=mission_top_secret".to_string(),
+        ..SurrealConfig::from_env()
+    })
+    .await
+    .unwrap();
+    web::Data::new(AppState {
+        store: TimeoutStore::new(store, config.storage_timeout()),
+        capabilities: CapabilityCache::new(Duration::ZERO),
+        config,
+        policy: BasicPolicyEngine::with_default_rules(),
+        ceilings: TenantCeilings::from_env().unwrap(),
+        floors: TenantFloors::from_env().unwrap(),
+        rate_limiter: TenantRateLimiter::from_env().unwrap(),
+        read_only: ReadOnlyMode::new(false),
+        events,
+    })
+}
+
+/// A user of `tenant_id` with every permission, cleared to `clearance`.
+#[derive(Debug, Clone, Copy)]
+pub struct Caller {
+    pub tenant_id: TenantId,
+    pub user_id: UserId,
+    pub clearance: SecurityClassification,
+}
+
+impl Caller {
+    pub fn new(tenant_id: TenantId, clearance: SecurityClassification) -> Self {
+        Self {
+            tenant_id,
+            user_id: UserId::new(),
+            clearance,
+        }
+    }
+
+    pub fn get(&self, uri: &str) -> TestRequest {
+        self.identify(TestRequest::get().uri(uri))
+    }
+
+    pub fn identify(&self, request: TestRequest) -> TestRequest {
+        let clearance = serde_json::to_value(self.clearance).unwrap();
+        request
+            .insert_header(("x-c2-tenant-id", self.tenant_id.to_string()))
+            .insert_header(("x-c2-user-id", self.user_id.to_string()))
+            .insert_header(("x-c2-roles", "system_admin"))
+            .insert_header(("x-c2-permissions", PERMISSIONS))
+            .insert_header(("x-c2-clearance", clearance.as_str().unwrap().to_string()))
+    }
+}
+
+const PERMISSIONS: &str = "view_missions,edit_missions,dispatch_assets,view_units,edit_units,\
+view_teams,edit_teams,view_capabilities,edit_capabilities,view_incidents,ingest_data,\
+access_classified,admin";
@@ -1,30 +1,46 @@
 mod proxy;
 
 use c2_config::{GatewayConfig, ServiceConfig};
-use c2_observability::{init, log_startup, ObservabilityConfig};
+use c2_observability::{init, log_startup, ObservabilityConfig, TelemetryConfig};
+use http::header::HeaderName;
 use pingora::proxy::http_proxy_service;
 use pingora::server::Server;
 use proxy::GatewayProxy;
 
 fn main() {
     let config = ServiceConfig::from_env("c2-gateway");
-    let gateway_config = GatewayConfig::from_env();
+    let gateway_config = GatewayConfig::from_env()
+        .unwrap_or_else(|err| panic!("invalid gateway config: {err}"));
+    if let Err(err) = gateway_config.auth.validate(config.require_auth) {
+        panic!("invalid gateway config for {} environment: {err}", config.environment);
+    }
     let obs_config = ObservabilityConfig {
         service_name: config.service_name.clone(),
         environment: config.environment.to_string(),
         log_level: config.log_level.clone(),
+        log_format: config.log_format.clone(),
         metrics_addr: config.metrics_addr.clone(),
+        telemetry: TelemetryConfig {
+            enabled: config.telemetry,
+            interval: config.telemetry_interval(),
+            endpoint: config.telemetry_endpoint.clone(),
+        },
     };
     let handle = init(&obs_config);
     log_startup(&handle, &obs_config.environment);
 
     let bind_addr = config.bind_addr.clone();
-    let _trusted_proxies = config.trusted_proxies;
+    let trusted_proxies = config.trusted_proxies;
+    let correlation_header = HeaderName::try_from(config.correlation_header.as_str())
+        .expect("invalid C2_CORRELATION_HEADER");
 
     let mut server = Server::new(None).expect("failed to create Pingora server");
     server.bootstrap();
 
-    let mut proxy = http_proxy_service(&server.configuration, GatewayProxy::new(gateway_config.clone()));
+    let mut proxy = http_proxy_service(
+        &server.configuration,
+        GatewayProxy::new(gateway_config.clone(), trusted_proxies, correlation_header),
+    );
     proxy.add_tcp(&bind_addr);
     if let Some(tls) = gateway_config.tls.as_ref() {
         proxy
@@ -1,35 +1,69 @@
 use async_trait::async_trait;
-use c2_config::GatewayConfig;
+use c2_config::{real_client_ip, GatewayConfig, GatewayTarget};
+use c2_core::CorrelationId;
 use http::header::{HeaderName, AUTHORIZATION};
+use pingora::http::{RequestHeader, ResponseHeader};
 use pingora::proxy::{ProxyHttp, Session};
 use pingora::upstreams::peer::HttpPeer;
 use pingora::Result;
 
+/// Set to how the gateway admitted the request: `token` when it carried the
+/// gateway token, `anonymous` otherwise.
+const CLIENT_HEADER: &str = "x-c2-client";
+
 #[derive(Debug, Clone)]
 pub struct GatewayProxy {
     config: GatewayConfig,
+    trusted_proxies: Vec<String>,
+    correlation_header: HeaderName,
 }
 
 impl GatewayProxy {
-    pub fn new(config: GatewayConfig) -> Self {
-        Self { config }
+    pub fn new(
+        config: GatewayConfig,
+        trusted_proxies: Vec<String>,
+        correlation_header: HeaderName,
+    ) -> Self {
+        Self {
+            config,
+            trusted_proxies,
+            correlation_header,
+        }
     }
-}
 
-#[async_trait]
-impl ProxyHttp for GatewayProxy {
-    type CTX = ();
+    /// Drops client-sent copies of the gateway's own headers and the
+    /// configured ones, so nothing downstream can be spoofed through them.
+    fn strip_headers(&self, request: &mut RequestHeader) {
+        for name in self.config.headers.stripped() {
+            request.remove_header(name);
+        }
+    }
 
-    fn new_ctx(&self) -> Self::CTX {}
+    fn inject_headers(&self, request: &mut RequestHeader, ctx: &RequestContext) -> Result<()> {
+        for (name, value) in &self.config.headers.set {
+            request.insert_header(name.clone(), value.as_str())?;
+        }
+        let client = if ctx.authenticated { "token" } else { "anonymous" };
+        request.insert_header(CLIENT_HEADER, client)?;
+        let proto = if ctx.tls { "https" } else { "http" };
+        request.insert_header("x-forwarded-proto", proto)?;
+        Ok(())
+    }
 
-    async fn request_filter(&self, session: &mut Session, _ctx: &mut Self::CTX) -> Result<bool> {
+    /// Every path routed to the API or MCP service needs the gateway token,
+    /// whatever its prefix, unless it is listed in the bypass paths. The web
+    /// UI runs its own login.
+    fn authenticate(&self, request: &RequestHeader) -> Admission {
         let Some(token) = self.config.auth.api_token.as_ref() else {
-            return Ok(false);
+            return Admission::Open;
         };
-
-        let path = session.req_header().uri.path();
-        if !path.starts_with("/v1") || self.config.auth.is_bypassed(path) {
-            return Ok(false);
+        let path = request.uri.path();
+        let protected = matches!(
+            self.config.route(path),
+            GatewayTarget::Api | GatewayTarget::Mcp
+        );
+        if !protected || self.config.auth.is_bypassed(path) {
+            return Admission::Open;
         }
 
         let header_name = self.config.auth.header_name.to_ascii_lowercase();
@@ -40,8 +74,7 @@ impl ProxyHttp for GatewayProxy {
         };
 
         let header_value = header_name.and_then(|name| {
-            session
-                .req_header()
+            request
                 .headers
                 .get(name)
                 .and_then(|value| value.to_str().ok())
@@ -51,13 +84,56 @@ impl ProxyHttp for GatewayProxy {
         let authorized = header_value
             .map(|value| value == expected || value == token)
             .unwrap_or(false);
-
-        if !authorized {
-            let _ = session.respond_error(401).await;
-            return Ok(true);
+        if authorized {
+            Admission::Token
+        } else {
+            Admission::Rejected
         }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    /// No token needed for this path.
+    Open,
+    /// Carried the gateway token.
+    Token,
+    Rejected,
+}
+
+#[derive(Debug, Default)]
+pub struct RequestContext {
+    /// The correlation id forwarded upstream, echoed back to the client.
+    correlation_id: Option<CorrelationId>,
+    authenticated: bool,
+    tls: bool,
+}
+
+#[async_trait]
+impl ProxyHttp for GatewayProxy {
+    type CTX = RequestContext;
 
-        Ok(false)
+    fn new_ctx(&self) -> Self::CTX {
+        RequestContext::default()
+    }
+
+    async fn request_filter(&self, session: &mut Session, ctx: &mut Self::CTX) -> Result<bool> {
+        self.strip_headers(session.req_header_mut());
+        ctx.tls = session
+            .digest()
+            .is_some_and(|digest| digest.ssl_digest.is_some());
+
+        match self.authenticate(session.req_header()) {
+            Admission::Open => Ok(false),
+            Admission::Token => {
+                ctx.authenticated = true;
+                Ok(false)
+            }
+            Admission::Rejected => {
+                let _ = session.respond_error(401).await;
+                Ok(true)
+            }
+        }
     }
 
     async fn upstream_peer(
@@ -66,11 +142,7 @@ impl ProxyHttp for GatewayProxy {
         _ctx: &mut Self::CTX,
     ) -> Result<Box<HttpPeer>> {
         let path = session.req_header().uri.path();
-        let upstream = if path.starts_with("/v1") || path.starts_with("/health") {
-            &self.config.api
-        } else {
-            &self.config.web
-        };
+        let upstream = self.config.upstream(self.config.route(path));
         let sni = upstream.sni.clone().unwrap_or_else(|| upstream.host.clone());
         let peer = Box::new(HttpPeer::new(
             (upstream.host.as_str(), upstream.port),
@@ -79,4 +151,148 @@ impl ProxyHttp for GatewayProxy {
         ));
         Ok(peer)
     }
+
+    async fn upstream_request_filter(
+        &self,
+        session: &mut Session,
+        upstream_request: &mut RequestHeader,
+        ctx: &mut Self::CTX,
+    ) -> Result<()> {
+        // Clients may bring their own id to follow a request across services;
+        // otherwise the request gets one here.
+        let correlation_id = CorrelationId::from_header(
+            session
+                .req_header()
+                .headers
+                .get(&self.correlation_header)
+                .and_then(|value| value.to_str().ok()),
+        );
+        upstream_request
+            .insert_header(self.correlation_header.clone(), correlation_id.to_string())?;
+        ctx.correlation_id = Some(correlation_id);
+        self.inject_headers(upstream_request, ctx)?;
+
+        let Some(peer_ip) = session
+            .client_addr()
+            .and_then(|addr| addr.as_inet())
+            .map(|addr| addr.ip())
+        else {
+            return Ok(());
+        };
+
+        let forwarded_for: Vec<String> = session
+            .req_header()
+            .headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(str::to_string)
+            .collect();
+        let client_ip = real_client_ip(
+            forwarded_for.iter().map(String::as_str),
+            peer_ip,
+            &self.trusted_proxies,
+        );
+
+        // Only a trusted peer may extend the chain; anything else is replaced so
+        // upstreams never see a spoofed X-Forwarded-For.
+        let chain = if forwarded_for.is_empty() || client_ip == peer_ip {
+            peer_ip.to_string()
+        } else {
+            format!("{}, {}", forwarded_for.join(", "), peer_ip)
+        };
+        upstream_request.insert_header("x-forwarded-for", chain)?;
+        upstream_request.insert_header("x-real-ip", client_ip.to_string())?;
+        Ok(())
+    }
+
+    async fn response_filter(
+        &self,
+        _session: &mut Session,
+        upstream_response: &mut ResponseHeader,
+        ctx: &mut Self::CTX,
+    ) -> Result<()> {
+        if let Some(correlation_id) = &ctx.correlation_id {
+            upstream_response
+                .insert_header(self.correlation_header.clone(), correlation_id.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_config::{GatewayAuthConfig, GatewayHeaderRules, GatewayUpstream};
+    use std::collections::BTreeMap;
+
+    fn proxy_with_auth(auth: GatewayAuthConfig) -> GatewayProxy {
+        let upstream = |host: &str| GatewayUpstream {
+            host: host.to_string(),
+            port: 8080,
+            tls: false,
+            sni: None,
+        };
+        let config = GatewayConfig {
+            api: upstream("c2-api"),
+            web: upstream("c2-web"),
+            mcp: upstream("c2-mcp"),
+            routes: GatewayConfig::default_routes(),
+            tls: None,
+            auth,
+            headers: GatewayHeaderRules {
+                strip: vec!["x-debug".to_string()],
+                set: BTreeMap::from([("x-c2-edge".to_string(), "eu-1".to_string())]),
+            },
+        };
+        GatewayProxy::new(config, Vec::new(), HeaderName::from_static("x-correlation-id"))
+    }
+
+    fn proxy() -> GatewayProxy {
+        proxy_with_auth(GatewayAuthConfig::from_env())
+    }
+
+    #[test]
+    fn client_sent_identity_header_is_replaced_by_the_gateways() {
+        let proxy = proxy();
+        let mut request = RequestHeader::build("GET", b"/v1/missions", None).unwrap();
+        request.insert_header(CLIENT_HEADER, "token").unwrap();
+        request.insert_header("x-debug", "1").unwrap();
+        request.insert_header("x-c2-tenant-id", "tenant").unwrap();
+
+        proxy.strip_headers(&mut request);
+        assert!(request.headers.get(CLIENT_HEADER).is_none());
+        assert!(request.headers.get("x-debug").is_none());
+
+        let ctx = RequestContext::default();
+        proxy.inject_headers(&mut request, &ctx).unwrap();
+        let header = |name: &str| request.headers.get(name).and_then(|value| value.to_str().ok());
+        assert_eq!(header(CLIENT_HEADER), Some("anonymous"));
+        assert_eq!(header("x-forwarded-proto"), Some("http"));
+        assert_eq!(header("x-c2-edge"), Some("eu-1"));
+        assert_eq!(header("x-c2-tenant-id"), Some("tenant"));
+    }
+
+    #[test]
+    fn mcp_and_api_paths_need_the_gateway_token() {
+        let proxy = proxy_with_auth(GatewayAuthConfig {
+            api_token: Some("gateway-secret".to_string()),
+            header_name: "authorization".to_string(),
+            bypass_paths: vec!["/health".to_string()],
+        });
+        let admit = |path: &str, token: Option<&str>| {
+            let mut request = RequestHeader::build("POST", path.as_bytes(), None).unwrap();
+            if let Some(token) = token {
+                request.insert_header("authorization", token).unwrap();
+            }
+            proxy.authenticate(&request)
+        };
+
+        assert_eq!(admit("/mcp", None), Admission::Rejected);
+        assert_eq!(admit("/mcp", Some("Bearer wrong")), Admission::Rejected);
+        assert_eq!(admit("/mcp", Some("Bearer gateway-secret")), Admission::Token);
+        assert_eq!(admit("/v1/missions", None), Admission::Rejected);
+        assert_eq!(admit("/health", None), Admission::Open);
+        assert_eq!(admit("/", None), Admission::Open);
+    }
 }
@@ -0,0 +1,36 @@
+use axum::extract::{Request, State};
+use axum::http::header::{HeaderName, HeaderValue};
+use axum::http::request::Parts;
+use axum::middleware::Next;
+use axum::response::Response;
+use c2_core::CorrelationId;
+use rmcp::model::Extensions;
+
+/// Takes the correlation id from the `header` of each HTTP request, or makes
+/// one up, stores it in the request extensions for [`for_call`] and echoes it
+/// back in the response.
+pub async fn correlation_id(
+    State(header): State<HeaderName>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let id = CorrelationId::from_header(
+        req.headers().get(&header).and_then(|value| value.to_str().ok()),
+    );
+    req.extensions_mut().insert(id);
+    let mut response = next.run(req).await;
+    if let Ok(value) = HeaderValue::try_from(id.to_string()) {
+        response.headers_mut().insert(header, value);
+    }
+    response
+}
+
+/// The id [`correlation_id`] attached to the HTTP request behind a tool call,
+/// or a fresh one for calls that did not come over HTTP.
+pub fn for_call(extensions: &Extensions) -> CorrelationId {
+    extensions
+        .get::<Parts>()
+        .and_then(|parts| parts.extensions.get::<CorrelationId>())
+        .copied()
+        .unwrap_or_else(CorrelationId::new)
+}
@@ -0,0 +1,166 @@
+use c2_core::TenantId;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const DEFAULT_TTL_SECS: u64 = 600;
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Maps client-supplied idempotency keys to the id generated for the first
+/// create, so a retried create lands on the same record. Each key remembers
+/// a fingerprint of the request it was first used with, and reusing it for
+/// a different request is refused.
+pub struct IdempotencyCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+/// Every entry lives for the same TTL, so insertion order is expiry order:
+/// expired and evicted keys always come off the front of `order`.
+#[derive(Default)]
+struct Entries {
+    by_key: HashMap<(TenantId, String), Entry>,
+    order: VecDeque<(Instant, (TenantId, String))>,
+}
+
+struct Entry {
+    id: Uuid,
+    fingerprint: u64,
+    expires_at: Instant,
+}
+
+/// The key was first used with a different request body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyReused;
+
+impl Entries {
+    fn expire(&mut self, now: Instant) {
+        while let Some((expires_at, _)) = self.order.front() {
+            if *expires_at > now {
+                break;
+            }
+            if let Some((_, key)) = self.order.pop_front() {
+                self.by_key.remove(&key);
+            }
+        }
+    }
+}
+
+impl IdempotencyCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let ttl_secs = env::var("C2_MCP_IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TTL_SECS);
+        let capacity = env::var("C2_MCP_IDEMPOTENCY_CAPACITY")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_CAPACITY);
+        Self::new(Duration::from_secs(ttl_secs), capacity)
+    }
+
+    /// Returns the id already bound to `key`, or binds a fresh one.
+    /// The flag is `true` when the key was seen before.
+    pub fn reserve(
+        &self,
+        tenant_id: TenantId,
+        key: &str,
+        fingerprint: u64,
+    ) -> Result<(Uuid, bool), KeyReused> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.expire(now);
+        let map_key = (tenant_id, key.to_string());
+        if let Some(entry) = entries.by_key.get(&map_key) {
+            if entry.fingerprint != fingerprint {
+                return Err(KeyReused);
+            }
+            return Ok((entry.id, true));
+        }
+        if entries.by_key.len() >= self.capacity
+            && let Some((_, oldest)) = entries.order.pop_front()
+        {
+            entries.by_key.remove(&oldest);
+        }
+        let id = Uuid::new_v4();
+        let expires_at = now + self.ttl;
+        entries.order.push_back((expires_at, map_key.clone()));
+        entries.by_key.insert(
+            map_key,
+            Entry {
+                id,
+                fingerprint,
+                expires_at,
+            },
+        );
+        Ok((id, false))
+    }
+
+    /// Looks up `key` without binding it; used by dry runs.
+    pub fn peek(
+        &self,
+        tenant_id: TenantId,
+        key: &str,
+        fingerprint: u64,
+    ) -> Result<Option<Uuid>, KeyReused> {
+        let now = Instant::now();
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        match entries.by_key.get(&(tenant_id, key.to_string())) {
+            Some(entry) if entry.expires_at <= now => Ok(None),
+            Some(entry) if entry.fingerprint != fingerprint => Err(KeyReused),
+            Some(entry) => Ok(Some(entry.id)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_key_replays_only_the_request_it_was_first_used_with() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60), 10);
+        let tenant_id = TenantId::new();
+
+        let (id, replayed) = cache.reserve(tenant_id, "create-1", 7).unwrap();
+        assert!(!replayed);
+        assert_eq!(cache.reserve(tenant_id, "create-1", 7), Ok((id, true)));
+        assert_eq!(cache.peek(tenant_id, "create-1", 7), Ok(Some(id)));
+
+        assert_eq!(cache.reserve(tenant_id, "create-1", 8), Err(KeyReused));
+        assert_eq!(cache.peek(tenant_id, "create-1", 8), Err(KeyReused));
+        assert_eq!(cache.reserve(TenantId::new(), "create-1", 8).map(|(_, seen)| seen), Ok(false));
+    }
+
+    #[test]
+    fn keys_expire_and_are_evicted_oldest_first() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60), 2);
+        let tenant_id = TenantId::new();
+        let (first, _) = cache.reserve(tenant_id, "a", 1).unwrap();
+        cache.reserve(tenant_id, "b", 1).unwrap();
+        cache.reserve(tenant_id, "c", 1).unwrap();
+        assert_eq!(cache.peek(tenant_id, "a", 1), Ok(None));
+        assert!(cache.peek(tenant_id, "b", 1).unwrap().is_some());
+        let (again, replayed) = cache.reserve(tenant_id, "a", 1).unwrap();
+        assert!(!replayed);
+        assert_ne!(again, first);
+
+        let cache = IdempotencyCache::new(Duration::ZERO, 10);
+        cache.reserve(tenant_id, "a", 1).unwrap();
+        assert_eq!(cache.peek(tenant_id, "a", 2), Ok(None));
+        assert_eq!(cache.reserve(tenant_id, "a", 2).map(|(_, seen)| seen), Ok(false));
+        assert_eq!(cache.entries.lock().unwrap().order.len(), 1);
+    }
+}
@@ -1,20 +1,42 @@
-use c2_config::ServiceConfig;
+mod correlation;
+mod idempotency;
+mod prompts;
+mod situation;
+mod summary_cache;
+mod tool_metrics;
+
+use c2_config::{ReadOnlyMode, ServiceConfig};
 use c2_core::{
-    Asset, AssetId, CommsStatus, Incident, IncidentId, MaintenanceState, Mission, MissionId,
-    OperationalPriority, ReadinessState, SecurityClassification, Task, TaskId, TenantId,
-    now_epoch_millis,
+    Asset, AssetId, AssetStatus, CapabilityId, CommsStatus, EpochMillis, Incident, IncidentId,
+    MaintenanceState, Mission, MissionAssignment, MissionId, MissionStatus, OperationalPriority,
+    ReadinessState, SecurityClassification, SymbolTable, Task, TaskId, TeamId, TenantId, UnitId,
+    now_epoch_millis, sidc_for,
 };
+use c2_geo::{BoundingBox, Coordinate};
 use c2_identity::{Permission, Role, Subject};
-use c2_observability::{init, log_startup, ObservabilityConfig};
-use c2_policy::{BasicPolicyEngine, PolicyContext, PolicyDecision, PolicyEngine, PolicyRequest, ResourceDescriptor};
-use axum::{routing::any_service, Router};
-use c2_storage::{AssetRepository, IncidentRepository, MissionRepository, StorageError, TaskRepository};
+use c2_messaging::{ChangeRelay, DomainEvent, EntityKind, EventBus};
+use c2_observability::{
+    check_requested, init, log_startup, CheckReport, ObservabilityConfig, TelemetryConfig,
+};
+use c2_policy::{
+    record_denial, BasicPolicyEngine, PolicyContext, PolicyDecision, PolicyEngine, PolicyOutcome,
+    PolicyRequest, PositionPrecision, ResourceDescriptor, TenantCeilings, TenantFloors,
+    TenantRateLimiter,
+};
+use axum::http::HeaderName;
+use axum::{middleware, routing::any_service, Router};
+use c2_storage::{
+    AssetRepository, AssignmentRepository, CapabilityCache, CapabilityRepository, IncidentFilter,
+    IncidentRepository, IncidentSort, MissionRepository, StorageError, TaskRepository,
+    TeamRepository, TimeoutStore, UnitRepository, check_task_dependencies,
+};
 use c2_storage_surreal::{SurrealConfig, SurrealStore};
 use rmcp::handler::server::tool::ToolRouter;
 use rmcp::handler::server::wrapper::{Json, Parameters};
 use rmcp::model::{
-    Annotated, ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParam,
-    Meta, RawResource, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, Resource,
+    Annotated, GetPromptRequestParam, GetPromptResult, ListPromptsResult,
+    ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParam, Meta, RawResource,
+    RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, Resource,
     ResourceContents, ResourceTemplate, ServerCapabilities, ServerInfo,
 };
 use rmcp::transport::{
@@ -24,31 +46,486 @@ use rmcp::transport::{
 use rmcp::{tool, tool_handler, tool_router, ErrorData, ServerHandler};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::env;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::str::FromStr;
 use std::sync::Arc;
+use idempotency::IdempotencyCache;
+use prompts::{PromptArgs, PromptCount};
+use situation::SituationSummary;
+use summary_cache::SummaryCache;
 use tokio::net::TcpListener;
+use tool_metrics::{MeteredToolRouter, Refusal};
 use uuid::Uuid;
 
+const REDACTED: &str = "[REDACTED]";
+const RATE_LIMITED: &str = "rate limit exceeded";
+/// Cache key of `c2.situation_summary` in [`SummaryCache`].
+const SITUATION: &str = "situation_summary";
+
 #[derive(Clone)]
 struct C2McpService {
-    store: Arc<SurrealStore>,
+    config: ServiceConfig,
+    store: Arc<TimeoutStore<SurrealStore>>,
     policy: BasicPolicyEngine,
+    ceilings: TenantCeilings,
+    floors: TenantFloors,
     default_auth: Option<AuthorizedContext>,
+    redaction_window: u8,
+    position_precision: PositionPrecision,
+    rate_limiter: Arc<TenantRateLimiter>,
+    read_only: Arc<ReadOnlyMode>,
+    idempotency: Arc<IdempotencyCache>,
+    capabilities: Arc<CapabilityCache>,
+    summaries: Arc<SummaryCache>,
+    events: EventBus,
     tool_router: ToolRouter<Self>,
 }
 
 impl C2McpService {
-    fn new(store: SurrealStore, policy: BasicPolicyEngine) -> Self {
-        let store = Arc::new(store);
+    fn new(
+        config: ServiceConfig,
+        store: SurrealStore,
+        policy: BasicPolicyEngine,
+        ceilings: TenantCeilings,
+        position_precision: PositionPrecision,
+        rate_limiter: TenantRateLimiter,
+        events: EventBus,
+    ) -> Self {
+        let store = Arc::new(TimeoutStore::new(store, config.storage_timeout()));
+        let read_only = Arc::new(ReadOnlyMode::new(config.read_only));
+        let capabilities = Arc::new(CapabilityCache::new(config.capability_cache_ttl()));
         let default_auth = load_default_auth();
+        let redaction_window = env::var("C2_MCP_REDACTION_WINDOW")
+            .ok()
+            .and_then(|value| value.trim().parse::<u8>().ok())
+            .unwrap_or(0);
         Self {
+            config,
             store,
             policy,
+            ceilings,
+            floors: TenantFloors::default(),
             default_auth,
+            redaction_window,
+            position_precision,
+            rate_limiter: Arc::new(rate_limiter),
+            read_only,
+            idempotency: Arc::new(IdempotencyCache::from_env()),
+            summaries: Arc::new(SummaryCache::from_env()),
+            capabilities,
+            events,
             tool_router: Self::tool_router(),
         }
     }
+
+    /// Sets the classification floors applied to created records.
+    fn with_floors(mut self, floors: TenantFloors) -> Self {
+        self.floors = floors;
+        self
+    }
+
+    /// Resolves the caller like [`resolve_auth`] and takes one call from
+    /// their tenant's rate limit. Exceeding it is a retryable error carrying
+    /// `retryAfterMs`.
+    fn resolve_auth(
+        &self,
+        auth: Option<McpAuthContext>,
+        meta: &Meta,
+    ) -> Result<AuthorizedContext, ErrorData> {
+        let auth = resolve_auth(auth, meta, self.default_auth.as_ref())?;
+        if let Err(retry_after) = self.rate_limiter.check(auth.subject.tenant_id) {
+            return Err(Refusal::Throttled.error(
+                RATE_LIMITED,
+                Some(serde_json::json!({
+                    "retryable": true,
+                    "retryAfterMs": retry_after.as_millis(),
+                })),
+            ));
+        }
+        Ok(auth)
+    }
+
+    /// The store as seen at `clearance`: mission partitions above it are
+    /// never read.
+    fn cleared_store(&self, clearance: SecurityClassification) -> TimeoutStore<SurrealStore> {
+        self.store.scoped(|store| store.with_clearance(clearance))
+    }
+
+    /// Picks the id for an upsert. An explicit id wins; otherwise an
+    /// idempotency key reuses the id bound by an earlier create of the same
+    /// `input`. The flag reports whether the key was already known.
+    fn resolve_upsert_id(
+        &self,
+        auth: &AuthorizedContext,
+        id: Option<&str>,
+        input: &impl Serialize,
+        idempotency_key: Option<&str>,
+        dry_run: bool,
+    ) -> Result<(Uuid, bool), ErrorData> {
+        if let Some(value) = id {
+            return Ok((parse_uuid(value)?, false));
+        }
+        let Some(key) = idempotency_key.map(str::trim).filter(|key| !key.is_empty()) else {
+            return Ok((Uuid::new_v4(), false));
+        };
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_vec(input)
+            .map_err(|err| ErrorData::invalid_params(err.to_string(), None))?
+            .hash(&mut hasher);
+        let fingerprint = hasher.finish();
+        let tenant_id = auth.subject.tenant_id;
+        let reserved = if dry_run {
+            self.idempotency
+                .peek(tenant_id, key, fingerprint)
+                .map(|id| id.map_or_else(|| (Uuid::new_v4(), false), |id| (id, true)))
+        } else {
+            self.idempotency.reserve(tenant_id, key, fingerprint)
+        };
+        reserved.map_err(|_| {
+            ErrorData::invalid_request("idempotency key was used for a different request", None)
+        })
+    }
+
+    /// Parses a unit reference, which must exist in the caller's tenant. An
+    /// empty value clears the assignment.
+    async fn checked_unit_id(
+        &self,
+        auth: &AuthorizedContext,
+        value: &str,
+    ) -> Result<Option<UnitId>, ErrorData> {
+        if value.trim().is_empty() {
+            return Ok(None);
+        }
+        let unit_id = UnitId::from_uuid(parse_uuid(value.trim())?);
+        let unit = UnitRepository::get(&*self.store, unit_id)
+            .await
+            .map_err(storage_error)?;
+        match unit {
+            Some(unit) if unit.tenant_id == auth.subject.tenant_id => Ok(Some(unit_id)),
+            _ => Err(ErrorData::invalid_params("unknown unitId", None)),
+        }
+    }
+
+    /// Parses the unit or team named by an assignment; exactly one must be
+    /// given, and it must exist in the caller's tenant.
+    async fn checked_assignee(
+        &self,
+        auth: &AuthorizedContext,
+        unit_id: Option<&str>,
+        team_id: Option<&str>,
+    ) -> Result<(Option<UnitId>, Option<TeamId>), ErrorData> {
+        match (unit_id, team_id) {
+            (Some(unit_id), None) => match self.checked_unit_id(auth, unit_id).await? {
+                Some(unit_id) => Ok((Some(unit_id), None)),
+                None => Err(ErrorData::invalid_params("unitId must not be empty", None)),
+            },
+            (None, Some(team_id)) => {
+                let team_id = TeamId::from_uuid(parse_uuid(team_id.trim())?);
+                let team = TeamRepository::get(&*self.store, team_id)
+                    .await
+                    .map_err(storage_error)?;
+                match team {
+                    Some(team) if team.tenant_id == auth.subject.tenant_id => {
+                        Ok((None, Some(team_id)))
+                    }
+                    _ => Err(ErrorData::invalid_params("unknown teamId", None)),
+                }
+            }
+            _ => Err(ErrorData::invalid_params("set exactly one of unitId and teamId", None)),
+        }
+    }
+
+    /// Loads a mission in the caller's tenant for a change to its
+    /// assignments, which needs both mission editing and dispatch rights at
+    /// the mission's classification.
+    async fn mission_for_assignment(
+        &self,
+        auth: &AuthorizedContext,
+        mission_id: &str,
+    ) -> Result<Mission, ErrorData> {
+        let mission_id = MissionId::from_uuid(parse_uuid(mission_id)?);
+        let store = self.cleared_store(auth.subject.clearance);
+        let mission = MissionRepository::get(&store, mission_id)
+            .await
+            .map_err(storage_error)?;
+        let Some(mission) = mission.filter(|mission| mission.tenant_id == auth.subject.tenant_id)
+        else {
+            return Err(ErrorData::resource_not_found("mission not found", None));
+        };
+        for permission in [Permission::EditMissions, Permission::DispatchAssets] {
+            authorize_action(
+                &self.policy,
+                auth,
+                permission,
+                mission.classification,
+                "mission",
+                Some(mission.id.to_string()),
+            )?;
+        }
+        Ok(mission)
+    }
+
+    /// Applies `status` to each mission in `ids` that the caller may edit and
+    /// that can take it, reading them in one batch.
+    async fn set_mission_statuses(
+        &self,
+        auth: &AuthorizedContext,
+        ids: &[String],
+        status: MissionStatus,
+    ) -> Result<Vec<BulkStatusItem>, ErrorData> {
+        let parsed: Vec<Option<MissionId>> = ids
+            .iter()
+            .map(|id| parse_uuid(id).ok().map(MissionId::from_uuid))
+            .collect();
+        let lookup: Vec<MissionId> = parsed.iter().flatten().copied().collect();
+        let mut missions: HashMap<MissionId, Mission> =
+            MissionRepository::get_many(&self.cleared_store(auth.subject.clearance), &lookup)
+                .await
+                .map_err(storage_error)?
+                .into_iter()
+                .filter(|mission| mission.tenant_id == auth.subject.tenant_id)
+                .map(|mission| (mission.id, mission))
+                .collect();
+
+        let mut results = Vec::with_capacity(ids.len());
+        for (id, mission_id) in ids.iter().zip(parsed) {
+            let Some(mut mission) = mission_id.and_then(|mission_id| missions.remove(&mission_id))
+            else {
+                let error = if mission_id.is_some() { "mission not found" } else { "invalid UUID" };
+                results.push(BulkStatusItem::failed(id, error));
+                continue;
+            };
+            if let Err(err) = authorize_action(
+                &self.policy,
+                auth,
+                Permission::EditMissions,
+                mission.classification,
+                "mission",
+                Some(mission.id.to_string()),
+            ) {
+                results.push(BulkStatusItem::failed(id, err.message));
+                continue;
+            }
+            if !mission.status.can_transition_to(status) {
+                let error = format!("mission is {:?} and cannot become {status:?}", mission.status);
+                results.push(BulkStatusItem::failed(id, error));
+                continue;
+            }
+            if status == MissionStatus::Completed {
+                let progress = TaskRepository::mission_progress(&*self.store, mission.id)
+                    .await
+                    .map_err(storage_error)?;
+                if progress.open() > 0 {
+                    let error = format!("mission still has {} open task(s)", progress.open());
+                    results.push(BulkStatusItem::failed(id, error));
+                    continue;
+                }
+            }
+            mission.status = status;
+            mission.updated_at_ms = now_epoch_millis();
+            MissionRepository::upsert(&*self.store, mission.clone())
+                .await
+                .map_err(storage_error)?;
+            self.events.publish(DomainEvent::upsert(
+                EntityKind::Mission,
+                mission.id,
+                mission.tenant_id,
+                mission.classification,
+                &mission,
+            ));
+            results.push(BulkStatusItem::updated(id));
+        }
+        Ok(results)
+    }
+
+    /// [`Self::set_mission_statuses`] for tasks.
+    async fn set_task_statuses(
+        &self,
+        auth: &AuthorizedContext,
+        ids: &[String],
+        status: c2_core::TaskStatus,
+    ) -> Result<Vec<BulkStatusItem>, ErrorData> {
+        let parsed: Vec<Option<TaskId>> = ids
+            .iter()
+            .map(|id| parse_uuid(id).ok().map(TaskId::from_uuid))
+            .collect();
+        let lookup: Vec<TaskId> = parsed.iter().flatten().copied().collect();
+        let mut tasks: HashMap<TaskId, Task> = TaskRepository::get_many(&*self.store, &lookup)
+            .await
+            .map_err(storage_error)?
+            .into_iter()
+            .filter(|task| task.tenant_id == auth.subject.tenant_id)
+            .map(|task| (task.id, task))
+            .collect();
+
+        let mut results = Vec::with_capacity(ids.len());
+        for (id, task_id) in ids.iter().zip(parsed) {
+            let Some(mut task) = task_id.and_then(|task_id| tasks.remove(&task_id)) else {
+                let error = if task_id.is_some() { "task not found" } else { "invalid UUID" };
+                results.push(BulkStatusItem::failed(id, error));
+                continue;
+            };
+            if let Err(err) = authorize_action(
+                &self.policy,
+                auth,
+                Permission::EditMissions,
+                task.classification,
+                "task",
+                Some(task.id.to_string()),
+            ) {
+                results.push(BulkStatusItem::failed(id, err.message));
+                continue;
+            }
+            if !task.status.can_transition_to(status) {
+                let error = format!("task is {:?} and cannot become {status:?}", task.status);
+                results.push(BulkStatusItem::failed(id, error));
+                continue;
+            }
+            task.status = status;
+            task.updated_at_ms = now_epoch_millis();
+            TaskRepository::upsert(&*self.store, task.clone())
+                .await
+                .map_err(storage_error)?;
+            self.events.publish(DomainEvent::upsert(
+                EntityKind::Task,
+                task.id,
+                task.tenant_id,
+                task.classification,
+                &task,
+            ));
+            results.push(BulkStatusItem::updated(id));
+        }
+        Ok(results)
+    }
+
+    /// Parses capability references, dropping duplicates. Each must exist in
+    /// the caller's tenant; the catalog is read through the capability cache.
+    async fn checked_capability_ids(
+        &self,
+        auth: &AuthorizedContext,
+        values: &[String],
+    ) -> Result<Vec<CapabilityId>, ErrorData> {
+        let mut capability_ids = Vec::with_capacity(values.len());
+        for value in values {
+            let capability_id = CapabilityId::from_uuid(parse_uuid(value.trim())?);
+            if !capability_ids.contains(&capability_id) {
+                capability_ids.push(capability_id);
+            }
+        }
+        let unknown = self
+            .capabilities
+            .first_unknown(&*self.store, auth.subject.tenant_id, &capability_ids)
+            .await
+            .map_err(storage_error)?;
+        if let Some(capability_id) = unknown {
+            return Err(ErrorData::invalid_params(
+                format!("unknown capabilityId {capability_id}"),
+                None,
+            ));
+        }
+        Ok(capability_ids)
+    }
+
+    /// Rejects a classification above the tenant's ceiling or below its floor.
+    fn check_classification(
+        &self,
+        auth: &AuthorizedContext,
+        classification: SecurityClassification,
+    ) -> Result<(), ErrorData> {
+        let tenant_id = auth.subject.tenant_id;
+        self.ceilings
+            .check(tenant_id, classification)
+            .and_then(|()| self.floors.check(tenant_id, classification))
+            .map_err(|message| ErrorData::invalid_request(message, None))
+    }
+
+    fn page_limit(&self, limit: Option<usize>) -> Result<usize, ErrorData> {
+        self.config
+            .page_limit(limit)
+            .ok_or_else(|| ErrorData::invalid_params("limit exceeds maximum page size", None))
+    }
+
+    /// Counts the records in one page that the caller can see at least
+    /// redacted, for filling prompt templates.
+    fn prompt_count<T>(
+        &self,
+        auth: &AuthorizedContext,
+        page: &[T],
+        limit: usize,
+        classification: impl Fn(&T) -> SecurityClassification,
+    ) -> PromptCount {
+        let value = page
+            .iter()
+            .filter(|item| self.visibility(auth, classification(item)) != Visibility::Hidden)
+            .count();
+        PromptCount {
+            value,
+            capped: page.len() >= limit,
+        }
+    }
+
+    /// Redacted stub of an incident above the caller's clearance, keeping
+    /// its position at the precision configured for the gap.
+    fn redacted_incident(&self, auth: &AuthorizedContext, incident: Incident) -> McpIncident {
+        let position = incident.position.and_then(|position| {
+            self.position_precision
+                .apply(position, incident.classification, auth.subject.clearance)
+        });
+        McpIncident::from(incident).redacted(position)
+    }
+
+    fn visible_assignments(
+        &self,
+        auth: &AuthorizedContext,
+        assignments: Vec<MissionAssignment>,
+    ) -> AssignmentList {
+        let assignments = assignments
+            .into_iter()
+            .filter_map(|assignment| match self.visibility(auth, assignment.classification) {
+                Visibility::Full => Some(McpAssignment::from(assignment)),
+                Visibility::Redacted => Some(McpAssignment::from(assignment).redacted()),
+                Visibility::Hidden => None,
+            })
+            .collect();
+        AssignmentList { assignments }
+    }
+
+    fn visibility(
+        &self,
+        auth: &AuthorizedContext,
+        classification: SecurityClassification,
+    ) -> Visibility {
+        let clearance = auth.subject.clearance;
+        if classification <= clearance {
+            Visibility::Full
+        } else if classification.level() - clearance.level() <= self.redaction_window {
+            Visibility::Redacted
+        } else {
+            Visibility::Hidden
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visibility {
+    Full,
+    Redacted,
+    Hidden,
+}
+
+impl Visibility {
+    fn checked_classification(
+        self,
+        auth: &AuthorizedContext,
+        classification: SecurityClassification,
+    ) -> SecurityClassification {
+        match self {
+            Visibility::Redacted => auth.subject.clearance,
+            _ => classification,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -184,6 +661,102 @@ impl From<c2_core::AssetStatus> for McpAssetStatus {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum McpReadinessState {
+    Ready,
+    Limited,
+    Degraded,
+    Unavailable,
+}
+
+impl From<McpReadinessState> for ReadinessState {
+    fn from(value: McpReadinessState) -> Self {
+        match value {
+            McpReadinessState::Ready => Self::Ready,
+            McpReadinessState::Limited => Self::Limited,
+            McpReadinessState::Degraded => Self::Degraded,
+            McpReadinessState::Unavailable => Self::Unavailable,
+        }
+    }
+}
+
+impl From<ReadinessState> for McpReadinessState {
+    fn from(value: ReadinessState) -> Self {
+        match value {
+            ReadinessState::Ready => Self::Ready,
+            ReadinessState::Limited => Self::Limited,
+            ReadinessState::Degraded => Self::Degraded,
+            ReadinessState::Unavailable => Self::Unavailable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum McpCommsStatus {
+    Online,
+    Intermittent,
+    Offline,
+    Unknown,
+}
+
+impl From<McpCommsStatus> for CommsStatus {
+    fn from(value: McpCommsStatus) -> Self {
+        match value {
+            McpCommsStatus::Online => Self::Online,
+            McpCommsStatus::Intermittent => Self::Intermittent,
+            McpCommsStatus::Offline => Self::Offline,
+            McpCommsStatus::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<CommsStatus> for McpCommsStatus {
+    fn from(value: CommsStatus) -> Self {
+        match value {
+            CommsStatus::Online => Self::Online,
+            CommsStatus::Intermittent => Self::Intermittent,
+            CommsStatus::Offline => Self::Offline,
+            CommsStatus::Unknown => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum McpMaintenanceState {
+    None,
+    Scheduled,
+    InProgress,
+    Deferred,
+    Complete,
+}
+
+impl From<McpMaintenanceState> for MaintenanceState {
+    fn from(value: McpMaintenanceState) -> Self {
+        match value {
+            McpMaintenanceState::None => Self::None,
+            McpMaintenanceState::Scheduled => Self::Scheduled,
+            McpMaintenanceState::InProgress => Self::InProgress,
+            McpMaintenanceState::Deferred => Self::Deferred,
+            McpMaintenanceState::Complete => Self::Complete,
+        }
+    }
+}
+
+impl From<MaintenanceState> for McpMaintenanceState {
+    fn from(value: MaintenanceState) -> Self {
+        match value {
+            MaintenanceState::None => Self::None,
+            MaintenanceState::Scheduled => Self::Scheduled,
+            MaintenanceState::InProgress => Self::InProgress,
+            MaintenanceState::Deferred => Self::Deferred,
+            MaintenanceState::Complete => Self::Complete,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 enum McpAssetKind {
@@ -347,6 +920,24 @@ impl From<SecurityClassification> for McpSecurityClassification {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum McpPolicyDecision {
+    Permit,
+    Deny,
+    Indeterminate,
+}
+
+impl From<PolicyDecision> for McpPolicyDecision {
+    fn from(value: PolicyDecision) -> Self {
+        match value {
+            PolicyDecision::Permit => Self::Permit,
+            PolicyDecision::Deny => Self::Deny,
+            PolicyDecision::Indeterminate => Self::Indeterminate,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct McpAuthContext {
@@ -386,10 +977,55 @@ struct GetByIdParams {
 #[serde(rename_all = "camelCase")]
 struct MissionInput {
     id: Option<String>,
-    name: String,
-    status: McpMissionStatus,
-    priority: McpOperationalPriority,
-    classification: McpSecurityClassification,
+    /// Required when creating.
+    name: Option<String>,
+    status: Option<McpMissionStatus>,
+    priority: Option<McpOperationalPriority>,
+    classification: Option<McpSecurityClassification>,
+}
+
+impl MissionInput {
+    /// Overlays the provided fields on the stored mission, or on create
+    /// defaults when there is none.
+    fn merge(
+        self,
+        existing: Option<Mission>,
+        id: MissionId,
+        tenant_id: TenantId,
+        default_classification: SecurityClassification,
+        now: EpochMillis,
+    ) -> Result<Mission, ErrorData> {
+        let mut mission = match existing {
+            Some(mission) => mission,
+            None => Mission {
+                id,
+                tenant_id,
+                name: self.name.clone().ok_or_else(|| {
+                    ErrorData::invalid_params("name is required when creating a mission", None)
+                })?,
+                status: MissionStatus::Planned,
+                priority: OperationalPriority::Routine,
+                classification: default_classification,
+                created_at_ms: now,
+                updated_at_ms: now,
+                archived_at_ms: None,
+            },
+        };
+        if let Some(name) = self.name {
+            mission.name = name;
+        }
+        if let Some(status) = self.status {
+            mission.status = status.into();
+        }
+        if let Some(priority) = self.priority {
+            mission.priority = priority.into();
+        }
+        if let Some(classification) = self.classification {
+            mission.classification = classification.into();
+        }
+        mission.updated_at_ms = now;
+        Ok(mission)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -397,6 +1033,9 @@ struct MissionInput {
 struct UpsertMissionParams {
     auth: Option<McpAuthContext>,
     mission: MissionInput,
+    #[serde(default)]
+    dry_run: bool,
+    idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -411,10 +1050,111 @@ struct ListAssetsParams {
 #[serde(rename_all = "camelCase")]
 struct AssetInput {
     id: Option<String>,
-    name: String,
-    kind: McpAssetKind,
-    status: McpAssetStatus,
-    classification: McpSecurityClassification,
+    /// Required when creating.
+    name: Option<String>,
+    /// Required when creating.
+    kind: Option<McpAssetKind>,
+    status: Option<McpAssetStatus>,
+    readiness: Option<McpReadinessState>,
+    comms_status: Option<McpCommsStatus>,
+    maintenance_state: Option<McpMaintenanceState>,
+    /// Replaces the maintenance window; kept when omitted. Setting a window
+    /// on an asset without pending maintenance also marks it scheduled
+    /// unless `maintenanceState` is given.
+    maintenance_window: Option<McpMaintenanceWindow>,
+    classification: Option<McpSecurityClassification>,
+    /// Unit the asset belongs to; an empty string clears it. Kept when omitted.
+    unit_id: Option<String>,
+    /// Replaces the asset's capabilities; kept when omitted.
+    capability_ids: Option<Vec<String>>,
+}
+
+impl AssetInput {
+    /// Overlays the provided fields on the stored asset, or on create
+    /// defaults when there is none. Unit and capability references are
+    /// resolved by the caller.
+    fn merge(
+        self,
+        existing: Option<Asset>,
+        id: AssetId,
+        tenant_id: TenantId,
+        default_classification: SecurityClassification,
+        now: EpochMillis,
+    ) -> Result<Asset, ErrorData> {
+        let mut asset = match existing {
+            Some(asset) => asset,
+            None => Asset {
+                id,
+                tenant_id,
+                name: self.name.clone().ok_or_else(|| {
+                    ErrorData::invalid_params("name is required when creating an asset", None)
+                })?,
+                kind: self
+                    .kind
+                    .clone()
+                    .ok_or_else(|| {
+                        ErrorData::invalid_params("kind is required when creating an asset", None)
+                    })?
+                    .into(),
+                status: AssetStatus::Available,
+                readiness: ReadinessState::default(),
+                comms_status: CommsStatus::default(),
+                maintenance_state: MaintenanceState::default(),
+                maintenance_window: None,
+                unit_id: None,
+                capability_ids: Vec::new(),
+                position: None,
+                classification: default_classification,
+                created_at_ms: now,
+                updated_at_ms: now,
+            },
+        };
+        if let Some(name) = self.name {
+            asset.name = name;
+        }
+        if let Some(kind) = self.kind {
+            asset.kind = kind.into();
+        }
+        if let Some(status) = self.status {
+            asset.status = status.into();
+        }
+        if let Some(readiness) = self.readiness {
+            asset.readiness = readiness.into();
+        }
+        if let Some(comms_status) = self.comms_status {
+            asset.comms_status = comms_status.into();
+        }
+        if let Some(window) = self.maintenance_window {
+            if window.end_ms <= window.start_ms {
+                return Err(ErrorData::invalid_params(
+                    "maintenanceWindow endMs must be after startMs",
+                    None,
+                ));
+            }
+            asset.maintenance_window = Some((window.start_ms, window.end_ms));
+            if matches!(
+                asset.maintenance_state,
+                MaintenanceState::None | MaintenanceState::Complete
+            ) {
+                asset.maintenance_state = MaintenanceState::Scheduled;
+            }
+        }
+        if let Some(maintenance_state) = self.maintenance_state {
+            asset.maintenance_state = maintenance_state.into();
+        }
+        if let Some(classification) = self.classification {
+            asset.classification = classification.into();
+        }
+        asset.updated_at_ms = now;
+        Ok(asset)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct McpMaintenanceWindow {
+    start_ms: u64,
+    end_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -422,6 +1162,57 @@ struct AssetInput {
 struct UpsertAssetParams {
     auth: Option<McpAuthContext>,
     asset: AssetInput,
+    #[serde(default)]
+    dry_run: bool,
+    idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct SetAssetStatusParams {
+    auth: Option<McpAuthContext>,
+    id: String,
+    status: Option<McpAssetStatus>,
+    readiness: Option<McpReadinessState>,
+    comms_status: Option<McpCommsStatus>,
+    maintenance_state: Option<McpMaintenanceState>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct McpCoordinate {
+    latitude: f64,
+    longitude: f64,
+    altitude_m: Option<f64>,
+    accuracy_m: Option<f64>,
+}
+
+impl From<McpCoordinate> for Coordinate {
+    fn from(value: McpCoordinate) -> Self {
+        Self {
+            latitude: value.latitude,
+            longitude: value.longitude,
+            altitude_m: value.altitude_m,
+            accuracy_m: value.accuracy_m,
+        }
+    }
+}
+
+impl From<Coordinate> for McpCoordinate {
+    fn from(value: Coordinate) -> Self {
+        let Coordinate {
+            latitude,
+            longitude,
+            altitude_m,
+            accuracy_m,
+        } = value;
+        Self {
+            latitude,
+            longitude,
+            altitude_m,
+            accuracy_m,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -430,6 +1221,21 @@ struct ListIncidentsParams {
     auth: Option<McpAuthContext>,
     limit: Option<usize>,
     offset: Option<usize>,
+    priority: Option<McpOperationalPriority>,
+    /// Order by priority, critical first, instead of newest first.
+    #[serde(default)]
+    by_priority: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct IncidentsInBboxParams {
+    auth: Option<McpAuthContext>,
+    north: f64,
+    south: f64,
+    east: f64,
+    west: f64,
+    limit: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -438,7 +1244,12 @@ struct IncidentInput {
     id: Option<String>,
     incident_type: McpIncidentType,
     status: McpIncidentStatus,
+    /// Defaults to routine.
+    priority: Option<McpOperationalPriority>,
     summary: String,
+    position: Option<McpCoordinate>,
+    /// Radius of the affected area around position, in metres.
+    affected_radius_m: Option<f64>,
     classification: McpSecurityClassification,
 }
 
@@ -447,6 +1258,9 @@ struct IncidentInput {
 struct UpsertIncidentParams {
     auth: Option<McpAuthContext>,
     incident: IncidentInput,
+    #[serde(default)]
+    dry_run: bool,
+    idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -460,43 +1274,210 @@ struct ListTasksParams {
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-struct TaskInput {
-    id: Option<String>,
+struct ListMissionAssignmentsParams {
+    auth: Option<McpAuthContext>,
     mission_id: String,
-    title: String,
-    status: McpTaskStatus,
-    priority: McpOperationalPriority,
-    classification: McpSecurityClassification,
+    limit: Option<usize>,
+    offset: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-struct UpsertTaskParams {
+struct ListUnitAssignmentsParams {
     auth: Option<McpAuthContext>,
-    task: TaskInput,
+    unit_id: String,
+    limit: Option<usize>,
+    offset: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-struct MissionList {
-    missions: Vec<McpMission>,
+struct AssignToMissionParams {
+    auth: Option<McpAuthContext>,
+    mission_id: String,
+    /// Set exactly one of unitId and teamId.
+    unit_id: Option<String>,
+    team_id: Option<String>,
+    /// What the unit or team does on the mission, such as `lead` or `support`.
+    role: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-struct AssetList {
-    assets: Vec<McpAsset>,
+struct UnassignFromMissionParams {
+    auth: Option<McpAuthContext>,
+    mission_id: String,
+    unit_id: Option<String>,
+    team_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-struct IncidentList {
-    incidents: Vec<McpIncident>,
+struct TaskInput {
+    id: Option<String>,
+    mission_id: String,
+    title: String,
+    status: McpTaskStatus,
+    priority: McpOperationalPriority,
+    classification: McpSecurityClassification,
+    /// IDs of tasks in the same tenant that must complete first; the task is
+    /// kept blocked until they do.
+    #[serde(default)]
+    depends_on: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-struct TaskList {
+struct UpsertTaskParams {
+    auth: Option<McpAuthContext>,
+    task: TaskInput,
+    #[serde(default)]
+    dry_run: bool,
+    idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct MoveTaskParams {
+    auth: Option<McpAuthContext>,
+    id: String,
+    /// The mission the task moves to, in the caller's tenant.
+    mission_id: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum McpStatusTarget {
+    Mission,
+    Task,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct BulkSetStatusParams {
+    auth: Option<McpAuthContext>,
+    /// Whether `ids` are missions or tasks.
+    target: McpStatusTarget,
+    ids: Vec<String>,
+    /// A mission status for missions, such as `completed`, or a task status
+    /// for tasks.
+    status: String,
+}
+
+/// The outcome for one id of a bulk change; `error` says why it was skipped.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct BulkStatusItem {
+    id: String,
+    ok: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct BulkStatusResults {
+    updated: usize,
+    results: Vec<BulkStatusItem>,
+}
+
+impl BulkStatusItem {
+    fn updated(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            ok: true,
+            error: None,
+        }
+    }
+
+    fn failed(id: &str, error: impl Into<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            ok: false,
+            error: Some(error.into()),
+        }
+    }
+}
+
+impl BulkStatusResults {
+    fn new(results: Vec<BulkStatusItem>) -> Self {
+        let updated = results.iter().filter(|item| item.ok).count();
+        Self { updated, results }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct CheckPermissionParams {
+    auth: Option<McpAuthContext>,
+    subject: Option<McpAuthContext>,
+    permission: String,
+    classification: McpSecurityClassification,
+    resource_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ListCapabilityCodesParams {
+    auth: Option<McpAuthContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct SituationSummaryParams {
+    auth: Option<McpAuthContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct CapabilityCodeCatalog {
+    categories: Vec<McpCapabilityCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct McpCapabilityCategory {
+    category: Option<String>,
+    codes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct PermissionCheck {
+    permission: String,
+    classification: McpSecurityClassification,
+    resource_type: String,
+    decision: McpPolicyDecision,
+    matched_rule: Option<String>,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct MissionList {
+    missions: Vec<McpMission>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct AssetList {
+    assets: Vec<McpAsset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct IncidentList {
+    incidents: Vec<McpIncident>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct AssignmentList {
+    assignments: Vec<McpAssignment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct TaskList {
     tasks: Vec<McpTask>,
 }
 
@@ -506,24 +1487,77 @@ struct McpMission {
     id: String,
     tenant_id: String,
     name: String,
-    status: McpMissionStatus,
-    priority: McpOperationalPriority,
+    /// Status, priority and timestamps are left out of redacted missions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<McpMissionStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<McpOperationalPriority>,
     classification: McpSecurityClassification,
-    created_at_ms: u64,
-    updated_at_ms: u64,
+    /// Banner text for the classification, such as `TOP SECRET`.
+    marking: String,
+    /// Share of the mission's tasks completed, cancelled ones excluded. Only
+    /// filled in by `c2.get_mission`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    progress: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at_ms: Option<u64>,
+    /// Set while the mission is archived and out of the default listings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    archived_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    redacted: bool,
 }
 
+// The `From` impls for records destructure them without `..`, so a field
+// added to a c2-core record fails to compile until its DTO carries it.
 impl From<Mission> for McpMission {
     fn from(value: Mission) -> Self {
+        let Mission {
+            id,
+            tenant_id,
+            name,
+            status,
+            priority,
+            classification,
+            created_at_ms,
+            updated_at_ms,
+            archived_at_ms,
+        } = value;
         Self {
-            id: value.id.to_string(),
-            tenant_id: value.tenant_id.to_string(),
-            name: value.name,
-            status: value.status.into(),
-            priority: value.priority.into(),
-            classification: value.classification.into(),
-            created_at_ms: value.created_at_ms,
-            updated_at_ms: value.updated_at_ms,
+            id: id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            name,
+            status: Some(status.into()),
+            priority: Some(priority.into()),
+            classification: classification.into(),
+            marking: classification.marking().to_string(),
+            progress: None,
+            created_at_ms: Some(created_at_ms),
+            updated_at_ms: Some(updated_at_ms),
+            archived_at_ms,
+            dry_run: false,
+            redacted: false,
+        }
+    }
+}
+
+impl McpMission {
+    /// Keeps only the ids and the classification.
+    fn redacted(self) -> Self {
+        Self {
+            name: REDACTED.to_string(),
+            status: None,
+            priority: None,
+            progress: None,
+            created_at_ms: None,
+            updated_at_ms: None,
+            archived_at_ms: None,
+            redacted: true,
+            ..self
         }
     }
 }
@@ -535,25 +1569,127 @@ struct McpTask {
     mission_id: String,
     tenant_id: String,
     title: String,
-    status: McpTaskStatus,
-    priority: McpOperationalPriority,
+    /// Status, priority, dependencies and timestamps are left out of
+    /// redacted tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<McpTaskStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<McpOperationalPriority>,
     classification: McpSecurityClassification,
-    created_at_ms: u64,
-    updated_at_ms: u64,
+    /// Banner text for the classification, such as `TOP SECRET`.
+    marking: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    depends_on: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    redacted: bool,
 }
 
 impl From<Task> for McpTask {
     fn from(value: Task) -> Self {
+        let Task {
+            id,
+            mission_id,
+            tenant_id,
+            title,
+            status,
+            priority,
+            classification,
+            depends_on,
+            created_at_ms,
+            updated_at_ms,
+        } = value;
+        Self {
+            id: id.to_string(),
+            mission_id: mission_id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            title,
+            status: Some(status.into()),
+            priority: Some(priority.into()),
+            classification: classification.into(),
+            marking: classification.marking().to_string(),
+            depends_on: Some(depends_on.iter().map(ToString::to_string).collect()),
+            created_at_ms: Some(created_at_ms),
+            updated_at_ms: Some(updated_at_ms),
+            dry_run: false,
+            redacted: false,
+        }
+    }
+}
+
+impl McpTask {
+    /// Keeps only the ids and the classification.
+    fn redacted(self) -> Self {
+        Self {
+            title: REDACTED.to_string(),
+            status: None,
+            priority: None,
+            depends_on: None,
+            created_at_ms: None,
+            updated_at_ms: None,
+            redacted: true,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct McpAssignment {
+    id: String,
+    mission_id: String,
+    tenant_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unit_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    team_id: Option<String>,
+    role: String,
+    classification: McpSecurityClassification,
+    /// Banner text for the classification, such as `TOP SECRET`.
+    marking: String,
+    assigned_at_ms: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    redacted: bool,
+}
+
+impl From<MissionAssignment> for McpAssignment {
+    fn from(value: MissionAssignment) -> Self {
+        let MissionAssignment {
+            id,
+            tenant_id,
+            mission_id,
+            unit_id,
+            team_id,
+            role,
+            classification,
+            assigned_at_ms,
+        } = value;
+        Self {
+            id: id.to_string(),
+            mission_id: mission_id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            unit_id: unit_id.map(|id| id.to_string()),
+            team_id: team_id.map(|id| id.to_string()),
+            role,
+            classification: classification.into(),
+            marking: classification.marking().to_string(),
+            assigned_at_ms,
+            redacted: false,
+        }
+    }
+}
+
+impl McpAssignment {
+    fn redacted(self) -> Self {
         Self {
-            id: value.id.to_string(),
-            mission_id: value.mission_id.to_string(),
-            tenant_id: value.tenant_id.to_string(),
-            title: value.title,
-            status: value.status.into(),
-            priority: value.priority.into(),
-            classification: value.classification.into(),
-            created_at_ms: value.created_at_ms,
-            updated_at_ms: value.updated_at_ms,
+            role: REDACTED.to_string(),
+            redacted: true,
+            ..self
         }
     }
 }
@@ -566,22 +1702,79 @@ struct McpAsset {
     name: String,
     kind: McpAssetKind,
     status: McpAssetStatus,
+    readiness: McpReadinessState,
+    comms_status: McpCommsStatus,
+    maintenance_state: McpMaintenanceState,
+    maintenance_window: Option<McpMaintenanceWindow>,
+    unit_id: Option<String>,
+    capability_ids: Vec<String>,
+    position: Option<McpCoordinate>,
+    /// MIL-STD-2525C symbol code derived from kind and status.
+    sidc: String,
     classification: McpSecurityClassification,
+    /// Banner text for the classification, such as `TOP SECRET`.
+    marking: String,
     created_at_ms: u64,
     updated_at_ms: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    redacted: bool,
 }
 
 impl From<Asset> for McpAsset {
     fn from(value: Asset) -> Self {
+        let Asset {
+            id,
+            tenant_id,
+            name,
+            kind,
+            status,
+            readiness,
+            comms_status,
+            maintenance_state,
+            maintenance_window,
+            unit_id,
+            capability_ids,
+            position,
+            classification,
+            created_at_ms,
+            updated_at_ms,
+        } = value;
+        Self {
+            id: id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            name,
+            kind: kind.into(),
+            status: status.into(),
+            readiness: readiness.into(),
+            comms_status: comms_status.into(),
+            maintenance_state: maintenance_state.into(),
+            maintenance_window: maintenance_window
+                .map(|(start_ms, end_ms)| McpMaintenanceWindow { start_ms, end_ms }),
+            unit_id: unit_id.map(|id| id.to_string()),
+            capability_ids: capability_ids.iter().map(|id| id.to_string()).collect(),
+            position: position.map(Into::into),
+            sidc: sidc_for(kind, status),
+            classification: classification.into(),
+            marking: classification.marking().to_string(),
+            created_at_ms,
+            updated_at_ms,
+            dry_run: false,
+            redacted: false,
+        }
+    }
+}
+
+impl McpAsset {
+    fn redacted(self) -> Self {
         Self {
-            id: value.id.to_string(),
-            tenant_id: value.tenant_id.to_string(),
-            name: value.name,
-            kind: value.kind.into(),
-            status: value.status.into(),
-            classification: value.classification.into(),
-            created_at_ms: value.created_at_ms,
-            updated_at_ms: value.updated_at_ms,
+            name: REDACTED.to_string(),
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: None,
+            redacted: true,
+            ..self
         }
     }
 }
@@ -593,23 +1786,63 @@ struct McpIncident {
     tenant_id: String,
     incident_type: McpIncidentType,
     status: McpIncidentStatus,
+    priority: McpOperationalPriority,
     summary: String,
+    position: Option<McpCoordinate>,
+    affected_radius_m: Option<f64>,
     classification: McpSecurityClassification,
+    /// Banner text for the classification, such as `TOP SECRET`.
+    marking: String,
     created_at_ms: u64,
     updated_at_ms: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    redacted: bool,
 }
 
 impl From<Incident> for McpIncident {
     fn from(value: Incident) -> Self {
+        let Incident {
+            id,
+            tenant_id,
+            incident_type,
+            status,
+            priority,
+            summary,
+            position,
+            affected_radius_m,
+            classification,
+            created_at_ms,
+            updated_at_ms,
+        } = value;
+        Self {
+            id: id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            incident_type: incident_type.into(),
+            status: status.into(),
+            priority: priority.into(),
+            summary,
+            position: position.map(Into::into),
+            affected_radius_m,
+            classification: classification.into(),
+            marking: classification.marking().to_string(),
+            created_at_ms,
+            updated_at_ms,
+            dry_run: false,
+            redacted: false,
+        }
+    }
+}
+
+impl McpIncident {
+    fn redacted(self, position: Option<Coordinate>) -> Self {
         Self {
-            id: value.id.to_string(),
-            tenant_id: value.tenant_id.to_string(),
-            incident_type: value.incident_type.into(),
-            status: value.status.into(),
-            summary: value.summary,
-            classification: value.classification.into(),
-            created_at_ms: value.created_at_ms,
-            updated_at_ms: value.updated_at_ms,
+            summary: REDACTED.to_string(),
+            position: position.map(Into::into),
+            affected_radius_m: None,
+            redacted: true,
+            ..self
         }
     }
 }
@@ -627,7 +1860,7 @@ impl C2McpService {
         meta: Meta,
     ) -> Result<Json<MissionList>, ErrorData> {
         let ListMissionsParams { auth, limit, offset } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(auth, &meta)?;
         authorize_action(
             &self.policy,
             &auth,
@@ -637,22 +1870,27 @@ impl C2McpService {
             None,
         )?;
 
-        let limit = limit.unwrap_or(100);
+        let limit = self.page_limit(limit)?;
         let offset = offset.unwrap_or(0);
-        let missions = MissionRepository::list_by_tenant(&*self.store, auth.subject.tenant_id, limit, offset)
-            .await
-            .map_err(storage_error)?;
+        let store = self.cleared_store(auth.subject.clearance);
+        let missions =
+            MissionRepository::list_by_tenant(&store, auth.subject.tenant_id, limit, offset)
+                .await
+                .map_err(storage_error)?;
         let missions = missions
             .into_iter()
-            .filter(|mission| mission.classification <= auth.subject.clearance)
-            .map(McpMission::from)
+            .filter_map(|mission| match self.visibility(&auth, mission.classification) {
+                Visibility::Full => Some(McpMission::from(mission)),
+                Visibility::Redacted => Some(McpMission::from(mission).redacted()),
+                Visibility::Hidden => None,
+            })
             .collect();
         Ok(Json(MissionList { missions }))
     }
 
     #[tool(
         name = "c2.get_mission",
-        description = "Fetch a mission by ID.",
+        description = "Fetch a mission by ID, with progress as the share of its tasks completed.",
         annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
     )]
     async fn get_mission(
@@ -661,29 +1899,41 @@ impl C2McpService {
         meta: Meta,
     ) -> Result<Json<McpMission>, ErrorData> {
         let GetByIdParams { auth, id } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(auth, &meta)?;
         let mission_id = parse_uuid(&id)?;
         let mission_id = MissionId::from_uuid(mission_id);
-        let mission = MissionRepository::get(&*self.store, mission_id)
+        let store = self.cleared_store(auth.subject.clearance);
+        let mission = MissionRepository::get(&store, mission_id)
             .await
             .map_err(storage_error)?;
         let Some(mission) = mission else {
             return Err(ErrorData::resource_not_found("mission not found", None));
         };
+        let visibility = self.visibility(&auth, mission.classification);
         authorize_action(
             &self.policy,
             &auth,
             Permission::ViewMissions,
-            mission.classification,
+            visibility.checked_classification(&auth, mission.classification),
             "mission",
             Some(mission.id.to_string()),
         )?;
-        Ok(Json(McpMission::from(mission)))
+        let progress = TaskRepository::mission_progress(&*self.store, mission.id)
+            .await
+            .map_err(storage_error)?;
+        let mission = McpMission {
+            progress: Some(progress.fraction()),
+            ..McpMission::from(mission)
+        };
+        if visibility == Visibility::Redacted {
+            return Ok(Json(mission.redacted()));
+        }
+        Ok(Json(mission))
     }
 
     #[tool(
         name = "c2.upsert_mission",
-        description = "Create or update a mission.",
+        description = "Create or update a mission. On update, omitted fields keep their stored values; name is required on create. Set dryRun to validate and authorize without writing. Pass idempotencyKey on creates so retries return the original record.",
         annotations(read_only_hint = false, idempotent_hint = false, destructive_hint = true)
     )]
     async fn upsert_mission(
@@ -691,52 +1941,137 @@ impl C2McpService {
         params: Parameters<UpsertMissionParams>,
         meta: Meta,
     ) -> Result<Json<McpMission>, ErrorData> {
-        let UpsertMissionParams { auth, mission } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
-        let mission_id = match &mission.id {
-            Some(value) => MissionId::from_uuid(parse_uuid(value)?),
-            None => MissionId::new(),
+        let UpsertMissionParams {
+            auth,
+            mission,
+            dry_run,
+            idempotency_key,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        let (mission_id, replayed) = self.resolve_upsert_id(
+            &auth,
+            mission.id.as_deref(),
+            &mission,
+            idempotency_key.as_deref(),
+            dry_run,
+        )?;
+        let mission_id = MissionId::from_uuid(mission_id);
+        // A freshly generated id cannot exist yet, so only explicit ids and
+        // replays need the current record (for merging and replay responses).
+        // The lookup spans every partition: a write moves the mission out of
+        // the one it was in, so a copy above the caller's clearance must not
+        // be replaced by one they can see.
+        let existing = if mission.id.is_some() || replayed {
+            MissionRepository::get(&*self.store, mission_id)
+                .await
+                .map_err(storage_error)?
+        } else {
+            None
         };
-        let existing = MissionRepository::get(&*self.store, mission_id)
-            .await
-            .map_err(storage_error)?;
         if let Some(existing) = &existing {
             if existing.tenant_id != auth.subject.tenant_id {
                 return Err(ErrorData::invalid_request("tenant mismatch", None));
             }
+            if existing.classification > auth.subject.clearance {
+                return Err(Refusal::Denied.error("permission denied", None));
+            }
         }
 
-        let classification: SecurityClassification = mission.classification.into();
+        let mut mission = mission.merge(
+            existing.clone(),
+            mission_id,
+            auth.subject.tenant_id,
+            self.floors.floor(auth.subject.tenant_id),
+            now_epoch_millis(),
+        )?;
         authorize_action(
             &self.policy,
             &auth,
             Permission::EditMissions,
-            classification,
+            mission.classification,
             "mission",
             Some(mission_id.to_string()),
         )?;
+        if replayed && let Some(existing) = existing {
+            return Ok(Json(McpMission::from(existing)));
+        }
+        self.check_classification(&auth, mission.classification)?;
+        if mission.status == MissionStatus::Completed {
+            let progress = TaskRepository::mission_progress(&*self.store, mission_id)
+                .await
+                .map_err(storage_error)?;
+            if progress.open() > 0 {
+                return Err(ErrorData::invalid_params(
+                    format!("mission still has {} open task(s)", progress.open()),
+                    None,
+                ));
+            }
+        }
 
-        let created_at_ms = existing
-            .as_ref()
-            .map(|mission| mission.created_at_ms)
-            .unwrap_or_else(now_epoch_millis);
-        let updated_at_ms = now_epoch_millis();
-        let mission = Mission {
-            id: mission_id,
-            tenant_id: auth.subject.tenant_id,
-            name: mission.name,
-            status: mission.status.into(),
-            priority: mission.priority.into(),
-            classification,
-            created_at_ms,
-            updated_at_ms,
-        };
-        MissionRepository::upsert(&*self.store, mission.clone())
+        if dry_run {
+            return Ok(Json(McpMission {
+                dry_run: true,
+                ..McpMission::from(mission)
+            }));
+        }
+        let outcome = MissionRepository::insert_or_update(&*self.store, mission.clone())
             .await
-            .map_err(storage_error)?;
+            .map_err(storage_error)?
+            .ok_or_else(|| ErrorData::invalid_request("tenant mismatch", None))?;
+        mission.created_at_ms = outcome.created_at_ms;
+        self.events.publish(DomainEvent::upsert(
+            EntityKind::Mission,
+            mission.id,
+            mission.tenant_id,
+            mission.classification,
+            &mission,
+        ));
         Ok(Json(McpMission::from(mission)))
     }
 
+    #[tool(
+        name = "c2.check_permission",
+        description = "Explain whether a subject would be allowed to perform an action on a resource of a given classification. Defaults to the caller when subject is omitted; reads no data.",
+        annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn check_permission(
+        &self,
+        params: Parameters<CheckPermissionParams>,
+        meta: Meta,
+    ) -> Result<Json<PermissionCheck>, ErrorData> {
+        let CheckPermissionParams {
+            auth,
+            subject,
+            permission,
+            classification,
+            resource_type,
+        } = params.0;
+        let caller = self.resolve_auth(auth, &meta)?;
+        let subject = match subject {
+            Some(subject) => parse_auth(&subject)?,
+            None => caller,
+        };
+        let action = Permission::from_str(&permission)
+            .map_err(|_| ErrorData::invalid_params("invalid permission", None))?;
+        let resource_type = resource_type.unwrap_or_else(|| "resource".to_string());
+        let outcome = explain_action(
+            &self.policy,
+            &subject,
+            action,
+            classification.clone().into(),
+            &resource_type,
+            None,
+        );
+        Ok(Json(PermissionCheck {
+            permission,
+            classification,
+            resource_type,
+            decision: outcome.decision.into(),
+            matched_rule: outcome.matched_rule,
+            reason: outcome.reason,
+        }))
+    }
+
     #[tool(
         name = "c2.list_assets",
         description = "List assets for a tenant.",
@@ -748,7 +2083,7 @@ impl C2McpService {
         meta: Meta,
     ) -> Result<Json<AssetList>, ErrorData> {
         let ListAssetsParams { auth, limit, offset } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(auth, &meta)?;
         authorize_action(
             &self.policy,
             &auth,
@@ -757,15 +2092,18 @@ impl C2McpService {
             "asset",
             None,
         )?;
-        let limit = limit.unwrap_or(100);
+        let limit = self.page_limit(limit)?;
         let offset = offset.unwrap_or(0);
         let assets = AssetRepository::list_by_tenant(&*self.store, auth.subject.tenant_id, limit, offset)
             .await
             .map_err(storage_error)?;
         let assets = assets
             .into_iter()
-            .filter(|asset| asset.classification <= auth.subject.clearance)
-            .map(McpAsset::from)
+            .filter_map(|asset| match self.visibility(&auth, asset.classification) {
+                Visibility::Full => Some(McpAsset::from(asset)),
+                Visibility::Redacted => Some(McpAsset::from(asset).redacted()),
+                Visibility::Hidden => None,
+            })
             .collect();
         Ok(Json(AssetList { assets }))
     }
@@ -781,7 +2119,7 @@ impl C2McpService {
         meta: Meta,
     ) -> Result<Json<McpAsset>, ErrorData> {
         let GetByIdParams { auth, id } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(auth, &meta)?;
         let asset_id = AssetId::from_uuid(parse_uuid(&id)?);
         let asset = AssetRepository::get(&*self.store, asset_id)
             .await
@@ -789,20 +2127,25 @@ impl C2McpService {
         let Some(asset) = asset else {
             return Err(ErrorData::resource_not_found("asset not found", None));
         };
+        let visibility = self.visibility(&auth, asset.classification);
         authorize_action(
             &self.policy,
             &auth,
             Permission::DispatchAssets,
-            asset.classification,
+            visibility.checked_classification(&auth, asset.classification),
             "asset",
             Some(asset.id.to_string()),
         )?;
-        Ok(Json(McpAsset::from(asset)))
+        let asset = McpAsset::from(asset);
+        if visibility == Visibility::Redacted {
+            return Ok(Json(asset.redacted()));
+        }
+        Ok(Json(asset))
     }
 
     #[tool(
         name = "c2.upsert_asset",
-        description = "Create or update an asset.",
+        description = "Create or update an asset. On update, omitted fields keep their stored values; name and kind are required on create. unitId and capabilityIds must reference records in the tenant. Set dryRun to validate and authorize without writing. Pass idempotencyKey on creates so retries return the original record.",
         annotations(read_only_hint = false, idempotent_hint = false, destructive_hint = true)
     )]
     async fn upsert_asset(
@@ -810,60 +2153,157 @@ impl C2McpService {
         params: Parameters<UpsertAssetParams>,
         meta: Meta,
     ) -> Result<Json<McpAsset>, ErrorData> {
-        let UpsertAssetParams { auth, asset } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
-        let asset_id = match &asset.id {
-            Some(value) => AssetId::from_uuid(parse_uuid(value)?),
-            None => AssetId::new(),
+        let UpsertAssetParams {
+            auth,
+            mut asset,
+            dry_run,
+            idempotency_key,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        let (asset_id, replayed) = self.resolve_upsert_id(
+            &auth,
+            asset.id.as_deref(),
+            &asset,
+            idempotency_key.as_deref(),
+            dry_run,
+        )?;
+        let asset_id = AssetId::from_uuid(asset_id);
+        let existing = if asset.id.is_some() || replayed {
+            AssetRepository::get(&*self.store, asset_id)
+                .await
+                .map_err(storage_error)?
+        } else {
+            None
         };
-        let existing = AssetRepository::get(&*self.store, asset_id)
-            .await
-            .map_err(storage_error)?;
         if let Some(existing) = &existing {
             if existing.tenant_id != auth.subject.tenant_id {
                 return Err(ErrorData::invalid_request("tenant mismatch", None));
             }
         }
 
-        let classification: SecurityClassification = asset.classification.into();
+        let unit_input = asset.unit_id.take();
+        let capability_input = asset.capability_ids.take();
+        let mut asset = asset.merge(
+            existing.clone(),
+            asset_id,
+            auth.subject.tenant_id,
+            self.floors.floor(auth.subject.tenant_id),
+            now_epoch_millis(),
+        )?;
         authorize_action(
             &self.policy,
             &auth,
             Permission::DispatchAssets,
-            classification,
+            asset.classification,
             "asset",
             Some(asset_id.to_string()),
         )?;
+        if replayed && let Some(existing) = existing {
+            return Ok(Json(McpAsset::from(existing)));
+        }
+        self.check_classification(&auth, asset.classification)?;
 
-        let created_at_ms = existing
-            .as_ref()
-            .map(|asset| asset.created_at_ms)
-            .unwrap_or_else(now_epoch_millis);
-        let updated_at_ms = now_epoch_millis();
-        let asset = Asset {
-            id: asset_id,
-            tenant_id: auth.subject.tenant_id,
-            name: asset.name,
-            kind: asset.kind.into(),
-            status: asset.status.into(),
-            readiness: ReadinessState::default(),
-            comms_status: CommsStatus::default(),
-            maintenance_state: MaintenanceState::default(),
-            unit_id: None,
-            capability_ids: Vec::new(),
-            classification,
-            created_at_ms,
-            updated_at_ms,
-        };
-        AssetRepository::upsert(&*self.store, asset.clone())
+        if let Some(value) = unit_input.as_deref() {
+            asset.unit_id = self.checked_unit_id(&auth, value).await?;
+        }
+        if let Some(values) = capability_input.as_deref() {
+            asset.capability_ids = self.checked_capability_ids(&auth, values).await?;
+        }
+        if dry_run {
+            return Ok(Json(McpAsset {
+                dry_run: true,
+                ..McpAsset::from(asset)
+            }));
+        }
+        let outcome = AssetRepository::insert_or_update(&*self.store, asset.clone())
+            .await
+            .map_err(storage_error)?
+            .ok_or_else(|| ErrorData::invalid_request("tenant mismatch", None))?;
+        asset.created_at_ms = outcome.created_at_ms;
+        self.events.publish(DomainEvent::upsert(
+            EntityKind::Asset,
+            asset.id,
+            asset.tenant_id,
+            asset.classification,
+            &asset,
+        ));
+        Ok(Json(McpAsset::from(asset)))
+    }
+
+    #[tool(
+        name = "c2.set_asset_status",
+        description = "Update only an asset's status, readiness, commsStatus and/or maintenanceState. Other fields are left untouched.",
+        annotations(read_only_hint = false, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn set_asset_status(
+        &self,
+        params: Parameters<SetAssetStatusParams>,
+        meta: Meta,
+    ) -> Result<Json<McpAsset>, ErrorData> {
+        let SetAssetStatusParams {
+            auth,
+            id,
+            status,
+            readiness,
+            comms_status,
+            maintenance_state,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        if status.is_none()
+            && readiness.is_none()
+            && comms_status.is_none()
+            && maintenance_state.is_none()
+        {
+            return Err(ErrorData::invalid_params("no status fields provided", None));
+        }
+        let asset_id = AssetId::from_uuid(parse_uuid(&id)?);
+        let asset = AssetRepository::get(&*self.store, asset_id)
             .await
             .map_err(storage_error)?;
+        let Some(mut asset) = asset else {
+            return Err(ErrorData::resource_not_found("asset not found", None));
+        };
+        if asset.tenant_id != auth.subject.tenant_id {
+            return Err(ErrorData::invalid_request("tenant mismatch", None));
+        }
+        authorize_action(
+            &self.policy,
+            &auth,
+            Permission::DispatchAssets,
+            asset.classification,
+            "asset",
+            Some(asset.id.to_string()),
+        )?;
+
+        if let Some(status) = status {
+            asset.status = status.into();
+        }
+        if let Some(readiness) = readiness {
+            asset.readiness = readiness.into();
+        }
+        if let Some(comms_status) = comms_status {
+            asset.comms_status = comms_status.into();
+        }
+        if let Some(maintenance_state) = maintenance_state {
+            asset.maintenance_state = maintenance_state.into();
+        }
+        asset.updated_at_ms = now_epoch_millis();
+        AssetRepository::upsert(&*self.store, asset.clone())
+            .await
+            .map_err(storage_error)?;
+        self.events.publish(DomainEvent::upsert(
+            EntityKind::Asset,
+            asset.id,
+            asset.tenant_id,
+            asset.classification,
+            &asset,
+        ));
         Ok(Json(McpAsset::from(asset)))
     }
 
     #[tool(
         name = "c2.list_incidents",
-        description = "List incidents for a tenant.",
+        description = "List incidents for a tenant, newest first. Set priority to filter and byPriority to list critical first.",
         annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
     )]
     async fn list_incidents(
@@ -871,8 +2311,14 @@ impl C2McpService {
         params: Parameters<ListIncidentsParams>,
         meta: Meta,
     ) -> Result<Json<IncidentList>, ErrorData> {
-        let ListIncidentsParams { auth, limit, offset } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let ListIncidentsParams {
+            auth,
+            limit,
+            offset,
+            priority,
+            by_priority,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
         authorize_action(
             &self.policy,
             &auth,
@@ -881,16 +2327,85 @@ impl C2McpService {
             "incident",
             None,
         )?;
-        let limit = limit.unwrap_or(100);
+        let limit = self.page_limit(limit)?;
         let offset = offset.unwrap_or(0);
+        let filter = IncidentFilter {
+            priority: priority.map(Into::into),
+            sort: if by_priority {
+                IncidentSort::Priority
+            } else {
+                IncidentSort::Newest
+            },
+            ..IncidentFilter::default()
+        };
+        let incidents = IncidentRepository::list_filtered(
+            &*self.store,
+            auth.subject.tenant_id,
+            &filter,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(storage_error)?;
+        let incidents = incidents
+            .into_iter()
+            .filter_map(|incident| match self.visibility(&auth, incident.classification) {
+                Visibility::Full => Some(McpIncident::from(incident)),
+                Visibility::Redacted => Some(self.redacted_incident(&auth, incident)),
+                Visibility::Hidden => None,
+            })
+            .collect();
+        Ok(Json(IncidentList { incidents }))
+    }
+
+    #[tool(
+        name = "c2.incidents_in_bbox",
+        description = "List positioned incidents inside a bounding box, newest first. Edges are in degrees with south <= north and west <= east.",
+        annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn incidents_in_bbox(
+        &self,
+        params: Parameters<IncidentsInBboxParams>,
+        meta: Meta,
+    ) -> Result<Json<IncidentList>, ErrorData> {
+        let IncidentsInBboxParams {
+            auth,
+            north,
+            south,
+            east,
+            west,
+            limit,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        authorize_action(
+            &self.policy,
+            &auth,
+            Permission::ViewIncidents,
+            SecurityClassification::Unclassified,
+            "incident",
+            None,
+        )?;
+        let limit = self.page_limit(limit)?;
+        let bbox = BoundingBox {
+            north,
+            south,
+            east,
+            west,
+        };
+        if !bbox.is_valid() {
+            return Err(ErrorData::invalid_params("invalid bounding box", None));
+        }
         let incidents =
-            IncidentRepository::list_by_tenant(&*self.store, auth.subject.tenant_id, limit, offset)
+            IncidentRepository::list_in_bbox(&*self.store, auth.subject.tenant_id, bbox, limit)
                 .await
                 .map_err(storage_error)?;
         let incidents = incidents
             .into_iter()
-            .filter(|incident| incident.classification <= auth.subject.clearance)
-            .map(McpIncident::from)
+            .filter_map(|incident| match self.visibility(&auth, incident.classification) {
+                Visibility::Full => Some(McpIncident::from(incident)),
+                Visibility::Redacted => Some(self.redacted_incident(&auth, incident)),
+                Visibility::Hidden => None,
+            })
             .collect();
         Ok(Json(IncidentList { incidents }))
     }
@@ -906,7 +2421,7 @@ impl C2McpService {
         meta: Meta,
     ) -> Result<Json<McpIncident>, ErrorData> {
         let GetByIdParams { auth, id } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(auth, &meta)?;
         let incident_id = IncidentId::from_uuid(parse_uuid(&id)?);
         let incident = IncidentRepository::get(&*self.store, incident_id)
             .await
@@ -914,20 +2429,24 @@ impl C2McpService {
         let Some(incident) = incident else {
             return Err(ErrorData::resource_not_found("incident not found", None));
         };
+        let visibility = self.visibility(&auth, incident.classification);
         authorize_action(
             &self.policy,
             &auth,
             Permission::ViewIncidents,
-            incident.classification,
+            visibility.checked_classification(&auth, incident.classification),
             "incident",
             Some(incident.id.to_string()),
         )?;
+        if visibility == Visibility::Redacted {
+            return Ok(Json(self.redacted_incident(&auth, incident)));
+        }
         Ok(Json(McpIncident::from(incident)))
     }
 
     #[tool(
         name = "c2.upsert_incident",
-        description = "Create or update an incident.",
+        description = "Create or update an incident. Set dryRun to validate and authorize without writing. Pass idempotencyKey on creates so retries return the original record.",
         annotations(read_only_hint = false, idempotent_hint = false, destructive_hint = true)
     )]
     async fn upsert_incident(
@@ -935,15 +2454,30 @@ impl C2McpService {
         params: Parameters<UpsertIncidentParams>,
         meta: Meta,
     ) -> Result<Json<McpIncident>, ErrorData> {
-        let UpsertIncidentParams { auth, incident } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
-        let incident_id = match &incident.id {
-            Some(value) => IncidentId::from_uuid(parse_uuid(value)?),
-            None => IncidentId::new(),
+        let UpsertIncidentParams {
+            auth,
+            incident,
+            dry_run,
+            idempotency_key,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        let (incident_id, replayed) = self.resolve_upsert_id(
+            &auth,
+            incident.id.as_deref(),
+            &incident,
+            idempotency_key.as_deref(),
+            dry_run,
+        )?;
+        let incident_id = IncidentId::from_uuid(incident_id);
+        // Writes replace the whole record and the store checks the tenant, so
+        // the current record is only needed to answer replays and dry runs.
+        let existing = if replayed || dry_run {
+            IncidentRepository::get(&*self.store, incident_id)
+                .await
+                .map_err(storage_error)?
+        } else {
+            None
         };
-        let existing = IncidentRepository::get(&*self.store, incident_id)
-            .await
-            .map_err(storage_error)?;
         if let Some(existing) = &existing {
             if existing.tenant_id != auth.subject.tenant_id {
                 return Err(ErrorData::invalid_request("tenant mismatch", None));
@@ -959,25 +2493,60 @@ impl C2McpService {
             "incident",
             Some(incident_id.to_string()),
         )?;
+        if replayed && let Some(existing) = existing {
+            return Ok(Json(McpIncident::from(existing)));
+        }
+        self.check_classification(&auth, classification)?;
+        let position = incident.position.map(Coordinate::from);
+        if position.is_some_and(|position| !position.is_valid()) {
+            return Err(ErrorData::invalid_params("position is out of range", None));
+        }
+        if incident
+            .affected_radius_m
+            .is_some_and(|radius| !radius.is_finite() || radius < 0.0)
+        {
+            return Err(ErrorData::invalid_params(
+                "affectedRadiusM must be a non-negative number",
+                None,
+            ));
+        }
 
         let created_at_ms = existing
             .as_ref()
             .map(|incident| incident.created_at_ms)
             .unwrap_or_else(now_epoch_millis);
         let updated_at_ms = now_epoch_millis();
-        let incident = Incident {
+        let mut incident = Incident {
             id: incident_id,
             tenant_id: auth.subject.tenant_id,
             incident_type: incident.incident_type.into(),
             status: incident.status.into(),
+            priority: incident.priority.map(Into::into).unwrap_or_default(),
             summary: incident.summary,
+            position,
+            affected_radius_m: incident.affected_radius_m,
             classification,
             created_at_ms,
             updated_at_ms,
         };
-        IncidentRepository::upsert(&*self.store, incident.clone())
+        if dry_run {
+            return Ok(Json(McpIncident {
+                dry_run: true,
+                ..McpIncident::from(incident)
+            }));
+        }
+        let outcome = IncidentRepository::insert_or_update(&*self.store, incident.clone())
             .await
-            .map_err(storage_error)?;
+            .map_err(storage_error)?
+            .ok_or_else(|| ErrorData::invalid_request("tenant mismatch", None))?;
+        incident.created_at_ms = outcome.created_at_ms;
+        self.events.publish(DomainEvent::upsert(
+            EntityKind::Incident,
+            incident.id,
+            incident.tenant_id,
+            incident.classification,
+            &incident,
+        ));
         Ok(Json(McpIncident::from(incident)))
     }
 
@@ -997,7 +2566,7 @@ impl C2McpService {
             limit,
             offset,
         } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(auth, &meta)?;
         authorize_action(
             &self.policy,
             &auth,
@@ -1007,15 +2576,18 @@ impl C2McpService {
             None,
         )?;
         let mission_id = MissionId::from_uuid(parse_uuid(&mission_id)?);
-        let limit = limit.unwrap_or(100);
+        let limit = self.page_limit(limit)?;
         let offset = offset.unwrap_or(0);
         let tasks = TaskRepository::list_by_mission(&*self.store, mission_id, limit, offset)
             .await
             .map_err(storage_error)?;
         let tasks = tasks
             .into_iter()
-            .filter(|task| task.classification <= auth.subject.clearance)
-            .map(McpTask::from)
+            .filter_map(|task| match self.visibility(&auth, task.classification) {
+                Visibility::Full => Some(McpTask::from(task)),
+                Visibility::Redacted => Some(McpTask::from(task).redacted()),
+                Visibility::Hidden => None,
+            })
             .collect();
         Ok(Json(TaskList { tasks }))
     }
@@ -1031,7 +2603,7 @@ impl C2McpService {
         meta: Meta,
     ) -> Result<Json<McpTask>, ErrorData> {
         let GetByIdParams { auth, id } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(auth, &meta)?;
         let task_id = TaskId::from_uuid(parse_uuid(&id)?);
         let task = TaskRepository::get(&*self.store, task_id)
             .await
@@ -1039,20 +2611,25 @@ impl C2McpService {
         let Some(task) = task else {
             return Err(ErrorData::resource_not_found("task not found", None));
         };
+        let visibility = self.visibility(&auth, task.classification);
         authorize_action(
             &self.policy,
             &auth,
             Permission::ViewMissions,
-            task.classification,
+            visibility.checked_classification(&auth, task.classification),
             "task",
             Some(task.id.to_string()),
         )?;
-        Ok(Json(McpTask::from(task)))
+        let task = McpTask::from(task);
+        if visibility == Visibility::Redacted {
+            return Ok(Json(task.redacted()));
+        }
+        Ok(Json(task))
     }
 
     #[tool(
         name = "c2.upsert_task",
-        description = "Create or update a task.",
+        description = "Create or update a task. dependsOn lists tasks that must complete first; while any is incomplete the task is kept blocked, and dependency cycles are rejected. Set dryRun to validate and authorize without writing. Pass idempotencyKey on creates so retries return the original record.",
         annotations(read_only_hint = false, idempotent_hint = false, destructive_hint = true)
     )]
     async fn upsert_task(
@@ -1060,14 +2637,29 @@ impl C2McpService {
         params: Parameters<UpsertTaskParams>,
         meta: Meta,
     ) -> Result<Json<McpTask>, ErrorData> {
-        let UpsertTaskParams { auth, task } = params.0;
-        let auth = resolve_auth(auth, &meta, self.default_auth.as_ref())?;
-        let task_id = match &task.id {
-            Some(value) => TaskId::from_uuid(parse_uuid(value)?),
-            None => TaskId::new(),
-        };
+        let UpsertTaskParams {
+            auth,
+            task,
+            dry_run,
+            idempotency_key,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        let (task_id, replayed) = self.resolve_upsert_id(
+            &auth,
+            task.id.as_deref(),
+            &task,
+            idempotency_key.as_deref(),
+            dry_run,
+        )?;
+        let task_id = TaskId::from_uuid(task_id);
         let mission_id = MissionId::from_uuid(parse_uuid(&task.mission_id)?);
-        let mission = MissionRepository::get(&*self.store, mission_id)
+        let depends_on = task
+            .depends_on
+            .iter()
+            .map(|id| parse_uuid(id).map(TaskId::from_uuid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let store = self.cleared_store(auth.subject.clearance);
+        let mission = MissionRepository::get(&store, mission_id)
             .await
             .map_err(storage_error)?;
         let Some(mission) = mission else {
@@ -1076,9 +2668,13 @@ impl C2McpService {
         if mission.tenant_id != auth.subject.tenant_id {
             return Err(ErrorData::invalid_request("tenant mismatch", None));
         }
-        let existing = TaskRepository::get(&*self.store, task_id)
-            .await
-            .map_err(storage_error)?;
+        let existing = if replayed || dry_run {
+            TaskRepository::get(&*self.store, task_id)
+                .await
+                .map_err(storage_error)?
+        } else {
+            None
+        };
         if let Some(existing) = &existing {
             if existing.tenant_id != auth.subject.tenant_id {
                 return Err(ErrorData::invalid_request("tenant mismatch", None));
@@ -1089,41 +2685,439 @@ impl C2McpService {
         authorize_action(
             &self.policy,
             &auth,
-            Permission::EditMissions,
-            classification,
-            "task",
-            Some(task_id.to_string()),
+            Permission::EditMissions,
+            classification,
+            "task",
+            Some(task_id.to_string()),
+        )?;
+        if replayed && let Some(existing) = existing {
+            return Ok(Json(McpTask::from(existing)));
+        }
+        self.check_classification(&auth, classification)?;
+
+        let created_at_ms = existing
+            .as_ref()
+            .map(|task| task.created_at_ms)
+            .unwrap_or_else(now_epoch_millis);
+        let updated_at_ms = now_epoch_millis();
+        let mut task = Task {
+            id: task_id,
+            mission_id,
+            tenant_id: auth.subject.tenant_id,
+            title: task.title,
+            status: task.status.into(),
+            priority: task.priority.into(),
+            classification,
+            depends_on,
+            created_at_ms,
+            updated_at_ms,
+        };
+        if let Some(conflict) = check_task_dependencies(&*self.store, &task)
+            .await
+            .map_err(storage_error)?
+        {
+            return Err(ErrorData::invalid_params(conflict.to_string(), None));
+        }
+        if dry_run {
+            return Ok(Json(McpTask {
+                dry_run: true,
+                ..McpTask::from(task)
+            }));
+        }
+        let outcome = TaskRepository::insert_or_update(&*self.store, task.clone())
+            .await
+            .map_err(storage_error)?
+            .ok_or_else(|| ErrorData::invalid_request("tenant mismatch", None))?;
+        task.created_at_ms = outcome.created_at_ms;
+        self.events.publish(DomainEvent::upsert(
+            EntityKind::Task,
+            task.id,
+            task.tenant_id,
+            task.classification,
+            &task,
+        ));
+        Ok(Json(McpTask::from(task)))
+    }
+
+    #[tool(
+        name = "c2.move_task",
+        description = "Move a task to another mission of the same tenant, keeping its other fields. The target mission must exist.",
+        annotations(read_only_hint = false, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn move_task(
+        &self,
+        params: Parameters<MoveTaskParams>,
+        meta: Meta,
+    ) -> Result<Json<McpTask>, ErrorData> {
+        let MoveTaskParams {
+            auth,
+            id,
+            mission_id,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        let task_id = TaskId::from_uuid(parse_uuid(&id)?);
+        let mission_id = MissionId::from_uuid(parse_uuid(&mission_id)?);
+        let task = TaskRepository::get(&*self.store, task_id)
+            .await
+            .map_err(storage_error)?;
+        let Some(mut task) = task else {
+            return Err(ErrorData::resource_not_found("task not found", None));
+        };
+        if task.tenant_id != auth.subject.tenant_id {
+            return Err(ErrorData::invalid_request("tenant mismatch", None));
+        }
+        let store = self.cleared_store(auth.subject.clearance);
+        let mission = MissionRepository::get(&store, mission_id)
+            .await
+            .map_err(storage_error)?;
+        let Some(mission) = mission.filter(|mission| mission.tenant_id == auth.subject.tenant_id)
+        else {
+            return Err(ErrorData::resource_not_found("mission not found", None));
+        };
+        authorize_action(
+            &self.policy,
+            &auth,
+            Permission::EditMissions,
+            task.classification,
+            "task",
+            Some(task.id.to_string()),
+        )?;
+        authorize_action(
+            &self.policy,
+            &auth,
+            Permission::EditMissions,
+            mission.classification,
+            "mission",
+            Some(mission.id.to_string()),
+        )?;
+
+        task.mission_id = mission.id;
+        task.updated_at_ms = now_epoch_millis();
+        TaskRepository::upsert(&*self.store, task.clone())
+            .await
+            .map_err(storage_error)?;
+        self.events.publish(DomainEvent::upsert(
+            EntityKind::Task,
+            task.id,
+            task.tenant_id,
+            task.classification,
+            &task,
+        ));
+        Ok(Json(McpTask::from(task)))
+    }
+
+    #[tool(
+        name = "c2.bulk_set_status",
+        description = "Set the status of many missions or tasks at once, such as completing every mission of an exercise. Each id is authorized and validated on its own: completed or aborted missions and completed or cancelled tasks keep their status, and a mission with open tasks cannot be completed. Returns a result per id; failures leave that record unchanged.",
+        annotations(read_only_hint = false, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn bulk_set_status(
+        &self,
+        params: Parameters<BulkSetStatusParams>,
+        meta: Meta,
+    ) -> Result<Json<BulkStatusResults>, ErrorData> {
+        let BulkSetStatusParams {
+            auth,
+            target,
+            ids,
+            status,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        if ids.is_empty() {
+            return Err(ErrorData::invalid_params("ids is required", None));
+        }
+        if ids.len() > self.config.max_page_size {
+            return Err(ErrorData::invalid_params("too many ids", None));
+        }
+        let mut unique = Vec::with_capacity(ids.len());
+        for id in ids {
+            if !unique.contains(&id) {
+                unique.push(id);
+            }
+        }
+        let status = serde_json::Value::String(status);
+        let results = match target {
+            McpStatusTarget::Mission => {
+                let status: McpMissionStatus = serde_json::from_value(status)
+                    .map_err(|_| ErrorData::invalid_params("invalid mission status", None))?;
+                self.set_mission_statuses(&auth, &unique, status.into()).await?
+            }
+            McpStatusTarget::Task => {
+                let status: McpTaskStatus = serde_json::from_value(status)
+                    .map_err(|_| ErrorData::invalid_params("invalid task status", None))?;
+                self.set_task_statuses(&auth, &unique, status.into()).await?
+            }
+        };
+        Ok(Json(BulkStatusResults::new(results)))
+    }
+
+    #[tool(
+        name = "c2.list_mission_assignments",
+        description = "List the units and teams assigned to a mission, in the order they were assigned.",
+        annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn list_mission_assignments(
+        &self,
+        params: Parameters<ListMissionAssignmentsParams>,
+        meta: Meta,
+    ) -> Result<Json<AssignmentList>, ErrorData> {
+        let ListMissionAssignmentsParams {
+            auth,
+            mission_id,
+            limit,
+            offset,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        authorize_action(
+            &self.policy,
+            &auth,
+            Permission::ViewMissions,
+            SecurityClassification::Unclassified,
+            "mission",
+            None,
+        )?;
+        let mission_id = MissionId::from_uuid(parse_uuid(&mission_id)?);
+        let limit = self.page_limit(limit)?;
+        let offset = offset.unwrap_or(0);
+        let assignments =
+            AssignmentRepository::list_by_mission(&*self.store, mission_id, limit, offset)
+                .await
+                .map_err(storage_error)?;
+        Ok(Json(self.visible_assignments(&auth, assignments)))
+    }
+
+    #[tool(
+        name = "c2.list_unit_assignments",
+        description = "List the missions a unit is assigned to, newest first.",
+        annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn list_unit_assignments(
+        &self,
+        params: Parameters<ListUnitAssignmentsParams>,
+        meta: Meta,
+    ) -> Result<Json<AssignmentList>, ErrorData> {
+        let ListUnitAssignmentsParams {
+            auth,
+            unit_id,
+            limit,
+            offset,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        authorize_action(
+            &self.policy,
+            &auth,
+            Permission::ViewMissions,
+            SecurityClassification::Unclassified,
+            "mission",
+            None,
+        )?;
+        let unit_id = UnitId::from_uuid(parse_uuid(&unit_id)?);
+        let limit = self.page_limit(limit)?;
+        let offset = offset.unwrap_or(0);
+        let assignments = AssignmentRepository::list_by_unit(&*self.store, unit_id, limit, offset)
+            .await
+            .map_err(storage_error)?;
+        Ok(Json(self.visible_assignments(&auth, assignments)))
+    }
+
+    #[tool(
+        name = "c2.assign_to_mission",
+        description = "Assign a unit or a team to a mission with a role such as lead or support. Set exactly one of unitId and teamId; assigning the same one again replaces its role.",
+        annotations(read_only_hint = false, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn assign_to_mission(
+        &self,
+        params: Parameters<AssignToMissionParams>,
+        meta: Meta,
+    ) -> Result<Json<McpAssignment>, ErrorData> {
+        let AssignToMissionParams {
+            auth,
+            mission_id,
+            unit_id,
+            team_id,
+            role,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        let role = role.trim().to_string();
+        if role.is_empty() {
+            return Err(ErrorData::invalid_params("role is required", None));
+        }
+        let mission = self.mission_for_assignment(&auth, &mission_id).await?;
+        let (unit_id, team_id) = self
+            .checked_assignee(&auth, unit_id.as_deref(), team_id.as_deref())
+            .await?;
+
+        let assignment = MissionAssignment {
+            id: MissionAssignment::derive_id(mission.tenant_id, mission.id, unit_id, team_id),
+            tenant_id: mission.tenant_id,
+            mission_id: mission.id,
+            unit_id,
+            team_id,
+            role,
+            classification: mission.classification,
+            assigned_at_ms: now_epoch_millis(),
+        };
+        AssignmentRepository::upsert(&*self.store, assignment.clone())
+            .await
+            .map_err(storage_error)?;
+        self.events.publish(DomainEvent::upsert(
+            EntityKind::Assignment,
+            assignment.id,
+            assignment.tenant_id,
+            assignment.classification,
+            &assignment,
+        ));
+        Ok(Json(McpAssignment::from(assignment)))
+    }
+
+    #[tool(
+        name = "c2.unassign_from_mission",
+        description = "Remove a unit or team from a mission and return the removed assignment. Set exactly one of unitId and teamId.",
+        annotations(read_only_hint = false, idempotent_hint = false, destructive_hint = true)
+    )]
+    async fn unassign_from_mission(
+        &self,
+        params: Parameters<UnassignFromMissionParams>,
+        meta: Meta,
+    ) -> Result<Json<McpAssignment>, ErrorData> {
+        let UnassignFromMissionParams {
+            auth,
+            mission_id,
+            unit_id,
+            team_id,
+        } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        let mission = self.mission_for_assignment(&auth, &mission_id).await?;
+        let (unit_id, team_id) = self
+            .checked_assignee(&auth, unit_id.as_deref(), team_id.as_deref())
+            .await?;
+
+        let assignment_id =
+            MissionAssignment::derive_id(mission.tenant_id, mission.id, unit_id, team_id);
+        let Some(assignment) = AssignmentRepository::get(&*self.store, assignment_id)
+            .await
+            .map_err(storage_error)?
+        else {
+            return Err(ErrorData::resource_not_found("assignment not found", None));
+        };
+        AssignmentRepository::delete(&*self.store, assignment_id)
+            .await
+            .map_err(storage_error)?;
+        self.events.publish(DomainEvent::delete(
+            EntityKind::Assignment,
+            assignment.id,
+            assignment.tenant_id,
+            assignment.classification,
+        ));
+        Ok(Json(McpAssignment::from(assignment)))
+    }
+
+    #[tool(
+        name = "c2.list_capability_codes",
+        description = "List the distinct capability codes in the tenant's catalog, grouped by category. Use these codes when assigning capabilities to assets.",
+        annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn list_capability_codes(
+        &self,
+        params: Parameters<ListCapabilityCodesParams>,
+        meta: Meta,
+    ) -> Result<Json<CapabilityCodeCatalog>, ErrorData> {
+        let ListCapabilityCodesParams { auth } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        authorize_action(
+            &self.policy,
+            &auth,
+            Permission::ViewCapabilities,
+            SecurityClassification::Unclassified,
+            "capability",
+            None,
         )?;
 
-        let created_at_ms = existing
-            .as_ref()
-            .map(|task| task.created_at_ms)
-            .unwrap_or_else(now_epoch_millis);
-        let updated_at_ms = now_epoch_millis();
-        let task = Task {
-            id: task_id,
-            mission_id,
-            tenant_id: auth.subject.tenant_id,
-            title: task.title,
-            status: task.status.into(),
-            priority: task.priority.into(),
-            classification,
-            created_at_ms,
-            updated_at_ms,
-        };
-        TaskRepository::upsert(&*self.store, task.clone())
+        let codes = CapabilityRepository::distinct_codes(&*self.store, auth.subject.tenant_id)
             .await
             .map_err(storage_error)?;
-        Ok(Json(McpTask::from(task)))
+        let mut grouped: BTreeMap<Option<String>, BTreeSet<String>> = BTreeMap::new();
+        for code in codes
+            .into_iter()
+            .filter(|code| self.visibility(&auth, code.classification) == Visibility::Full)
+        {
+            grouped.entry(code.category).or_default().insert(code.code);
+        }
+        let categories = grouped
+            .into_iter()
+            .map(|(category, codes)| McpCapabilityCategory {
+                category,
+                codes: codes.into_iter().collect(),
+            })
+            .collect();
+        Ok(Json(CapabilityCodeCatalog { categories }))
+    }
+
+    #[tool(
+        name = "c2.situation_summary",
+        description = "Summarize the tenant's operational picture in one call: missions by status, incidents by type and status, and assets by readiness, counting only records visible at your clearance.",
+        annotations(read_only_hint = true, idempotent_hint = true, destructive_hint = false)
+    )]
+    async fn situation_summary(
+        &self,
+        params: Parameters<SituationSummaryParams>,
+        meta: Meta,
+    ) -> Result<Json<SituationSummary>, ErrorData> {
+        let SituationSummaryParams { auth } = params.0;
+        let auth = self.resolve_auth(auth, &meta)?;
+        for (permission, resource_type) in [
+            (Permission::ViewMissions, "mission"),
+            (Permission::ViewIncidents, "incident"),
+            (Permission::DispatchAssets, "asset"),
+        ] {
+            authorize_action(
+                &self.policy,
+                &auth,
+                permission,
+                SecurityClassification::Unclassified,
+                resource_type,
+                None,
+            )?;
+        }
+
+        let tenant_id = auth.subject.tenant_id;
+        let clearance = auth.subject.clearance;
+        if let Some(summary) = self.summaries.get(&self.events, tenant_id, SITUATION, clearance) {
+            return Ok(Json(summary));
+        }
+        let sequence = self.events.last_sequence(tenant_id);
+        let missions = MissionRepository::count_by_status(&self.cleared_store(clearance), tenant_id)
+            .await
+            .map_err(storage_error)?;
+        let incidents = IncidentRepository::count_by_type_and_status(&*self.store, tenant_id)
+            .await
+            .map_err(storage_error)?;
+        let assets = AssetRepository::count_by_readiness(&*self.store, tenant_id)
+            .await
+            .map_err(storage_error)?;
+        // Redacted records still show up in the lists, so they count here too.
+        let summary = situation::summarize(missions, incidents, assets, |classification| {
+            self.visibility(&auth, classification) != Visibility::Hidden
+        });
+        self.summaries.insert(
+            tenant_id,
+            SITUATION,
+            clearance,
+            &[EntityKind::Mission, EntityKind::Incident, EntityKind::Asset],
+            sequence,
+            summary.clone(),
+        );
+        Ok(Json(summary))
     }
 }
 
-#[tool_handler]
+#[tool_handler(router = MeteredToolRouter::new(&self.tool_router, &self.read_only))]
 impl ServerHandler for C2McpService {
     fn get_info(&self) -> ServerInfo {
         let capabilities = ServerCapabilities::builder()
             .enable_tools()
             .enable_resources()
+            .enable_prompts()
             .build();
         ServerInfo {
             protocol_version: Default::default(),
@@ -1142,12 +3136,82 @@ impl ServerHandler for C2McpService {
         }
     }
 
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        Ok(ListPromptsResult::with_all_items(prompts::catalog()))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let auth = self.resolve_auth(None, &context.meta)?;
+        let args = PromptArgs::parse(request.arguments, auth.subject.tenant_id)?;
+        let limit = self.config.max_page_size;
+        match request.name.as_str() {
+            prompts::TRIAGE_INCIDENTS => {
+                authorize_action(
+                    &self.policy,
+                    &auth,
+                    Permission::ViewIncidents,
+                    SecurityClassification::Unclassified,
+                    "prompt.incident",
+                    None,
+                )?;
+                let incidents =
+                    IncidentRepository::list_by_tenant(&*self.store, args.tenant_id, limit, 0)
+                        .await
+                        .map_err(storage_error)?;
+                let count = self.prompt_count(&auth, &incidents, limit, |item| item.classification);
+                Ok(prompts::triage_incidents(&args, count))
+            }
+            prompts::PLAN_MISSION => {
+                authorize_action(
+                    &self.policy,
+                    &auth,
+                    Permission::EditMissions,
+                    SecurityClassification::Unclassified,
+                    "prompt.mission",
+                    None,
+                )?;
+                let mission_name = args.required("missionName")?;
+                let store = self.cleared_store(auth.subject.clearance);
+                let missions =
+                    MissionRepository::list_by_tenant(&store, args.tenant_id, limit, 0)
+                        .await
+                        .map_err(storage_error)?;
+                let count = self.prompt_count(&auth, &missions, limit, |item| item.classification);
+                Ok(prompts::plan_mission(&args, mission_name, count))
+            }
+            prompts::REVIEW_ASSET_READINESS => {
+                authorize_action(
+                    &self.policy,
+                    &auth,
+                    Permission::DispatchAssets,
+                    SecurityClassification::Unclassified,
+                    "prompt.asset",
+                    None,
+                )?;
+                let assets = AssetRepository::list_by_tenant(&*self.store, args.tenant_id, limit, 0)
+                    .await
+                    .map_err(storage_error)?;
+                let count = self.prompt_count(&auth, &assets, limit, |item| item.classification);
+                Ok(prompts::review_asset_readiness(&args, count))
+            }
+            _ => Err(ErrorData::invalid_params("unknown prompt", None)),
+        }
+    }
+
     async fn list_resources(
         &self,
         request: Option<PaginatedRequestParam>,
         context: rmcp::service::RequestContext<rmcp::RoleServer>,
     ) -> Result<ListResourcesResult, ErrorData> {
-        let auth = resolve_auth(None, &context.meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(None, &context.meta)?;
         authorize_action(
             &self.policy,
             &auth,
@@ -1162,8 +3226,9 @@ impl ServerHandler for C2McpService {
             .and_then(|value| value.parse::<usize>().ok())
             .unwrap_or(0);
         let limit = 50;
+        let store = self.cleared_store(auth.subject.clearance);
         let missions =
-            MissionRepository::list_by_tenant(&*self.store, auth.subject.tenant_id, limit, offset)
+            MissionRepository::list_by_tenant(&store, auth.subject.tenant_id, limit, offset)
                 .await
                 .map_err(storage_error)?;
         let resources = missions
@@ -1206,11 +3271,12 @@ impl ServerHandler for C2McpService {
         request: ReadResourceRequestParam,
         context: rmcp::service::RequestContext<rmcp::RoleServer>,
     ) -> Result<ReadResourceResult, ErrorData> {
-        let auth = resolve_auth(None, &context.meta, self.default_auth.as_ref())?;
+        let auth = self.resolve_auth(None, &context.meta)?;
         let (kind, id) = parse_resource_uri(&request.uri)?;
         match kind {
             ResourceKind::Mission => {
-                let mission = MissionRepository::get(&*self.store, MissionId::from_uuid(id))
+                let store = self.cleared_store(auth.subject.clearance);
+                let mission = MissionRepository::get(&store, MissionId::from_uuid(id))
                     .await
                     .map_err(storage_error)?;
                 let Some(mission) = mission else {
@@ -1300,11 +3366,20 @@ impl ServerHandler for C2McpService {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = ServiceConfig::from_env("c2-mcp");
+    if check_requested() {
+        run_check(&config).await;
+    }
     let obs_config = ObservabilityConfig {
         service_name: config.service_name.clone(),
         environment: config.environment.to_string(),
         log_level: config.log_level.clone(),
+        log_format: config.log_format.clone(),
         metrics_addr: config.metrics_addr.clone(),
+        telemetry: TelemetryConfig {
+            enabled: config.telemetry,
+            interval: config.telemetry_interval(),
+            endpoint: config.telemetry_endpoint.clone(),
+        },
     };
     let handle = init(&obs_config);
     log_startup(&handle, &obs_config.environment);
@@ -1312,7 +3387,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let surreal_config = SurrealConfig::from_env();
     let store = SurrealStore::connect_with_retry(&surreal_config).await?;
     let policy = BasicPolicyEngine::with_default_rules();
-    let service = C2McpService::new(store, policy);
+    let ceilings = TenantCeilings::from_env()?;
+    let floors = TenantFloors::from_env()?;
+    let position_precision = PositionPrecision::from_env()?;
+    let rate_limiter = TenantRateLimiter::from_env()?;
+    SymbolTable::from_env()?.install();
+    let mut events = EventBus::default();
+    if let Some(relay) = ChangeRelay::from_env(&config.service_name)? {
+        tracing::info!("c2-mcp relaying domain changes over ZeroMQ");
+        events = events.with_relay(relay);
+    }
+    let service = C2McpService::new(
+        config.clone(),
+        store,
+        policy,
+        ceilings,
+        position_precision,
+        rate_limiter,
+        events,
+    )
+    .with_floors(floors);
+
+    if service.read_only.is_enabled() {
+        tracing::info!("c2-mcp starting read-only");
+    }
+    toggle_read_only_on_signal(service.read_only.clone())?;
 
     let session_manager = Arc::new(LocalSessionManager::default());
     let http_service = StreamableHttpService::new(
@@ -1323,7 +3422,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         session_manager,
         StreamableHttpServerConfig::default(),
     );
-    let app = Router::new().route("/mcp", any_service(http_service));
+    let correlation_header = HeaderName::try_from(config.correlation_header.as_str())?;
+    let app = Router::new()
+        .route("/mcp", any_service(http_service))
+        .layer(middleware::from_fn_with_state(
+            correlation_header,
+            correlation::correlation_id,
+        ));
     let listener = TcpListener::bind(&config.bind_addr).await?;
     tracing::info!("c2-mcp http listening on {}", config.bind_addr);
 
@@ -1339,6 +3444,45 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Flips read-only mode on each SIGUSR1, so operators can stop writes for a
+/// migration without restarting.
+#[cfg(unix)]
+fn toggle_read_only_on_signal(read_only: Arc<ReadOnlyMode>) -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut signals = signal(SignalKind::user_defined1())?;
+    tokio::spawn(async move {
+        while signals.recv().await.is_some() {
+            let enabled = read_only.toggle();
+            tracing::warn!(read_only = enabled, "read-only mode changed by SIGUSR1");
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn toggle_read_only_on_signal(_read_only: Arc<ReadOnlyMode>) -> std::io::Result<()> {
+    Ok(())
+}
+
+async fn run_check(config: &ServiceConfig) -> ! {
+    let mut report = CheckReport::new(&config.service_name);
+    report.pass(
+        "config",
+        format!("environment={} bind_addr={}", config.environment, config.bind_addr),
+    );
+    for (name, result) in c2_policy::env_checks() {
+        report.record(name, result);
+    }
+    report.record(
+        "position_precision",
+        PositionPrecision::from_env().map(|_| "parsed"),
+    );
+    report.record("sidc_overrides", SymbolTable::from_env().map(|_| "parsed"));
+    report.record("surreal", SurrealStore::check(&SurrealConfig::from_env()).await);
+    report.exit()
+}
+
 fn parse_uuid(value: &str) -> Result<Uuid, ErrorData> {
     Uuid::parse_str(value).map_err(|_| ErrorData::invalid_params("invalid UUID", None))
 }
@@ -1388,18 +3532,29 @@ fn parse_auth(auth: &McpAuthContext) -> Result<AuthorizedContext, ErrorData> {
     })
 }
 
-fn authorize_action(
+fn explain_action(
     policy: &BasicPolicyEngine,
     auth: &AuthorizedContext,
     permission: Permission,
     classification: SecurityClassification,
     resource_type: &str,
     resource_id: Option<String>,
-) -> Result<(), ErrorData> {
+) -> PolicyOutcome {
     if !auth.permissions.contains(&permission) {
-        return Err(ErrorData::invalid_request("permission denied", None));
+        return PolicyOutcome::missing_permission(permission);
     }
-    let request = PolicyRequest {
+    let request = policy_request(auth, permission, classification, resource_type, resource_id);
+    policy.explain(&request)
+}
+
+fn policy_request(
+    auth: &AuthorizedContext,
+    permission: Permission,
+    classification: SecurityClassification,
+    resource_type: &str,
+    resource_id: Option<String>,
+) -> PolicyRequest {
+    PolicyRequest {
         subject: auth.subject.clone(),
         action: permission,
         resource: ResourceDescriptor {
@@ -1412,19 +3567,42 @@ fn authorize_action(
             mission_id: None,
             incident_id: None,
             tags: vec![],
+            correlation_id: c2_messaging::correlation::current(),
         },
-    };
-    match policy.evaluate(&request) {
+    }
+}
+
+fn authorize_action(
+    policy: &BasicPolicyEngine,
+    auth: &AuthorizedContext,
+    permission: Permission,
+    classification: SecurityClassification,
+    resource_type: &str,
+    resource_id: Option<String>,
+) -> Result<(), ErrorData> {
+    let request = policy_request(auth, permission, classification, resource_type, resource_id);
+    if !auth.permissions.contains(&permission) {
+        let outcome = PolicyOutcome::missing_permission(permission);
+        record_denial(&request, PolicyDecision::Deny, &outcome);
+        return Err(Refusal::Denied.error("permission denied", None));
+    }
+    match policy.authorize(&request) {
         PolicyDecision::Permit => Ok(()),
-        PolicyDecision::Deny => Err(ErrorData::invalid_request("policy denied", None)),
+        PolicyDecision::Deny => Err(Refusal::Denied.error("policy denied", None)),
         PolicyDecision::Indeterminate => {
             Err(ErrorData::internal_error("policy indeterminate", None))
         }
     }
 }
 
+/// Timeouts are reported as retryable so agents can try again instead of
+/// treating the store as broken.
 fn storage_error(err: StorageError) -> ErrorData {
-    ErrorData::internal_error(err.message, None)
+    if err.is_timeout() {
+        ErrorData::internal_error(err.message, Some(serde_json::json!({ "retryable": true })))
+    } else {
+        ErrorData::internal_error(err.message, None)
+    }
 }
 
 fn resource_template(uri_template: &str, name: &str, description: &str) -> ResourceTemplate {
@@ -1576,3 +3754,758 @@ fn parse_resource_uri(uri: &str) -> Result<(ResourceKind, Uuid), ErrorData> {
     };
     Ok((kind, parsed))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{AssetKind, Capability, IncidentStatus, IncidentType, Unit};
+    use c2_messaging::EventOp;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use rmcp::model::{CallToolRequestParam, RequestId};
+    use rmcp::service::RequestContext;
+
+    /// Fails for any field of the serialized core record, in its camelCase
+    /// form, that the serialized DTO lacks.
+    fn assert_covers(core: impl Serialize, dto: impl Serialize) {
+        let core = serde_json::to_value(core).unwrap();
+        let dto = serde_json::to_value(dto).unwrap();
+        for field in core.as_object().unwrap().keys() {
+            let mut camel = String::new();
+            let mut upper = false;
+            for ch in field.chars() {
+                match ch {
+                    '_' => upper = true,
+                    _ if upper => {
+                        camel.extend(ch.to_uppercase());
+                        upper = false;
+                    }
+                    _ => camel.push(ch),
+                }
+            }
+            assert!(dto.get(&camel).is_some_and(|value| !value.is_null()), "no DTO field {camel}");
+        }
+    }
+
+    #[test]
+    fn dtos_carry_every_core_field() {
+        let tenant_id = TenantId::new();
+        let classification = SecurityClassification::Secret;
+        let position = Coordinate {
+            latitude: 45.8,
+            longitude: 15.9,
+            altitude_m: Some(120.0),
+            accuracy_m: Some(5.0),
+        };
+        let mission = Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: "Bridge survey".to_string(),
+            status: MissionStatus::Active,
+            priority: OperationalPriority::Urgent,
+            classification,
+            created_at_ms: 1,
+            updated_at_ms: 2,
+            archived_at_ms: Some(3),
+        };
+        let task = Task {
+            id: TaskId::new(),
+            mission_id: mission.id,
+            tenant_id,
+            title: "Clear the route".to_string(),
+            status: c2_core::TaskStatus::Pending,
+            priority: OperationalPriority::Routine,
+            classification,
+            depends_on: vec![TaskId::new()],
+            created_at_ms: 1,
+            updated_at_ms: 2,
+        };
+        let assignment = MissionAssignment {
+            id: c2_core::AssignmentId::new(),
+            tenant_id,
+            mission_id: mission.id,
+            unit_id: Some(UnitId::new()),
+            team_id: Some(TeamId::new()),
+            role: "overwatch".to_string(),
+            classification,
+            assigned_at_ms: 1,
+        };
+        let asset = Asset {
+            id: AssetId::new(),
+            tenant_id,
+            name: "Rescue 1".to_string(),
+            kind: AssetKind::Vehicle,
+            status: AssetStatus::Assigned,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::Scheduled,
+            maintenance_window: Some((10, 20)),
+            unit_id: Some(UnitId::new()),
+            capability_ids: vec![CapabilityId::new()],
+            position: Some(position),
+            classification,
+            created_at_ms: 1,
+            updated_at_ms: 2,
+        };
+        let incident = Incident {
+            id: IncidentId::new(),
+            tenant_id,
+            incident_type: IncidentType::Fire,
+            status: IncidentStatus::Responding,
+            priority: OperationalPriority::Critical,
+            summary: "Warehouse fire".to_string(),
+            position: Some(position),
+            affected_radius_m: Some(250.0),
+            classification,
+            created_at_ms: 1,
+            updated_at_ms: 2,
+        };
+
+        assert_covers(&mission, McpMission::from(mission.clone()));
+        assert_covers(&task, McpTask::from(task.clone()));
+        assert_covers(&assignment, McpAssignment::from(assignment.clone()));
+        assert_covers(&asset, McpAsset::from(asset.clone()));
+        assert_covers(&incident, McpIncident::from(incident.clone()));
+        assert_covers(position, McpCoordinate::from(position));
+    }
+
+    async fn service() -> C2McpService {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        C2McpService::new(
+            ServiceConfig::from_env("c2-mcp"),
+            store,
+            BasicPolicyEngine::with_default_rules(),
+            TenantCeilings::default(),
+            PositionPrecision::default(),
+            TenantRateLimiter::default(),
+            EventBus::default(),
+        )
+    }
+
+    /// An operations user of `tenant_id` who may view and edit missions.
+    fn editor(tenant_id: TenantId) -> Option<McpAuthContext> {
+        Some(McpAuthContext {
+            tenant_id: Some(tenant_id.to_string()),
+            user_id: Some(Uuid::new_v4().to_string()),
+            roles: vec!["operations".to_string()],
+            permissions: vec!["view_missions".to_string(), "edit_missions".to_string()],
+            clearance: Some(McpSecurityClassification::Restricted),
+        })
+    }
+
+    fn mission(tenant_id: TenantId, name: &str, status: MissionStatus) -> Mission {
+        Mission {
+            id: MissionId::new(),
+            tenant_id,
+            name: name.to_string(),
+            status,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+            archived_at_ms: None,
+        }
+    }
+
+    fn task(mission: &Mission, title: &str) -> Task {
+        Task {
+            id: TaskId::new(),
+            mission_id: mission.id,
+            tenant_id: mission.tenant_id,
+            title: title.to_string(),
+            status: c2_core::TaskStatus::Pending,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            depends_on: Vec::new(),
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        }
+    }
+
+    /// An operations user of `tenant_id` who may dispatch assets.
+    fn dispatcher(tenant_id: TenantId) -> Option<McpAuthContext> {
+        Some(McpAuthContext {
+            permissions: vec!["dispatch_assets".to_string()],
+            ..editor(tenant_id).unwrap()
+        })
+    }
+
+    /// Dispatcher upsert params for an asset given in its wire form.
+    fn asset_upsert(
+        tenant_id: TenantId,
+        asset: serde_json::Value,
+    ) -> Parameters<UpsertAssetParams> {
+        Parameters(UpsertAssetParams {
+            auth: dispatcher(tenant_id),
+            asset: serde_json::from_value(asset).unwrap(),
+            dry_run: false,
+            idempotency_key: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn records_just_above_clearance_keep_only_ids_and_classification() {
+        let mut service = service().await;
+        service.redaction_window = 2;
+        let tenant_id = TenantId::new();
+        let mut reader = editor(tenant_id).unwrap();
+        reader.permissions.push("view_incidents".to_string());
+        let by_id = |id: String| {
+            Parameters(GetByIdParams {
+                auth: Some(reader.clone()),
+                id,
+            })
+        };
+        let keys = |dto: serde_json::Value| {
+            let mut keys: Vec<String> = dto.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+
+        let incident = Incident {
+            id: IncidentId::new(),
+            tenant_id,
+            incident_type: IncidentType::Fire,
+            status: IncidentStatus::Responding,
+            priority: OperationalPriority::Critical,
+            summary: "Depot fire".to_string(),
+            position: None,
+            affected_radius_m: Some(250.0),
+            classification: SecurityClassification::Secret,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        IncidentRepository::upsert(&*service.store, incident.clone()).await.unwrap();
+        let Json(seen) =
+            service.get_incident(by_id(incident.id.to_string()), Meta::default()).await.unwrap();
+        assert_eq!(seen.id, incident.id.to_string());
+        assert_eq!(seen.summary, REDACTED);
+        assert!(seen.redacted);
+
+        let secret = Mission {
+            classification: SecurityClassification::Secret,
+            ..mission(tenant_id, "Night crossing", MissionStatus::Active)
+        };
+        let top_secret = Mission {
+            classification: SecurityClassification::TopSecret,
+            ..mission(tenant_id, "Deep strike", MissionStatus::Active)
+        };
+        for mission in [&secret, &top_secret] {
+            MissionRepository::upsert(&*service.store, mission.clone()).await.unwrap();
+        }
+        let first = task(&secret, "Mark the ford");
+        let second = Task {
+            classification: SecurityClassification::Secret,
+            depends_on: vec![first.id],
+            ..task(&secret, "Cross at night")
+        };
+        for task in [&first, &second] {
+            TaskRepository::upsert(&*service.store, task.clone()).await.unwrap();
+        }
+
+        let Json(seen) =
+            service.get_mission(by_id(secret.id.to_string()), Meta::default()).await.unwrap();
+        assert_eq!(seen.name, REDACTED);
+        assert_eq!(
+            keys(serde_json::to_value(&seen).unwrap()),
+            ["classification", "id", "marking", "name", "redacted", "tenantId"]
+        );
+
+        let params = Parameters(ListTasksParams {
+            auth: Some(reader.clone()),
+            mission_id: secret.id.to_string(),
+            limit: None,
+            offset: None,
+        });
+        let Json(listed) = service.list_tasks(params, Meta::default()).await.unwrap();
+        let seen = listed.tasks.into_iter().find(|task| task.id == second.id.to_string());
+        assert_eq!(
+            keys(serde_json::to_value(seen.unwrap()).unwrap()),
+            ["classification", "id", "marking", "missionId", "redacted", "tenantId", "title"]
+        );
+
+        let params = Parameters(ListMissionsParams {
+            auth: Some(reader.clone()),
+            limit: None,
+            offset: None,
+        });
+        let Json(listed) = service.list_missions(params, Meta::default()).await.unwrap();
+        let ids: Vec<String> = listed.missions.into_iter().map(|mission| mission.id).collect();
+        assert_eq!(ids, [secret.id.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn move_task_checks_the_target_mission_and_updates_both_listings() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let source = mission(tenant_id, "Bridge survey", MissionStatus::Active);
+        let target = mission(tenant_id, "Route clearance", MissionStatus::Active);
+        for mission in [&source, &target] {
+            MissionRepository::upsert(&*service.store, mission.clone()).await.unwrap();
+        }
+        let task = task(&source, "Clear the route");
+        TaskRepository::upsert(&*service.store, task.clone()).await.unwrap();
+        let params = |mission_id: MissionId| {
+            Parameters(MoveTaskParams {
+                auth: editor(tenant_id),
+                id: task.id.to_string(),
+                mission_id: mission_id.to_string(),
+            })
+        };
+        let listed = |mission_id: MissionId| {
+            let store = service.store.clone();
+            async move {
+                TaskRepository::list_by_mission(&*store, mission_id, 10, 0)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|task| task.id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let Err(missing) = service.move_task(params(MissionId::new()), Meta::default()).await
+        else {
+            panic!("moved to a mission that does not exist");
+        };
+        assert_eq!(missing.message, "mission not found");
+        assert_eq!(listed(source.id).await, vec![task.id]);
+
+        let Json(moved) = service.move_task(params(target.id), Meta::default()).await.unwrap();
+        assert_eq!(moved.mission_id, target.id.to_string());
+        assert!(listed(source.id).await.is_empty());
+        assert_eq!(listed(target.id).await, vec![task.id]);
+        let stored = TaskRepository::get(&*service.store, task.id).await.unwrap().unwrap();
+        assert!(stored.updated_at_ms > task.updated_at_ms);
+    }
+
+    #[tokio::test]
+    async fn bulk_set_status_reports_each_id_and_changes_only_valid_ones() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let active = mission(tenant_id, "Exercise North", MissionStatus::Active);
+        let aborted = mission(tenant_id, "Exercise South", MissionStatus::Aborted);
+        let busy = mission(tenant_id, "Exercise East", MissionStatus::Active);
+        for mission in [&active, &aborted, &busy] {
+            MissionRepository::upsert(&*service.store, mission.clone()).await.unwrap();
+        }
+        TaskRepository::upsert(&*service.store, task(&busy, "Debrief")).await.unwrap();
+        let missing = MissionId::new();
+
+        let ids = [active.id, aborted.id, busy.id, missing].map(|id| id.to_string());
+        let params = Parameters(BulkSetStatusParams {
+            auth: editor(tenant_id),
+            target: McpStatusTarget::Mission,
+            ids: ids.to_vec(),
+            status: "completed".to_string(),
+        });
+        let Json(outcome) = service.bulk_set_status(params, Meta::default()).await.unwrap();
+
+        assert_eq!(outcome.updated, 1);
+        let results: Vec<(&str, bool, Option<&str>)> = outcome
+            .results
+            .iter()
+            .map(|item| (item.id.as_str(), item.ok, item.error.as_deref()))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                (ids[0].as_str(), true, None),
+                (ids[1].as_str(), false, Some("mission is Aborted and cannot become Completed")),
+                (ids[2].as_str(), false, Some("mission still has 1 open task(s)")),
+                (ids[3].as_str(), false, Some("mission not found")),
+            ]
+        );
+        let status = |id: MissionId| {
+            let store = service.store.clone();
+            async move { MissionRepository::get(&*store, id).await.unwrap().unwrap().status }
+        };
+        assert_eq!(status(active.id).await, MissionStatus::Completed);
+        assert_eq!(status(aborted.id).await, MissionStatus::Aborted);
+        assert_eq!(status(busy.id).await, MissionStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn situation_summary_is_cached_until_a_counted_entity_changes() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let first = mission(tenant_id, "Harbor watch", MissionStatus::Active);
+        MissionRepository::upsert(&*service.store, first.clone()).await.unwrap();
+        let mut watcher = editor(tenant_id).unwrap();
+        watcher.permissions.extend(["view_incidents", "dispatch_assets"].map(String::from));
+        let summary = || async {
+            let params = Parameters(SituationSummaryParams {
+                auth: Some(watcher.clone()),
+            });
+            let Json(summary) = service.situation_summary(params, Meta::default()).await.unwrap();
+            summary.missions_by_status.iter().map(|group| group.count).sum::<u64>()
+        };
+        assert_eq!(summary().await, 1);
+
+        // Written straight to storage, so nothing tells the cache.
+        let second = mission(tenant_id, "Harbor patrol", MissionStatus::Active);
+        MissionRepository::upsert(&*service.store, second).await.unwrap();
+        assert_eq!(summary().await, 1);
+
+        let params = Parameters(BulkSetStatusParams {
+            auth: editor(tenant_id),
+            target: McpStatusTarget::Mission,
+            ids: vec![first.id.to_string()],
+            status: "completed".to_string(),
+        });
+        service.bulk_set_status(params, Meta::default()).await.unwrap();
+        assert_eq!(summary().await, 2);
+    }
+
+    #[tokio::test]
+    async fn dry_run_upserts_authorize_without_writing() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let params = |classification: McpSecurityClassification| {
+            Parameters(UpsertMissionParams {
+                auth: editor(tenant_id),
+                mission: MissionInput {
+                    id: None,
+                    name: Some("Harbor sweep".to_string()),
+                    status: None,
+                    priority: None,
+                    classification: Some(classification),
+                },
+                dry_run: true,
+                idempotency_key: None,
+            })
+        };
+        let stored = || async {
+            MissionRepository::list_by_tenant(&*service.store, tenant_id, 10, 0)
+                .await
+                .unwrap()
+                .len()
+        };
+
+        let Json(planned) = service
+            .upsert_mission(params(McpSecurityClassification::Restricted), Meta::default())
+            .await
+            .unwrap();
+        assert!(planned.dry_run);
+        assert_eq!(planned.name, "Harbor sweep");
+        assert_eq!(stored().await, 0);
+
+        let Err(denied) = service
+            .upsert_mission(params(McpSecurityClassification::Secret), Meta::default())
+            .await
+        else {
+            panic!("a dry run above the caller's clearance was permitted");
+        };
+        assert_eq!(denied.message, "policy denied");
+        assert_eq!(stored().await, 0);
+    }
+
+    #[tokio::test]
+    async fn check_permission_explains_a_clearance_denial() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let check = |classification: McpSecurityClassification| {
+            let params = Parameters(CheckPermissionParams {
+                auth: editor(tenant_id),
+                subject: None,
+                permission: "edit_missions".to_string(),
+                classification,
+                resource_type: Some("mission".to_string()),
+            });
+            service.check_permission(params, Meta::default())
+        };
+
+        let Json(denied) = check(McpSecurityClassification::Secret).await.unwrap();
+        assert!(matches!(denied.decision, McpPolicyDecision::Deny));
+        assert_eq!(denied.matched_rule, None);
+        assert_eq!(denied.reason, "clearance Restricted is below resource classification Secret");
+
+        let Json(permitted) = check(McpSecurityClassification::Restricted).await.unwrap();
+        assert!(matches!(permitted.decision, McpPolicyDecision::Permit));
+        assert!(permitted.matched_rule.is_some());
+    }
+
+    #[tokio::test]
+    async fn capability_codes_are_grouped_by_category() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let capability = |code: &str, category: &str, classification| Capability {
+            id: CapabilityId::new(),
+            tenant_id,
+            code: code.to_string(),
+            name: code.to_lowercase(),
+            category: Some(category.to_string()),
+            description: None,
+            classification,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        for capability in [
+            capability("TRIAGE", "medical", SecurityClassification::Unclassified),
+            capability("MEDEVAC", "medical", SecurityClassification::Restricted),
+            capability("AIRLIFT", "transport", SecurityClassification::Unclassified),
+            capability("HALO", "transport", SecurityClassification::TopSecret),
+        ] {
+            CapabilityRepository::upsert(&*service.store, capability).await.unwrap();
+        }
+        let mut reader = editor(tenant_id).unwrap();
+        reader.permissions = vec!["view_capabilities".to_string()];
+
+        let params = Parameters(ListCapabilityCodesParams { auth: Some(reader) });
+        let Json(catalog) = service.list_capability_codes(params, Meta::default()).await.unwrap();
+        let grouped: Vec<(Option<&str>, Vec<&str>)> = catalog
+            .categories
+            .iter()
+            .map(|group| {
+                let codes = group.codes.iter().map(String::as_str).collect();
+                (group.category.as_deref(), codes)
+            })
+            .collect();
+        assert_eq!(
+            grouped,
+            [
+                (Some("medical"), vec!["MEDEVAC", "TRIAGE"]),
+                (Some("transport"), vec!["AIRLIFT"]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn upsert_asset_sets_unit_and_capabilities_and_keeps_them_when_omitted() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let unit = Unit {
+            id: UnitId::new(),
+            tenant_id,
+            classification: SecurityClassification::Unclassified,
+            callsign: Some("R1".to_string()),
+            display_name: "Rescue team 1".to_string(),
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            team_id: None,
+            capability_ids: Vec::new(),
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        UnitRepository::upsert(&*service.store, unit.clone()).await.unwrap();
+        let mut capability_ids = Vec::new();
+        for code in ["WINCH", "TOW"] {
+            let capability = Capability {
+                id: CapabilityId::new(),
+                tenant_id,
+                code: code.to_string(),
+                name: code.to_lowercase(),
+                category: None,
+                description: None,
+                classification: SecurityClassification::Unclassified,
+                created_at_ms: 1,
+                updated_at_ms: 1,
+            };
+            capability_ids.push(capability.id);
+            CapabilityRepository::upsert(&*service.store, capability).await.unwrap();
+        }
+        let stored = |id: &str| {
+            let store = service.store.clone();
+            let id = AssetId::from_uuid(Uuid::parse_str(id).unwrap());
+            async move { AssetRepository::get(&*store, id).await.unwrap().unwrap() }
+        };
+
+        let asset = serde_json::json!({
+            "name": "Rescue 1",
+            "kind": "vehicle",
+            "unitId": unit.id.to_string(),
+            "capabilityIds": capability_ids.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        });
+        let Json(created) =
+            service.upsert_asset(asset_upsert(tenant_id, asset), Meta::default()).await.unwrap();
+        let asset = stored(&created.id).await;
+        assert_eq!(asset.unit_id, Some(unit.id));
+        assert_eq!(asset.capability_ids, capability_ids);
+
+        let rename = serde_json::json!({ "id": created.id, "name": "Rescue One" });
+        service.upsert_asset(asset_upsert(tenant_id, rename), Meta::default()).await.unwrap();
+        let asset = stored(&created.id).await;
+        assert_eq!(asset.name, "Rescue One");
+        assert_eq!(asset.unit_id, Some(unit.id));
+        assert_eq!(asset.capability_ids, capability_ids);
+
+        let unknown = CapabilityId::new();
+        let swap = serde_json::json!({ "id": created.id, "capabilityIds": [unknown.to_string()] });
+        let Err(err) =
+            service.upsert_asset(asset_upsert(tenant_id, swap), Meta::default()).await
+        else {
+            panic!("an unknown capability was accepted");
+        };
+        assert_eq!(err.message, format!("unknown capabilityId {unknown}"));
+        assert_eq!(stored(&created.id).await.capability_ids, capability_ids);
+    }
+
+    #[tokio::test]
+    async fn upserts_keep_the_stored_fields_they_do_not_name() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let asset = serde_json::json!({
+            "name": "Relay 4",
+            "kind": "comms_relay",
+            "readiness": "degraded",
+            "commsStatus": "intermittent",
+        });
+        let Json(created) =
+            service.upsert_asset(asset_upsert(tenant_id, asset), Meta::default()).await.unwrap();
+        let rename = serde_json::json!({ "id": created.id, "name": "Relay Four" });
+        let Json(renamed) =
+            service.upsert_asset(asset_upsert(tenant_id, rename), Meta::default()).await.unwrap();
+        let id = AssetId::from_uuid(Uuid::parse_str(&created.id).unwrap());
+        let stored = AssetRepository::get(&*service.store, id).await.unwrap().unwrap();
+        assert_eq!(renamed.name, "Relay Four");
+        assert_eq!(stored.name, "Relay Four");
+        assert_eq!(stored.kind, AssetKind::CommsRelay);
+        assert_eq!(stored.readiness, ReadinessState::Degraded);
+        assert_eq!(stored.comms_status, CommsStatus::Intermittent);
+
+        let stored_mission = Mission {
+            priority: OperationalPriority::Urgent,
+            ..mission(tenant_id, "Harbor watch", MissionStatus::Active)
+        };
+        MissionRepository::upsert(&*service.store, stored_mission.clone()).await.unwrap();
+        let params = Parameters(UpsertMissionParams {
+            auth: editor(tenant_id),
+            mission: MissionInput {
+                id: Some(stored_mission.id.to_string()),
+                name: Some("Harbor patrol".to_string()),
+                status: None,
+                priority: None,
+                classification: None,
+            },
+            dry_run: false,
+            idempotency_key: None,
+        });
+        service.upsert_mission(params, Meta::default()).await.unwrap();
+        let stored = MissionRepository::get(&*service.store, stored_mission.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.name, "Harbor patrol");
+        assert_eq!(stored.status, MissionStatus::Active);
+        assert_eq!(stored.priority, OperationalPriority::Urgent);
+    }
+
+    #[tokio::test]
+    async fn set_asset_status_changes_only_the_given_fields() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let asset = Asset {
+            id: AssetId::new(),
+            tenant_id,
+            name: "Scout 2".to_string(),
+            kind: AssetKind::Drone,
+            status: AssetStatus::Assigned,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::None,
+            maintenance_window: None,
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: None,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        AssetRepository::upsert(&*service.store, asset.clone()).await.unwrap();
+
+        let params = Parameters(SetAssetStatusParams {
+            auth: dispatcher(tenant_id),
+            id: asset.id.to_string(),
+            status: None,
+            readiness: None,
+            comms_status: Some(McpCommsStatus::Offline),
+            maintenance_state: None,
+        });
+        service.set_asset_status(params, Meta::default()).await.unwrap();
+
+        let stored = AssetRepository::get(&*service.store, asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.comms_status, CommsStatus::Offline);
+        assert_eq!(stored.name, asset.name);
+        assert_eq!(stored.kind, asset.kind);
+        assert_eq!(stored.status, asset.status);
+        assert_eq!(stored.readiness, asset.readiness);
+        assert!(stored.updated_at_ms > asset.updated_at_ms);
+    }
+
+    #[tokio::test]
+    async fn upserts_are_published_to_their_tenant_only() {
+        let service = service().await;
+        let tenant_id = TenantId::new();
+        let mut ours = service.events.subscribe(tenant_id);
+        let mut theirs = service.events.subscribe(TenantId::new());
+
+        let params = Parameters(UpsertMissionParams {
+            auth: editor(tenant_id),
+            mission: MissionInput {
+                id: None,
+                name: Some("Harbor sweep".to_string()),
+                status: None,
+                priority: None,
+                classification: None,
+            },
+            dry_run: false,
+            idempotency_key: None,
+        });
+        let Json(created) = service.upsert_mission(params, Meta::default()).await.unwrap();
+
+        let event = ours.try_recv().unwrap();
+        assert_eq!(event.entity, EntityKind::Mission);
+        assert_eq!(event.op, EventOp::Upsert);
+        assert_eq!(event.id, created.id);
+        assert_eq!(event.tenant, tenant_id);
+        assert!(ours.try_recv().is_err());
+        assert!(theirs.try_recv().is_err());
+    }
+
+    #[test]
+    fn tool_calls_are_counted_by_outcome() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                let service = service().await;
+                let (transport, _client) = tokio::io::duplex(4096);
+                let running = rmcp::service::serve_directly(service.clone(), transport, None);
+                let list_missions = |auth: Option<McpAuthContext>| {
+                    let context = RequestContext {
+                        ct: Default::default(),
+                        id: RequestId::Number(1),
+                        meta: Default::default(),
+                        extensions: Default::default(),
+                        peer: running.peer().clone(),
+                    };
+                    let request = CallToolRequestParam {
+                        name: "c2.list_missions".into(),
+                        arguments: serde_json::json!({ "auth": auth }).as_object().cloned(),
+                    };
+                    ServerHandler::call_tool(&service, request, context)
+                };
+                let tenant_id = TenantId::new();
+                assert!(list_missions(editor(tenant_id)).await.is_ok());
+                let outsider = McpAuthContext {
+                    permissions: vec!["view_incidents".to_string()],
+                    ..editor(tenant_id).unwrap()
+                };
+                let err = list_missions(Some(outsider)).await.unwrap_err();
+                assert_eq!(err.message, "permission denied");
+            })
+        });
+
+        let rendered = recorder.handle().render();
+        let calls = |result: &str| {
+            format!("c2_mcp_tool_calls_total{{tool=\"c2.list_missions\",result=\"{result}\"}} 1")
+        };
+        assert!(rendered.contains(&calls("ok")), "{rendered}");
+        assert!(rendered.contains(&calls("denied")), "{rendered}");
+        assert!(!rendered.contains("result=\"error\""), "{rendered}");
+    }
+}
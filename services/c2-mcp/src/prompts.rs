@@ -0,0 +1,186 @@
+use c2_core::TenantId;
+use rmcp::model::{
+    GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+use rmcp::ErrorData;
+use std::fmt;
+use uuid::Uuid;
+
+pub const TRIAGE_INCIDENTS: &str = "c2.triage_incidents";
+pub const PLAN_MISSION: &str = "c2.plan_mission";
+pub const REVIEW_ASSET_READINESS: &str = "c2.review_asset_readiness";
+
+/// Number of visible records, counted over one page; `capped` means the page
+/// was full and there may be more.
+#[derive(Debug, Clone, Copy)]
+pub struct PromptCount {
+    pub value: usize,
+    pub capped: bool,
+}
+
+impl fmt::Display for PromptCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.capped {
+            write!(f, "{}+", self.value)
+        } else {
+            write!(f, "{}", self.value)
+        }
+    }
+}
+
+pub struct PromptArgs {
+    pub tenant_id: TenantId,
+    arguments: JsonObject,
+}
+
+impl PromptArgs {
+    /// Reads the prompt arguments. `tenantId` defaults to the caller's tenant
+    /// and must match it when given.
+    pub fn parse(arguments: Option<JsonObject>, caller: TenantId) -> Result<Self, ErrorData> {
+        let arguments = arguments.unwrap_or_default();
+        let tenant_id = match arguments.get("tenantId").and_then(|value| value.as_str()) {
+            Some(value) => Uuid::parse_str(value.trim())
+                .map(TenantId::from_uuid)
+                .map_err(|_| ErrorData::invalid_params("invalid tenantId", None))?,
+            None => caller,
+        };
+        if tenant_id != caller {
+            return Err(ErrorData::invalid_params("tenant mismatch", None));
+        }
+        Ok(Self {
+            tenant_id,
+            arguments,
+        })
+    }
+
+    fn text(&self, name: &str) -> Option<&str> {
+        self.arguments
+            .get(name)
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn required(&self, name: &str) -> Result<&str, ErrorData> {
+        self.text(name)
+            .ok_or_else(|| ErrorData::invalid_params(format!("missing argument {name}"), None))
+    }
+
+    pub fn optional(&self, name: &str) -> Option<&str> {
+        self.text(name)
+    }
+}
+
+pub fn catalog() -> Vec<Prompt> {
+    vec![
+        Prompt::new(
+            TRIAGE_INCIDENTS,
+            Some("Triage the latest incidents for a tenant and propose next actions."),
+            Some(vec![tenant_argument()]),
+        ),
+        Prompt::new(
+            PLAN_MISSION,
+            Some("Create a mission with an initial set of tasks."),
+            Some(vec![
+                tenant_argument(),
+                argument("missionName", "Name of the mission to create.", true),
+                argument("objective", "What the mission should achieve.", false),
+            ]),
+        ),
+        Prompt::new(
+            REVIEW_ASSET_READINESS,
+            Some("Review asset readiness and flag degraded or lost assets."),
+            Some(vec![tenant_argument()]),
+        ),
+    ]
+}
+
+pub fn triage_incidents(args: &PromptArgs, incidents: PromptCount) -> GetPromptResult {
+    prompt_result(
+        "Triage the latest incidents",
+        format!(
+            "Tenant {} has {} visible incidents. Call c2.list_incidents for this tenant, \
+             order them by most recently updated, and for each unresolved incident summarize \
+             what happened and recommend a next status. Use c2.upsert_incident only after I \
+             confirm the changes.",
+            args.tenant_id, incidents
+        ),
+    )
+}
+
+pub fn plan_mission(
+    args: &PromptArgs,
+    mission_name: &str,
+    missions: PromptCount,
+) -> GetPromptResult {
+    let objective = args
+        .optional("objective")
+        .map(|objective| format!(" The objective is: {objective}."))
+        .unwrap_or_default();
+    prompt_result(
+        "Create a mission with initial tasks",
+        format!(
+            "Tenant {} currently has {} visible missions. Create a mission named \
+             \"{mission_name}\" with c2.upsert_mission (status planned).{objective} Then propose \
+             three to five initial tasks and create each with c2.upsert_task using the new \
+             mission id. Use dryRun first and show me the plan before committing.",
+            args.tenant_id, missions
+        ),
+    )
+}
+
+pub fn review_asset_readiness(args: &PromptArgs, assets: PromptCount) -> GetPromptResult {
+    prompt_result(
+        "Review asset readiness",
+        format!(
+            "Tenant {} has {} visible assets. Call c2.list_assets, group the assets by status, \
+             and list every asset that is degraded, in maintenance, or lost with a suggested \
+             follow-up.",
+            args.tenant_id, assets
+        ),
+    )
+}
+
+fn prompt_result(description: &str, text: String) -> GetPromptResult {
+    GetPromptResult {
+        description: Some(description.to_string()),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    }
+}
+
+fn tenant_argument() -> PromptArgument {
+    argument("tenantId", "Tenant to work on; defaults to the caller's tenant.", false)
+}
+
+fn argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        title: None,
+        description: Some(description.to_string()),
+        required: Some(required),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_lists_every_prompt_and_fills_in_counts() {
+        let names: Vec<String> = catalog().into_iter().map(|prompt| prompt.name).collect();
+        assert_eq!(names, [TRIAGE_INCIDENTS, PLAN_MISSION, REVIEW_ASSET_READINESS]);
+
+        let tenant_id = TenantId::new();
+        let args = PromptArgs::parse(None, tenant_id).unwrap();
+        let count = PromptCount {
+            value: 50,
+            capped: true,
+        };
+        let text = serde_json::to_string(&triage_incidents(&args, count)).unwrap();
+        assert!(text.contains(&format!("Tenant {tenant_id} has 50+ visible incidents")));
+
+        let mut arguments = JsonObject::new();
+        arguments.insert("tenantId".to_string(), TenantId::new().to_string().into());
+        assert!(PromptArgs::parse(Some(arguments), tenant_id).is_err());
+    }
+}
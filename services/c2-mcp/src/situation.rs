@@ -0,0 +1,165 @@
+use c2_core::{
+    GroupCount, IncidentStatus, IncidentType, MissionStatus, ReadinessState,
+    SecurityClassification,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{McpIncidentStatus, McpIncidentType, McpMissionStatus, McpReadinessState};
+
+/// Tenant-wide record counts for one caller. Groups the caller cannot see are
+/// left out of every count, so totals match what the list tools would return.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SituationSummary {
+    pub active_missions: u64,
+    /// Incidents that are neither resolved nor closed.
+    pub open_incidents: u64,
+    pub missions_by_status: Vec<MissionStatusCount>,
+    pub incidents_by_type_and_status: Vec<IncidentCount>,
+    pub assets_by_readiness: Vec<ReadinessCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionStatusCount {
+    pub status: McpMissionStatus,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IncidentCount {
+    pub incident_type: McpIncidentType,
+    pub status: McpIncidentStatus,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessCount {
+    pub readiness: McpReadinessState,
+    pub count: u64,
+}
+
+/// Folds per-classification storage counts into the summary, dropping the
+/// classifications `visible` rejects. Each list is ordered by count, largest
+/// first.
+pub fn summarize(
+    missions: Vec<GroupCount<MissionStatus>>,
+    incidents: Vec<GroupCount<(IncidentType, IncidentStatus)>>,
+    assets: Vec<GroupCount<ReadinessState>>,
+    visible: impl Fn(SecurityClassification) -> bool,
+) -> SituationSummary {
+    let missions = merge(missions, &visible);
+    let incidents = merge(incidents, &visible);
+    let assets = merge(assets, &visible);
+    SituationSummary {
+        active_missions: missions
+            .iter()
+            .filter(|(status, _)| *status == MissionStatus::Active)
+            .map(|(_, count)| count)
+            .sum(),
+        open_incidents: incidents
+            .iter()
+            .filter(|((_, status), _)| {
+                !matches!(status, IncidentStatus::Resolved | IncidentStatus::Closed)
+            })
+            .map(|(_, count)| count)
+            .sum(),
+        missions_by_status: missions
+            .into_iter()
+            .map(|(status, count)| MissionStatusCount {
+                status: status.into(),
+                count,
+            })
+            .collect(),
+        incidents_by_type_and_status: incidents
+            .into_iter()
+            .map(|((incident_type, status), count)| IncidentCount {
+                incident_type: incident_type.into(),
+                status: status.into(),
+                count,
+            })
+            .collect(),
+        assets_by_readiness: assets
+            .into_iter()
+            .map(|(readiness, count)| ReadinessCount {
+                readiness: readiness.into(),
+                count,
+            })
+            .collect(),
+    }
+}
+
+fn merge<K: PartialEq>(
+    groups: Vec<GroupCount<K>>,
+    visible: &impl Fn(SecurityClassification) -> bool,
+) -> Vec<(K, u64)> {
+    let mut merged: Vec<(K, u64)> = Vec::new();
+    for group in groups.into_iter().filter(|group| visible(group.classification)) {
+        match merged.iter_mut().find(|(key, _)| *key == group.key) {
+            Some((_, count)) => *count += group.count,
+            None => merged.push((group.key, group.count)),
+        }
+    }
+    merged.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn group<K>(key: K, classification: SecurityClassification, count: u64) -> GroupCount<K> {
+        GroupCount {
+            key,
+            classification,
+            count,
+        }
+    }
+
+    #[test]
+    fn summary_counts_only_visible_classifications() {
+        use SecurityClassification::{Secret, TopSecret, Unclassified};
+
+        let missions = vec![
+            group(MissionStatus::Active, Unclassified, 2),
+            group(MissionStatus::Completed, Unclassified, 1),
+            group(MissionStatus::Active, Secret, 1),
+            group(MissionStatus::Planned, TopSecret, 4),
+        ];
+        let incidents = vec![
+            group((IncidentType::Fire, IncidentStatus::Reported), Unclassified, 2),
+            group((IncidentType::Fire, IncidentStatus::Closed), Unclassified, 1),
+            group((IncidentType::Medical, IncidentStatus::Responding), Secret, 1),
+            group((IncidentType::Medical, IncidentStatus::Reported), TopSecret, 3),
+        ];
+        let assets = vec![
+            group(ReadinessState::Ready, Unclassified, 5),
+            group(ReadinessState::Degraded, TopSecret, 2),
+        ];
+
+        let summary = summarize(missions, incidents, assets, |classification| {
+            classification <= Secret
+        });
+        assert_eq!(
+            serde_json::to_value(summary).unwrap(),
+            json!({
+                "activeMissions": 3,
+                "openIncidents": 3,
+                "missionsByStatus": [
+                    { "status": "active", "count": 3 },
+                    { "status": "completed", "count": 1 },
+                ],
+                "incidentsByTypeAndStatus": [
+                    { "incidentType": "fire", "status": "reported", "count": 2 },
+                    { "incidentType": "fire", "status": "closed", "count": 1 },
+                    { "incidentType": "medical", "status": "responding", "count": 1 },
+                ],
+                "assetsByReadiness": [{ "readiness": "ready", "count": 5 }],
+            })
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use c2_core::{SecurityClassification, TenantId};
+use c2_messaging::{EntityKind, EventBus, Replay};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::situation::SituationSummary;
+
+const DEFAULT_TTL_MS: u64 = 5_000;
+
+type Key = (TenantId, &'static str, SecurityClassification);
+
+/// Short-lived copy of each summary a caller asked for, keyed by tenant,
+/// query and clearance, so dashboards polling the same counts every few
+/// seconds read storage once per TTL. An entry is dropped as soon as this
+/// process's [`EventBus`] has published a change to one of the entity kinds
+/// it counts; writes made by other processes show up once the TTL runs out.
+/// A zero TTL turns caching off.
+pub struct SummaryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<Key, Entry>>,
+}
+
+struct Entry {
+    summary: SituationSummary,
+    counts: &'static [EntityKind],
+    /// The tenant's last event sequence before the counts were read.
+    sequence: u64,
+    expires_at: Instant,
+}
+
+impl SummaryCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let ttl_ms = env::var("C2_MCP_SUMMARY_CACHE_TTL_MS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TTL_MS);
+        Self::new(Duration::from_millis(ttl_ms))
+    }
+
+    /// The cached summary, unless it expired or `events` has published a
+    /// change to something it counts since it was read.
+    pub fn get(
+        &self,
+        events: &EventBus,
+        tenant_id: TenantId,
+        query: &'static str,
+        clearance: SecurityClassification,
+    ) -> Option<SituationSummary> {
+        let key = (tenant_id, query, clearance);
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        let entry = entries.get(&key)?;
+        let fresh = entry.expires_at > Instant::now()
+            && match events.resume(tenant_id, entry.sequence).0 {
+                Replay::Events(missed) => {
+                    missed.iter().all(|event| !entry.counts.contains(&event.entity))
+                }
+                Replay::Gap => false,
+            };
+        if fresh {
+            return Some(entry.summary.clone());
+        }
+        entries.remove(&key);
+        None
+    }
+
+    /// Caches `summary`, which counts `counts` as of event `sequence`; read
+    /// the sequence with [`EventBus::last_sequence`] before the counts.
+    pub fn insert(
+        &self,
+        tenant_id: TenantId,
+        query: &'static str,
+        clearance: SecurityClassification,
+        counts: &'static [EntityKind],
+        sequence: u64,
+        summary: SituationSummary,
+    ) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.retain(|_, entry| entry.expires_at > now);
+        entries.insert(
+            (tenant_id, query, clearance),
+            Entry {
+                summary,
+                counts,
+                sequence,
+                expires_at: now + self.ttl,
+            },
+        );
+    }
+}
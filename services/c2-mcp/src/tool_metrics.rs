@@ -0,0 +1,135 @@
+use c2_config::{ReadOnlyMode, READ_ONLY_MESSAGE};
+use metrics::{counter, histogram};
+use rmcp::handler::server::tool::{ToolCallContext, ToolRouter};
+use rmcp::model::{CallToolResult, Tool};
+use rmcp::ErrorData;
+use std::time::Instant;
+use tracing::Instrument;
+
+use crate::correlation;
+
+pub const TOOL_DURATION_SECONDS: &str = "c2_mcp_tool_duration_seconds";
+pub const TOOL_CALLS_TOTAL: &str = "c2_mcp_tool_calls_total";
+
+/// Why a call was refused before the tool did any work. The kind travels in
+/// the error's `data.kind`, so metrics and agents never have to parse the
+/// message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refusal {
+    Denied,
+    Throttled,
+    ReadOnly,
+}
+
+impl Refusal {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Refusal::Denied => "denied",
+            Refusal::Throttled => "throttled",
+            Refusal::ReadOnly => "read_only",
+        }
+    }
+
+    /// An `invalid_request` error tagged with this refusal; any `data`
+    /// fields are kept alongside `kind`.
+    pub fn error(self, message: &'static str, data: Option<serde_json::Value>) -> ErrorData {
+        let mut data = data.unwrap_or_else(|| serde_json::json!({}));
+        if let Some(fields) = data.as_object_mut() {
+            fields.insert("kind".to_string(), self.as_str().into());
+        }
+        ErrorData::invalid_request(message, Some(data))
+    }
+
+    fn of(err: &ErrorData) -> Option<Self> {
+        match err.data.as_ref()?.get("kind")?.as_str()? {
+            "denied" => Some(Refusal::Denied),
+            "throttled" => Some(Refusal::Throttled),
+            "read_only" => Some(Refusal::ReadOnly),
+            _ => None,
+        }
+    }
+}
+
+pub struct MeteredToolRouter<'a, S> {
+    router: &'a ToolRouter<S>,
+    read_only: &'a ReadOnlyMode,
+}
+
+impl<'a, S> MeteredToolRouter<'a, S>
+where
+    S: Send + Sync + 'static,
+{
+    pub fn new(router: &'a ToolRouter<S>, read_only: &'a ReadOnlyMode) -> Self {
+        Self { router, read_only }
+    }
+
+    pub async fn call(&self, context: ToolCallContext<'_, S>) -> Result<CallToolResult, ErrorData> {
+        let tool = context.name().to_string();
+        let started = Instant::now();
+        let correlation_id = correlation::for_call(&context.request_context.extensions);
+        let span = tracing::info_span!("tool_call", tool = %tool, correlation_id = %correlation_id);
+        let result = if self.read_only.is_enabled() && !self.is_read_only_tool(&tool) {
+            Err(Refusal::ReadOnly.error(
+                READ_ONLY_MESSAGE,
+                Some(serde_json::json!({ "retryable": true })),
+            ))
+        } else {
+            c2_messaging::correlation::scope(correlation_id, self.router.call(context))
+                .instrument(span)
+                .await
+        };
+        histogram!(TOOL_DURATION_SECONDS, "tool" => tool.clone())
+            .record(started.elapsed().as_secs_f64());
+        counter!(TOOL_CALLS_TOTAL, "tool" => tool, "result" => call_outcome(&result))
+            .increment(1);
+        result
+    }
+
+    pub fn list_all(&self) -> Vec<Tool> {
+        self.router.list_all()
+    }
+
+    /// Tools annotated `read_only_hint = true` keep working in read-only
+    /// mode; every other tool is treated as a write.
+    fn is_read_only_tool(&self, name: &str) -> bool {
+        self.router
+            .map
+            .get(name)
+            .and_then(|route| route.attr.annotations.as_ref())
+            .and_then(|annotations| annotations.read_only_hint)
+            .unwrap_or(false)
+    }
+}
+
+fn call_outcome(result: &Result<CallToolResult, ErrorData>) -> &'static str {
+    match result {
+        Ok(result) if result.is_error == Some(true) => "error",
+        Ok(_) => "ok",
+        Err(err) => Refusal::of(err).map_or("error", Refusal::as_str),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes_follow_the_refusal_kind_not_the_message() {
+        let denied = Refusal::Denied.error("access denied", None);
+        assert_eq!(call_outcome(&Err(denied)), "denied");
+
+        let throttled = Refusal::Throttled.error(
+            "slow down",
+            Some(serde_json::json!({ "retryable": true, "retryAfterMs": 250 })),
+        );
+        assert_eq!(throttled.data.as_ref().unwrap()["retryAfterMs"], 250);
+        assert_eq!(call_outcome(&Err(throttled)), "throttled");
+
+        let read_only = Refusal::ReadOnly.error(READ_ONLY_MESSAGE, None);
+        assert_eq!(call_outcome(&Err(read_only)), "read_only");
+
+        let untagged = ErrorData::invalid_request("request denied", None);
+        assert_eq!(call_outcome(&Err(untagged)), "error");
+        assert_eq!(call_outcome(&Ok(CallToolResult::success(Vec::new()))), "ok");
+    }
+}
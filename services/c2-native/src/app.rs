@@ -1,13 +1,14 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use egui_dock::DockState;
 use glam::Vec3;
 use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event::{Event, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::keyboard::{KeyCode, PhysicalKey};
 use winit::window::{Window, WindowAttributes, WindowId};
 
 use crate::ecs::{RenderInstance, WorldState, KIND_FLIGHT, KIND_SATELLITE, KIND_SHIP};
@@ -28,6 +29,17 @@ const WEATHER_MAX_ZOOM: u8 = 6;
 const SEA_MIN_ZOOM: u8 = 0;
 const SEA_MAX_ZOOM: u8 = 6;
 const MAP_UPDATE_INTERVAL_MS: u64 = 220;
+/// Saves the current viewport as a PNG in the working directory.
+const CAPTURE_KEY: KeyCode = KeyCode::F12;
+/// Flies the camera to the entity last clicked on the globe.
+const FLY_TO_SELECTED_KEY: KeyCode = KeyCode::KeyF;
+/// Flies back to the startup overview of the globe.
+const HOME_VIEW_KEY: KeyCode = KeyCode::Home;
+const HOME_VIEW: (f32, f32, f32) = (17.0, 23.0, 320.0);
+const FOLLOW_DISTANCE: f32 = 180.0;
+const FLY_DURATION_SECS: f32 = 1.2;
+/// Clicks further than this from every entity clear the selection.
+const PICK_RADIUS_DEG: f32 = 2.0;
 const WEATHER_UPDATE_INTERVAL_MS: u64 = 900;
 const SEA_UPDATE_INTERVAL_MS: u64 = 1100;
 const MAX_TILE_UPLOADS_PER_FRAME: usize = 24;
@@ -108,6 +120,7 @@ struct App {
     detached_tabs: HashMap<DockTab, WindowId>,
     active_drag: Option<DockDragStart>,
     main_cursor_pos: Option<PhysicalPosition<f64>>,
+    selected_entity: Option<u64>,
 }
 
 struct DetachedWindow {
@@ -191,6 +204,7 @@ impl App {
             detached_tabs: HashMap::new(),
             active_drag: None,
             main_cursor_pos: None,
+            selected_entity: None,
         })
     }
 
@@ -489,6 +503,33 @@ impl App {
         }
     }
 
+    fn save_frame_capture(&mut self) {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default();
+        let path = format!("c2-capture-{stamp}.png");
+        match self.renderer.capture_frame() {
+            Ok(image) => match image.save(&path) {
+                Ok(()) => eprintln!("saved frame capture to {path}"),
+                Err(error) => eprintln!("frame capture save failed: {error:?}"),
+            },
+            Err(error) => eprintln!("frame capture failed: {error:?}"),
+        }
+    }
+
+    fn fly_to_selected(&mut self) {
+        let Some(id) = self.selected_entity else {
+            return;
+        };
+        match self.world.entity_geo(id) {
+            Some((lat, lon)) => {
+                self.renderer.fly_to(lat, lon, FOLLOW_DISTANCE, FLY_DURATION_SECS);
+            }
+            None => self.selected_entity = None,
+        }
+    }
+
     fn handle_drag_release(&mut self, window_id: WindowId) {
         let Some(active_drag) = self.active_drag.take() else {
             return;
@@ -656,6 +697,8 @@ impl App {
                 0.0,
                 0.0,
             );
+            self.renderer
+                .set_globe_segments(self.overlay_settings.globe_segments);
             self.tile_layers.apply_settings(
                 &self.overlay_settings,
                 &mut self.renderer,
@@ -733,7 +776,8 @@ impl App {
     fn handle_window_event(&mut self, event: &WindowEvent) -> bool {
         let window = self.window.as_ref();
         let egui_response = self.egui_state.on_window_event(window, event);
-        if !egui_response.consumed {
+        let consumed = egui_response.consumed;
+        if !consumed {
             self.renderer.handle_input(event);
         }
         match event {
@@ -755,6 +799,20 @@ impl App {
                     self.handle_drag_release(self.main_window_id);
                 }
             }
+            WindowEvent::KeyboardInput { event, .. }
+                if event.state == winit::event::ElementState::Pressed && !event.repeat =>
+            {
+                match event.physical_key {
+                    PhysicalKey::Code(CAPTURE_KEY) => self.save_frame_capture(),
+                    _ if consumed => {}
+                    PhysicalKey::Code(FLY_TO_SELECTED_KEY) => self.fly_to_selected(),
+                    PhysicalKey::Code(HOME_VIEW_KEY) => {
+                        let (lat, lon, distance) = HOME_VIEW;
+                        self.renderer.fly_to(lat, lon, distance, FLY_DURATION_SECS);
+                    }
+                    _ => {}
+                }
+            }
             _ => {}
         }
         true
@@ -767,6 +825,9 @@ impl App {
         let delta = (now - self.last_frame).as_secs_f32();
         self.last_frame = now;
         let fps = if delta > 0.0 { 1.0 / delta } else { 0.0 };
+        if self.renderer.advance_camera(delta) {
+            self.cull_dirty = true;
+        }
 
         let mut world_updated = false;
         let world_start = Instant::now();
@@ -851,6 +912,19 @@ impl App {
                     if input.pointer.primary_pressed() && hovered {
                         self.globe_dragging = true;
                     }
+                    if input.pointer.primary_clicked() && hovered {
+                        let ndc_x = (pos.x - rect.left()) / rect.width().max(1.0) * 2.0 - 1.0;
+                        let ndc_y = 1.0 - (pos.y - rect.top()) / rect.height().max(1.0) * 2.0;
+                        self.selected_entity =
+                            sample_geo(&self.renderer, ndc_x, ndc_y, DEFAULT_GLOBE_RADIUS)
+                                .and_then(|sample| {
+                                    self.world.nearest_entity(
+                                        sample.lat,
+                                        sample.lon,
+                                        PICK_RADIUS_DEG,
+                                    )
+                                });
+                    }
                     if input.pointer.primary_released() {
                         self.globe_dragging = false;
                     }
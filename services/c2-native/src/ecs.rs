@@ -129,6 +129,34 @@ impl WorldState {
         }
     }
 
+    /// Id of the entity closest to `lat_deg`/`lon_deg`, if one lies within
+    /// `max_angle_deg` of it along the globe surface.
+    pub fn nearest_entity(
+        &mut self,
+        lat_deg: f32,
+        lon_deg: f32,
+        max_angle_deg: f32,
+    ) -> Option<u64> {
+        let target = unit_vector(lat_deg, lon_deg);
+        let min_dot = max_angle_deg.to_radians().cos();
+        let mut query = self.world.query::<(&EntityId, &GeoPosition)>();
+        query
+            .iter(&self.world)
+            .map(|(id, geo)| (id.0, unit_vector(geo.lat_deg, geo.lon_deg).dot(target)))
+            .filter(|(_, dot)| *dot >= min_dot)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    /// Current latitude and longitude of the entity with `id`.
+    pub fn entity_geo(&mut self, id: u64) -> Option<(f32, f32)> {
+        let mut query = self.world.query::<(&EntityId, &GeoPosition)>();
+        query
+            .iter(&self.world)
+            .find(|(entity_id, _)| entity_id.0 == id)
+            .map(|(_, geo)| (geo.lat_deg, geo.lon_deg))
+    }
+
     pub fn entity_count(&self) -> usize {
         self.world.entities().len() as usize
     }
@@ -209,6 +237,12 @@ fn advance_motion(
     }
 }
 
+fn unit_vector(lat_deg: f32, lon_deg: f32) -> Vec3 {
+    let lat = lat_deg.to_radians();
+    let lon = lon_deg.to_radians();
+    Vec3::new(lat.cos() * lon.cos(), lat.sin(), lat.cos() * lon.sin())
+}
+
 fn clamp_lat(lat: f32) -> f32 {
     lat.max(-85.0).min(85.0)
 }
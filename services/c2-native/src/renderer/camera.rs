@@ -1,6 +1,10 @@
 use glam::{Mat4, Vec3};
 use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
 
+pub const MIN_CAMERA_DISTANCE: f32 = 130.0;
+pub const MAX_CAMERA_DISTANCE: f32 = 600.0;
+const MAX_PITCH: f32 = 1.45;
+
 pub struct Camera {
     pub distance: f32,
     pub yaw: f32,
@@ -9,6 +13,16 @@ pub struct Camera {
     pub fov_y: f32,
     pub near: f32,
     pub far: f32,
+    flight: Option<Flight>,
+}
+
+/// An eased camera move between two orbit poses, stepped once per frame.
+#[derive(Debug, Clone, Copy)]
+struct Flight {
+    from: (f32, f32, f32),
+    to: (f32, f32, f32),
+    elapsed: f32,
+    duration: f32,
 }
 
 impl Camera {
@@ -21,9 +35,52 @@ impl Camera {
             fov_y: 45.0_f32.to_radians(),
             near: 0.1,
             far: 2000.0,
+            flight: None,
         }
     }
 
+    /// Starts a move that centres `lat_deg`/`lon_deg` at `distance` (clamped
+    /// to the zoom range) over `duration` seconds. The yaw takes the short
+    /// way round the globe.
+    pub fn fly_to(&mut self, lat_deg: f32, lon_deg: f32, distance: f32, duration: f32) {
+        let pitch = lat_deg.to_radians().clamp(-MAX_PITCH, MAX_PITCH);
+        let yaw = self.yaw + wrap_angle(lon_deg.to_radians() - self.yaw);
+        let distance = distance.clamp(MIN_CAMERA_DISTANCE, MAX_CAMERA_DISTANCE);
+        self.flight = Some(Flight {
+            from: (self.yaw, self.pitch, self.distance),
+            to: (yaw, pitch, distance),
+            elapsed: 0.0,
+            duration: duration.max(0.0),
+        });
+    }
+
+    pub fn cancel_flight(&mut self) {
+        self.flight = None;
+    }
+
+    /// Steps an active flight by `delta` seconds. Returns whether the camera
+    /// moved.
+    pub fn advance(&mut self, delta: f32) -> bool {
+        let Some(flight) = self.flight.as_mut() else {
+            return false;
+        };
+        flight.elapsed += delta.max(0.0);
+        let t = if flight.duration > 0.0 {
+            (flight.elapsed / flight.duration).min(1.0)
+        } else {
+            1.0
+        };
+        let eased = ease_in_out_cubic(t);
+        let (from, to) = (flight.from, flight.to);
+        self.yaw = from.0 + (to.0 - from.0) * eased;
+        self.pitch = from.1 + (to.1 - from.1) * eased;
+        self.distance = from.2 + (to.2 - from.2) * eased;
+        if t >= 1.0 {
+            self.flight = None;
+        }
+        true
+    }
+
     pub fn view_proj(&self) -> Mat4 {
         let position = self.position();
         let view = Mat4::look_at_rh(position, Vec3::ZERO, Vec3::Y);
@@ -61,8 +118,8 @@ impl CameraController {
             zoom_sensitivity: 0.002,
             dragging: false,
             last_cursor: (0.0, 0.0),
-            min_distance: 130.0,
-            max_distance: 600.0,
+            min_distance: MIN_CAMERA_DISTANCE,
+            max_distance: MAX_CAMERA_DISTANCE,
         }
     }
 
@@ -76,12 +133,13 @@ impl CameraController {
             WindowEvent::CursorMoved { position, .. } => {
                 let (x, y) = (position.x as f32, position.y as f32);
                 if self.dragging {
+                    camera.cancel_flight();
                     let dx = x - self.last_cursor.0;
                     let dy = y - self.last_cursor.1;
                     let scale = self.rotation_scale(camera);
                     camera.yaw += dx * self.rotate_sensitivity * scale;
                     camera.pitch = (camera.pitch + dy * self.rotate_sensitivity * scale)
-                        .clamp(-1.45, 1.45);
+                        .clamp(-MAX_PITCH, MAX_PITCH);
                 }
                 self.last_cursor = (x, y);
             }
@@ -97,9 +155,11 @@ impl CameraController {
     }
 
     pub fn orbit_delta(&self, dx: f32, dy: f32, camera: &mut Camera) {
+        camera.cancel_flight();
         let scale = self.rotation_scale(camera);
         camera.yaw += dx * self.rotate_sensitivity * scale;
-        camera.pitch = (camera.pitch + dy * self.rotate_sensitivity * scale).clamp(-1.45, 1.45);
+        camera.pitch =
+            (camera.pitch + dy * self.rotate_sensitivity * scale).clamp(-MAX_PITCH, MAX_PITCH);
     }
 
     pub fn zoom_delta(&self, scroll: f32, camera: &mut Camera) {
@@ -110,6 +170,7 @@ impl CameraController {
         if scroll.abs() < f32::EPSILON {
             return;
         }
+        camera.cancel_flight();
         let delta = (scroll * self.zoom_sensitivity).clamp(-0.25, 0.25);
         camera.distance = (camera.distance * (1.0 - delta))
             .clamp(self.min_distance, self.max_distance);
@@ -121,3 +182,46 @@ impl CameraController {
         t
     }
 }
+
+fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fly_to_approaches_target_monotonically() {
+        let mut camera = Camera::new(1.0, 320.0);
+        camera.yaw = 170.0_f32.to_radians();
+        camera.fly_to(40.0, -170.0, 900.0, 1.0);
+
+        let target_yaw = 190.0_f32.to_radians();
+        let target_pitch = 40.0_f32.to_radians();
+        let mut last = (camera.yaw, camera.pitch, camera.distance);
+        for _ in 0..20 {
+            assert!(camera.advance(0.05));
+            assert!(camera.yaw >= last.0 && camera.yaw <= target_yaw + 1e-4);
+            assert!(camera.pitch >= last.1 && camera.pitch <= target_pitch + 1e-4);
+            assert!(camera.distance >= last.2 && camera.distance <= MAX_CAMERA_DISTANCE);
+            last = (camera.yaw, camera.pitch, camera.distance);
+        }
+
+        // Crossing the antimeridian goes east by 20 degrees, not west by 340.
+        assert!(camera.flight.is_none());
+        assert!((camera.yaw - target_yaw).abs() < 1e-4);
+        assert!((camera.pitch - target_pitch).abs() < 1e-4);
+        assert_eq!(camera.distance, MAX_CAMERA_DISTANCE);
+        assert!(!camera.advance(0.05));
+    }
+}
@@ -0,0 +1,77 @@
+use image::RgbaImage;
+
+/// Row pitch of a texture-to-buffer copy, rounded up to wgpu's required
+/// alignment.
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(align) * align
+}
+
+/// Drops the row padding from a read-back viewport copy and swaps blue and
+/// red when the viewport renders in a BGRA format.
+pub fn image_from_padded_rows(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+) -> anyhow::Result<RgbaImage> {
+    let row_bytes = (width * 4) as usize;
+    let padded = padded_bytes_per_row(width) as usize;
+    let needed = padded * height.saturating_sub(1) as usize + row_bytes;
+    if data.len() < needed {
+        anyhow::bail!("frame readback too short: {} < {needed} bytes", data.len());
+    }
+    let swap_red_blue = match format {
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+        other => anyhow::bail!("frame capture does not support {other:?}"),
+    };
+    let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+    for row in data.chunks(padded).take(height as usize) {
+        pixels.extend_from_slice(&row[..row_bytes]);
+    }
+    if swap_red_blue {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+    RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| anyhow::anyhow!("frame capture buffer does not match {width}x{height}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padded_rows_decode_to_viewport_sized_image() {
+        let (width, height) = (70, 3);
+        let padded = padded_bytes_per_row(width) as usize;
+        assert_eq!(padded, 512);
+        let mut data = vec![0xEE; padded * height as usize];
+        for y in 0..height as usize {
+            for x in 0..width as usize {
+                let offset = y * padded + x * 4;
+                data[offset..offset + 4].copy_from_slice(&[10, 20, y as u8, 255]);
+            }
+        }
+
+        let image =
+            image_from_padded_rows(&data, width, height, wgpu::TextureFormat::Bgra8UnormSrgb)
+                .unwrap();
+        assert_eq!(image.dimensions(), (width, height));
+        assert_eq!(image.get_pixel(69, 2).0, [2, 20, 10, 255]);
+        // No padding bytes leak into the pixels.
+        assert!(image.pixels().all(|pixel| pixel.0[3] == 255));
+
+        let rgba =
+            image_from_padded_rows(&data, width, height, wgpu::TextureFormat::Rgba8Unorm)
+                .unwrap();
+        assert_eq!(rgba.get_pixel(0, 1).0, [10, 20, 1, 255]);
+        let short = &data[..padded];
+        assert!(
+            image_from_padded_rows(short, width, height, wgpu::TextureFormat::Rgba8Unorm).is_err()
+        );
+    }
+}
@@ -1,4 +1,11 @@
 use glam::Vec3;
+use wgpu::util::DeviceExt;
+
+pub const GLOBE_RADIUS: f32 = 120.0;
+/// Longitude segments of the globe mesh; latitude rings are half as many.
+pub const DEFAULT_GLOBE_SEGMENTS: u32 = 128;
+const MIN_GLOBE_SEGMENTS: u32 = 16;
+const MAX_GLOBE_SEGMENTS: u32 = 512;
 
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
@@ -98,3 +105,65 @@ pub fn build_sphere(radius: f32, segments: u32, rings: u32) -> (Vec<GlobeVertex>
 
     (vertices, indices)
 }
+
+/// `C2_NATIVE_GLOBE_SEGMENTS`, clamped to a usable range; lower it on weak
+/// GPUs.
+pub fn globe_segments_from_env() -> u32 {
+    std::env::var("C2_NATIVE_GLOBE_SEGMENTS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .map(clamp_globe_segments)
+        .unwrap_or(DEFAULT_GLOBE_SEGMENTS)
+}
+
+pub fn clamp_globe_segments(segments: u32) -> u32 {
+    segments.clamp(MIN_GLOBE_SEGMENTS, MAX_GLOBE_SEGMENTS)
+}
+
+pub struct GlobeMesh {
+    pub vertex_buffer: wgpu::Buffer,
+    pub index_buffer: wgpu::Buffer,
+    pub index_count: u32,
+    pub segments: u32,
+}
+
+impl GlobeMesh {
+    pub fn new(device: &wgpu::Device, segments: u32) -> Self {
+        let segments = clamp_globe_segments(segments);
+        let (vertices, indices) = build_sphere(GLOBE_RADIUS, segments, segments / 2);
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("globe vertex buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("globe index buffer"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        Self {
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+            segments,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_sphere_scales_with_subdivision() {
+        let (low_vertices, low_indices) = build_sphere(GLOBE_RADIUS, 32, 16);
+        let (high_vertices, high_indices) = build_sphere(GLOBE_RADIUS, 64, 32);
+
+        assert_eq!(low_vertices.len(), 33 * 17);
+        assert_eq!(high_vertices.len(), 65 * 33);
+        assert_eq!(low_indices.len(), 32 * 16 * 6);
+        // Doubling both axes quadruples the quads and so the indices.
+        assert_eq!(high_indices.len(), 4 * low_indices.len());
+        assert!(high_indices.iter().all(|index| (*index as usize) < high_vertices.len()));
+    }
+}
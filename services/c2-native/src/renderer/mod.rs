@@ -1,6 +1,8 @@
 mod camera;
+mod capture;
 mod globe;
 mod instance;
+mod msaa;
 mod texture;
 
 use wgpu::util::DeviceExt;
@@ -9,8 +11,10 @@ use crate::ecs::RenderInstance;
 use crate::tiles::{TileKind, MAP_TILE_CAPACITY, SEA_TILE_CAPACITY, TILE_SIZE, WEATHER_TILE_CAPACITY};
 
 pub use camera::{Camera, CameraController};
-use globe::{build_sphere, GlobeVertex};
+use globe::{GlobeMesh, GlobeVertex};
+pub use globe::{clamp_globe_segments, globe_segments_from_env};
 pub use instance::{quad_vertices, InstanceRaw, Vertex};
+use msaa::{clamp_msaa_samples, msaa_samples_from_env, supported_msaa_samples};
 pub use texture::Texture;
 use texture::{rgba_from_png, rgba_from_png_with_size, rgba_from_svg, TextureArray};
 
@@ -95,9 +99,7 @@ pub struct Renderer {
     surface_format: wgpu::TextureFormat,
     size: (u32, u32),
     globe_pipeline: wgpu::RenderPipeline,
-    globe_vertex_buffer: wgpu::Buffer,
-    globe_index_buffer: wgpu::Buffer,
-    globe_index_count: u32,
+    globe_mesh: GlobeMesh,
     globe_bind_group_layout: wgpu::BindGroupLayout,
     globe_bind_group: wgpu::BindGroup,
     marker_pipeline: wgpu::RenderPipeline,
@@ -130,6 +132,10 @@ pub struct Renderer {
     viewport_view: wgpu::TextureView,
     viewport_depth: wgpu::Texture,
     viewport_depth_view: wgpu::TextureView,
+    /// Multisampled color target resolved into `viewport_view`; `None` when
+    /// MSAA is off.
+    viewport_msaa_view: Option<wgpu::TextureView>,
+    msaa_samples: u32,
     viewport_size: (u32, u32),
 }
 
@@ -150,10 +156,13 @@ impl Renderer {
             })
             .await?;
 
+        // Lets MSAA use every sample count the adapter reports, not just 1 and 4.
+        let required_features = adapter.features()
+            & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor {
                 label: Some("c2-native device"),
-                required_features: wgpu::Features::empty(),
+                required_features,
                 required_limits: wgpu::Limits::default(),
                 experimental_features: wgpu::ExperimentalFeatures::default(),
                 memory_hints: wgpu::MemoryHints::default(),
@@ -181,6 +190,17 @@ impl Renderer {
         surface.configure(&device, &config);
         let config_size = (config.width, config.height);
 
+        let requested_samples = msaa_samples_from_env();
+        let supported_samples =
+            supported_msaa_samples(&adapter, surface_format, wgpu::TextureFormat::Depth32Float);
+        let msaa_samples = clamp_msaa_samples(requested_samples, &supported_samples);
+        if msaa_samples != requested_samples {
+            eprintln!(
+                "MSAA x{requested_samples} unsupported (adapter offers {supported_samples:?}); \
+                 using x{msaa_samples}"
+            );
+        }
+
         let camera = Camera::new(config.width as f32 / config.height as f32, 320.0);
         let controller = CameraController::new();
         let camera_uniform = CameraUniform {
@@ -204,7 +224,20 @@ impl Renderer {
         });
 
         let (viewport_texture, viewport_view, viewport_depth, viewport_depth_view) =
-            create_viewport_target(&device, surface_format, config.width, config.height);
+            create_viewport_target(
+                &device,
+                surface_format,
+                config.width,
+                config.height,
+                msaa_samples,
+            );
+        let viewport_msaa_view = create_msaa_view(
+            &device,
+            surface_format,
+            config.width,
+            config.height,
+            msaa_samples,
+        );
 
         let layer_size = 4096u32;
         let (base_rgba, base_width, base_height) =
@@ -546,7 +579,7 @@ impl Renderer {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -604,7 +637,7 @@ impl Renderer {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -660,7 +693,7 @@ impl Renderer {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: msaa_samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -668,18 +701,7 @@ impl Renderer {
             cache: None,
         });
 
-        let (globe_vertices, globe_indices) = build_sphere(120.0, 128, 64);
-        let globe_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("globe vertex buffer"),
-            contents: bytemuck::cast_slice(&globe_vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-        let globe_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("globe index buffer"),
-            contents: bytemuck::cast_slice(&globe_indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-        let globe_index_count = globe_indices.len() as u32;
+        let globe_mesh = GlobeMesh::new(&device, globe_segments_from_env());
 
         let (vertices, indices) = quad_vertices();
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -758,9 +780,7 @@ impl Renderer {
             surface_format,
             size: config_size,
             globe_pipeline,
-            globe_vertex_buffer,
-            globe_index_buffer,
-            globe_index_count,
+            globe_mesh,
             globe_bind_group_layout,
             globe_bind_group,
             marker_pipeline,
@@ -793,6 +813,8 @@ impl Renderer {
             viewport_view,
             viewport_depth,
             viewport_depth_view,
+            viewport_msaa_view,
+            msaa_samples,
             viewport_size: config_size,
         })
     }
@@ -809,6 +831,10 @@ impl Renderer {
         self.viewport_size
     }
 
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples
+    }
+
     pub fn layer_size(&self) -> u32 {
         self.layer_size
     }
@@ -876,8 +902,20 @@ impl Renderer {
         if (width, height) == self.viewport_size {
             return false;
         }
-        let (texture, view, depth, depth_view) =
-            create_viewport_target(&self.device, self.surface_format, width, height);
+        let (texture, view, depth, depth_view) = create_viewport_target(
+            &self.device,
+            self.surface_format,
+            width,
+            height,
+            self.msaa_samples,
+        );
+        self.viewport_msaa_view = create_msaa_view(
+            &self.device,
+            self.surface_format,
+            width,
+            height,
+            self.msaa_samples,
+        );
         self.viewport_texture = texture;
         self.viewport_view = view;
         self.viewport_depth = depth;
@@ -946,9 +984,9 @@ impl Renderer {
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("globe + marker pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &self.viewport_view,
+                view: self.viewport_msaa_view.as_ref().unwrap_or(&self.viewport_view),
                 depth_slice: None,
-                resolve_target: None,
+                resolve_target: self.viewport_msaa_view.as_ref().map(|_| &self.viewport_view),
                 ops: wgpu::Operations {
                     load: wgpu::LoadOp::Clear(wgpu::Color {
                         r: 0.04,
@@ -956,7 +994,12 @@ impl Renderer {
                         b: 0.07,
                         a: 1.0,
                     }),
-                    store: wgpu::StoreOp::Store,
+                    // The resolve target keeps the image; the samples can go.
+                    store: if self.viewport_msaa_view.is_some() {
+                        wgpu::StoreOp::Discard
+                    } else {
+                        wgpu::StoreOp::Store
+                    },
                 },
             })],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
@@ -973,9 +1016,9 @@ impl Renderer {
         });
         pass.set_pipeline(&self.globe_pipeline);
         pass.set_bind_group(0, &self.globe_bind_group, &[]);
-        pass.set_vertex_buffer(0, self.globe_vertex_buffer.slice(..));
-        pass.set_index_buffer(self.globe_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-        pass.draw_indexed(0..self.globe_index_count, 0, 0..1);
+        pass.set_vertex_buffer(0, self.globe_mesh.vertex_buffer.slice(..));
+        pass.set_index_buffer(self.globe_mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.draw_indexed(0..self.globe_mesh.index_count, 0, 0..1);
 
         self.render_tile_layer(&mut pass, &self.map_tiles);
         self.render_tile_layer(&mut pass, &self.sea_tiles);
@@ -1005,6 +1048,63 @@ impl Renderer {
         pass.draw_indexed(0..self.tile_index_count, 0, 0..layer.instance_count);
     }
 
+    /// Renders the scene into the viewport texture and reads it back as an
+    /// RGBA image the size of the viewport. Blocks until the GPU finishes.
+    pub fn capture_frame(&mut self) -> anyhow::Result<image::RgbaImage> {
+        let (width, height) = (self.viewport_size.0.max(1), self.viewport_size.1.max(1));
+        let padded_bytes_per_row = capture::padded_bytes_per_row(width);
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("frame capture buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("frame capture encoder"),
+            });
+        self.render_scene(&mut encoder);
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.viewport_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.device.poll(wgpu::PollType::wait_indefinitely())?;
+        rx.recv()??;
+        let image = capture::image_from_padded_rows(
+            &slice.get_mapped_range(),
+            width,
+            height,
+            self.surface_format,
+        );
+        buffer.unmap();
+        image
+    }
+
     pub fn orbit_delta(&mut self, dx: f32, dy: f32) {
         self.controller.orbit_delta(dx, dy, &mut self.camera);
     }
@@ -1013,6 +1113,16 @@ impl Renderer {
         self.controller.zoom_delta(scroll, &mut self.camera);
     }
 
+    /// Eases the camera over `lat_deg`/`lon_deg`; see [`Camera::fly_to`].
+    pub fn fly_to(&mut self, lat_deg: f32, lon_deg: f32, distance: f32, duration: f32) {
+        self.camera.fly_to(lat_deg, lon_deg, distance, duration);
+    }
+
+    /// Steps any camera flight; returns whether the view changed.
+    pub fn advance_camera(&mut self, delta: f32) -> bool {
+        self.camera.advance(delta)
+    }
+
     pub fn update_layer(&mut self, kind: GlobeLayer, width: u32, height: u32, data: &[u8]) {
         let label = match kind {
             GlobeLayer::Base => "base map layer",
@@ -1118,6 +1228,14 @@ impl Renderer {
         queue.write_buffer(&layer.uniform_buffer, 0, bytemuck::bytes_of(&uniform));
     }
 
+    /// Rebuilds the globe vertex and index buffers when the subdivision
+    /// changes.
+    pub fn set_globe_segments(&mut self, segments: u32) {
+        if clamp_globe_segments(segments) != self.globe_mesh.segments {
+            self.globe_mesh = GlobeMesh::new(&self.device, segments);
+        }
+    }
+
     pub fn update_overlay(&self, base: f32, map: f32, sea: f32, weather: f32) {
         let uniform = OverlayUniform {
             base_opacity: base,
@@ -1285,6 +1403,7 @@ fn create_viewport_target(
     format: wgpu::TextureFormat,
     width: u32,
     height: u32,
+    sample_count: u32,
 ) -> (
     wgpu::Texture,
     wgpu::TextureView,
@@ -1302,7 +1421,9 @@ fn create_viewport_target(
         sample_count: 1,
         dimension: wgpu::TextureDimension::D2,
         format,
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC,
         view_formats: &[],
     });
     let color_view = color.create_view(&wgpu::TextureViewDescriptor::default());
@@ -1315,7 +1436,7 @@ fn create_viewport_target(
             depth_or_array_layers: 1,
         },
         mip_level_count: 1,
-        sample_count: 1,
+        sample_count,
         dimension: wgpu::TextureDimension::D2,
         format: wgpu::TextureFormat::Depth32Float,
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -1326,6 +1447,33 @@ fn create_viewport_target(
     (color, color_view, depth, depth_view)
 }
 
+fn create_msaa_view(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+    sample_count: u32,
+) -> Option<wgpu::TextureView> {
+    if sample_count <= 1 {
+        return None;
+    }
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("viewport msaa color"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+}
+
 fn build_tile_mesh(segments: u32) -> (Vec<TileVertex>, Vec<u16>) {
     let segments = segments.max(2);
     let stride = segments + 1;
@@ -0,0 +1,64 @@
+/// Sample count used when `C2_NATIVE_MSAA` is unset.
+pub const DEFAULT_MSAA_SAMPLES: u32 = 4;
+/// Counts every wgpu device accepts without adapter-specific format features.
+const BASELINE_SAMPLE_COUNTS: [u32; 2] = [1, 4];
+
+/// `C2_NATIVE_MSAA` (1, 2, 4 or 8); the renderer clamps it to what the
+/// adapter supports.
+pub fn msaa_samples_from_env() -> u32 {
+    std::env::var("C2_NATIVE_MSAA")
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .unwrap_or(DEFAULT_MSAA_SAMPLES)
+}
+
+/// Sample counts usable for both the viewport color format and the depth
+/// target on this adapter. Counts other than 1 and 4 need
+/// `TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`, which the device then enables.
+pub fn supported_msaa_samples(
+    adapter: &wgpu::Adapter,
+    color_format: wgpu::TextureFormat,
+    depth_format: wgpu::TextureFormat,
+) -> Vec<u32> {
+    let adapter_specific = adapter
+        .features()
+        .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES);
+    let color = adapter.get_texture_format_features(color_format).flags;
+    let depth = adapter.get_texture_format_features(depth_format).flags;
+    color
+        .supported_sample_counts()
+        .into_iter()
+        .filter(|count| depth.sample_count_supported(*count))
+        .filter(|count| adapter_specific || BASELINE_SAMPLE_COUNTS.contains(count))
+        .collect()
+}
+
+/// Largest supported count not above `requested`; 1 (no MSAA) is always
+/// available.
+pub fn clamp_msaa_samples(requested: u32, supported: &[u32]) -> u32 {
+    supported
+        .iter()
+        .copied()
+        .filter(|count| *count <= requested.max(1))
+        .max()
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_sample_counts_clamp_to_adapter_support() {
+        let baseline = [1, 4];
+        assert_eq!(clamp_msaa_samples(8, &baseline), 4);
+        assert_eq!(clamp_msaa_samples(2, &baseline), 1);
+        assert_eq!(clamp_msaa_samples(4, &baseline), 4);
+        assert_eq!(clamp_msaa_samples(0, &baseline), 1);
+
+        let wide = [1, 2, 4, 8, 16];
+        assert_eq!(clamp_msaa_samples(8, &wide), 8);
+        assert_eq!(clamp_msaa_samples(3, &wide), 2);
+        assert_eq!(clamp_msaa_samples(8, &[]), 1);
+    }
+}
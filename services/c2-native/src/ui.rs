@@ -2,7 +2,7 @@ use egui_dock::{DockArea, DockState, NodeIndex, Style, TabViewer};
 use glam::{Vec2, Vec3, Vec4};
 
 use crate::ecs::{RenderInstance, WorldState};
-use crate::renderer::Renderer;
+use crate::renderer::{globe_segments_from_env, Renderer};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DockTab {
@@ -42,6 +42,7 @@ pub struct OperationsState {
     pub tile_provider: String,
     pub weather_field: String,
     pub sea_field: String,
+    pub globe_segments: u32,
 }
 
 impl Default for OperationsState {
@@ -57,6 +58,7 @@ impl Default for OperationsState {
             tile_provider: "osm".to_string(),
             weather_field: "IMERG_Precipitation_Rate".to_string(),
             sea_field: "OSCAR_Sea_Surface_Currents_Zonal".to_string(),
+            globe_segments: globe_segments_from_env(),
         }
     }
 }
@@ -111,6 +113,13 @@ const WEATHER_FIELDS: &[&str] = &[
     "MERRA2_2m_Air_Temperature_Monthly",
 ];
 
+const GLOBE_DETAIL_LEVELS: &[(&str, u32)] = &[
+    ("Low", 48),
+    ("Medium", 96),
+    ("High", 128),
+    ("Ultra", 256),
+];
+
 const SEA_FIELDS: &[&str] = &[
     "OSCAR_Sea_Surface_Currents_Zonal",
     "OSCAR_Sea_Surface_Currents_Meridional",
@@ -515,6 +524,21 @@ fn operations_panel(ui: &mut egui::Ui, operations: &mut OperationsState) {
             }
         });
     ui.add_space(8.0);
+    ui.separator();
+    ui.label("Globe detail");
+    let detail_label = GLOBE_DETAIL_LEVELS
+        .iter()
+        .find(|(_, segments)| *segments == operations.globe_segments)
+        .map(|(name, _)| (*name).to_string())
+        .unwrap_or_else(|| format!("Custom ({})", operations.globe_segments));
+    egui::ComboBox::from_id_salt("globe-detail")
+        .selected_text(detail_label)
+        .show_ui(ui, |ui| {
+            for (name, segments) in GLOBE_DETAIL_LEVELS {
+                ui.selectable_value(&mut operations.globe_segments, *segments, *name);
+            }
+        });
+    ui.add_space(8.0);
     ui.label("Status: connected to ECS runtime.");
 }
 
@@ -533,6 +557,7 @@ fn inspector_panel(ui: &mut egui::Ui, renderer: &Renderer, diagnostics: &Diagnos
         renderer.size().0,
         renderer.size().1
     ));
+    ui.label(format!("MSAA: x{}", renderer.msaa_samples()));
     let perf = diagnostics.perf;
     ui.add_space(4.0);
     ui.label(format!(
@@ -1,5 +1,5 @@
 use c2_config::ServiceConfig;
-use c2_observability::{init, log_startup, ObservabilityConfig};
+use c2_observability::{init, log_startup, ObservabilityConfig, TelemetryConfig};
 use c2_operator::{
     C2Cluster, C2ClusterSpec, C2ClusterStatus, DatabaseSpec, EnvVarSourceSpec, EnvVarSpec,
     ResourceRequirementsSpec, RuntimeSpec, ServiceSpec, ServiceStatus,
@@ -83,7 +83,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         service_name: config.service_name.clone(),
         environment: config.environment.to_string(),
         log_level: config.log_level.clone(),
+        log_format: config.log_format.clone(),
         metrics_addr: config.metrics_addr.clone(),
+        telemetry: TelemetryConfig {
+            enabled: config.telemetry,
+            interval: config.telemetry_interval(),
+            endpoint: config.telemetry_endpoint.clone(),
+        },
     };
     let handle = init(&obs_config);
     log_startup(&handle, &obs_config.environment);
@@ -343,6 +349,7 @@ fn build_env(
     if matches!(component, Component::Gateway) {
         let api_name = resource_name(&cluster.name_any(), Component::Api);
         let web_name = resource_name(&cluster.name_any(), Component::Web);
+        let mcp_name = resource_name(&cluster.name_any(), Component::Mcp);
         insert_env(
             &mut vars,
             EnvVar {
@@ -375,6 +382,22 @@ fn build_env(
                 value_from: None,
             },
         );
+        insert_env(
+            &mut vars,
+            EnvVar {
+                name: "C2_GATEWAY_MCP_HOST".to_string(),
+                value: Some(mcp_name),
+                value_from: None,
+            },
+        );
+        insert_env(
+            &mut vars,
+            EnvVar {
+                name: "C2_GATEWAY_MCP_PORT".to_string(),
+                value: Some(service_port(Component::Mcp, component_spec(&cluster.spec, Component::Mcp)).to_string()),
+                value_from: None,
+            },
+        );
     }
 
     for env in &cluster.spec.global_env {
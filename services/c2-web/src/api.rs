@@ -1,28 +1,132 @@
 use c2_core::{
-    now_epoch_millis, Asset, AssetStatus, Incident, IncidentStatus, Mission, MissionStatus, Unit,
+    now_epoch_millis, Asset, AssetStatus, Incident, IncidentStatus, Mission, MissionStatus,
+    SecurityClassification, TenantId, Unit, sidc_for,
 };
+use actix_web::{HttpResponse, ResponseError};
 use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{Client, Url};
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 
+use crate::resilience::{CircuitBreaker, RetryPolicy};
+
+/// Why a call to `c2-api` failed. Responses with an error status keep the
+/// status and the `error` (and `code`, when present) from the API's body, so
+/// handlers can tell a bad UI identity from an API that is down.
 #[derive(Debug)]
-pub struct ApiError {
+pub enum ApiError {
+    /// `C2_UI_*` auth is not configured, so no data request is sent.
+    MissingAuth,
+    /// Recent calls kept failing, so the circuit breaker is not letting
+    /// requests through until its cooldown ends.
+    CircuitOpen,
+    /// `C2_API_BASE_URL` or a path joined onto it is not a valid URL.
+    InvalidUrl(String),
+    /// The request was not sent or got no response.
+    Request(String),
+    /// The response body was not what the endpoint returns.
+    Decode(String),
+    Unauthorized(ApiFailure),
+    Forbidden(ApiFailure),
+    NotFound(ApiFailure),
+    /// 502, 503 or 504: the API, or the storage behind it, is down or slow.
+    Unavailable(ApiFailure),
+    /// Any other error status.
+    Status(ApiFailure),
+}
+
+/// An error response from `c2-api`.
+#[derive(Debug, Clone)]
+pub struct ApiFailure {
+    /// What was requested, e.g. `missions`.
+    pub request: &'static str,
+    pub status: StatusCode,
+    pub code: Option<String>,
     pub message: String,
 }
 
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+    #[serde(default)]
+    code: Option<String>,
+}
+
 impl ApiError {
-    pub fn new(message: impl Into<String>) -> Self {
-        Self {
-            message: message.into(),
+    /// `response` as-is when its status is a success, otherwise the error
+    /// for its status and body.
+    async fn check(
+        response: reqwest::Response,
+        request: &'static str,
+    ) -> Result<reqwest::Response, Self> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        let (code, message) = match serde_json::from_str::<ErrorBody>(&body) {
+            Ok(body) => (body.code, body.error),
+            Err(_) if body.trim().is_empty() => {
+                (None, status.canonical_reason().unwrap_or_default().to_string())
+            }
+            Err(_) => (None, body),
+        };
+        let failure = ApiFailure {
+            request,
+            status,
+            code,
+            message,
+        };
+        Err(match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(failure),
+            StatusCode::FORBIDDEN => Self::Forbidden(failure),
+            StatusCode::NOT_FOUND => Self::NotFound(failure),
+            StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => Self::Unavailable(failure),
+            _ => Self::Status(failure),
+        })
+    }
+
+    /// Whether `c2-api` could not be reached or could not serve the request;
+    /// only these are retried and counted by the circuit breaker.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, Self::Request(_) | Self::Unavailable(_) | Self::CircuitOpen)
+    }
+
+    pub fn failure(&self) -> Option<&ApiFailure> {
+        match self {
+            Self::Unauthorized(failure)
+            | Self::Forbidden(failure)
+            | Self::NotFound(failure)
+            | Self::Unavailable(failure)
+            | Self::Status(failure) => Some(failure),
+            _ => None,
         }
     }
 }
 
 impl std::fmt::Display for ApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
+        match self {
+            Self::MissingAuth => write!(f, "missing C2_UI_* auth configuration"),
+            Self::CircuitOpen => write!(f, "c2-api is unavailable; retrying after a cooldown"),
+            Self::InvalidUrl(message) => write!(f, "invalid API URL: {message}"),
+            Self::Request(message) => write!(f, "API request failed: {message}"),
+            Self::Decode(message) => write!(f, "unexpected API response: {message}"),
+            Self::Unauthorized(failure)
+            | Self::Forbidden(failure)
+            | Self::NotFound(failure)
+            | Self::Unavailable(failure)
+            | Self::Status(failure) => write!(
+                f,
+                "{} request failed with {}: {}",
+                failure.request, failure.status, failure.message
+            ),
+        }
     }
 }
 
@@ -30,13 +134,49 @@ impl std::error::Error for ApiError {}
 
 impl From<reqwest::Error> for ApiError {
     fn from(error: reqwest::Error) -> Self {
-        Self::new(error.to_string())
+        if error.is_decode() {
+            Self::Decode(error.to_string())
+        } else {
+            Self::Request(error.to_string())
+        }
+    }
+}
+
+impl From<url::ParseError> for ApiError {
+    fn from(error: url::ParseError) -> Self {
+        Self::InvalidUrl(error.to_string())
+    }
+}
+
+/// Lets handlers return an `ApiError` with `?`: the UI identity's 401 and
+/// 403 and the API's 404 pass through, an API that is down or unconfigured
+/// is a 503, and anything else from upstream a 502.
+impl ResponseError for ApiError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        use actix_web::http::StatusCode as Web;
+        match self {
+            Self::Unauthorized(_) => Web::UNAUTHORIZED,
+            Self::Forbidden(_) => Web::FORBIDDEN,
+            Self::NotFound(_) => Web::NOT_FOUND,
+            Self::MissingAuth | Self::CircuitOpen | Self::Unavailable(_) => {
+                Web::SERVICE_UNAVAILABLE
+            }
+            Self::InvalidUrl(_) => Web::INTERNAL_SERVER_ERROR,
+            Self::Request(_) | Self::Decode(_) | Self::Status(_) => Web::BAD_GATEWAY,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let code = self.failure().and_then(|failure| failure.code.clone());
+        HttpResponse::build(self.status_code())
+            .json(serde_json::json!({ "error": self.to_string(), "code": code }))
     }
 }
 
 #[derive(Clone)]
 struct ApiAuth {
     tenant_id: String,
+    clearance: SecurityClassification,
     headers: HeaderMap,
 }
 
@@ -63,8 +203,14 @@ impl ApiAuth {
         let clearance_value = HeaderValue::from_str(&clearance)
             .unwrap_or_else(|_| HeaderValue::from_static("unclassified"));
         headers.insert("x-c2-clearance", clearance_value);
+        let clearance =
+            SecurityClassification::from_str(&clearance).unwrap_or(SecurityClassification::Unclassified);
 
-        Some(Self { tenant_id, headers })
+        Some(Self {
+            tenant_id,
+            clearance,
+            headers,
+        })
     }
 }
 
@@ -75,6 +221,11 @@ pub struct ApiClient {
     auth: Option<ApiAuth>,
     poll_interval: Duration,
     list_limit: usize,
+    page_size: usize,
+    retry: RetryPolicy,
+    breaker: Arc<CircuitBreaker>,
+    /// The last snapshot read in full, served while `c2-api` is unavailable.
+    last_entities: Arc<Mutex<Option<UiEntitySnapshot>>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -158,11 +309,90 @@ impl UiSnapshot {
     }
 }
 
+/// Asset as sent to the browser, with its MIL-STD-2525C symbol code.
+#[derive(Debug, Serialize, Clone)]
+pub struct UiAsset {
+    #[serde(flatten)]
+    pub asset: Asset,
+    pub sidc: String,
+}
+
+impl From<Asset> for UiAsset {
+    fn from(asset: Asset) -> Self {
+        let sidc = sidc_for(asset.kind, asset.status);
+        Self { asset, sidc }
+    }
+}
+
+/// Filters and page for a web entity table, read from the request query string
+/// and forwarded to the API list route. Values pass through unchecked; the API
+/// rejects unknown ones.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TableQuery {
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub classification: Option<String>,
+    pub q: Option<String>,
+    /// Row order, for tables whose route takes one (`priority` for incidents).
+    pub sort: Option<String>,
+    /// 1-based.
+    pub page: Option<usize>,
+}
+
+impl TableQuery {
+    pub fn page(&self) -> usize {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    /// Filters with a non-blank value, as API query parameters.
+    pub fn filters(&self) -> Vec<(&'static str, String)> {
+        [
+            ("status", &self.status),
+            ("priority", &self.priority),
+            ("classification", &self.classification),
+            ("q", &self.q),
+            ("sort", &self.sort),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let value = value.as_deref()?.trim();
+            (!value.is_empty()).then(|| (name, value.to_string()))
+        })
+        .collect()
+    }
+
+    /// Query string for the same filters on another page.
+    pub fn for_page(&self, page: usize) -> String {
+        let mut pairs = self.filters();
+        pairs.push(("page", page.to_string()));
+        serde_urlencoded::to_string(pairs).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TablePage<T> {
+    pub rows: Vec<T>,
+    pub page: usize,
+    pub page_size: usize,
+    pub has_next: bool,
+}
+
+impl<T> TablePage<T> {
+    pub fn empty(query: &TableQuery) -> Self {
+        Self {
+            rows: Vec::new(),
+            page: query.page(),
+            page_size: 0,
+            has_next: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct UiEntitySnapshot {
     pub timestamp_ms: u64,
     pub missions: Vec<Mission>,
-    pub assets: Vec<Asset>,
+    pub assets: Vec<UiAsset>,
     pub incidents: Vec<Incident>,
     pub units: Vec<Unit>,
 }
@@ -183,15 +413,20 @@ impl ApiClient {
     pub fn from_env() -> Result<Self, ApiError> {
         let base = env::var("C2_API_BASE_URL").unwrap_or_else(|_| "http://c2-api:8080".to_string());
         let base = format!("{}/", base.trim_end_matches('/'));
-        let base_url = Url::parse(&base).map_err(|err| ApiError::new(err.to_string()))?;
+        let base_url = Url::parse(&base)?;
         let poll_interval_ms = env_var_u64("C2_UI_POLL_INTERVAL_MS", 2000);
         let list_limit = env_var_usize("C2_UI_LIST_LIMIT", 200);
+        let page_size = env_var_usize("C2_UI_PAGE_SIZE", 25);
         Ok(Self {
             client: Client::new(),
             base_url,
             auth: ApiAuth::from_env(),
             poll_interval: Duration::from_millis(poll_interval_ms),
             list_limit: list_limit.max(10),
+            page_size: page_size.max(1),
+            retry: RetryPolicy::from_env(),
+            breaker: Arc::new(CircuitBreaker::from_env()),
+            last_entities: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -203,25 +438,46 @@ impl ApiClient {
         self.auth.is_some()
     }
 
+    /// Tenant of the configured UI identity.
+    pub fn tenant_id(&self) -> Option<TenantId> {
+        self.auth.as_ref().and_then(|auth| auth.tenant_id.parse().ok())
+    }
+
+    /// Clearance of the configured UI identity; records above it are not
+    /// shown even when the API returns them.
+    pub fn clearance(&self) -> SecurityClassification {
+        self.auth
+            .as_ref()
+            .map(|auth| auth.clearance)
+            .unwrap_or(SecurityClassification::Unclassified)
+    }
+
     pub async fn status(&self) -> Result<StatusResponse, ApiError> {
-        let url = self
-            .base_url
-            .join("v1/status")
-            .map_err(|err| ApiError::new(err.to_string()))?;
+        let url = self.base_url.join("v1/status")?;
         let mut request = self.client.get(url);
         if let Some(auth) = &self.auth {
             request = request.headers(auth.headers.clone());
         }
-        let response = request.send().await?;
-        if !response.status().is_success() {
-            return Err(ApiError::new(format!(
-                "status request failed with {}",
-                response.status()
-            )));
-        }
+        let response = self.send(request, "status").await?;
         Ok(response.json::<StatusResponse>().await?)
     }
 
+    pub async fn assets(&self) -> Result<Vec<Asset>, ApiError> {
+        let auth = self
+            .auth
+            .as_ref()
+            .ok_or(ApiError::MissingAuth)?;
+        self.list_assets(auth, self.list_limit, 0, &[]).await
+    }
+
+    pub async fn incidents(&self) -> Result<Vec<Incident>, ApiError> {
+        let auth = self
+            .auth
+            .as_ref()
+            .ok_or(ApiError::MissingAuth)?;
+        self.list_incidents(auth, self.list_limit, 0, &[]).await
+    }
+
     pub async fn snapshot(&self) -> Result<UiSnapshot, ApiError> {
         let entities = self.entities().await?;
         Ok(UiSnapshot::from_entities(&entities))
@@ -231,31 +487,138 @@ impl ApiClient {
         let auth = self
             .auth
             .as_ref()
-            .ok_or_else(|| ApiError::new("missing C2_UI_* auth configuration"))?;
-        let missions = self
-            .list_missions(auth, self.list_limit, 0)
-            .await
-            .unwrap_or_default();
-        let assets = self
-            .list_assets(auth, self.list_limit, 0)
-            .await
-            .unwrap_or_default();
-        let incidents = self
-            .list_incidents(auth, self.list_limit, 0)
-            .await
-            .unwrap_or_default();
-        let units = self
-            .list_units(auth, self.list_limit, 0)
-            .await
-            .unwrap_or_default();
-
-        Ok(UiEntitySnapshot {
+            .ok_or(ApiError::MissingAuth)?;
+        let missions = self.list_missions(auth, self.list_limit, 0, &[]).await;
+        let assets = self.list_assets(auth, self.list_limit, 0, &[]).await;
+        let incidents = self.list_incidents(auth, self.list_limit, 0, &[]).await;
+        let units = self.list_units(auth, self.list_limit, 0).await;
+
+        let unavailable = [
+            missions.as_ref().err(),
+            assets.as_ref().err(),
+            incidents.as_ref().err(),
+            units.as_ref().err(),
+        ];
+        let complete = unavailable.iter().all(Option::is_none);
+        // While the API is down, the last full snapshot (with its own
+        // timestamp) beats an empty map.
+        if unavailable.iter().flatten().any(|err| err.is_unavailable())
+            && let Some(last) = self.last_entities().clone()
+        {
+            return Ok(last);
+        }
+        let snapshot = UiEntitySnapshot {
             timestamp_ms: now_epoch_millis(),
-            missions,
-            assets,
-            incidents,
-            units,
-        })
+            missions: missions.unwrap_or_default(),
+            assets: assets
+                .unwrap_or_default()
+                .into_iter()
+                .map(UiAsset::from)
+                .collect(),
+            incidents: incidents.unwrap_or_default(),
+            units: units.unwrap_or_default(),
+        };
+        if complete {
+            *self.last_entities() = Some(snapshot.clone());
+        }
+        Ok(snapshot)
+    }
+
+    pub async fn missions_page(&self, query: &TableQuery) -> Result<TablePage<Mission>, ApiError> {
+        let auth = self
+            .auth
+            .as_ref()
+            .ok_or(ApiError::MissingAuth)?;
+        let (limit, offset) = self.page_window(query);
+        let rows = self.list_missions(auth, limit, offset, &query.filters()).await?;
+        Ok(self.to_page(query, rows))
+    }
+
+    pub async fn assets_page(&self, query: &TableQuery) -> Result<TablePage<UiAsset>, ApiError> {
+        let auth = self
+            .auth
+            .as_ref()
+            .ok_or(ApiError::MissingAuth)?;
+        let (limit, offset) = self.page_window(query);
+        let rows = self
+            .list_assets(auth, limit, offset, &query.filters())
+            .await?
+            .into_iter()
+            .map(UiAsset::from)
+            .collect();
+        Ok(self.to_page(query, rows))
+    }
+
+    pub async fn incidents_page(
+        &self,
+        query: &TableQuery,
+    ) -> Result<TablePage<Incident>, ApiError> {
+        let auth = self
+            .auth
+            .as_ref()
+            .ok_or(ApiError::MissingAuth)?;
+        let (limit, offset) = self.page_window(query);
+        let rows = self.list_incidents(auth, limit, offset, &query.filters()).await?;
+        Ok(self.to_page(query, rows))
+    }
+
+    /// Fetches one row past the page so the caller knows whether a next page
+    /// exists.
+    fn page_window(&self, query: &TableQuery) -> (usize, usize) {
+        (self.page_size + 1, (query.page() - 1) * self.page_size)
+    }
+
+    fn to_page<T>(&self, query: &TableQuery, mut rows: Vec<T>) -> TablePage<T> {
+        let has_next = rows.len() > self.page_size;
+        rows.truncate(self.page_size);
+        TablePage {
+            rows,
+            page: query.page(),
+            page_size: self.page_size,
+            has_next,
+        }
+    }
+
+    fn last_entities(&self) -> MutexGuard<'_, Option<UiEntitySnapshot>> {
+        self.last_entities.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Sends a GET through the circuit breaker, retrying with backoff while
+    /// `c2-api` is unreachable or unavailable.
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        name: &'static str,
+    ) -> Result<Response, ApiError> {
+        if !self.breaker.allow() {
+            return Err(ApiError::CircuitOpen);
+        }
+        let mut attempt = 0;
+        loop {
+            let result = match request.try_clone() {
+                Some(request) => match request.send().await {
+                    Ok(response) => ApiError::check(response, name).await,
+                    Err(err) => Err(err.into()),
+                },
+                None => Err(ApiError::Request(format!("{name} request cannot be sent"))),
+            };
+            match result {
+                Err(err) if err.is_unavailable() && attempt < self.retry.retries => {
+                    actix_web::rt::time::sleep(self.retry.delay(attempt)).await;
+                    attempt += 1;
+                }
+                Err(err) if err.is_unavailable() => {
+                    if self.breaker.record_failure() {
+                        tracing::warn!(error = %err, "c2-api circuit breaker opened");
+                    }
+                    return Err(err);
+                }
+                result => {
+                    self.breaker.record_success();
+                    return result;
+                }
+            }
+        }
     }
 
     async fn list_missions(
@@ -263,12 +626,10 @@ impl ApiClient {
         auth: &ApiAuth,
         limit: usize,
         offset: usize,
+        filters: &[(&'static str, String)],
     ) -> Result<Vec<Mission>, ApiError> {
-        let url = self
-            .base_url
-            .join("v1/missions")
-            .map_err(|err| ApiError::new(err.to_string()))?;
-        let response = self
+        let url = self.base_url.join("v1/missions")?;
+        let request = self
             .client
             .get(url)
             .headers(auth.headers.clone())
@@ -277,14 +638,8 @@ impl ApiClient {
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
             ])
-            .send()
-            .await?;
-        if !response.status().is_success() {
-            return Err(ApiError::new(format!(
-                "missions request failed with {}",
-                response.status()
-            )));
-        }
+            .query(filters);
+        let response = self.send(request, "missions").await?;
         Ok(response.json::<Vec<Mission>>().await?)
     }
 
@@ -293,12 +648,10 @@ impl ApiClient {
         auth: &ApiAuth,
         limit: usize,
         offset: usize,
+        filters: &[(&'static str, String)],
     ) -> Result<Vec<Asset>, ApiError> {
-        let url = self
-            .base_url
-            .join("v1/assets")
-            .map_err(|err| ApiError::new(err.to_string()))?;
-        let response = self
+        let url = self.base_url.join("v1/assets")?;
+        let request = self
             .client
             .get(url)
             .headers(auth.headers.clone())
@@ -307,14 +660,8 @@ impl ApiClient {
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
             ])
-            .send()
-            .await?;
-        if !response.status().is_success() {
-            return Err(ApiError::new(format!(
-                "assets request failed with {}",
-                response.status()
-            )));
-        }
+            .query(filters);
+        let response = self.send(request, "assets").await?;
         Ok(response.json::<Vec<Asset>>().await?)
     }
 
@@ -323,12 +670,10 @@ impl ApiClient {
         auth: &ApiAuth,
         limit: usize,
         offset: usize,
+        filters: &[(&'static str, String)],
     ) -> Result<Vec<Incident>, ApiError> {
-        let url = self
-            .base_url
-            .join("v1/incidents")
-            .map_err(|err| ApiError::new(err.to_string()))?;
-        let response = self
+        let url = self.base_url.join("v1/incidents")?;
+        let request = self
             .client
             .get(url)
             .headers(auth.headers.clone())
@@ -337,14 +682,8 @@ impl ApiClient {
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
             ])
-            .send()
-            .await?;
-        if !response.status().is_success() {
-            return Err(ApiError::new(format!(
-                "incidents request failed with {}",
-                response.status()
-            )));
-        }
+            .query(filters);
+        let response = self.send(request, "incidents").await?;
         Ok(response.json::<Vec<Incident>>().await?)
     }
 
@@ -354,11 +693,8 @@ impl ApiClient {
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Unit>, ApiError> {
-        let url = self
-            .base_url
-            .join("v1/units")
-            .map_err(|err| ApiError::new(err.to_string()))?;
-        let response = self
+        let url = self.base_url.join("v1/units")?;
+        let request = self
             .client
             .get(url)
             .headers(auth.headers.clone())
@@ -366,15 +702,8 @@ impl ApiClient {
                 ("tenant_id", auth.tenant_id.as_str()),
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
-            ])
-            .send()
-            .await?;
-        if !response.status().is_success() {
-            return Err(ApiError::new(format!(
-                "units request failed with {}",
-                response.status()
-            )));
-        }
+            ]);
+        let response = self.send(request, "units").await?;
         Ok(response.json::<Vec<Unit>>().await?)
     }
 }
@@ -400,7 +729,7 @@ fn summarize_missions(missions: &[Mission]) -> MissionSummary {
     summary
 }
 
-fn summarize_assets(assets: &[Asset]) -> AssetSummary {
+fn summarize_assets(assets: &[UiAsset]) -> AssetSummary {
     let mut summary = AssetSummary {
         total: assets.len(),
         ready: 0,
@@ -408,7 +737,7 @@ fn summarize_assets(assets: &[Asset]) -> AssetSummary {
         maintenance: 0,
         lost: 0,
     };
-    for asset in assets {
+    for UiAsset { asset, .. } in assets {
         match asset.status {
             AssetStatus::Available | AssetStatus::Assigned => summary.ready += 1,
             AssetStatus::Degraded => summary.degraded += 1,
@@ -455,3 +784,88 @@ fn env_var_usize(name: &str, default: usize) -> usize {
         .and_then(|value| value.parse::<usize>().ok())
         .unwrap_or(default)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpRequest, HttpServer};
+
+    fn client(port: u16, auth: Option<ApiAuth>) -> ApiClient {
+        ApiClient {
+            client: Client::new(),
+            base_url: Url::parse(&format!("http://127.0.0.1:{port}/")).unwrap(),
+            auth,
+            poll_interval: Duration::from_secs(1),
+            list_limit: 10,
+            page_size: 10,
+            retry: RetryPolicy {
+                retries: 0,
+                backoff: Duration::ZERO,
+            },
+            breaker: Arc::new(CircuitBreaker::new(0, Duration::ZERO)),
+            last_entities: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    #[actix_web::test]
+    async fn missing_record_from_the_api_is_not_found() {
+        let upstream = HttpServer::new(|| {
+            App::new().default_service(actix_web::web::to(|| async {
+                HttpResponse::NotFound().json(serde_json::json!({ "error": "mission not found" }))
+            }))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let port = upstream.addrs()[0].port();
+        actix_web::rt::spawn(upstream.run());
+
+        let err = client(port, None).status().await.unwrap_err();
+        let ApiError::NotFound(failure) = &err else {
+            panic!("expected NotFound, got {err:?}");
+        };
+        assert_eq!(failure.status, StatusCode::NOT_FOUND);
+        assert_eq!(failure.message, "mission not found");
+        assert_eq!(err.status_code(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn table_filters_are_forwarded_to_the_api_list_route() {
+        let query = actix_web::web::Query::<TableQuery>::from_query("status=active&q=+&page=2")
+            .unwrap()
+            .into_inner();
+        assert_eq!(query.filters(), [("status", "active".to_string())]);
+        assert_eq!(query.for_page(3), "status=active&page=3");
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&seen);
+        let upstream = HttpServer::new(move || {
+            let recorded = Arc::clone(&recorded);
+            App::new().default_service(actix_web::web::to(move |req: HttpRequest| {
+                let recorded = Arc::clone(&recorded);
+                async move {
+                    let uri = req.uri().to_string();
+                    recorded.lock().unwrap().push(uri);
+                    HttpResponse::Ok().json(Vec::<Mission>::new())
+                }
+            }))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let port = upstream.addrs()[0].port();
+        actix_web::rt::spawn(upstream.run());
+
+        let auth = ApiAuth {
+            tenant_id: "tenant-1".to_string(),
+            clearance: SecurityClassification::Unclassified,
+            headers: HeaderMap::new(),
+        };
+        let page = client(port, Some(auth)).missions_page(&query).await.unwrap();
+        assert!(page.rows.is_empty());
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["/v1/missions?tenant_id=tenant-1&limit=11&offset=10&status=active"]
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Per-tenant title, logo and accent color for the console, read from the
+//! JSON file named by `C2_WEB_BRANDING_FILE`:
+//!
+//! ```json
+//! {
+//!   "default": { "title": "C2 Walaris" },
+//!   "tenants": {
+//!     "<tenant uuid>": { "title": "Harbor Ops", "primary_color": "#0ea5e9" }
+//!   }
+//! }
+//! ```
+//!
+//! A tenant's entry only overrides the fields it sets.
+
+use c2_core::TenantId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+
+const DEFAULT_TITLE: &str = "C2 Walaris";
+const DEFAULT_PRIMARY_COLOR: &str = "#f97316";
+
+/// What the templates see as `branding`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Branding {
+    pub title: String,
+    pub logo_url: Option<String>,
+    pub primary_color: String,
+}
+
+impl Default for Branding {
+    fn default() -> Self {
+        Self {
+            title: DEFAULT_TITLE.to_string(),
+            logo_url: None,
+            primary_color: DEFAULT_PRIMARY_COLOR.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BrandingOverride {
+    pub title: Option<String>,
+    pub logo_url: Option<String>,
+    pub primary_color: Option<String>,
+}
+
+impl BrandingOverride {
+    fn apply(&self, branding: &mut Branding) {
+        if let Some(title) = &self.title {
+            branding.title = title.clone();
+        }
+        if let Some(logo_url) = &self.logo_url {
+            branding.logo_url = Some(logo_url.clone());
+        }
+        if let Some(primary_color) = &self.primary_color {
+            branding.primary_color = primary_color.clone();
+        }
+    }
+
+    /// The color lands in a stylesheet and the logo in an `img`, so only
+    /// hex colors and http(s) or site-relative URLs are accepted.
+    fn validate(&self, name: &str) -> Result<(), String> {
+        if let Some(color) = &self.primary_color
+            && !is_hex_color(color)
+        {
+            return Err(format!("{name}: primary_color {color:?} is not a #rgb or #rrggbb color"));
+        }
+        if let Some(url) = &self.logo_url
+            && !(url.starts_with("https://") || url.starts_with("http://") || url.starts_with('/'))
+        {
+            return Err(format!("{name}: logo_url {url:?} must be http(s) or start with /"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BrandingConfig {
+    #[serde(default)]
+    pub default: BrandingOverride,
+    #[serde(default)]
+    pub tenants: HashMap<TenantId, BrandingOverride>,
+}
+
+impl BrandingConfig {
+    /// Built-in branding when `C2_WEB_BRANDING_FILE` is unset.
+    pub fn from_env() -> Result<Self, String> {
+        let Some(path) = env::var("C2_WEB_BRANDING_FILE")
+            .ok()
+            .filter(|path| !path.trim().is_empty())
+        else {
+            return Ok(Self::default());
+        };
+        let raw = fs::read_to_string(&path)
+            .map_err(|err| format!("C2_WEB_BRANDING_FILE {path}: {err}"))?;
+        Self::parse(&raw).map_err(|err| format!("C2_WEB_BRANDING_FILE {path}: {err}"))
+    }
+
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(raw).map_err(|err| err.to_string())?;
+        config.default.validate("default")?;
+        for (tenant_id, branding) in &config.tenants {
+            branding.validate(&format!("tenant {tenant_id}"))?;
+        }
+        Ok(config)
+    }
+
+    /// The tenant's branding over the configured default.
+    pub fn for_tenant(&self, tenant_id: Option<TenantId>) -> Branding {
+        let mut branding = Branding::default();
+        self.default.apply(&mut branding);
+        if let Some(tenant) = tenant_id.and_then(|tenant_id| self.tenants.get(&tenant_id)) {
+            tenant.apply(&mut branding);
+        }
+        branding
+    }
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6) && hex.chars().all(|ch| ch.is_ascii_hexdigit())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::UiSnapshot;
+    use crate::render::{build_context, UiTemplateData};
+    use tera::Tera;
+
+    fn render_layout(tera: &Tera, branding: Branding) -> String {
+        let data = UiTemplateData {
+            service_name: "c2-web".to_string(),
+            environment: "dev".to_string(),
+            branding,
+            status: None,
+            snapshot: UiSnapshot::empty(),
+            tile_config_json: None,
+            weather_config_json: None,
+            sea_config_json: None,
+            flight_config_json: None,
+            satellite_config_json: None,
+            ship_config_json: None,
+        };
+        let context = build_context(&data);
+        let branding = serde_json::to_value(&data.branding).unwrap();
+        assert_eq!(context.get("branding"), Some(&branding));
+        tera.render("layout.html", &context).unwrap()
+    }
+
+    #[test]
+    fn tenant_branding_reaches_the_template_and_others_get_the_default() {
+        let branded = TenantId::new();
+        let config = serde_json::json!({
+            "tenants": {
+                branded.to_string(): { "title": "Harbor Ops", "primary_color": "#0ea5e9" }
+            }
+        });
+        let config = BrandingConfig::parse(&config.to_string()).unwrap();
+        let tera = Tera::new(concat!(env!("CARGO_MANIFEST_DIR"), "/templates/**/*")).unwrap();
+
+        let page = render_layout(&tera, config.for_tenant(Some(branded)));
+        assert!(page.contains("<title>Harbor Ops</title>"));
+        assert!(page.contains("--color-c2-ember: #0ea5e9;"));
+
+        let page = render_layout(&tera, config.for_tenant(Some(TenantId::new())));
+        assert!(page.contains("<title>C2 Walaris</title>"));
+        assert!(page.contains("--color-c2-ember: #f97316;"));
+
+        let invalid = serde_json::json!({
+            "tenants": { branded.to_string(): { "primary_color": "red;}" } }
+        });
+        assert!(BrandingConfig::parse(&invalid.to_string()).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, ContentEncoding};
+use actix_web::middleware::Next;
+use actix_web::Error;
+
+/// Smallest response body worth compressing; registered as app data.
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionMinBytes(pub usize);
+
+/// Content types that are already compressed, or must reach the client one
+/// event at a time. `Compress` itself skips images and video.
+const SKIPPED_CONTENT_TYPES: [&str; 7] = [
+    "application/gzip",
+    "application/x-protobuf",
+    "application/vnd.mapbox-vector-tile",
+    "application/zip",
+    "audio/",
+    "font/woff",
+    "text/event-stream",
+];
+
+/// Runs inside `actix_web::middleware::Compress` and marks responses it should
+/// leave alone with `Content-Encoding: identity`: bodies under
+/// [`CompressionMinBytes`] and the [`SKIPPED_CONTENT_TYPES`].
+pub async fn skip_uncompressible(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let min_bytes = req
+        .app_data::<CompressionMinBytes>()
+        .map_or(0, |min_bytes| min_bytes.0);
+    let mut response = next.call(req).await?;
+    let small = match response.response().body().size() {
+        BodySize::Sized(size) => size < min_bytes as u64,
+        BodySize::None => true,
+        BodySize::Stream => false,
+    };
+    let skipped_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            let content_type = content_type.to_ascii_lowercase();
+            SKIPPED_CONTENT_TYPES
+                .iter()
+                .any(|skipped| content_type.starts_with(skipped))
+        });
+    if (small || skipped_type) && !response.headers().contains_key(header::CONTENT_ENCODING) {
+        response.headers_mut().insert(
+            header::CONTENT_ENCODING,
+            ContentEncoding::Identity.to_header_value(),
+        );
+    }
+    Ok(response)
+}
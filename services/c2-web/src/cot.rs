@@ -0,0 +1,128 @@
+use c2_core::{Asset, AssetKind, EpochMillis, SecurityClassification};
+
+use crate::xml::escape;
+
+/// CoT value for an unknown height or error ellipse.
+const COT_UNKNOWN: f64 = 9_999_999.0;
+
+/// Friendly-force CoT type (MIL-STD-2525 atoms) for an asset kind.
+pub fn asset_type(kind: AssetKind) -> &'static str {
+    match kind {
+        AssetKind::Personnel => "a-f-G-U-C-I",
+        AssetKind::Drone => "a-f-A-M-F-Q",
+        AssetKind::Ugv | AssetKind::Vehicle => "a-f-G-E-V",
+        AssetKind::Aircraft => "a-f-A-M",
+        AssetKind::Sensor => "a-f-G-E-S",
+        AssetKind::CommsRelay => "a-f-G-U-U-S",
+        AssetKind::CommandPost => "a-f-G-U-H",
+        AssetKind::Other => "a-f-G",
+    }
+}
+
+/// CoT `<event>` for a positioned asset, or `None` without a position.
+/// The event goes stale `stale_ms` after `now_ms`.
+pub fn asset_event(asset: &Asset, now_ms: EpochMillis, stale_ms: u64) -> Option<String> {
+    let position = asset.position?;
+    let time = cot_time(now_ms);
+    Some(format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+         <event version=\"2.0\" uid=\"c2-asset-{}\" type=\"{}\" how=\"m-g\" time=\"{time}\" start=\"{time}\" stale=\"{}\">\
+         <point lat=\"{}\" lon=\"{}\" hae=\"{}\" ce=\"{}\" le=\"{COT_UNKNOWN}\"/>\
+         <detail><contact callsign=\"{}\"/><remarks>{}</remarks></detail>\
+         </event>\n",
+        asset.id,
+        asset_type(asset.kind),
+        cot_time(now_ms.saturating_add(stale_ms)),
+        position.latitude,
+        position.longitude,
+        position.altitude_m.unwrap_or(COT_UNKNOWN),
+        position.accuracy_m.unwrap_or(COT_UNKNOWN),
+        escape(&asset.name),
+        escape(&format!("status {:?}, classification {:?}", asset.status, asset.classification)),
+    ))
+}
+
+/// Events for the positioned assets at or below `clearance`.
+pub fn asset_events(
+    assets: &[Asset],
+    clearance: SecurityClassification,
+    now_ms: EpochMillis,
+    stale_ms: u64,
+) -> String {
+    assets
+        .iter()
+        .filter(|asset| asset.classification <= clearance)
+        .filter_map(|asset| asset_event(asset, now_ms, stale_ms))
+        .collect()
+}
+
+/// `YYYY-MM-DDTHH:MM:SS.sssZ` for a Unix epoch in milliseconds.
+fn cot_time(epoch_ms: EpochMillis) -> String {
+    let millis = epoch_ms % 1000;
+    let secs = epoch_ms / 1000;
+    let (hour, minute, second) = ((secs / 3600) % 24, (secs / 60) % 60, secs % 60);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{millis:03}Z")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{AssetId, AssetStatus, CommsStatus, MaintenanceState, ReadinessState, TenantId};
+    use c2_geo::Coordinate;
+
+    #[test]
+    fn drone_events_carry_an_air_type_and_the_point() {
+        let asset = Asset {
+            id: AssetId::new(),
+            tenant_id: TenantId::new(),
+            name: "Raven 1".to_string(),
+            kind: AssetKind::Drone,
+            status: AssetStatus::Available,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::None,
+            maintenance_window: None,
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: Some(Coordinate {
+                latitude: 45.8,
+                longitude: 15.9,
+                altitude_m: Some(120.0),
+                accuracy_m: None,
+            }),
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+
+        let event = asset_event(&asset, 1_700_000_000_000, 60_000).unwrap();
+        let parsed = roxmltree::Document::parse(&event).unwrap();
+        let root = parsed.root_element();
+        assert_eq!(root.tag_name().name(), "event");
+        assert_eq!(root.attribute("type"), Some("a-f-A-M-F-Q"));
+        assert_eq!(root.attribute("uid"), Some(format!("c2-asset-{}", asset.id).as_str()));
+        assert_eq!(root.attribute("time"), Some("2023-11-14T22:13:20.000Z"));
+        assert_eq!(root.attribute("stale"), Some("2023-11-14T22:14:20.000Z"));
+        let point = root.children().find(|node| node.has_tag_name("point")).unwrap();
+        let attributes: Vec<_> = ["lat", "lon", "hae", "ce"]
+            .map(|name| point.attribute(name).unwrap())
+            .to_vec();
+        assert_eq!(attributes, ["45.8", "15.9", "120", "9999999"]);
+
+        let unpositioned = Asset {
+            position: None,
+            ..asset
+        };
+        assert!(asset_event(&unpositioned, 1_700_000_000_000, 60_000).is_none());
+    }
+}
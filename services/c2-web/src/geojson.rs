@@ -0,0 +1,221 @@
+use c2_core::{
+    sidc_for, Asset, AssetKind, AssetStatus, Incident, IncidentStatus, IncidentType,
+    OperationalPriority, SecurityClassification,
+};
+use c2_geo::Coordinate;
+use c2_policy::PositionPrecision;
+use serde::Serialize;
+
+const REDACTED: &str = "[REDACTED]";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    /// `[longitude, latitude]`, plus altitude in metres when known.
+    Point { coordinates: Vec<f64> },
+}
+
+impl From<Coordinate> for Geometry {
+    fn from(value: Coordinate) -> Self {
+        let mut coordinates = vec![value.longitude, value.latitude];
+        if let Some(altitude_m) = value.altitude_m {
+            coordinates.push(altitude_m);
+        }
+        Self::Point { coordinates }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetProperties {
+    pub id: String,
+    pub name: String,
+    pub kind: AssetKind,
+    pub status: AssetStatus,
+    pub classification: SecurityClassification,
+    pub sidc: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub redacted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentProperties {
+    pub id: String,
+    pub incident_type: IncidentType,
+    pub status: IncidentStatus,
+    pub priority: OperationalPriority,
+    pub summary: String,
+    /// Impact circle radius around the point, in metres.
+    pub affected_radius_m: Option<f64>,
+    pub classification: SecurityClassification,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub redacted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename = "Feature")]
+pub struct Feature<P> {
+    pub id: String,
+    pub geometry: Geometry,
+    pub properties: P,
+}
+
+impl Feature<AssetProperties> {
+    /// `None` when the asset has no position or it is withheld from
+    /// `clearance`. Assets above `clearance` keep a coarsened position and
+    /// lose their name.
+    pub fn from_asset(
+        asset: &Asset,
+        clearance: SecurityClassification,
+        precision: &PositionPrecision,
+    ) -> Option<Self> {
+        let position = precision.apply(asset.position?, asset.classification, clearance)?;
+        let redacted = asset.classification > clearance;
+        Some(Self {
+            id: asset.id.to_string(),
+            geometry: position.into(),
+            properties: AssetProperties {
+                id: asset.id.to_string(),
+                name: if redacted {
+                    REDACTED.to_string()
+                } else {
+                    asset.name.clone()
+                },
+                kind: asset.kind,
+                status: asset.status,
+                classification: asset.classification,
+                sidc: sidc_for(asset.kind, asset.status),
+                redacted,
+            },
+        })
+    }
+}
+
+impl Feature<IncidentProperties> {
+    /// Like [`Feature::from_asset`]; redacted incidents also lose their
+    /// summary and affected radius.
+    pub fn from_incident(
+        incident: &Incident,
+        clearance: SecurityClassification,
+        precision: &PositionPrecision,
+    ) -> Option<Self> {
+        let position =
+            precision.apply(incident.position?, incident.classification, clearance)?;
+        let redacted = incident.classification > clearance;
+        Some(Self {
+            id: incident.id.to_string(),
+            geometry: position.into(),
+            properties: IncidentProperties {
+                id: incident.id.to_string(),
+                incident_type: incident.incident_type,
+                status: incident.status,
+                priority: incident.priority,
+                summary: if redacted {
+                    REDACTED.to_string()
+                } else {
+                    incident.summary.clone()
+                },
+                affected_radius_m: incident.affected_radius_m.filter(|_| !redacted),
+                classification: incident.classification,
+                redacted,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename = "FeatureCollection")]
+pub struct FeatureCollection<P> {
+    pub features: Vec<Feature<P>>,
+}
+
+/// Positioned assets visible at `clearance`: those at or below it, plus
+/// redacted ones above it that `precision` keeps a position for.
+pub fn asset_collection(
+    assets: &[Asset],
+    clearance: SecurityClassification,
+    precision: &PositionPrecision,
+) -> FeatureCollection<AssetProperties> {
+    FeatureCollection {
+        features: assets
+            .iter()
+            .filter_map(|asset| Feature::from_asset(asset, clearance, precision))
+            .collect(),
+    }
+}
+
+/// Like [`asset_collection`], for positioned incidents.
+pub fn incident_collection(
+    incidents: &[Incident],
+    clearance: SecurityClassification,
+    precision: &PositionPrecision,
+) -> FeatureCollection<IncidentProperties> {
+    FeatureCollection {
+        features: incidents
+            .iter()
+            .filter_map(|incident| Feature::from_incident(incident, clearance, precision))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{AssetId, CommsStatus, MaintenanceState, ReadinessState, TenantId};
+
+    #[test]
+    fn positioned_assets_become_point_features_in_lon_lat_order() {
+        let asset = Asset {
+            id: AssetId::new(),
+            tenant_id: TenantId::new(),
+            name: "Raven 1".to_string(),
+            kind: AssetKind::Drone,
+            status: AssetStatus::Available,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::None,
+            maintenance_window: None,
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: Some(Coordinate {
+                latitude: 45.8,
+                longitude: 15.9,
+                altitude_m: Some(120.0),
+                accuracy_m: None,
+            }),
+            classification: SecurityClassification::Restricted,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        let unpositioned = Asset {
+            id: AssetId::new(),
+            position: None,
+            ..asset.clone()
+        };
+
+        let collection = asset_collection(
+            &[asset.clone(), unpositioned],
+            SecurityClassification::Restricted,
+            &PositionPrecision::default(),
+        );
+        let id = asset.id.to_string();
+        assert_eq!(
+            serde_json::to_value(collection).unwrap(),
+            serde_json::json!({
+                "type": "FeatureCollection",
+                "features": [{
+                    "type": "Feature",
+                    "id": id,
+                    "geometry": { "type": "Point", "coordinates": [15.9, 45.8, 120.0] },
+                    "properties": {
+                        "id": id,
+                        "name": "Raven 1",
+                        "kind": "drone",
+                        "status": "available",
+                        "classification": "restricted",
+                        "sidc": sidc_for(AssetKind::Drone, AssetStatus::Available),
+                    },
+                }],
+            })
+        );
+    }
+}
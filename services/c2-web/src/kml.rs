@@ -0,0 +1,138 @@
+use c2_core::{Asset, AssetKind, SecurityClassification};
+use std::fmt::Write;
+
+use crate::xml::escape;
+
+const ASSET_KINDS: [AssetKind; 9] = [
+    AssetKind::Personnel,
+    AssetKind::Drone,
+    AssetKind::Ugv,
+    AssetKind::Vehicle,
+    AssetKind::Aircraft,
+    AssetKind::Sensor,
+    AssetKind::CommsRelay,
+    AssetKind::CommandPost,
+    AssetKind::Other,
+];
+
+/// Style id and KML icon colour (`aabbggrr`) for an asset kind.
+fn kind_style(kind: AssetKind) -> (&'static str, &'static str) {
+    match kind {
+        AssetKind::Personnel => ("asset-personnel", "ff00d7ff"),
+        AssetKind::Drone => ("asset-drone", "ffffaa00"),
+        AssetKind::Ugv => ("asset-ugv", "ff00aa55"),
+        AssetKind::Vehicle => ("asset-vehicle", "ff0055ff"),
+        AssetKind::Aircraft => ("asset-aircraft", "ffff5500"),
+        AssetKind::Sensor => ("asset-sensor", "ffaa00ff"),
+        AssetKind::CommsRelay => ("asset-comms-relay", "ffffff00"),
+        AssetKind::CommandPost => ("asset-command-post", "ff0000ff"),
+        AssetKind::Other => ("asset-other", "ffaaaaaa"),
+    }
+}
+
+/// KML document with one Placemark per positioned asset at or below
+/// `clearance`; unpositioned assets are skipped.
+pub fn asset_document(assets: &[Asset], clearance: SecurityClassification) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n<Document>\n");
+    out.push_str("<name>C2 assets</name>\n");
+    for kind in ASSET_KINDS {
+        let (id, color) = kind_style(kind);
+        let _ = writeln!(
+            out,
+            "<Style id=\"{id}\"><IconStyle><color>{color}</color></IconStyle></Style>"
+        );
+    }
+    for asset in assets
+        .iter()
+        .filter(|asset| asset.classification <= clearance)
+    {
+        let Some(position) = asset.position else {
+            continue;
+        };
+        let (style, _) = kind_style(asset.kind);
+        let description = format!(
+            "kind: {}\nstatus: {}\nclassification: {}",
+            enum_name(&asset.kind),
+            enum_name(&asset.status),
+            enum_name(&asset.classification)
+        );
+        let (altitude_mode, altitude) = match position.altitude_m {
+            Some(altitude_m) => ("absolute", altitude_m),
+            None => ("clampToGround", 0.0),
+        };
+        let _ = writeln!(
+            out,
+            "<Placemark id=\"{}\"><name>{}</name><description>{}</description><styleUrl>#{style}</styleUrl><Point><altitudeMode>{altitude_mode}</altitudeMode><coordinates>{},{},{}</coordinates></Point></Placemark>",
+            asset.id,
+            escape(&asset.name),
+            escape(&description),
+            position.longitude,
+            position.latitude,
+            altitude
+        );
+    }
+    out.push_str("</Document>\n</kml>\n");
+    out
+}
+
+/// Serde name of a unit enum variant, e.g. `comms_relay`.
+fn enum_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{AssetId, AssetStatus, CommsStatus, MaintenanceState, ReadinessState, TenantId};
+    use c2_geo::Coordinate;
+
+    #[test]
+    fn documents_are_well_formed_with_a_placemark_per_visible_asset() {
+        let asset = Asset {
+            id: AssetId::new(),
+            tenant_id: TenantId::new(),
+            name: "Search & Rescue <1>".to_string(),
+            kind: AssetKind::Aircraft,
+            status: AssetStatus::Available,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::None,
+            maintenance_window: None,
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: Some(Coordinate {
+                latitude: 45.8,
+                longitude: 15.9,
+                altitude_m: Some(120.0),
+                accuracy_m: None,
+            }),
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        let secret = Asset {
+            id: AssetId::new(),
+            classification: SecurityClassification::Secret,
+            ..asset.clone()
+        };
+
+        let document = asset_document(&[asset.clone(), secret], SecurityClassification::Restricted);
+        let parsed = roxmltree::Document::parse(&document).unwrap();
+        let placemarks: Vec<_> =
+            parsed.descendants().filter(|node| node.has_tag_name("Placemark")).collect();
+        assert_eq!(placemarks.len(), 1);
+        let placemark = placemarks[0];
+        let text = |tag: &str| {
+            placemark.descendants().find(|node| node.has_tag_name(tag)).and_then(|node| node.text())
+        };
+        assert_eq!(placemark.attribute("id"), Some(asset.id.to_string().as_str()));
+        assert_eq!(text("name"), Some("Search & Rescue <1>"));
+        assert_eq!(text("styleUrl"), Some("#asset-aircraft"));
+        assert_eq!(text("coordinates"), Some("15.9,45.8,120"));
+    }
+}
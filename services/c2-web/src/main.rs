@@ -1,17 +1,31 @@
 mod api;
+mod branding;
+mod compression;
+mod cot;
 mod flights;
+mod geojson;
+mod kml;
+mod oidc;
 mod satellites;
 mod ships;
 mod render;
+mod resilience;
 mod routes;
 mod state;
 mod tiles;
+mod xml;
 
 use actix_files::Files;
-use actix_web::{web, App, HttpServer};
+use actix_web::{middleware, web, App, HttpServer};
 use c2_config::ServiceConfig;
-use c2_observability::{init, log_startup, ObservabilityConfig};
+use c2_core::SymbolTable;
+use c2_http::HttpPolicy;
+use c2_observability::{init, log_startup, ObservabilityConfig, TelemetryConfig};
+use c2_policy::PositionPrecision;
 use api::ApiClient;
+use branding::BrandingConfig;
+use compression::CompressionMinBytes;
+use oidc::Oidc;
 use state::AppState;
 use std::env;
 use std::io;
@@ -26,7 +40,13 @@ async fn main() -> io::Result<()> {
         service_name: config.service_name.clone(),
         environment: config.environment.to_string(),
         log_level: config.log_level.clone(),
+        log_format: config.log_format.clone(),
         metrics_addr: config.metrics_addr.clone(),
+        telemetry: TelemetryConfig {
+            enabled: config.telemetry,
+            interval: config.telemetry_interval(),
+            endpoint: config.telemetry_endpoint.clone(),
+        },
     };
     let handle = init(&obs_config);
     log_startup(&handle, &obs_config.environment);
@@ -42,8 +62,17 @@ async fn main() -> io::Result<()> {
         "services/c2-web/static".to_string()
     };
     let bind_addr = config.bind_addr.clone();
-    let api = ApiClient::from_env()
-        .map_err(|err| io::Error::new(io::ErrorKind::Other, err.message))?;
+    let api = ApiClient::from_env().map_err(io::Error::other)?;
+    let oidc = Oidc::from_env()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?
+        .map(|oidc| web::Data::new(oidc.admit_only(api.tenant_id(), api.clearance())));
+    let branding = BrandingConfig::from_env()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    SymbolTable::from_env()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?
+        .install();
+    let position_precision = PositionPrecision::from_env()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
     let tile_config_value = env::var("C2_WEB_TILE_CONFIG")
         .ok()
         .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok());
@@ -463,10 +492,14 @@ async fn main() -> io::Result<()> {
         "sample": ship_sample_enabled,
     })
     .to_string();
+    let compression_min_bytes = CompressionMinBytes(config.compression_min_bytes);
+    let http_policy = web::Data::new(HttpPolicy::from_config(&config));
     let state = web::Data::new(AppState {
         config,
         tera,
         api,
+        branding,
+        position_precision,
         tile_config_json,
         tile_providers,
         tile_client,
@@ -529,8 +562,21 @@ async fn main() -> io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(state.clone())
+            .app_data(compression_min_bytes)
+            .app_data(http_policy.clone())
+            .wrap(middleware::from_fn(oidc::require_session))
+            .wrap(middleware::from_fn(compression::skip_uncompressible))
+            .wrap(middleware::Compress::default())
+            .wrap(middleware::from_fn(c2_http::trusted_origin))
+            .wrap(middleware::from_fn(c2_http::access_log))
             .service(Files::new("/static", static_root.clone()).prefer_utf8(true))
             .configure(routes::configure)
+            .configure(|cfg| {
+                if let Some(oidc) = &oidc {
+                    cfg.app_data(oidc.clone());
+                    routes::configure_auth(cfg);
+                }
+            })
     })
         .bind(bind_addr)?
         .run()
@@ -0,0 +1,510 @@
+//! OIDC login for the console. Enabled by `C2_WEB_OIDC_ISSUER`: visitors
+//! without a session are sent to the IdP's authorization endpoint, the
+//! callback trades the code for an ID token, and the subject it names is kept
+//! in a signed `c2_session` cookie. The ID token must carry `tenant_id`,
+//! `roles` and `clearance` claims alongside the standard ones.
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpMessage, HttpResponse, ResponseError};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use c2_core::{now_epoch_millis, EpochMillis, SecurityClassification, TenantId, UserId};
+use c2_identity::{verify_token, Role, Subject, TokenClaims, TokenError};
+use hmac::{Hmac, Mac};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use rand::RngCore;
+use reqwest::{Client, Url};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::env;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const SESSION_COOKIE: &str = "c2_session";
+pub const LOGIN_COOKIE: &str = "c2_login";
+
+/// How long a login started at the IdP may take to come back.
+const LOGIN_TTL: Duration = Duration::from_secs(600);
+const MIN_SECRET_BYTES: usize = 32;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub authorize_url: Url,
+    pub token_url: Url,
+    /// Keys for RS/ES/PS-signed ID tokens; HS-signed ones use the client
+    /// secret.
+    pub jwks_url: Option<Url>,
+    /// This service's `/auth/callback`, as registered with the IdP.
+    pub redirect_url: Url,
+    pub scopes: String,
+    pub session_ttl: Duration,
+}
+
+impl OidcConfig {
+    /// `None` when `C2_WEB_OIDC_ISSUER` is unset, which leaves the console
+    /// open as before.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Some(issuer) = optional("C2_WEB_OIDC_ISSUER") else {
+            return Ok(None);
+        };
+        let jwks_url = optional("C2_WEB_OIDC_JWKS_URL")
+            .map(|value| parse_url("C2_WEB_OIDC_JWKS_URL", &value))
+            .transpose()?;
+        Ok(Some(Self {
+            issuer,
+            client_id: required("C2_WEB_OIDC_CLIENT_ID")?,
+            client_secret: required("C2_WEB_OIDC_CLIENT_SECRET")?,
+            authorize_url: required_url("C2_WEB_OIDC_AUTHORIZE_URL")?,
+            token_url: required_url("C2_WEB_OIDC_TOKEN_URL")?,
+            jwks_url,
+            redirect_url: required_url("C2_WEB_OIDC_REDIRECT_URL")?,
+            scopes: optional("C2_WEB_OIDC_SCOPES").unwrap_or_else(|| "openid profile".to_string()),
+            session_ttl: Duration::from_secs(
+                optional("C2_WEB_SESSION_TTL_SECS")
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .unwrap_or(8 * 3_600),
+            ),
+        }))
+    }
+}
+
+/// Why a login did not produce a session.
+#[derive(Debug)]
+pub enum OidcError {
+    /// The callback did not match a login started here, or the IdP sent an
+    /// error instead of a code.
+    BadCallback(String),
+    /// The token endpoint or the IdP's keys could not be reached or read.
+    Exchange(String),
+    /// The ID token's signature or contents are not acceptable.
+    InvalidToken(String),
+    Rejected(TokenError),
+    /// The user is not of the console's tenant or is cleared below its data.
+    NotAdmitted,
+}
+
+impl std::fmt::Display for OidcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadCallback(message) => write!(f, "login callback rejected: {message}"),
+            Self::Exchange(message) => write!(f, "identity provider request failed: {message}"),
+            Self::InvalidToken(message) => write!(f, "invalid ID token: {message}"),
+            Self::Rejected(err) => write!(f, "invalid ID token: {err}"),
+            Self::NotAdmitted => write!(f, "this account may not use this console"),
+        }
+    }
+}
+
+impl std::error::Error for OidcError {}
+
+impl ResponseError for OidcError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        use actix_web::http::StatusCode;
+        match self {
+            Self::BadCallback(_) => StatusCode::BAD_REQUEST,
+            Self::Exchange(_) => StatusCode::BAD_GATEWAY,
+            Self::InvalidToken(_) | Self::Rejected(_) => StatusCode::UNAUTHORIZED,
+            Self::NotAdmitted => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .json(serde_json::json!({ "error": self.to_string() }))
+    }
+}
+
+/// What the console keeps about a signed-in user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub subject: Subject,
+    pub expires_at_ms: EpochMillis,
+}
+
+/// A login waiting for the IdP to call back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingLogin {
+    pub state: String,
+    pub nonce: String,
+    /// Where to go once signed in; always a local path.
+    pub next: String,
+    pub expires_at_ms: EpochMillis,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    iss: String,
+    aud: Value,
+    sub: String,
+    exp: u64,
+    #[serde(default)]
+    iat: u64,
+    #[serde(default)]
+    nonce: Option<String>,
+    #[serde(default)]
+    tenant_id: Option<String>,
+    #[serde(default)]
+    roles: Vec<String>,
+    #[serde(default)]
+    clearance: Option<String>,
+}
+
+pub struct Oidc {
+    config: OidcConfig,
+    session_key: Vec<u8>,
+    client: Client,
+    jwks: Mutex<Option<Arc<JwkSet>>>,
+    /// The tenant whose data the console shows; users of others are turned
+    /// away.
+    tenant_id: Option<TenantId>,
+    /// Clearance of the console's own API identity; users cleared below it
+    /// would see more than they may.
+    min_clearance: SecurityClassification,
+}
+
+impl Oidc {
+    pub fn new(config: OidcConfig, session_secret: &str) -> Result<Self, String> {
+        if session_secret.len() < MIN_SECRET_BYTES {
+            return Err(format!(
+                "C2_WEB_SESSION_SECRET must be at least {MIN_SECRET_BYTES} bytes"
+            ));
+        }
+        Ok(Self {
+            config,
+            session_key: session_secret.as_bytes().to_vec(),
+            client: Client::new(),
+            jwks: Mutex::new(None),
+            tenant_id: None,
+            min_clearance: SecurityClassification::Unclassified,
+        })
+    }
+
+    /// `None` when OIDC is not configured.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Some(config) = OidcConfig::from_env()? else {
+            return Ok(None);
+        };
+        Self::new(config, &required("C2_WEB_SESSION_SECRET")?).map(Some)
+    }
+
+    /// Admits only users of `tenant_id` cleared at least to `clearance`.
+    pub fn admit_only(
+        mut self,
+        tenant_id: Option<TenantId>,
+        clearance: SecurityClassification,
+    ) -> Self {
+        self.tenant_id = tenant_id;
+        self.min_clearance = clearance;
+        self
+    }
+
+    pub fn secure_cookies(&self) -> bool {
+        self.config.redirect_url.scheme() == "https"
+    }
+
+    pub fn session_ttl(&self) -> Duration {
+        self.config.session_ttl
+    }
+
+    /// A new pending login and the IdP URL to send the browser to.
+    pub fn start_login(&self, next: &str) -> (PendingLogin, Url) {
+        let login = PendingLogin {
+            state: random_token(),
+            nonce: random_token(),
+            next: local_path(next).to_string(),
+            expires_at_ms: now_epoch_millis() + LOGIN_TTL.as_millis() as u64,
+        };
+        let mut url = self.config.authorize_url.clone();
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", self.config.redirect_url.as_str())
+            .append_pair("scope", &self.config.scopes)
+            .append_pair("state", &login.state)
+            .append_pair("nonce", &login.nonce);
+        (login, url)
+    }
+
+    /// Trades an authorization code for the ID token and returns the subject
+    /// it names, once the token checks out and the user is admitted.
+    pub async fn complete_login(
+        &self,
+        code: &str,
+        login: &PendingLogin,
+    ) -> Result<Subject, OidcError> {
+        let response = self
+            .client
+            .post(self.config.token_url.clone())
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.config.redirect_url.as_str()),
+                ("client_id", &self.config.client_id),
+                ("client_secret", &self.config.client_secret),
+            ])
+            .send()
+            .await
+            .map_err(|err| OidcError::Exchange(err.to_string()))?;
+        if !response.status().is_success() {
+            return Err(OidcError::Exchange(format!(
+                "token request failed with {}",
+                response.status()
+            )));
+        }
+        let tokens = response
+            .json::<TokenResponse>()
+            .await
+            .map_err(|err| OidcError::Exchange(err.to_string()))?;
+        let claims = self.decode_id_token(&tokens.id_token).await?;
+        if claims.nonce.as_deref() != Some(login.nonce.as_str()) {
+            return Err(OidcError::InvalidToken("nonce does not match".to_string()));
+        }
+        let claims = self.token_claims(claims)?;
+        let subject = verify_token(
+            &claims,
+            &self.config.issuer,
+            &self.config.client_id,
+            now_epoch_millis(),
+        )
+        .map_err(OidcError::Rejected)?;
+        if self.tenant_id.is_some_and(|tenant_id| tenant_id != subject.tenant_id)
+            || subject.clearance < self.min_clearance
+        {
+            return Err(OidcError::NotAdmitted);
+        }
+        Ok(subject)
+    }
+
+    /// Checks the ID token's signature only; [`verify_token`] checks the
+    /// claims.
+    async fn decode_id_token(&self, id_token: &str) -> Result<IdTokenClaims, OidcError> {
+        let invalid = |err: jsonwebtoken::errors::Error| OidcError::InvalidToken(err.to_string());
+        let header = jsonwebtoken::decode_header(id_token).map_err(invalid)?;
+        let key = match header.alg {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                DecodingKey::from_secret(self.config.client_secret.as_bytes())
+            }
+            _ => self.jwk(header.kid.as_deref()).await?,
+        };
+        let mut validation = Validation::new(header.alg);
+        validation.validate_exp = false;
+        validation.validate_aud = false;
+        validation.required_spec_claims.clear();
+        jsonwebtoken::decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map(|data| data.claims)
+            .map_err(invalid)
+    }
+
+    /// The IdP key named `kid`, reading the key set again once when a cached
+    /// copy lacks it, since the IdP may have rotated keys.
+    async fn jwk(&self, kid: Option<&str>) -> Result<DecodingKey, OidcError> {
+        let cached = self.jwks.lock().unwrap_or_else(|err| err.into_inner()).clone();
+        for jwks in [cached, None] {
+            let jwks = match jwks {
+                Some(jwks) => jwks,
+                None => self.fetch_jwks().await?,
+            };
+            let jwk = match kid {
+                Some(kid) => jwks.find(kid),
+                None => jwks.keys.first(),
+            };
+            if let Some(jwk) = jwk {
+                return DecodingKey::from_jwk(jwk)
+                    .map_err(|err| OidcError::InvalidToken(err.to_string()));
+            }
+        }
+        Err(OidcError::InvalidToken("signing key not found".to_string()))
+    }
+
+    async fn fetch_jwks(&self) -> Result<Arc<JwkSet>, OidcError> {
+        let url = self.config.jwks_url.clone().ok_or_else(|| {
+            OidcError::InvalidToken("asymmetric ID token without C2_WEB_OIDC_JWKS_URL".to_string())
+        })?;
+        let jwks = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| OidcError::Exchange(err.to_string()))?
+            .json::<JwkSet>()
+            .await
+            .map_err(|err| OidcError::Exchange(err.to_string()))?;
+        let jwks = Arc::new(jwks);
+        *self.jwks.lock().unwrap_or_else(|err| err.into_inner()) = Some(jwks.clone());
+        Ok(jwks)
+    }
+
+    fn token_claims(&self, claims: IdTokenClaims) -> Result<TokenClaims, OidcError> {
+        let missing = |claim: &str| OidcError::InvalidToken(format!("missing {claim} claim"));
+        let tenant_id = claims
+            .tenant_id
+            .as_deref()
+            .ok_or_else(|| missing("tenant_id"))?
+            .parse::<TenantId>()
+            .map_err(|err| OidcError::InvalidToken(err.to_string()))?;
+        let clearance = claims.clearance.as_deref().ok_or_else(|| missing("clearance"))?;
+        let clearance = SecurityClassification::from_str(clearance)
+            .map_err(|_| OidcError::InvalidToken(format!("unknown clearance {clearance}")))?;
+        // Subjects that are not UUIDs map to a stable id within the tenant.
+        let user_id = claims
+            .sub
+            .parse::<UserId>()
+            .unwrap_or_else(|_| UserId::from_name(tenant_id, &claims.sub));
+        // Several audiences are fine as long as this client is one of them.
+        let audience = match &claims.aud {
+            Value::String(audience) => audience.clone(),
+            Value::Array(audiences)
+                if audiences
+                    .iter()
+                    .any(|audience| audience.as_str() == Some(self.config.client_id.as_str())) =>
+            {
+                self.config.client_id.clone()
+            }
+            _ => String::new(),
+        };
+        Ok(TokenClaims {
+            issuer: claims.iss,
+            audience,
+            tenant_id,
+            user_id,
+            roles: claims
+                .roles
+                .iter()
+                .filter_map(|role| Role::from_str(role).ok())
+                .collect(),
+            clearance,
+            issued_at_ms: claims.iat.saturating_mul(1_000),
+            expires_at_ms: claims.exp.saturating_mul(1_000),
+        })
+    }
+
+    /// `value` as `<base64 JSON>.<hex HMAC>` for a cookie.
+    pub fn seal<T: Serialize>(&self, value: &T) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(value).unwrap_or_default());
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        format!("{payload}.{}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// The value sealed into `sealed`, if the signature holds.
+    pub fn open<T: DeserializeOwned>(&self, sealed: &str) -> Option<T> {
+        let (payload, signature) = sealed.rsplit_once('.')?;
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&hex::decode(signature).ok()?).ok()?;
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()
+    }
+
+    /// The session in a `c2_session` cookie value, unless forged or expired.
+    pub fn session(&self, sealed: &str) -> Option<Session> {
+        self.open::<Session>(sealed)
+            .filter(|session| session.expires_at_ms > now_epoch_millis())
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.session_key).expect("HMAC accepts keys of any length")
+    }
+}
+
+/// Lets requests with a valid session through, with its [`Subject`] in the
+/// request extensions, and sends others to log in: pages are redirected,
+/// htmx gets an `HX-Redirect`, and anything else a 401. Health checks,
+/// static files and `/auth/*` stay open. Does nothing without [`Oidc`].
+pub async fn require_session(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let Some(oidc) = req.app_data::<web::Data<Oidc>>().cloned() else {
+        return Ok(next.call(req).await?.map_into_left_body());
+    };
+    let path = req.path();
+    if path == "/health" || path.starts_with("/auth/") || path.starts_with("/static/") {
+        return Ok(next.call(req).await?.map_into_left_body());
+    }
+    if let Some(session) = req
+        .cookie(SESSION_COOKIE)
+        .and_then(|cookie| oidc.session(cookie.value()))
+    {
+        req.extensions_mut().insert(session.subject);
+        return Ok(next.call(req).await?.map_into_left_body());
+    }
+
+    let next_path = req
+        .uri()
+        .path_and_query()
+        .map_or("/", |path_and_query| path_and_query.as_str());
+    let login = format!(
+        "/auth/login?{}",
+        serde_urlencoded::to_string([("next", next_path)]).unwrap_or_default()
+    );
+    let accepts_html = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"));
+    let response = if req.headers().contains_key("hx-request") {
+        HttpResponse::Unauthorized()
+            .insert_header(("HX-Redirect", login))
+            .finish()
+    } else if accepts_html {
+        HttpResponse::Found()
+            .insert_header((header::LOCATION, login))
+            .finish()
+    } else {
+        HttpResponse::Unauthorized().json(serde_json::json!({ "error": "login required" }))
+    };
+    Ok(req.into_response(response).map_into_right_body())
+}
+
+/// `next` when it is a path on this site, `/` otherwise, so the login
+/// cannot be used to send users elsewhere.
+fn local_path(next: &str) -> &str {
+    if next.starts_with('/') && !next.starts_with("//") && !next.contains('\\') {
+        next
+    } else {
+        "/"
+    }
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 16];
+    rand::rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn optional(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn required(name: &str) -> Result<String, String> {
+    optional(name).ok_or_else(|| format!("{name} is required when C2_WEB_OIDC_ISSUER is set"))
+}
+
+fn required_url(name: &str) -> Result<Url, String> {
+    parse_url(name, &required(name)?)
+}
+
+fn parse_url(name: &str, value: &str) -> Result<Url, String> {
+    Url::parse(value).map_err(|err| format!("{name} is not a valid URL: {err}"))
+}
@@ -1,12 +1,17 @@
 use tera::Context;
 
-use crate::api::{StatusResponse, UiSnapshot};
+use c2_core::TenantId;
+use serde::Serialize;
+
+use crate::api::{StatusResponse, TablePage, TableQuery, UiSnapshot};
+use crate::branding::Branding;
 use crate::state::AppState;
 
 #[derive(Debug, Clone)]
 pub struct UiTemplateData {
     pub service_name: String,
     pub environment: String,
+    pub branding: Branding,
     pub status: Option<StatusResponse>,
     pub snapshot: UiSnapshot,
     pub tile_config_json: Option<String>,
@@ -18,10 +23,12 @@ pub struct UiTemplateData {
 }
 
 impl UiTemplateData {
+    /// Branded for the console's own tenant; see [`Self::for_tenant`].
     pub fn from_state(state: &AppState, status: Option<StatusResponse>, snapshot: UiSnapshot) -> Self {
         Self {
             service_name: state.config.service_name.clone(),
             environment: state.config.environment.to_string(),
+            branding: state.branding.for_tenant(state.api.tenant_id()),
             status,
             snapshot,
             tile_config_json: state.tile_config_json.clone(),
@@ -32,12 +39,19 @@ impl UiTemplateData {
             ship_config_json: state.ship_config_json.clone(),
         }
     }
+
+    /// Rebrands the page for the tenant of the signed-in user.
+    pub fn for_tenant(mut self, state: &AppState, tenant_id: TenantId) -> Self {
+        self.branding = state.branding.for_tenant(Some(tenant_id));
+        self
+    }
 }
 
 pub fn build_context(data: &UiTemplateData) -> Context {
     let mut context = Context::new();
     context.insert("service_name", &data.service_name);
     context.insert("environment", &data.environment);
+    context.insert("branding", &data.branding);
     context.insert("status", &data.status);
     context.insert("snapshot", &data.snapshot);
     context.insert("tile_config_json", &data.tile_config_json);
@@ -48,3 +62,37 @@ pub fn build_context(data: &UiTemplateData) -> Context {
     context.insert("ship_config_json", &data.ship_config_json);
     context
 }
+
+#[derive(Debug, Serialize)]
+struct ActiveFilter {
+    name: &'static str,
+    value: String,
+}
+
+/// Context for an entity table partial: the rows, the query that produced
+/// them, the active filters and the pager links. `path` is the partial's own
+/// route, which the filter form and pager request.
+pub fn build_table_context<T: Serialize>(
+    path: &str,
+    query: &TableQuery,
+    page: &TablePage<T>,
+    error: Option<&str>,
+) -> Context {
+    let filters: Vec<ActiveFilter> = query
+        .filters()
+        .into_iter()
+        .map(|(name, value)| ActiveFilter { name, value })
+        .collect();
+    let prev_query = (page.page > 1).then(|| query.for_page(page.page - 1));
+    let next_query = page.has_next.then(|| query.for_page(page.page + 1));
+
+    let mut context = Context::new();
+    context.insert("path", path);
+    context.insert("query", query);
+    context.insert("filters", &filters);
+    context.insert("page", page);
+    context.insert("prev_query", &prev_query);
+    context.insert("next_query", &next_query);
+    context.insert("error", &error);
+    context
+}
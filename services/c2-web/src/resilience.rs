@@ -0,0 +1,169 @@
+//! Retries and a circuit breaker for the calls `ApiClient` makes to `c2-api`.
+//! Only GETs go through here, so retrying one is always safe.
+
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often a GET that failed because `c2-api` was unreachable or
+/// unavailable is tried again, waiting `backoff`, then twice that, and so on.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_env() -> Self {
+        Self {
+            retries: env_var("C2_UI_API_RETRIES", 2),
+            backoff: Duration::from_millis(env_var("C2_UI_API_RETRY_BACKOFF_MS", 100)),
+        }
+    }
+
+    /// The wait before retry number `attempt`, counting from 0.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.backoff.saturating_mul(2u32.saturating_pow(attempt))
+    }
+}
+
+/// Stops calling `c2-api` after `threshold` calls in a row failed, so a
+/// struggling API is not hammered by every page and poll. Once `cooldown`
+/// has passed one call is let through: success closes the breaker again,
+/// failure reopens it for another cooldown. A trial that never reports back
+/// (its request was dropped) counts as failed once another cooldown passes.
+/// A zero threshold turns it off.
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BreakerState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    /// The trial call after a cooldown is in flight; past `deadline` it is
+    /// given up on and the breaker is open again.
+    HalfOpen { deadline: Instant },
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            state: Mutex::new(BreakerState::Closed { failures: 0 }),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(
+            env_var("C2_UI_API_BREAKER_THRESHOLD", 5),
+            Duration::from_millis(env_var("C2_UI_API_BREAKER_COOLDOWN_MS", 10_000)),
+        )
+    }
+
+    /// Whether a call may go out now. The first call after the cooldown is
+    /// the trial; others are turned away until it reports back or its
+    /// deadline passes, which is treated as a failure.
+    pub fn allow(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let now = Instant::now();
+        if let BreakerState::HalfOpen { deadline } = *state
+            && now >= deadline
+        {
+            *state = BreakerState::Open {
+                until: now + self.cooldown,
+            };
+        }
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } if now >= until => {
+                *state = BreakerState::HalfOpen {
+                    deadline: now + self.cooldown,
+                };
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => false,
+        }
+    }
+
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap_or_else(|err| err.into_inner()) =
+            BreakerState::Closed { failures: 0 };
+    }
+
+    /// Counts a failed call; true when this one opened the breaker.
+    pub fn record_failure(&self) -> bool {
+        if self.threshold == 0 {
+            return false;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let failures = match *state {
+            BreakerState::Closed { failures } => failures + 1,
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => self.threshold,
+        };
+        let opened = failures >= self.threshold;
+        *state = if opened {
+            BreakerState::Open {
+                until: Instant::now() + self.cooldown,
+            }
+        } else {
+            BreakerState::Closed { failures }
+        };
+        opened
+    }
+}
+
+fn env_var<T: std::str::FromStr>(name: &str, default: T) -> T {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse::<T>().ok())
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaker_opens_at_the_threshold_and_half_opens_after_the_cooldown() {
+        let breaker = CircuitBreaker::new(3, Duration::from_millis(50));
+        for _ in 0..2 {
+            assert!(breaker.allow());
+            assert!(!breaker.record_failure());
+        }
+        assert!(breaker.allow());
+        assert!(breaker.record_failure());
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow(), "one trial call after the cooldown");
+        assert!(!breaker.allow(), "only one trial call while half-open");
+        assert!(breaker.record_failure());
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow());
+        breaker.record_success();
+        assert!(breaker.allow());
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn a_trial_that_never_reports_back_reopens_the_breaker() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(50));
+        assert!(breaker.record_failure());
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow(), "the trial call, whose request is then dropped");
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!breaker.allow(), "the lost trial counts as a failure");
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow(), "a fresh trial after the next cooldown");
+        breaker.record_success();
+        assert!(breaker.allow());
+    }
+}
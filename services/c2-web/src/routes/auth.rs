@@ -0,0 +1,251 @@
+use actix_web::cookie::time::Duration as CookieDuration;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::http::header;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use c2_core::now_epoch_millis;
+use serde::Deserialize;
+
+use crate::oidc::{Oidc, OidcError, PendingLogin, Session, LOGIN_COOKIE, SESSION_COOKIE};
+
+#[derive(Debug, Deserialize)]
+pub struct LoginQuery {
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[get("/auth/login")]
+pub async fn login(oidc: web::Data<Oidc>, query: web::Query<LoginQuery>) -> HttpResponse {
+    let (pending, url) = oidc.start_login(query.next.as_deref().unwrap_or("/"));
+    HttpResponse::Found()
+        .insert_header((header::LOCATION, url.as_str()))
+        .cookie(cookie(
+            &oidc,
+            LOGIN_COOKIE,
+            oidc.seal(&pending),
+            CookieDuration::minutes(10),
+        ))
+        .finish()
+}
+
+#[get("/auth/callback")]
+pub async fn callback(
+    req: HttpRequest,
+    oidc: web::Data<Oidc>,
+    query: web::Query<CallbackQuery>,
+) -> Result<HttpResponse, OidcError> {
+    if let Some(error) = &query.error {
+        let detail = query.error_description.as_deref().unwrap_or_default();
+        return Err(OidcError::BadCallback(format!("{error} {detail}").trim().to_string()));
+    }
+    let pending = req
+        .cookie(LOGIN_COOKIE)
+        .and_then(|cookie| oidc.open::<PendingLogin>(cookie.value()))
+        .filter(|pending| pending.expires_at_ms > now_epoch_millis())
+        .ok_or_else(|| OidcError::BadCallback("no login in progress".to_string()))?;
+    if query.state.as_deref() != Some(pending.state.as_str()) {
+        return Err(OidcError::BadCallback("state does not match".to_string()));
+    }
+    let code = query
+        .code
+        .as_deref()
+        .ok_or_else(|| OidcError::BadCallback("missing code".to_string()))?;
+
+    let subject = oidc.complete_login(code, &pending).await?;
+    tracing::info!(
+        tenant_id = %subject.tenant_id,
+        user_id = %subject.user_id,
+        "Console login"
+    );
+    let ttl = oidc.session_ttl();
+    let session = Session {
+        subject,
+        expires_at_ms: now_epoch_millis() + ttl.as_millis() as u64,
+    };
+    let max_age = CookieDuration::seconds(ttl.as_secs() as i64);
+    Ok(HttpResponse::Found()
+        .insert_header((header::LOCATION, pending.next.as_str()))
+        .cookie(cookie(&oidc, SESSION_COOKIE, oidc.seal(&session), max_age))
+        .cookie(cookie(&oidc, LOGIN_COOKIE, String::new(), CookieDuration::ZERO))
+        .finish())
+}
+
+#[get("/auth/logout")]
+pub async fn logout(oidc: web::Data<Oidc>) -> HttpResponse {
+    HttpResponse::Found()
+        .insert_header((header::LOCATION, "/"))
+        .cookie(cookie(&oidc, SESSION_COOKIE, String::new(), CookieDuration::ZERO))
+        .finish()
+}
+
+/// Script-proof cookie for the whole site; a zero `max_age` removes it.
+fn cookie(
+    oidc: &Oidc,
+    name: &'static str,
+    value: String,
+    max_age: CookieDuration,
+) -> Cookie<'static> {
+    Cookie::build(name, value)
+        .path("/")
+        .http_only(true)
+        .secure(oidc.secure_cookies())
+        .same_site(SameSite::Lax)
+        .max_age(max_age)
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oidc::{require_session, OidcConfig};
+    use actix_web::{middleware, test, App, HttpMessage, HttpServer};
+    use c2_core::{SecurityClassification, TenantId};
+    use c2_identity::{Role, Subject};
+    use reqwest::Url;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    const ISSUER: &str = "https://idp.test";
+    const CLIENT_ID: &str = "c2-web";
+    const CLIENT_SECRET: &str = "client-secret-shared-with-the-idp";
+
+    /// Token endpoint that accepts the code `good` and answers with an
+    /// HS256 ID token for `nonce`.
+    async fn mock_idp(tenant_id: TenantId, nonce: Arc<Mutex<String>>) -> u16 {
+        let server = HttpServer::new(move || {
+            let nonce = nonce.clone();
+            App::new().route(
+                "/token",
+                web::post().to(move |form: web::Form<HashMap<String, String>>| {
+                    let nonce = nonce.lock().unwrap().clone();
+                    async move {
+                        if form.get("code").map(String::as_str) != Some("good")
+                            || form.get("client_secret").map(String::as_str)
+                                != Some(CLIENT_SECRET)
+                        {
+                            return HttpResponse::BadRequest()
+                                .json(serde_json::json!({ "error": "invalid_grant" }));
+                        }
+                        let now = now_epoch_millis() / 1_000;
+                        let claims = serde_json::json!({
+                            "iss": ISSUER,
+                            "aud": CLIENT_ID,
+                            "sub": "jane.doe",
+                            "iat": now,
+                            "exp": now + 300,
+                            "nonce": nonce,
+                            "tenant_id": tenant_id.to_string(),
+                            "roles": ["operations", "not_a_role"],
+                            "clearance": "secret",
+                        });
+                        let id_token = jsonwebtoken::encode(
+                            &jsonwebtoken::Header::default(),
+                            &claims,
+                            &jsonwebtoken::EncodingKey::from_secret(CLIENT_SECRET.as_bytes()),
+                        )
+                        .unwrap();
+                        HttpResponse::Ok().json(serde_json::json!({ "id_token": id_token }))
+                    }
+                }),
+            )
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let port = server.addrs()[0].port();
+        actix_web::rt::spawn(server.run());
+        port
+    }
+
+    #[actix_web::test]
+    async fn callback_exchanges_the_code_for_claims_and_sets_the_session() {
+        let tenant_id = TenantId::new();
+        let nonce = Arc::new(Mutex::new(String::new()));
+        let port = mock_idp(tenant_id, nonce.clone()).await;
+        let config = OidcConfig {
+            issuer: ISSUER.to_string(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: CLIENT_SECRET.to_string(),
+            authorize_url: Url::parse("https://idp.test/authorize").unwrap(),
+            token_url: Url::parse(&format!("http://127.0.0.1:{port}/token")).unwrap(),
+            jwks_url: None,
+            redirect_url: Url::parse("http://console.test/auth/callback").unwrap(),
+            scopes: "openid".to_string(),
+            session_ttl: Duration::from_secs(3_600),
+        };
+        let oidc = Oidc::new(config, "a-session-secret-of-at-least-32-bytes")
+            .unwrap()
+            .admit_only(Some(tenant_id), SecurityClassification::Restricted);
+        let oidc = web::Data::new(oidc);
+        let app = test::init_service(
+            App::new()
+                .app_data(oidc.clone())
+                .wrap(middleware::from_fn(require_session))
+                .service(login)
+                .service(callback)
+                .route(
+                    "/ui/summary",
+                    web::get().to(|req: HttpRequest| async move {
+                        let subject = req.extensions().get::<Subject>().cloned().unwrap();
+                        HttpResponse::Ok().json(subject)
+                    }),
+                ),
+        )
+        .await;
+
+        let request = test::TestRequest::get().uri("/ui/summary").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 401);
+
+        let request = test::TestRequest::get().uri("/auth/login?next=/ui/summary").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 302);
+        let location = response.headers().get(header::LOCATION).unwrap().to_str().unwrap();
+        let location = Url::parse(location).unwrap();
+        let params: HashMap<_, _> = location.query_pairs().into_owned().collect();
+        assert_eq!(params["client_id"], CLIENT_ID);
+        *nonce.lock().unwrap() = params["nonce"].clone();
+        let pending = response
+            .response()
+            .cookies()
+            .find(|cookie| cookie.name() == LOGIN_COOKIE)
+            .unwrap()
+            .into_owned();
+
+        let callback_uri =
+            |code: &str| format!("/auth/callback?code={code}&state={}", params["state"]);
+        let request = test::TestRequest::get()
+            .uri(&callback_uri("forged"))
+            .cookie(pending.clone())
+            .to_request();
+        assert_eq!(test::call_service(&app, request).await.status(), 502);
+
+        let request = test::TestRequest::get()
+            .uri(&callback_uri("good"))
+            .cookie(pending)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 302);
+        assert_eq!(response.headers().get(header::LOCATION).unwrap(), "/ui/summary");
+        let session = response
+            .response()
+            .cookies()
+            .find(|cookie| cookie.name() == SESSION_COOKIE)
+            .unwrap()
+            .into_owned();
+        assert!(session.http_only().unwrap_or(false));
+
+        let request = test::TestRequest::get().uri("/ui/summary").cookie(session).to_request();
+        let subject: Subject = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(subject.tenant_id, tenant_id);
+        assert_eq!(subject.roles, vec![Role::Operations]);
+        assert_eq!(subject.clearance, SecurityClassification::Secret);
+    }
+}
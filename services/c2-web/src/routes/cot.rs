@@ -0,0 +1,34 @@
+use actix_web::rt::time::interval;
+use actix_web::web::Bytes;
+use actix_web::{get, web, HttpResponse};
+use c2_core::now_epoch_millis;
+use futures_util::stream::unfold;
+
+use crate::cot::asset_events;
+use crate::state::AppState;
+
+/// Streams CoT events for positioned assets, one batch per poll interval.
+/// Each event stays fresh for three intervals so a missed poll does not
+/// drop tracks on TAK clients.
+#[get("/cot/assets")]
+pub async fn assets(state: web::Data<AppState>) -> HttpResponse {
+    if !state.api.auth_enabled() {
+        return HttpResponse::ServiceUnavailable()
+            .content_type("application/json")
+            .body("{\"error\":\"missing C2_UI_* auth configuration\"}");
+    }
+    let api = state.api.clone();
+    let stale_ms = api.poll_interval().as_millis() as u64 * 3;
+    let ticker = interval(api.poll_interval());
+    let stream = unfold((ticker, api), move |(mut ticker, api)| async move {
+        ticker.tick().await;
+        let assets = api.assets().await.unwrap_or_default();
+        let events = asset_events(&assets, api.clearance(), now_epoch_millis(), stale_ms);
+        Some((Ok::<Bytes, actix_web::Error>(Bytes::from(events)), (ticker, api)))
+    });
+
+    HttpResponse::Ok()
+        .insert_header(("Content-Type", "application/xml"))
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream)
+}
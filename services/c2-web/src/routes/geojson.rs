@@ -0,0 +1,36 @@
+use actix_web::{error::ErrorInternalServerError, get, web, Error, HttpResponse};
+
+use crate::geojson::{asset_collection, incident_collection};
+use crate::state::AppState;
+
+#[get("/geojson/assets")]
+pub async fn assets(state: web::Data<AppState>) -> Result<HttpResponse, Error> {
+    if !state.api.auth_enabled() {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .content_type("application/json")
+            .body("{\"error\":\"missing C2_UI_* auth configuration\"}"));
+    }
+    let assets = state.api.assets().await?;
+    let collection =
+        asset_collection(&assets, state.api.clearance(), &state.position_precision);
+    let body = serde_json::to_string(&collection).map_err(ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/geo+json")
+        .body(body))
+}
+
+#[get("/geojson/incidents")]
+pub async fn incidents(state: web::Data<AppState>) -> Result<HttpResponse, Error> {
+    if !state.api.auth_enabled() {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .content_type("application/json")
+            .body("{\"error\":\"missing C2_UI_* auth configuration\"}"));
+    }
+    let incidents = state.api.incidents().await?;
+    let collection =
+        incident_collection(&incidents, state.api.clearance(), &state.position_precision);
+    let body = serde_json::to_string(&collection).map_err(ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/geo+json")
+        .body(body))
+}
@@ -0,0 +1,17 @@
+use actix_web::{get, web, Error, HttpResponse};
+
+use crate::kml::asset_document;
+use crate::state::AppState;
+
+#[get("/kml/assets")]
+pub async fn assets(state: web::Data<AppState>) -> Result<HttpResponse, Error> {
+    if !state.api.auth_enabled() {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .content_type("application/json")
+            .body("{\"error\":\"missing C2_UI_* auth configuration\"}"));
+    }
+    let assets = state.api.assets().await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/vnd.google-earth.kml+xml")
+        .body(asset_document(&assets, state.api.clearance())))
+}
@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod health;
 pub mod partials;
 pub mod tiles;
@@ -7,6 +8,9 @@ pub mod flights;
 pub mod satellites;
 pub mod ships;
 pub mod media;
+pub mod geojson;
+pub mod kml;
+pub mod cot;
 
 use actix_web::web;
 
@@ -18,6 +22,10 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(ui_api::entities)
         .service(ui_api::sse)
         .service(ui_api::ws_route)
+        .service(geojson::assets)
+        .service(geojson::incidents)
+        .service(kml::assets)
+        .service(cot::assets)
         .service(flights::flights)
         .service(satellites::satellites)
         .service(ships::ships)
@@ -28,5 +36,15 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(tiles::tile)
         .service(partials::mission_feed)
         .service(partials::incidents)
-        .service(partials::assets);
+        .service(partials::assets)
+        .service(partials::mission_table)
+        .service(partials::incident_table)
+        .service(partials::asset_table);
+}
+
+/// Login routes, registered only when OIDC is configured.
+pub fn configure_auth(cfg: &mut web::ServiceConfig) {
+    cfg.service(auth::login)
+        .service(auth::callback)
+        .service(auth::logout);
 }
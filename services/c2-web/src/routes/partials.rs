@@ -1,7 +1,8 @@
 use actix_web::{error::ErrorInternalServerError, get, web, Error, HttpResponse};
+use serde::Serialize;
 
-use crate::api::UiSnapshot;
-use crate::render::{build_context, UiTemplateData};
+use crate::api::{ApiError, TablePage, TableQuery, UiSnapshot};
+use crate::render::{build_context, build_table_context, UiTemplateData};
 use crate::state::AppState;
 
 fn render_partial(
@@ -14,6 +15,25 @@ fn render_partial(
     state.tera.render(template, &context)
 }
 
+fn render_table<T: Serialize>(
+    state: &AppState,
+    template: &str,
+    path: &str,
+    query: &TableQuery,
+    page: Result<TablePage<T>, ApiError>,
+) -> Result<HttpResponse, Error> {
+    let (page, error) = match page {
+        Ok(page) => (page, None),
+        Err(err) => (TablePage::empty(query), Some(err.to_string())),
+    };
+    let context = build_table_context(path, query, &page, error.as_deref());
+    let body = state
+        .tera
+        .render(template, &context)
+        .map_err(ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().content_type("text/html; charset=utf-8").body(body))
+}
+
 #[get("/partials/mission-feed")]
 pub async fn mission_feed(state: web::Data<AppState>) -> Result<HttpResponse, Error> {
     let snapshot = state
@@ -49,3 +69,48 @@ pub async fn assets(state: web::Data<AppState>) -> Result<HttpResponse, Error> {
         .map_err(ErrorInternalServerError)?;
     Ok(HttpResponse::Ok().content_type("text/html; charset=utf-8").body(body))
 }
+
+#[get("/partials/missions/table")]
+pub async fn mission_table(
+    state: web::Data<AppState>,
+    query: web::Query<TableQuery>,
+) -> Result<HttpResponse, Error> {
+    let page = state.api.missions_page(&query).await;
+    render_table(
+        &state,
+        "partials/mission_table.html",
+        "/partials/missions/table",
+        &query,
+        page,
+    )
+}
+
+#[get("/partials/incidents/table")]
+pub async fn incident_table(
+    state: web::Data<AppState>,
+    query: web::Query<TableQuery>,
+) -> Result<HttpResponse, Error> {
+    let page = state.api.incidents_page(&query).await;
+    render_table(
+        &state,
+        "partials/incident_table.html",
+        "/partials/incidents/table",
+        &query,
+        page,
+    )
+}
+
+#[get("/partials/assets/table")]
+pub async fn asset_table(
+    state: web::Data<AppState>,
+    query: web::Query<TableQuery>,
+) -> Result<HttpResponse, Error> {
+    let page = state.api.assets_page(&query).await;
+    render_table(
+        &state,
+        "partials/asset_table.html",
+        "/partials/assets/table",
+        &query,
+        page,
+    )
+}
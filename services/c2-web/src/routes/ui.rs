@@ -1,17 +1,24 @@
-use actix_web::{error::ErrorInternalServerError, get, web, Error, HttpResponse};
+use actix_web::{
+    error::ErrorInternalServerError, get, web, Error, HttpMessage, HttpRequest, HttpResponse,
+};
+use c2_identity::Subject;
 use crate::state::AppState;
 use crate::api::UiSnapshot;
 use crate::render::{build_context, UiTemplateData};
 
 #[get("/")]
-pub async fn index(state: web::Data<AppState>) -> Result<HttpResponse, Error> {
+pub async fn index(req: HttpRequest, state: web::Data<AppState>) -> Result<HttpResponse, Error> {
     let status = state.api.status().await.ok();
     let snapshot = state
         .api
         .snapshot()
         .await
         .unwrap_or_else(|_| UiSnapshot::empty());
-    let data = UiTemplateData::from_state(&state, status, snapshot);
+    let mut data = UiTemplateData::from_state(&state, status, snapshot);
+    // With OIDC on, brand the page for whoever signed in.
+    if let Some(tenant_id) = req.extensions().get::<Subject>().map(|subject| subject.tenant_id) {
+        data = data.for_tenant(&state, tenant_id);
+    }
     let context = build_context(&data);
     let body = state
         .tera
@@ -1,5 +1,5 @@
 use actix::{Actor, ActorContext, ActorFutureExt, AsyncContext, StreamHandler};
-use actix_web::{error::ErrorInternalServerError, get, web, Error, HttpRequest, HttpResponse};
+use actix_web::{get, web, Error, HttpRequest, HttpResponse};
 use actix_web::web::Bytes;
 use actix_web::rt::time::interval;
 use actix_web_actors::ws;
@@ -8,16 +8,13 @@ use serde::Serialize;
 use std::time::{Duration, Instant};
 
 use crate::api::{ApiClient, UiEntitySnapshot, UiSnapshot};
+use crate::branding::Branding;
 use crate::render::{build_context, UiTemplateData};
 use crate::state::AppState;
 
 #[get("/ui/status")]
 pub async fn status(state: web::Data<AppState>) -> Result<HttpResponse, Error> {
-    let response = state
-        .api
-        .status()
-        .await
-        .map_err(|err| ErrorInternalServerError(err.message))?;
+    let response = state.api.status().await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
@@ -28,11 +25,7 @@ pub async fn summary(state: web::Data<AppState>) -> Result<HttpResponse, Error>
             .content_type("application/json")
             .body("{\"error\":\"missing C2_UI_* auth configuration\"}"));
     }
-    let snapshot = state
-        .api
-        .snapshot()
-        .await
-        .map_err(|err| ErrorInternalServerError(err.message))?;
+    let snapshot = state.api.snapshot().await?;
     Ok(HttpResponse::Ok().json(snapshot))
 }
 
@@ -43,11 +36,7 @@ pub async fn entities(state: web::Data<AppState>) -> Result<HttpResponse, Error>
             .content_type("application/json")
             .body("{\"error\":\"missing C2_UI_* auth configuration\"}"));
     }
-    let payload = state
-        .api
-        .entities()
-        .await
-        .map_err(|err| ErrorInternalServerError(err.message))?;
+    let payload = state.api.entities().await?;
     Ok(HttpResponse::Ok().json(payload))
 }
 
@@ -246,6 +235,7 @@ fn render_partials(
     let data = UiTemplateData {
         service_name: service_name.to_string(),
         environment: environment.to_string(),
+        branding: Branding::default(),
         status: None,
         snapshot: snapshot.clone(),
         tile_config_json: None,
@@ -1,4 +1,5 @@
 use c2_config::ServiceConfig;
+use c2_policy::PositionPrecision;
 use reqwest::Client;
 use std::collections::HashMap;
 use std::sync::Mutex;
@@ -6,6 +7,7 @@ use std::time::Duration;
 use tera::Tera;
 
 use crate::api::ApiClient;
+use crate::branding::BrandingConfig;
 use crate::flights::FlightCache;
 use crate::satellites::SatelliteCache;
 use crate::ships::ShipCache;
@@ -15,6 +17,8 @@ pub struct AppState {
     pub config: ServiceConfig,
     pub tera: Tera,
     pub api: ApiClient,
+    pub branding: BrandingConfig,
+    pub position_precision: PositionPrecision,
     pub tile_config_json: Option<String>,
     pub tile_providers: HashMap<String, TileProvider>,
     pub tile_client: Client,
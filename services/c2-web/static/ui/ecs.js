@@ -129,8 +129,27 @@ export const ecsRuntime = {
     return this.initPromise;
   },
   tick() {
-    if (!this.ready || !this.instance?.exports?.ecs_tick) return;
-    this.instance.exports.ecs_tick();
+    if (!this.ready || !this.instance?.exports) return;
+    const exports = this.instance.exports;
+    if (exports.ecs_tick_at) {
+      exports.ecs_tick_at(Date.now());
+    } else if (exports.ecs_tick) {
+      exports.ecs_tick();
+    }
+  },
+  setStaleness(fadeAfterMs, removeAfterMs) {
+    if (!this.ready || !this.instance?.exports?.ecs_set_staleness) return;
+    if (!Number.isFinite(fadeAfterMs) || !Number.isFinite(removeAfterMs)) return;
+    this.instance.exports.ecs_set_staleness(fadeAfterMs, removeAfterMs);
+  },
+  setMaxEntities(maxEntities) {
+    if (!this.ready || !this.instance?.exports?.ecs_set_max_entities) return;
+    if (!Number.isInteger(maxEntities) || maxEntities < 0) return;
+    this.instance.exports.ecs_set_max_entities(maxEntities);
+  },
+  lastEvicted() {
+    if (!this.ready || !this.instance?.exports?.ecs_last_evicted) return 0;
+    return this.instance.exports.ecs_last_evicted();
   },
   setGlobeRadius(radius) {
     if (!this.ready || !this.instance?.exports?.ecs_set_globe_radius) return;
@@ -205,10 +224,16 @@ export const ecsRuntime = {
         colors[cOffset + 3] = color[3] ?? 0xff;
       }
       if (headings) {
-        headings[index] = Number.isFinite(item.heading) ? item.heading : 0;
+        // NaN asks the ECS to derive the facing from the previous position.
+        headings[index] = Number.isFinite(item.heading) ? item.heading : NaN;
       }
     });
-    exports.ecs_ingest_commit(count);
+    const applied = exports.ecs_ingest_commit(count);
+    if (typeof applied === "number" && applied !== count) {
+      const code = exports.ecs_last_error ? exports.ecs_last_error() : applied;
+      console.warn(`ECS ingest applied ${applied}/${count} records (error ${code})`);
+      return false;
+    }
     return true;
   },
   upsertEntity(id, lat, lon, kind = ECS_KIND.unknown, style = null) {
@@ -217,7 +242,7 @@ export const ecsRuntime = {
     if (this.instance.exports.ecs_upsert_entity_style_heading && style) {
       const color = Array.isArray(style.color) ? style.color : [0x38, 0xbd, 0xf8, 0xff];
       const altitude = Number.isFinite(style.altitude) ? style.altitude : 0;
-      const heading = Number.isFinite(style.heading) ? style.heading : 0;
+      const heading = Number.isFinite(style.heading) ? style.heading : NaN;
       const size = Number.isFinite(style.size) ? style.size : 6.0;
       this.instance.exports.ecs_upsert_entity_style_heading(
         ecsId,
@@ -259,6 +284,52 @@ export const ecsRuntime = {
     const ecsId = typeof id === "bigint" ? id : BigInt(id);
     this.instance.exports.ecs_remove_entity(ecsId);
   },
+  upsertArc(id, from, to, bulgeM = 0, segments = 0) {
+    if (!this.ready || !this.instance?.exports?.ecs_upsert_arc) return;
+    const ecsId = typeof id === "bigint" ? id : BigInt(id);
+    this.instance.exports.ecs_upsert_arc(
+      ecsId,
+      from.lat,
+      from.lon,
+      to.lat,
+      to.lon,
+      bulgeM,
+      segments,
+    );
+  },
+  removeArc(id) {
+    if (!this.ready || !this.instance?.exports?.ecs_remove_arc) return;
+    const ecsId = typeof id === "bigint" ? id : BigInt(id);
+    this.instance.exports.ecs_remove_arc(ecsId);
+  },
+  readArcBuffers() {
+    if (!this.ready || !this.memory || !this.instance?.exports) return null;
+    const exports = this.instance.exports;
+    if (!exports.ecs_arc_ids_ptr || !exports.ecs_arc_positions_ptr) return null;
+    const idsLen = exports.ecs_arc_ids_len();
+    const countsLen = exports.ecs_arc_point_counts_len();
+    const positionsLen = exports.ecs_arc_positions_len();
+    if (!idsLen) {
+      return {
+        ids: new BigUint64Array(),
+        pointCounts: new Uint32Array(),
+        positions: new Float32Array(),
+      };
+    }
+    return {
+      ids: new BigUint64Array(this.memory.buffer, exports.ecs_arc_ids_ptr(), idsLen),
+      pointCounts: new Uint32Array(
+        this.memory.buffer,
+        exports.ecs_arc_point_counts_ptr(),
+        countsLen,
+      ),
+      positions: new Float32Array(
+        this.memory.buffer,
+        exports.ecs_arc_positions_ptr(),
+        positionsLen,
+      ),
+    };
+  },
   entityCount() {
     if (!this.ready || !this.instance?.exports?.ecs_entity_count) return 0;
     return this.instance.exports.ecs_entity_count();
@@ -355,6 +426,37 @@ export const ecsRuntime = {
     if (!ptr || !len) return new BigUint64Array();
     return new BigUint64Array(this.memory.buffer, ptr, len);
   },
+  queryRadius(lat, lon, radiusM) {
+    if (!this.ready || !this.memory || !this.instance?.exports) return null;
+    const exports = this.instance.exports;
+    if (!exports.ecs_query_radius || !exports.ecs_query_ids_ptr) return null;
+    const len = exports.ecs_query_radius(lat, lon, radiusM);
+    const ptr = exports.ecs_query_ids_ptr();
+    if (!ptr || !len) return new BigUint64Array();
+    return new BigUint64Array(this.memory.buffer, ptr, len).slice();
+  },
+  snapshot() {
+    if (!this.ready || !this.memory || !this.instance?.exports) return null;
+    const exports = this.instance.exports;
+    if (!exports.ecs_snapshot_ptr || !exports.ecs_snapshot_len) return null;
+    const ptr = exports.ecs_snapshot_ptr();
+    const len = exports.ecs_snapshot_len();
+    return new Uint8Array(this.memory.buffer, ptr, len).slice();
+  },
+  restore(bytes) {
+    if (!this.ready || !this.memory || !this.instance?.exports) return false;
+    const exports = this.instance.exports;
+    if (!exports.ecs_restore || !exports.ecs_snapshot_reserve) return false;
+    if (!(bytes instanceof Uint8Array)) return false;
+    const ptr = exports.ecs_snapshot_reserve(bytes.length);
+    new Uint8Array(this.memory.buffer, ptr, bytes.length).set(bytes);
+    const restored = exports.ecs_restore(ptr, bytes.length);
+    if (restored < 0) {
+      console.warn("ECS restore failed", restored);
+      return false;
+    }
+    return true;
+  },
   refreshKindCache(kinds) {
     this.kindCache.clear();
     if (!Array.isArray(kinds)) return this.kindCache;
@@ -0,0 +1,116 @@
+use c2_core::{EpochMillis, TaskId, TaskStatus};
+use c2_storage::{StorageError, TaskRepository};
+use std::collections::HashMap;
+
+/// One pass over open tasks with dependencies: each is blocked while any
+/// dependency is not completed and returns to pending once all are. A
+/// dependency deleted since it was recorded no longer holds the task back.
+pub async fn run_tick<S>(store: &S, now_ms: EpochMillis) -> Result<usize, StorageError>
+where
+    S: TaskRepository,
+{
+    let mut statuses: HashMap<TaskId, Option<TaskStatus>> = HashMap::new();
+    let mut count = 0;
+    for mut task in TaskRepository::list_with_dependencies(store).await? {
+        let mut dependencies_complete = true;
+        for dependency_id in &task.depends_on {
+            let status = match statuses.get(dependency_id) {
+                Some(status) => *status,
+                None => {
+                    let status = TaskRepository::get(store, *dependency_id)
+                        .await?
+                        .map(|dependency| dependency.status);
+                    statuses.insert(*dependency_id, status);
+                    status
+                }
+            };
+            if status.is_some_and(|status| status != TaskStatus::Completed) {
+                dependencies_complete = false;
+                break;
+            }
+        }
+        let Some(next) = task.dependency_transition(dependencies_complete) else {
+            continue;
+        };
+        tracing::info!(
+            task_id = %task.id,
+            tenant_id = %task.tenant_id,
+            status = ?next,
+            "task dependency status changed"
+        );
+        task.status = next;
+        task.updated_at_ms = now_ms;
+        statuses.insert(task.id, Some(next));
+        TaskRepository::upsert(store, task).await?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{MissionId, OperationalPriority, SecurityClassification, Task, TenantId};
+    use c2_storage::{check_task_dependencies, TaskDependencyConflict};
+    use c2_storage_surreal::{SurrealConfig, SurrealStore};
+
+    fn task(tenant_id: TenantId, mission_id: MissionId, depends_on: Vec<TaskId>) -> Task {
+        Task {
+            id: TaskId::new(),
+            mission_id,
+            tenant_id,
+            title: "Clear the route".to_string(),
+            status: TaskStatus::Pending,
+            priority: OperationalPriority::Routine,
+            classification: SecurityClassification::Unclassified,
+            depends_on,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn dependent_task_blocks_until_dependency_completes() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let (tenant_id, mission_id) = (TenantId::new(), MissionId::new());
+        let mut first = task(tenant_id, mission_id, Vec::new());
+        let second = task(tenant_id, mission_id, vec![first.id]);
+        TaskRepository::upsert(&store, first.clone()).await.unwrap();
+        assert_eq!(check_task_dependencies(&store, &second).await.unwrap(), None);
+        TaskRepository::upsert(&store, second.clone()).await.unwrap();
+
+        assert_eq!(run_tick(&store, 10).await.unwrap(), 1);
+        let blocked = TaskRepository::get(&store, second.id).await.unwrap().unwrap();
+        assert_eq!(blocked.status, TaskStatus::Blocked);
+        assert_eq!(run_tick(&store, 20).await.unwrap(), 0);
+
+        first.status = TaskStatus::Completed;
+        TaskRepository::upsert(&store, first.clone()).await.unwrap();
+        assert_eq!(run_tick(&store, 30).await.unwrap(), 1);
+        let unblocked = TaskRepository::get(&store, second.id).await.unwrap().unwrap();
+        assert_eq!(unblocked.status, TaskStatus::Pending);
+        assert_eq!(unblocked.updated_at_ms, 30);
+
+        // first -> second -> first
+        first.depends_on = vec![second.id];
+        assert_eq!(
+            check_task_dependencies(&store, &first).await.unwrap(),
+            Some(TaskDependencyConflict::Cycle(second.id))
+        );
+        first.depends_on = vec![first.id];
+        assert_eq!(
+            check_task_dependencies(&store, &first).await.unwrap(),
+            Some(TaskDependencyConflict::SelfReference)
+        );
+        let stranger = task(TenantId::new(), mission_id, vec![first.id]);
+        assert_eq!(
+            check_task_dependencies(&store, &stranger).await.unwrap(),
+            Some(TaskDependencyConflict::Unknown(first.id))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use c2_core::{
+    Asset, AssetId, EpochMillis, Geofence, GeofenceId, Incident, IncidentId, IncidentStatus,
+    IncidentType, OperationalPriority, SecurityClassification, TenantId,
+};
+use c2_geo::Coordinate;
+use c2_storage::{AssetRepository, GeofenceRepository, IncidentRepository, StorageError};
+use std::collections::{HashMap, HashSet};
+
+const ASSET_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeofenceTransition {
+    Enter,
+    Exit,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeofenceEvent {
+    pub tenant_id: TenantId,
+    pub asset_id: AssetId,
+    pub asset_name: String,
+    pub geofence_id: GeofenceId,
+    pub geofence_name: String,
+    pub transition: GeofenceTransition,
+    pub position: Coordinate,
+    pub classification: SecurityClassification,
+}
+
+impl GeofenceEvent {
+    pub fn into_incident(self, now_ms: EpochMillis) -> Incident {
+        let verb = match self.transition {
+            GeofenceTransition::Enter => "entered",
+            GeofenceTransition::Exit => "exited",
+        };
+        Incident {
+            id: IncidentId::new(),
+            tenant_id: self.tenant_id,
+            incident_type: IncidentType::Other,
+            status: IncidentStatus::Reported,
+            priority: OperationalPriority::Elevated,
+            summary: format!(
+                "Asset {} {} geofence {} at {:.5},{:.5}",
+                self.asset_name,
+                verb,
+                self.geofence_name,
+                self.position.latitude,
+                self.position.longitude
+            ),
+            position: Some(self.position),
+            affected_radius_m: None,
+            classification: self.classification,
+            created_at_ms: now_ms,
+            updated_at_ms: now_ms,
+        }
+    }
+}
+
+/// Tracks which fences each positioned asset is inside between ticks.
+/// The first sighting of an asset/fence pair only records its state, so a
+/// worker restart does not replay enter events for every asset.
+#[derive(Debug, Default)]
+pub struct GeofenceMonitor {
+    inside: HashMap<(AssetId, GeofenceId), bool>,
+}
+
+impl GeofenceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn evaluate(&mut self, fences: &[Geofence], assets: &[Asset]) -> Vec<GeofenceEvent> {
+        let mut events = Vec::new();
+        let mut seen = HashMap::with_capacity(self.inside.len());
+        for asset in assets {
+            let Some(position) = asset.position else {
+                continue;
+            };
+            for fence in fences
+                .iter()
+                .filter(|fence| fence.active && fence.tenant_id == asset.tenant_id)
+            {
+                let key = (asset.id, fence.id);
+                let now_inside = fence.fence.contains(position);
+                let transition = match self.inside.get(&key) {
+                    Some(false) if now_inside => Some(GeofenceTransition::Enter),
+                    Some(true) if !now_inside => Some(GeofenceTransition::Exit),
+                    _ => None,
+                };
+                if let Some(transition) = transition {
+                    events.push(GeofenceEvent {
+                        tenant_id: asset.tenant_id,
+                        asset_id: asset.id,
+                        asset_name: asset.name.clone(),
+                        geofence_id: fence.id,
+                        geofence_name: fence.name.clone(),
+                        transition,
+                        position,
+                        classification: asset.classification.max(fence.classification),
+                    });
+                }
+                seen.insert(key, now_inside);
+            }
+        }
+        self.inside = seen;
+        events
+    }
+}
+
+/// One evaluation pass: loads active fences and the positioned assets of
+/// their tenants, and raises an incident per transition.
+pub async fn run_tick<S>(
+    store: &S,
+    monitor: &mut GeofenceMonitor,
+    now_ms: EpochMillis,
+) -> Result<usize, StorageError>
+where
+    S: GeofenceRepository + AssetRepository + IncidentRepository,
+{
+    let fences = GeofenceRepository::list_active(store).await?;
+    let tenants: HashSet<TenantId> = fences.iter().map(|fence| fence.tenant_id).collect();
+    let mut assets = Vec::new();
+    for tenant_id in tenants {
+        let mut offset = 0;
+        loop {
+            let page =
+                AssetRepository::list_by_tenant(store, tenant_id, ASSET_PAGE_SIZE, offset).await?;
+            let len = page.len();
+            assets.extend(page.into_iter().filter(|asset| asset.position.is_some()));
+            if len < ASSET_PAGE_SIZE {
+                break;
+            }
+            offset += len;
+        }
+    }
+
+    let events = monitor.evaluate(&fences, &assets);
+    let count = events.len();
+    for event in events {
+        tracing::info!(
+            asset_id = %event.asset_id,
+            geofence_id = %event.geofence_id,
+            transition = ?event.transition,
+            "geofence transition"
+        );
+        IncidentRepository::upsert(store, event.into_incident(now_ms)).await?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{AssetKind, AssetStatus, CommsStatus, MaintenanceState, ReadinessState};
+    use c2_geo::GeoFence;
+
+    #[test]
+    fn leaving_a_circle_raises_one_exit() {
+        let tenant_id = TenantId::new();
+        let fence = Geofence {
+            id: GeofenceId::new(),
+            tenant_id,
+            name: "Harbor".to_string(),
+            fence: GeoFence::Circle {
+                center: Coordinate::new(45.0, 15.0),
+                radius_m: 1_000.0,
+            },
+            active: true,
+            classification: SecurityClassification::Restricted,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        let mut asset = Asset {
+            id: AssetId::new(),
+            tenant_id,
+            name: "Raven 1".to_string(),
+            kind: AssetKind::Drone,
+            status: AssetStatus::Available,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::None,
+            maintenance_window: None,
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: Some(Coordinate::new(45.001, 15.0)),
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        let fences = [fence.clone()];
+        let mut monitor = GeofenceMonitor::new();
+        assert!(monitor.evaluate(&fences, &[asset.clone()]).is_empty());
+
+        asset.position = Some(Coordinate::new(45.1, 15.0));
+        let events = monitor.evaluate(&fences, &[asset.clone()]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].transition, GeofenceTransition::Exit);
+        assert_eq!((events[0].asset_id, events[0].geofence_id), (asset.id, fence.id));
+        assert_eq!(events[0].classification, SecurityClassification::Restricted);
+
+        assert!(monitor.evaluate(&fences, &[asset]).is_empty());
+    }
+}
@@ -1,28 +1,184 @@
+mod dependencies;
+mod geofence;
+mod maintenance;
+#[cfg(feature = "seed")]
+mod seed;
+mod verify;
+
 use c2_config::ServiceConfig;
-use c2_observability::{init, log_startup, ObservabilityConfig};
+use c2_core::{Clock, SystemClock, TenantId};
+use c2_observability::{
+    check_requested, init, log_startup, CheckReport, ObservabilityConfig, TelemetryConfig,
+};
 use c2_storage_surreal::{SurrealConfig, SurrealStore};
+use geofence::GeofenceMonitor;
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = ServiceConfig::from_env("c2-worker");
+    if check_requested() {
+        run_check(&config).await;
+    }
+    #[cfg(feature = "seed")]
+    if env::args().nth(1).as_deref() == Some("seed") {
+        return run_seed().await;
+    }
+    if env::args().nth(1).as_deref() == Some("verify") {
+        return run_verify().await;
+    }
     let obs_config = ObservabilityConfig {
         service_name: config.service_name.clone(),
         environment: config.environment.to_string(),
         log_level: config.log_level.clone(),
+        log_format: config.log_format.clone(),
         metrics_addr: config.metrics_addr.clone(),
+        telemetry: TelemetryConfig {
+            enabled: config.telemetry,
+            interval: config.telemetry_interval(),
+            endpoint: config.telemetry_endpoint.clone(),
+        },
     };
     let handle = init(&obs_config);
     log_startup(&handle, &obs_config.environment);
 
     let surreal_config = SurrealConfig::from_env();
-    let _store = SurrealStore::connect_with_retry(&surreal_config).await?;
+    let store = SurrealStore::connect_with_retry(&surreal_config).await?;
 
-    // TODO: connect to messaging bus and start background processing loops.
+    // TODO: connect to messaging bus and start remaining background loops.
     let _data_dir = config.data_dir;
-    wait_for_shutdown().await;
+    let geofence_interval_ms = env_interval_ms("C2_WORKER_GEOFENCE_INTERVAL_MS", 5_000);
+    let maintenance_interval_ms = env_interval_ms("C2_WORKER_MAINTENANCE_INTERVAL_MS", 30_000);
+    let dependency_interval_ms = env_interval_ms("C2_WORKER_DEPENDENCY_INTERVAL_MS", 10_000);
+    tokio::select! {
+        _ = run_geofence_loop(&store, &SystemClock, geofence_interval_ms) => {},
+        _ = run_maintenance_loop(&store, &SystemClock, maintenance_interval_ms) => {},
+        _ = run_dependency_loop(&store, &SystemClock, dependency_interval_ms) => {},
+        _ = wait_for_shutdown() => {},
+    }
     Ok(())
 }
 
+fn env_interval_ms(key: &str, default: u64) -> u64 {
+    env::var(key)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(default)
+}
+
+/// Ticks every `interval_ms`; `0` disables the loop without ending the
+/// surrounding `select!`.
+fn ticker(interval_ms: u64) -> Option<tokio::time::Interval> {
+    if interval_ms == 0 {
+        return None;
+    }
+    let mut ticker = tokio::time::interval(Duration::from_millis(interval_ms));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    Some(ticker)
+}
+
+async fn run_geofence_loop(store: &SurrealStore, clock: &dyn Clock, interval_ms: u64) {
+    let Some(mut ticker) = ticker(interval_ms) else {
+        return std::future::pending().await;
+    };
+    let mut monitor = GeofenceMonitor::new();
+    loop {
+        ticker.tick().await;
+        match geofence::run_tick(store, &mut monitor, clock.now_ms()).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("geofence tick raised {} incident(s)", count),
+            Err(err) => tracing::warn!("geofence tick failed: {}", err.message),
+        }
+    }
+}
+
+async fn run_maintenance_loop(store: &SurrealStore, clock: &dyn Clock, interval_ms: u64) {
+    let Some(mut ticker) = ticker(interval_ms) else {
+        return std::future::pending().await;
+    };
+    loop {
+        ticker.tick().await;
+        match maintenance::run_tick(store, clock.now_ms()).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("maintenance tick updated {} asset(s)", count),
+            Err(err) => tracing::warn!("maintenance tick failed: {}", err.message),
+        }
+    }
+}
+
+async fn run_dependency_loop(store: &SurrealStore, clock: &dyn Clock, interval_ms: u64) {
+    let Some(mut ticker) = ticker(interval_ms) else {
+        return std::future::pending().await;
+    };
+    loop {
+        ticker.tick().await;
+        match dependencies::run_tick(store, clock.now_ms()).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("dependency tick updated {} task(s)", count),
+            Err(err) => tracing::warn!("dependency tick failed: {}", err.message),
+        }
+    }
+}
+
+#[cfg(feature = "seed")]
+async fn run_seed() -> Result<(), Box<dyn std::error::Error>> {
+    let seed_config = seed::SeedConfig::from_env()?;
+    let store = SurrealStore::connect_with_retry(&SurrealConfig::from_env()).await?;
+    let report = seed::run(&store, &seed_config, SystemClock.now_ms()).await?;
+    println!(
+        "seeded tenant {}: {} capabilities, {} units, {} assets, {} missions, {} tasks, \
+         {} incidents ({} new)",
+        seed_config.tenant_id,
+        report.capabilities,
+        report.units,
+        report.assets,
+        report.missions,
+        report.tasks,
+        report.incidents,
+        report.inserted
+    );
+    Ok(())
+}
+
+/// `c2-worker verify <tenant-id> [--fix]`: prints each dangling reference
+/// and fails while any is left unfixed.
+async fn run_verify() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(2).collect();
+    let fix = args.iter().any(|arg| arg == "--fix");
+    let tenant_id = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .ok_or("usage: c2-worker verify <tenant-id> [--fix]")?;
+    let tenant_id = TenantId::from_str(tenant_id.trim())
+        .map_err(|err| format!("invalid tenant id: {err}"))?;
+    let store = SurrealStore::connect_with_retry(&SurrealConfig::from_env()).await?;
+    let report = verify::run(&store, tenant_id, fix, SystemClock.now_ms()).await?;
+    for orphan in &report.orphans {
+        println!("{orphan}");
+    }
+    println!(
+        "verified tenant {tenant_id}: {} dangling reference(s), {} record(s) fixed",
+        report.orphans.len(),
+        report.fixed
+    );
+    if !fix && !report.orphans.is_empty() {
+        return Err(format!("{} dangling reference(s)", report.orphans.len()).into());
+    }
+    Ok(())
+}
+
+async fn run_check(config: &ServiceConfig) -> ! {
+    let mut report = CheckReport::new(&config.service_name);
+    report.pass(
+        "config",
+        format!("environment={} bind_addr={}", config.environment, config.bind_addr),
+    );
+    report.record("surreal", SurrealStore::check(&SurrealConfig::from_env()).await);
+    report.exit()
+}
+
 async fn wait_for_shutdown() {
     #[cfg(unix)]
     {
@@ -42,3 +198,69 @@ async fn wait_for_shutdown() {
         let _ = tokio::signal::ctrl_c().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{
+        Asset, AssetId, AssetKind, AssetStatus, CommsStatus, FixedClock, MaintenanceState,
+        ReadinessState, SecurityClassification,
+    };
+    use c2_storage::AssetRepository;
+
+    #[tokio::test]
+    async fn the_maintenance_loop_follows_the_injected_clock() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let asset = Asset {
+            id: AssetId::new(),
+            tenant_id: TenantId::new(),
+            name: "Raven 1".to_string(),
+            kind: AssetKind::Drone,
+            status: AssetStatus::Available,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::Scheduled,
+            maintenance_window: Some((1_000, 2_000)),
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: None,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        AssetRepository::upsert(&store, asset.clone()).await.unwrap();
+        let clock = FixedClock::new(500);
+        let state = || async {
+            AssetRepository::get(&store, asset.id).await.unwrap().unwrap().maintenance_state
+        };
+        let reaches = |expected| async move {
+            for _ in 0..500 {
+                if state().await == expected {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            panic!("asset never reached {expected:?}");
+        };
+
+        let steps = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(state().await, MaintenanceState::Scheduled);
+            clock.set(1_000);
+            reaches(MaintenanceState::InProgress).await;
+            clock.advance(1_000);
+            reaches(MaintenanceState::Complete).await;
+        };
+        tokio::select! {
+            _ = run_maintenance_loop(&store, &clock, 10) => unreachable!(),
+            _ = steps => {}
+        }
+        let stored = AssetRepository::get(&store, asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.updated_at_ms, 2_000);
+    }
+}
@@ -0,0 +1,95 @@
+use c2_core::{EpochMillis, MaintenanceState};
+use c2_storage::{AssetRepository, StorageError};
+
+/// One pass over assets whose maintenance window boundaries have passed:
+/// scheduled assets move to in progress at the window start and to complete
+/// at its end. Each change goes through `upsert`, so it is also recorded in
+/// the asset's status history.
+pub async fn run_tick<S>(store: &S, now_ms: EpochMillis) -> Result<usize, StorageError>
+where
+    S: AssetRepository,
+{
+    let mut count = 0;
+    for mut asset in AssetRepository::list_maintenance_due(store, now_ms).await? {
+        let Some(next) = asset.maintenance_transition(now_ms) else {
+            continue;
+        };
+        if next == MaintenanceState::InProgress {
+            tracing::info!(
+                asset_id = %asset.id,
+                tenant_id = %asset.tenant_id,
+                "asset entering maintenance"
+            );
+        } else {
+            tracing::info!(
+                asset_id = %asset.id,
+                tenant_id = %asset.tenant_id,
+                state = ?next,
+                "asset maintenance window ended"
+            );
+        }
+        asset.maintenance_state = next;
+        asset.updated_at_ms = now_ms;
+        AssetRepository::upsert(store, asset).await?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{
+        Asset, AssetId, AssetKind, AssetStatus, CommsStatus, ReadinessState,
+        SecurityClassification, TenantId,
+    };
+    use c2_storage_surreal::{SurrealConfig, SurrealStore};
+
+    #[tokio::test]
+    async fn assets_enter_and_leave_maintenance_as_their_window_passes() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let asset = |start_ms, end_ms| Asset {
+            id: AssetId::new(),
+            tenant_id: TenantId::new(),
+            name: "Raven 1".to_string(),
+            kind: AssetKind::Drone,
+            status: AssetStatus::Available,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::Scheduled,
+            maintenance_window: Some((start_ms, end_ms)),
+            unit_id: None,
+            capability_ids: Vec::new(),
+            position: None,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        let started = asset(100, 200);
+        let later = asset(500, 600);
+        for asset in [&started, &later] {
+            AssetRepository::upsert(&store, asset.clone()).await.unwrap();
+        }
+        let state = |asset: &Asset| {
+            let id = asset.id;
+            let store = &store;
+            async move {
+                AssetRepository::get(store, id).await.unwrap().unwrap().maintenance_state
+            }
+        };
+
+        assert_eq!(run_tick(&store, 150).await.unwrap(), 1);
+        assert_eq!(state(&started).await, MaintenanceState::InProgress);
+        assert_eq!(state(&later).await, MaintenanceState::Scheduled);
+        assert_eq!(run_tick(&store, 160).await.unwrap(), 0);
+
+        assert_eq!(run_tick(&store, 200).await.unwrap(), 1);
+        assert_eq!(state(&started).await, MaintenanceState::Complete);
+        assert_eq!(state(&later).await, MaintenanceState::Scheduled);
+    }
+}
@@ -0,0 +1,363 @@
+//! Demo data for one tenant, run as `c2-worker seed` when built with the
+//! `seed` feature. Every id comes from `from_name`, so running it again
+//! rewrites the same records instead of adding new ones.
+
+use c2_core::{
+    Asset, AssetId, AssetKind, AssetStatus, Capability, CapabilityId, CommsStatus, EpochMillis,
+    Incident, IncidentId, IncidentStatus, IncidentType, MaintenanceState, Mission, MissionId,
+    MissionStatus, OperationalPriority, ReadinessState, SecurityClassification, Task, TaskId,
+    TaskStatus, TenantId, Unit, UnitId,
+};
+use c2_geo::Coordinate;
+use c2_storage::{
+    AssetRepository, CapabilityRepository, IncidentRepository, MissionRepository, StorageError,
+    TaskRepository, UnitRepository, UpsertOutcome,
+};
+use std::env;
+use std::str::FromStr;
+
+const CAPABILITIES: [(&str, &str, &str); 4] = [
+    ("isr", "Intelligence, surveillance and reconnaissance", "sensing"),
+    ("medevac", "Medical evacuation", "medical"),
+    ("comms", "Communications relay", "signals"),
+    ("logistics", "Logistics resupply", "support"),
+];
+const ASSET_KINDS: [AssetKind; 6] = [
+    AssetKind::Drone,
+    AssetKind::Vehicle,
+    AssetKind::Ugv,
+    AssetKind::Sensor,
+    AssetKind::CommsRelay,
+    AssetKind::Personnel,
+];
+const MISSION_STATUSES: [MissionStatus; 4] = [
+    MissionStatus::Active,
+    MissionStatus::Planned,
+    MissionStatus::Active,
+    MissionStatus::Suspended,
+];
+const PRIORITIES: [OperationalPriority; 4] = [
+    OperationalPriority::Routine,
+    OperationalPriority::Elevated,
+    OperationalPriority::Urgent,
+    OperationalPriority::Critical,
+];
+const TASK_STATUSES: [TaskStatus; 3] =
+    [TaskStatus::Pending, TaskStatus::InProgress, TaskStatus::Completed];
+const INCIDENT_TYPES: [IncidentType; 4] = [
+    IncidentType::Fire,
+    IncidentType::Medical,
+    IncidentType::Infrastructure,
+    IncidentType::Defense,
+];
+const INCIDENT_STATUSES: [IncidentStatus; 3] = [
+    IncidentStatus::Reported,
+    IncidentStatus::Responding,
+    IncidentStatus::Contained,
+];
+/// Golden angle in radians; spreads positions evenly around the center.
+const GOLDEN_ANGLE: f64 = 2.399_963_229_728_653;
+
+#[derive(Debug, Clone)]
+pub struct SeedConfig {
+    pub tenant_id: TenantId,
+    pub missions: usize,
+    pub tasks_per_mission: usize,
+    pub assets: usize,
+    pub units: usize,
+    pub incidents: usize,
+    /// Latitude and longitude that asset positions spread around.
+    pub center: (f64, f64),
+}
+
+impl SeedConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let tenant_id = env::var("C2_SEED_TENANT_ID")
+            .map_err(|_| "C2_SEED_TENANT_ID is required".to_string())?;
+        let tenant_id = TenantId::from_str(tenant_id.trim())
+            .map_err(|err| format!("invalid C2_SEED_TENANT_ID: {err}"))?;
+        let center = match env::var("C2_SEED_CENTER") {
+            Ok(value) => parse_center(&value)
+                .ok_or_else(|| format!("invalid C2_SEED_CENTER {value:?} (expected lat,lon)"))?,
+            Err(_) => (45.815, 15.982),
+        };
+        Ok(Self {
+            tenant_id,
+            missions: env_count("C2_SEED_MISSIONS", 5)?,
+            tasks_per_mission: env_count("C2_SEED_TASKS_PER_MISSION", 3)?,
+            assets: env_count("C2_SEED_ASSETS", 12)?,
+            units: env_count("C2_SEED_UNITS", 3)?,
+            incidents: env_count("C2_SEED_INCIDENTS", 4)?,
+            center,
+        })
+    }
+}
+
+/// Records written per type; `inserted` counts those that did not exist yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeedReport {
+    pub capabilities: usize,
+    pub units: usize,
+    pub assets: usize,
+    pub missions: usize,
+    pub tasks: usize,
+    pub incidents: usize,
+    pub inserted: usize,
+}
+
+pub async fn run<S>(
+    store: &S,
+    config: &SeedConfig,
+    now_ms: EpochMillis,
+) -> Result<SeedReport, StorageError>
+where
+    S: MissionRepository
+        + TaskRepository
+        + AssetRepository
+        + UnitRepository
+        + CapabilityRepository
+        + IncidentRepository,
+{
+    let tenant_id = config.tenant_id;
+    let mut report = SeedReport::default();
+
+    let mut capability_ids = Vec::new();
+    for (code, name, category) in CAPABILITIES {
+        let id = CapabilityId::from_name(tenant_id, &format!("seed-capability-{code}"));
+        let existing = CapabilityRepository::get(store, id).await?;
+        report.inserted += usize::from(existing.is_none());
+        CapabilityRepository::upsert(
+            store,
+            Capability {
+                id,
+                tenant_id,
+                code: code.to_string(),
+                name: name.to_string(),
+                category: Some(category.to_string()),
+                description: None,
+                classification: SecurityClassification::Unclassified,
+                created_at_ms: existing.map_or(now_ms, |existing| existing.created_at_ms),
+                updated_at_ms: now_ms,
+            },
+        )
+        .await?;
+        capability_ids.push(id);
+        report.capabilities += 1;
+    }
+
+    let mut unit_ids = Vec::new();
+    for index in 0..config.units {
+        let id = UnitId::from_name(tenant_id, &format!("seed-unit-{index}"));
+        let existing = UnitRepository::get(store, id).await?;
+        report.inserted += usize::from(existing.is_none());
+        UnitRepository::upsert(
+            store,
+            Unit {
+                id,
+                tenant_id,
+                classification: SecurityClassification::Unclassified,
+                callsign: Some(format!("SEED-{}", index + 1)),
+                display_name: format!("Task Force {}", index + 1),
+                readiness: ReadinessState::Ready,
+                comms_status: CommsStatus::Online,
+                team_id: None,
+                capability_ids: vec![capability_ids[index % capability_ids.len()]],
+                created_at_ms: existing.map_or(now_ms, |existing| existing.created_at_ms),
+                updated_at_ms: now_ms,
+            },
+        )
+        .await?;
+        unit_ids.push(id);
+        report.units += 1;
+    }
+
+    for index in 0..config.assets {
+        let kind = ASSET_KINDS[index % ASSET_KINDS.len()];
+        let asset = Asset {
+            id: AssetId::from_name(tenant_id, &format!("seed-asset-{index}")),
+            tenant_id,
+            name: format!("{} {}", kind_label(kind), index + 1),
+            kind,
+            status: if index % 5 == 4 {
+                AssetStatus::Degraded
+            } else {
+                AssetStatus::Available
+            },
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::None,
+            maintenance_window: None,
+            unit_id: (!unit_ids.is_empty()).then(|| unit_ids[index % unit_ids.len()]),
+            capability_ids: vec![capability_ids[index % capability_ids.len()]],
+            position: Some(position(config.center, index)),
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: now_ms,
+            updated_at_ms: now_ms,
+        };
+        record(&mut report, AssetRepository::insert_or_update(store, asset).await?)?;
+        report.assets += 1;
+    }
+
+    for index in 0..config.missions {
+        let mission_id = MissionId::from_name(tenant_id, &format!("seed-mission-{index}"));
+        let priority = PRIORITIES[index % PRIORITIES.len()];
+        let mission = Mission {
+            id: mission_id,
+            tenant_id,
+            name: format!("Operation Seed {}", index + 1),
+            status: MISSION_STATUSES[index % MISSION_STATUSES.len()],
+            priority,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: now_ms,
+            updated_at_ms: now_ms,
+            archived_at_ms: None,
+        };
+        record(&mut report, MissionRepository::insert_or_update(store, mission).await?)?;
+        report.missions += 1;
+
+        for task_index in 0..config.tasks_per_mission {
+            let task = Task {
+                id: TaskId::from_name(tenant_id, &format!("seed-task-{index}-{task_index}")),
+                mission_id,
+                tenant_id,
+                title: format!("Phase {} of operation {}", task_index + 1, index + 1),
+                status: TASK_STATUSES[task_index % TASK_STATUSES.len()],
+                priority,
+                classification: SecurityClassification::Unclassified,
+                depends_on: Vec::new(),
+                created_at_ms: now_ms,
+                updated_at_ms: now_ms,
+            };
+            record(&mut report, TaskRepository::insert_or_update(store, task).await?)?;
+            report.tasks += 1;
+        }
+    }
+
+    for index in 0..config.incidents {
+        let incident_type = INCIDENT_TYPES[index % INCIDENT_TYPES.len()];
+        let incident = Incident {
+            id: IncidentId::from_name(tenant_id, &format!("seed-incident-{index}")),
+            tenant_id,
+            incident_type,
+            status: INCIDENT_STATUSES[index % INCIDENT_STATUSES.len()],
+            priority: PRIORITIES[(index + 1) % PRIORITIES.len()],
+            summary: format!("{incident_type:?} report {}", index + 1),
+            position: Some(position(config.center, config.assets + index)),
+            affected_radius_m: Some(250.0 * (index % 4 + 1) as f64),
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: now_ms,
+            updated_at_ms: now_ms,
+        };
+        record(&mut report, IncidentRepository::insert_or_update(store, incident).await?)?;
+        report.incidents += 1;
+    }
+
+    Ok(report)
+}
+
+fn record(report: &mut SeedReport, outcome: Option<UpsertOutcome>) -> Result<(), StorageError> {
+    let outcome =
+        outcome.ok_or_else(|| StorageError::new("seed id is already used by another tenant"))?;
+    report.inserted += usize::from(outcome.inserted);
+    Ok(())
+}
+
+fn position((latitude, longitude): (f64, f64), index: usize) -> Coordinate {
+    let radius = 0.02 * ((index + 1) as f64).sqrt();
+    let angle = index as f64 * GOLDEN_ANGLE;
+    Coordinate {
+        latitude: latitude + radius * angle.cos(),
+        longitude: longitude + radius * angle.sin() / latitude.to_radians().cos().max(0.1),
+        altitude_m: None,
+        accuracy_m: None,
+    }
+}
+
+fn kind_label(kind: AssetKind) -> &'static str {
+    match kind {
+        AssetKind::Drone => "Drone",
+        AssetKind::Vehicle => "Vehicle",
+        AssetKind::Ugv => "UGV",
+        AssetKind::Sensor => "Sensor",
+        AssetKind::CommsRelay => "Relay",
+        AssetKind::Personnel => "Team",
+        _ => "Asset",
+    }
+}
+
+fn parse_center(value: &str) -> Option<(f64, f64)> {
+    let (latitude, longitude) = value.split_once(',')?;
+    let latitude = latitude.trim().parse::<f64>().ok()?;
+    let longitude = longitude.trim().parse::<f64>().ok()?;
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+        .then_some((latitude, longitude))
+}
+
+fn env_count(key: &str, default: usize) -> Result<usize, String> {
+    match env::var(key) {
+        Ok(value) => value
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("invalid {key} {value:?}")),
+        Err(_) => Ok(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_storage_surreal::{SurrealConfig, SurrealStore};
+
+    #[tokio::test]
+    async fn seeding_writes_the_requested_counts_and_reruns_in_place() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let tenant_id = TenantId::new();
+        let config = SeedConfig {
+            tenant_id,
+            missions: 3,
+            tasks_per_mission: 2,
+            assets: 4,
+            units: 2,
+            incidents: 3,
+            center: (45.815, 15.982),
+        };
+        let expected = SeedReport {
+            capabilities: CAPABILITIES.len(),
+            units: 2,
+            assets: 4,
+            missions: 3,
+            tasks: 6,
+            incidents: 3,
+            inserted: CAPABILITIES.len() + 2 + 4 + 3 + 6 + 3,
+        };
+        let asset_ids = || async {
+            let mut ids: Vec<AssetId> = AssetRepository::list_by_tenant(&store, tenant_id, 100, 0)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|asset| asset.id)
+                .collect();
+            ids.sort_by_key(|id| id.to_string());
+            ids
+        };
+
+        assert_eq!(run(&store, &config, 1_000).await.unwrap(), expected);
+        let seeded = asset_ids().await;
+        assert_eq!(seeded.len(), 4);
+        let missions = MissionRepository::list_by_tenant(&store, tenant_id, 100, 0).await.unwrap();
+        assert_eq!(missions.len(), 3);
+
+        let rerun = run(&store, &config, 2_000).await.unwrap();
+        assert_eq!(rerun, SeedReport { inserted: 0, ..expected });
+        assert_eq!(asset_ids().await, seeded);
+        let incidents =
+            IncidentRepository::list_by_tenant(&store, tenant_id, 100, 0).await.unwrap();
+        assert_eq!(incidents.len(), 3);
+        assert!(incidents.iter().all(|incident| incident.created_at_ms == 1_000));
+    }
+}
@@ -0,0 +1,272 @@
+//! Dangling-reference scan for one tenant, run as `c2-worker verify`. Finds
+//! tasks whose mission is gone, assets whose unit or capabilities are gone,
+//! and units whose team is gone. A reference to another tenant's record
+//! counts as dangling. With `fix`, orphaned tasks are deleted and the other
+//! references are cleared.
+
+use c2_core::{
+    AssetId, CapabilityId, EpochMillis, MissionId, Task, TaskId, TeamId, TenantId, UnitId,
+};
+use c2_storage::{
+    AssetRepository, CapabilityRepository, ChangeCursor, MissionRepository, StorageError,
+    TaskRepository, TeamRepository, UnitRepository,
+};
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+
+const PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orphan {
+    TaskMission { task_id: TaskId, mission_id: MissionId },
+    AssetUnit { asset_id: AssetId, unit_id: UnitId },
+    AssetCapability { asset_id: AssetId, capability_id: CapabilityId },
+    UnitTeam { unit_id: UnitId, team_id: TeamId },
+}
+
+impl fmt::Display for Orphan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TaskMission { task_id, mission_id } => {
+                write!(f, "task {task_id} references missing mission {mission_id}")
+            }
+            Self::AssetUnit { asset_id, unit_id } => {
+                write!(f, "asset {asset_id} references missing unit {unit_id}")
+            }
+            Self::AssetCapability { asset_id, capability_id } => {
+                write!(f, "asset {asset_id} references missing capability {capability_id}")
+            }
+            Self::UnitTeam { unit_id, team_id } => {
+                write!(f, "unit {unit_id} references missing team {team_id}")
+            }
+        }
+    }
+}
+
+/// What a scan found; `fixed` counts the records deleted or rewritten.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub orphans: Vec<Orphan>,
+    pub fixed: usize,
+}
+
+pub async fn run<S>(
+    store: &S,
+    tenant_id: TenantId,
+    fix: bool,
+    now_ms: EpochMillis,
+) -> Result<VerifyReport, StorageError>
+where
+    S: MissionRepository
+        + TaskRepository
+        + AssetRepository
+        + UnitRepository
+        + TeamRepository
+        + CapabilityRepository,
+{
+    let mut report = VerifyReport::default();
+
+    let tasks = list_tasks(store, tenant_id).await?;
+    let mission_ids = distinct(tasks.iter().map(|task| task.mission_id));
+    let missions: HashSet<MissionId> = MissionRepository::get_many(store, &mission_ids)
+        .await?
+        .into_iter()
+        .filter(|mission| mission.tenant_id == tenant_id)
+        .map(|mission| mission.id)
+        .collect();
+    for task in tasks {
+        if missions.contains(&task.mission_id) {
+            continue;
+        }
+        report.orphans.push(Orphan::TaskMission {
+            task_id: task.id,
+            mission_id: task.mission_id,
+        });
+        if fix {
+            TaskRepository::delete(store, task.id).await?;
+            report.fixed += 1;
+        }
+    }
+
+    let assets =
+        list_pages(|offset| AssetRepository::list_by_tenant(store, tenant_id, PAGE_SIZE, offset))
+            .await?;
+    let units =
+        list_pages(|offset| UnitRepository::list_by_tenant(store, tenant_id, PAGE_SIZE, offset))
+            .await?;
+
+    let unit_ids = distinct(assets.iter().filter_map(|asset| asset.unit_id));
+    let existing_units: HashSet<UnitId> = UnitRepository::get_many(store, &unit_ids)
+        .await?
+        .into_iter()
+        .filter(|unit| unit.tenant_id == tenant_id)
+        .map(|unit| unit.id)
+        .collect();
+    let capability_ids = distinct(assets.iter().flat_map(|asset| asset.capability_ids.clone()));
+    let capabilities: HashSet<CapabilityId> =
+        CapabilityRepository::get_many(store, &capability_ids)
+            .await?
+            .into_iter()
+            .filter(|capability| capability.tenant_id == tenant_id)
+            .map(|capability| capability.id)
+            .collect();
+    for mut asset in assets {
+        let mut changed = false;
+        if let Some(unit_id) = asset.unit_id
+            && !existing_units.contains(&unit_id)
+        {
+            report.orphans.push(Orphan::AssetUnit {
+                asset_id: asset.id,
+                unit_id,
+            });
+            asset.unit_id = None;
+            changed = true;
+        }
+        for capability_id in &asset.capability_ids {
+            if !capabilities.contains(capability_id) {
+                report.orphans.push(Orphan::AssetCapability {
+                    asset_id: asset.id,
+                    capability_id: *capability_id,
+                });
+                changed = true;
+            }
+        }
+        if fix && changed {
+            asset.capability_ids.retain(|id| capabilities.contains(id));
+            asset.updated_at_ms = now_ms;
+            AssetRepository::upsert(store, asset).await?;
+            report.fixed += 1;
+        }
+    }
+
+    let team_ids = distinct(units.iter().filter_map(|unit| unit.team_id));
+    let teams: HashSet<TeamId> = TeamRepository::get_many(store, &team_ids)
+        .await?
+        .into_iter()
+        .filter(|team| team.tenant_id == tenant_id)
+        .map(|team| team.id)
+        .collect();
+    for mut unit in units {
+        let Some(team_id) = unit.team_id else {
+            continue;
+        };
+        if teams.contains(&team_id) {
+            continue;
+        }
+        report.orphans.push(Orphan::UnitTeam {
+            unit_id: unit.id,
+            team_id,
+        });
+        if fix {
+            unit.team_id = None;
+            unit.updated_at_ms = now_ms;
+            UnitRepository::upsert(store, unit).await?;
+            report.fixed += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Every page of a `limit`/`offset` listing.
+async fn list_pages<T, F, Fut>(mut page: F) -> Result<Vec<T>, StorageError>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, StorageError>>,
+{
+    let mut items = Vec::new();
+    loop {
+        let batch = page(items.len()).await?;
+        let done = batch.len() < PAGE_SIZE;
+        items.extend(batch);
+        if done {
+            return Ok(items);
+        }
+    }
+}
+
+/// The tenant's tasks. There is no per-tenant task listing, so this walks
+/// `list_updated_since` forward from the last task of each page.
+async fn list_tasks<S>(store: &S, tenant_id: TenantId) -> Result<Vec<Task>, StorageError>
+where
+    S: TaskRepository,
+{
+    let mut tasks = Vec::new();
+    let mut after = ChangeCursor::default();
+    loop {
+        let page = TaskRepository::list_updated_since(store, tenant_id, after, PAGE_SIZE).await?;
+        let full = page.len() == PAGE_SIZE;
+        if let Some(last) = page.last() {
+            after = ChangeCursor::after(last.updated_at_ms, last.id.as_uuid());
+        }
+        tasks.extend(page);
+        if !full {
+            return Ok(tasks);
+        }
+    }
+}
+
+fn distinct<T: Copy + Eq + Hash>(ids: impl Iterator<Item = T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    ids.filter(|id| seen.insert(*id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c2_core::{
+        Asset, AssetKind, AssetStatus, CommsStatus, MaintenanceState, ReadinessState,
+        SecurityClassification,
+    };
+    use c2_storage_surreal::{SurrealConfig, SurrealStore};
+
+    #[tokio::test]
+    async fn verify_flags_asset_with_missing_unit() {
+        let store = SurrealStore::connect(&SurrealConfig {
+            endpoint: "mem://".to_string(),
+            ..SurrealConfig::from_env()
+        })
+        .await
+        .unwrap();
+        let tenant_id = TenantId::new();
+        let missing_unit = UnitId::new();
+        let asset = Asset {
+            id: AssetId::new(),
+            tenant_id,
+            name: "Raven 1".to_string(),
+            kind: AssetKind::Drone,
+            status: AssetStatus::Available,
+            readiness: ReadinessState::Ready,
+            comms_status: CommsStatus::Online,
+            maintenance_state: MaintenanceState::None,
+            maintenance_window: None,
+            unit_id: Some(missing_unit),
+            capability_ids: Vec::new(),
+            position: None,
+            classification: SecurityClassification::Unclassified,
+            created_at_ms: 1,
+            updated_at_ms: 1,
+        };
+        AssetRepository::upsert(&store, asset.clone()).await.unwrap();
+
+        let report = run(&store, tenant_id, false, 10).await.unwrap();
+        assert_eq!(
+            report.orphans,
+            vec![Orphan::AssetUnit {
+                asset_id: asset.id,
+                unit_id: missing_unit,
+            }]
+        );
+        assert_eq!(report.fixed, 0);
+        let stored = AssetRepository::get(&store, asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.unit_id, Some(missing_unit));
+
+        let report = run(&store, tenant_id, true, 20).await.unwrap();
+        assert_eq!(report.fixed, 1);
+        let stored = AssetRepository::get(&store, asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.unit_id, None);
+        assert_eq!(stored.updated_at_ms, 20);
+        assert!(run(&store, tenant_id, false, 30).await.unwrap().orphans.is_empty());
+    }
+}